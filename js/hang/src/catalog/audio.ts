@@ -38,6 +38,17 @@ export const AudioConfigSchema = z.object({
 	// NOTE: The audio "frame" duration depends on the codec, sample rate, etc.
 	// ex: AAC often uses 1024 samples per frame, so at 44100Hz, this would be 1024/44100 = 23ms
 	jitter: z.optional(u53Schema),
+
+	// The integrated loudness of the audio in LUFS, if known.
+	// Players can use this to normalize loudness across renditions or broadcasts.
+	targetLoudness: z.optional(z.number()),
+
+	// The true peak of the audio in dBTP, if known.
+	// Players should avoid applying gain that would push the peak above 0 dBTP.
+	truePeak: z.optional(z.number()),
+
+	// The spoken language as a BCP-47 tag (ex. "en", "pt-BR"), if known.
+	lang: z.optional(z.string()),
 });
 
 export const AudioSchema = z.union([
@@ -9,6 +9,11 @@ import { TrackSchema } from "./track";
 import { UserSchema } from "./user";
 import { VideoSchema } from "./video";
 
+// Whether the broadcast is live or a recording; omitted means live.
+export const ModeSchema = z.enum(["live", "recording"]);
+
+export type Mode = z.infer<typeof ModeSchema>;
+
 export const RootSchema = z.object({
 	video: z.optional(VideoSchema),
 	audio: z.optional(AudioSchema),
@@ -17,6 +22,7 @@ export const RootSchema = z.object({
 	chat: z.optional(ChatSchema),
 	capabilities: z.optional(CapabilitiesSchema),
 	preview: z.optional(TrackSchema),
+	mode: z.optional(ModeSchema),
 });
 
 export type Root = z.infer<typeof RootSchema>;
@@ -43,3 +49,66 @@ export async function fetch(track: Moq.Track): Promise<Root | undefined> {
 	if (!frame) return undefined;
 	return decode(frame);
 }
+
+// Apply a JSON Merge Patch (RFC 7386) to the target, returning the result.
+export function mergePatch(target: unknown, patch: unknown): unknown {
+	if (typeof patch !== "object" || patch === null || Array.isArray(patch)) return patch;
+
+	const result: Record<string, unknown> =
+		typeof target === "object" && target !== null && !Array.isArray(target) ? { ...target } : {};
+
+	for (const [key, value] of Object.entries(patch)) {
+		if (value === null) {
+			delete result[key];
+		} else {
+			result[key] = mergePatch(result[key], value);
+		}
+	}
+
+	return result;
+}
+
+/**
+ * Reads catalog updates from the `catalog.patch` track.
+ *
+ * Each group starts with the full catalog, followed by JSON Merge Patch (RFC 7386) frames
+ * that are applied to a running copy of the document.
+ * The `catalog.json` track carries a full document per group; use {@link fetch} for it.
+ */
+export class Consumer {
+	#track: Moq.Track;
+	#group?: number;
+	#current?: unknown;
+
+	constructor(track: Moq.Track) {
+		this.#track = track;
+	}
+
+	async next(): Promise<Root | undefined> {
+		for (;;) {
+			const next = await this.#track.readFrameSequence();
+			if (!next) return undefined;
+
+			const str = new TextDecoder().decode(next.data);
+			const json = JSON.parse(str);
+
+			if (next.frame === 0) {
+				this.#group = next.group;
+				this.#current = json;
+			} else if (next.group === this.#group) {
+				this.#current = mergePatch(this.#current, json);
+			} else {
+				// We joined mid-group without the full document; wait for the next group.
+				console.warn("skipping catalog patch without a base", next.group, next.frame);
+				continue;
+			}
+
+			try {
+				return RootSchema.parse(this.#current);
+			} catch (error) {
+				console.warn("invalid catalog", this.#current);
+				throw error;
+			}
+		}
+	}
+}
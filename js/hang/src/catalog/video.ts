@@ -51,6 +51,9 @@ export const VideoConfigSchema = z.object({
 	// - If there can be up to 3 b-frames in a row, this would be 3 * 1000/fps.
 	// - If frames are buffered into 2s segments, this would be 2s.
 	jitter: z.optional(u53Schema),
+
+	// The language of the video as a BCP-47 tag (ex. "en"), if known.
+	lang: z.optional(z.string()),
 });
 
 // Mirrors VideoDecoderConfig
@@ -70,9 +73,10 @@ export const VideoSchema = z.union([
 			}),
 		),
 
-		// The rotation of the video in degrees.
+		// The clockwise rotation of the video in degrees.
+		// Only quarter turns are supported, matching WebCodecs.
 		// Default: 0
-		rotation: z.optional(z.number()),
+		rotation: z.optional(z.union([z.literal(0), z.literal(90), z.literal(180), z.literal(270)])),
 
 		// If true, the decoder will flip the video horizontally
 		// Default: false
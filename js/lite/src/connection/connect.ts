@@ -366,12 +366,14 @@ async function connectWebTransport(
 		const fingerprintText = await Promise.race([fingerprint.text(), cancel]);
 		if (fingerprintText === undefined) return undefined;
 
-		finalOptions.serverCertificateHashes = (finalOptions.serverCertificateHashes || []).concat([
-			{
-				algorithm: "sha-256",
-				value: Hex.toBytes(fingerprintText),
-			},
-		]);
+		// The server may serve multiple fingerprints, one per line (ex. ECDSA and Ed25519).
+		const hashes = fingerprintText
+			.split("\n")
+			.map((line) => line.trim())
+			.filter((line) => line.length > 0)
+			.map((line) => ({ algorithm: "sha-256", value: Hex.toBytes(line) }));
+
+		finalOptions.serverCertificateHashes = (finalOptions.serverCertificateHashes || []).concat(hashes);
 
 		finalUrl = new URL(url);
 		finalUrl.protocol = "https:";
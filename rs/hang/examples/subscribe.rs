@@ -74,7 +74,10 @@ async fn run_subscribe(mut consumer: moq_lite::OriginConsumer) -> anyhow::Result
 	// Subscribe to the video track.
 	let track = moq_lite::Track {
 		name: name.clone(),
-		priority: 1,
+		priority: moq_lite::Priority::low(),
+		clean_join: false,
+		datagrams: false,
+		ascending: false,
 	};
 
 	let track_consumer = broadcast.subscribe_track(&track)?;
@@ -43,7 +43,10 @@ fn create_track(broadcast: &mut moq_lite::BroadcastProducer) -> anyhow::Result<m
 	// Basic information about the video track.
 	let video_track = moq_lite::Track {
 		name: "video".to_string(),
-		priority: 1, // Video typically has lower priority than audio
+		priority: moq_lite::Priority::low(), // Video can tolerate more delay than audio
+		clean_join: false,
+		datagrams: false,
+		ascending: false,
 	};
 
 	// Example video configuration
@@ -69,6 +72,7 @@ fn create_track(broadcast: &mut moq_lite::BroadcastProducer) -> anyhow::Result<m
 		optimize_for_latency: None,
 		container: hang::catalog::Container::Legacy,
 		jitter: None,
+		lang: None,
 	};
 
 	// Create a map of video renditions
@@ -117,6 +121,7 @@ async fn run_broadcast(origin: moq_lite::OriginProducer) -> anyhow::Result<()> {
 		timestamp: moq_mux::container::Timestamp::from_secs(1).unwrap(),
 		payload: Bytes::from_static(b"keyframe NAL data"),
 		keyframe: true,
+		capture: None,
 	};
 	producer.write(frame)?;
 
@@ -126,6 +131,7 @@ async fn run_broadcast(origin: moq_lite::OriginProducer) -> anyhow::Result<()> {
 		timestamp: moq_mux::container::Timestamp::from_secs(2).unwrap(),
 		payload: Bytes::from_static(b"delta NAL data"),
 		keyframe: false,
+		capture: None,
 	};
 	producer.write(frame)?;
 
@@ -136,6 +142,7 @@ async fn run_broadcast(origin: moq_lite::OriginProducer) -> anyhow::Result<()> {
 		timestamp: moq_mux::container::Timestamp::from_secs(3).unwrap(),
 		payload: Bytes::from_static(b"keyframe NAL data"),
 		keyframe: true,
+		capture: None,
 	};
 	producer.write(frame)?;
 
@@ -15,6 +15,10 @@ pub enum AudioCodec {
 	#[display("opus")]
 	Opus,
 
+	/// Interleaved signed 16-bit little-endian PCM, as registered for WebCodecs.
+	#[display("pcm-s16")]
+	PcmS16,
+
 	/// Unknown or unsupported codec with original string
 	#[display("{_0}")]
 	Unknown(String),
@@ -28,6 +32,8 @@ impl FromStr for AudioCodec {
 			return AAC::from_str(s).map(Into::into);
 		} else if s == "opus" {
 			return Ok(Self::Opus);
+		} else if s == "pcm-s16" {
+			return Ok(Self::PcmS16);
 		}
 
 		Ok(Self::Unknown(s.to_string()))
@@ -11,7 +11,7 @@ use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, hex::Hex};
 
-use crate::catalog::Container;
+use crate::catalog::{CatalogWarning, Container};
 
 /// Information about an audio track in the catalog.
 ///
@@ -29,6 +29,9 @@ pub struct Audio {
 
 impl Audio {
 	/// Insert a track config, returning an error if the name already exists.
+	///
+	/// The name is used as-is, so a muxer can keep source track identifiers stable across
+	/// catalog updates, including when a rendition is removed and added again.
 	pub fn insert(&mut self, name: &str, config: AudioConfig) -> crate::Result<()> {
 		let btree_map::Entry::Vacant(entry) = self.renditions.entry(name.to_string()) else {
 			return Err(crate::Error::Duplicate(name.to_string()));
@@ -89,4 +92,51 @@ pub struct AudioConfig {
 	/// ex: AAC often uses 1024 samples per frame, so at 44100Hz, this would be 1024/44100 = 23ms
 	#[serde(default)]
 	pub jitter: Option<moq_lite::Time>,
+
+	/// The integrated loudness of the audio in LUFS, if known.
+	/// Players can use this to normalize loudness across renditions or broadcasts.
+	#[serde(default)]
+	pub target_loudness: Option<f64>,
+
+	/// The true peak of the audio in dBTP, if known.
+	/// Players should avoid applying gain that would push the peak above 0 dBTP.
+	#[serde(default)]
+	pub true_peak: Option<f64>,
+
+	/// The spoken language as a BCP-47 tag (ex. `en`, `pt-BR`), if known.
+	/// Players can use this to offer a language picker between renditions.
+	#[serde(default, deserialize_with = "crate::catalog::lang::deserialize_lang")]
+	pub lang: Option<String>,
+}
+
+impl AudioConfig {
+	/// The highest sample rate considered plausible.
+	const MAX_SAMPLE_RATE: u32 = 768_000;
+
+	/// The highest channel count considered plausible, matching Opus channel mapping.
+	const MAX_CHANNEL_COUNT: u32 = 255;
+
+	/// Check that the rendition can be decoded, appending any problems to `warnings`.
+	pub(crate) fn validate(&self, track: &str, warnings: &mut Vec<CatalogWarning>) {
+		if let AudioCodec::Unknown(codec) = &self.codec {
+			warnings.push(CatalogWarning::UnknownCodec {
+				track: track.to_string(),
+				codec: codec.clone(),
+			});
+		}
+
+		if !(1..=Self::MAX_SAMPLE_RATE).contains(&self.sample_rate) {
+			warnings.push(CatalogWarning::InvalidSampleRate {
+				track: track.to_string(),
+				sample_rate: self.sample_rate,
+			});
+		}
+
+		if !(1..=Self::MAX_CHANNEL_COUNT).contains(&self.channel_count) {
+			warnings.push(CatalogWarning::InvalidChannelCount {
+				track: track.to_string(),
+				channel_count: self.channel_count,
+			});
+		}
+	}
 }
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Deserializer};
+
+/// Deserialize an optional BCP-47 language tag, such as `en` or `pt-BR`.
+///
+/// Validation is deliberately loose: the tag must be non-empty ASCII letters, digits and
+/// hyphens, but the subtags themselves aren't checked against the registry.
+pub(crate) fn deserialize_lang<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+	let Some(lang) = Option::<String>::deserialize(deserializer)? else {
+		return Ok(None);
+	};
+
+	if lang.is_empty() || !lang.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+		return Err(serde::de::Error::custom(format!("invalid language tag: {lang:?}")));
+	}
+
+	Ok(Some(lang))
+}
+
+#[cfg(test)]
+mod test {
+	use serde::Deserialize;
+
+	#[derive(Deserialize)]
+	struct Config {
+		#[serde(default, deserialize_with = "super::deserialize_lang")]
+		lang: Option<String>,
+	}
+
+	fn parse(json: &str) -> Result<Option<String>, serde_json::Error> {
+		serde_json::from_str::<Config>(json).map(|config| config.lang)
+	}
+
+	#[test]
+	fn valid() {
+		assert_eq!(parse(r#"{"lang":"fr"}"#).unwrap().as_deref(), Some("fr"));
+		assert_eq!(parse(r#"{"lang":"pt-BR"}"#).unwrap().as_deref(), Some("pt-BR"));
+		assert_eq!(parse(r#"{}"#).unwrap(), None);
+		assert_eq!(parse(r#"{"lang":null}"#).unwrap(), None);
+	}
+
+	#[test]
+	fn invalid() {
+		for json in [r#"{"lang":""}"#, r#"{"lang":"fr FR"}"#, r#"{"lang":"français"}"#] {
+			assert!(parse(json).is_err(), "{json}");
+		}
+	}
+}
@@ -3,19 +3,28 @@
 //! This is a JSON blob that can be live updated like any other track in MoQ.
 //! It describes the available audio and video tracks, including codec information,
 //! resolution, bitrates, and other metadata.
+//!
+//! Live updates are published as JSON Merge Patches after the full document; see [`merge_diff`].
 
 mod audio;
 mod chat;
 mod container;
+mod lang;
+mod patch;
 mod preview;
 mod root;
+mod select;
 mod user;
 mod video;
+mod warning;
 
 pub use audio::*;
 pub use chat::*;
 pub use container::*;
+pub use patch::*;
 pub use preview::*;
 pub use root::*;
+pub use select::*;
 pub use user::*;
 pub use video::*;
+pub use warning::*;
@@ -0,0 +1,149 @@
+//! JSON Merge Patch ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)) helpers for catalog deltas.
+//!
+//! A live catalog is published as a full document followed by merge patches within the same
+//! group, so a late joiner always starts from the full document in the latest group.
+//!
+//! Merge patches can't express an explicit `null` value or partial array updates.
+//! Neither is an issue for the catalog: `None` fields are skipped and renditions are maps.
+
+use serde_json::{Map, Value};
+
+/// Apply a merge patch to `target` in place.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+	let Value::Object(patch) = patch else {
+		*target = patch.clone();
+		return;
+	};
+
+	if !target.is_object() {
+		*target = Value::Object(Map::new());
+	}
+
+	let Value::Object(target) = target else {
+		unreachable!();
+	};
+
+	for (key, value) in patch {
+		if value.is_null() {
+			target.remove(key);
+		} else {
+			merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+		}
+	}
+}
+
+/// Compute the minimal merge patch that turns `old` into `new`.
+///
+/// Returns `None` if the documents are identical.
+pub fn merge_diff(old: &Value, new: &Value) -> Option<Value> {
+	if old == new {
+		return None;
+	}
+
+	let (Value::Object(old), Value::Object(new)) = (old, new) else {
+		return Some(new.clone());
+	};
+
+	let mut patch = Map::new();
+
+	for key in old.keys() {
+		if !new.contains_key(key) {
+			patch.insert(key.clone(), Value::Null);
+		}
+	}
+
+	for (key, value) in new {
+		let diff = match old.get(key) {
+			Some(existing) => merge_diff(existing, value),
+			None => Some(value.clone()),
+		};
+
+		if let Some(diff) = diff {
+			patch.insert(key.clone(), diff);
+		}
+	}
+
+	Some(Value::Object(patch))
+}
+
+#[cfg(test)]
+mod test {
+	use serde_json::json;
+
+	use super::*;
+
+	fn catalog() -> Value {
+		json!({
+			"video": {
+				"renditions": {
+					"720p": { "codec": "avc1.64001f", "codedWidth": 1280, "codedHeight": 720 },
+					"360p": { "codec": "avc1.64001f", "codedWidth": 640, "codedHeight": 360 },
+				}
+			},
+			"audio": { "renditions": {} }
+		})
+	}
+
+	fn roundtrip(old: &Value, new: &Value) -> Value {
+		let patch = merge_diff(old, new).expect("documents differ");
+
+		let mut patched = old.clone();
+		merge_patch(&mut patched, &patch);
+		assert_eq!(&patched, new);
+
+		patch
+	}
+
+	#[test]
+	fn add_rendition() {
+		let old = catalog();
+		let mut new = catalog();
+		new["audio"]["renditions"]["opus"] = json!({ "codec": "opus", "sampleRate": 48000 });
+
+		let patch = roundtrip(&old, &new);
+		assert_eq!(
+			patch,
+			json!({ "audio": { "renditions": { "opus": { "codec": "opus", "sampleRate": 48000 } } } })
+		);
+	}
+
+	#[test]
+	fn remove_rendition() {
+		let old = catalog();
+		let mut new = catalog();
+		new["video"]["renditions"].as_object_mut().unwrap().remove("360p");
+
+		let patch = roundtrip(&old, &new);
+		assert_eq!(patch, json!({ "video": { "renditions": { "360p": null } } }));
+	}
+
+	#[test]
+	fn modify_rendition() {
+		let old = catalog();
+		let mut new = catalog();
+		new["video"]["renditions"]["720p"]["codedWidth"] = json!(1920);
+		new["video"]["renditions"]["720p"]["bitrate"] = json!(6_000_000);
+
+		let patch = roundtrip(&old, &new);
+		assert_eq!(
+			patch,
+			json!({ "video": { "renditions": { "720p": { "codedWidth": 1920, "bitrate": 6000000 } } } })
+		);
+	}
+
+	#[test]
+	fn unchanged() {
+		assert_eq!(merge_diff(&catalog(), &catalog()), None);
+	}
+
+	#[test]
+	fn replace_non_object() {
+		let mut target = json!({ "flip": true, "rotation": [90] });
+		merge_patch(&mut target, &json!({ "rotation": [180], "flip": null }));
+		assert_eq!(target, json!({ "rotation": [180] }));
+
+		// A non-object patch replaces the whole document.
+		merge_patch(&mut target, &json!("reset"));
+		assert_eq!(target, json!("reset"));
+	}
+}
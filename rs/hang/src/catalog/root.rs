@@ -1,6 +1,6 @@
 //! This module contains the structs and functions for the MoQ catalog format
 use crate::Result;
-use crate::catalog::{Audio, Chat, User, Video};
+use crate::catalog::{Audio, AudioConfig, CatalogWarning, Chat, Container, User, Video, VideoConfig};
 use serde::{Deserialize, Serialize};
 
 /// A catalog track, created by a broadcaster to describe the tracks available in a broadcast.
@@ -34,12 +34,48 @@ pub struct Catalog {
 	/// Preview information about the broadcast
 	#[serde(default)]
 	pub preview: Option<moq_lite::Track>,
+
+	/// Whether the broadcast is live or a recording.
+	///
+	/// Omitted from the JSON when live, so older catalogs remain unchanged.
+	#[serde(default, skip_serializing_if = "Mode::is_live")]
+	pub mode: Mode,
+}
+
+/// Whether a broadcast is happening now or was recorded ahead of time.
+///
+/// Players use this to pick a join strategy: live broadcasts start at the live edge
+/// with a small buffer, while recordings start from the beginning and never skip.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Mode {
+	/// The broadcast is being produced in real time.
+	#[default]
+	Live,
+	/// The broadcast was produced ahead of time, e.g. imported from a file.
+	Recording,
+}
+
+impl Mode {
+	/// Returns true if the broadcast is live.
+	pub fn is_live(&self) -> bool {
+		matches!(self, Self::Live)
+	}
 }
 
 impl Catalog {
 	/// The default name for the catalog track.
 	pub const DEFAULT_NAME: &str = "catalog.json";
 
+	/// The default name for the catalog patch track.
+	///
+	/// Each group starts with the full catalog, followed by a JSON Merge Patch (RFC 7386) per update.
+	/// It's a separate track so consumers that expect a full document per frame are unaffected.
+	pub const PATCH_NAME: &str = "catalog.patch";
+
+	/// The default limit for [Self::ensure_max_renditions].
+	pub const DEFAULT_MAX_RENDITIONS: usize = 64;
+
 	/// Parse a catalog from a string.
 	#[allow(clippy::should_implement_trait)]
 	pub fn from_str(s: &str) -> Result<Self> {
@@ -56,6 +92,16 @@ impl Catalog {
 		Ok(serde_json::from_reader(reader)?)
 	}
 
+	/// Parse a catalog from a JSON value, such as one assembled from [merge patches](super::merge_patch).
+	pub fn from_value(value: serde_json::Value) -> Result<Self> {
+		Ok(serde_json::from_value(value)?)
+	}
+
+	/// Serialize the catalog to a JSON value, such as to [diff](super::merge_diff) against a previous version.
+	pub fn to_value(&self) -> Result<serde_json::Value> {
+		Ok(serde_json::to_value(self)?)
+	}
+
 	/// Serialize the catalog to a string.
 	pub fn to_string(&self) -> Result<String> {
 		Ok(serde_json::to_string(self)?)
@@ -79,7 +125,116 @@ impl Catalog {
 	pub fn default_track() -> moq_lite::Track {
 		moq_lite::Track {
 			name: Catalog::DEFAULT_NAME.to_string(),
-			priority: 100,
+			priority: moq_lite::Priority::MAX,
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
+		}
+	}
+
+	/// The track carrying the catalog as [merge patches](Self::PATCH_NAME).
+	pub fn patch_track() -> moq_lite::Track {
+		moq_lite::Track {
+			name: Catalog::PATCH_NAME.to_string(),
+			..Self::default_track()
+		}
+	}
+
+	/// Return an error if the video or audio section has more than `max` renditions.
+	///
+	/// The catalog is written by the publisher, so consumers should bound it before
+	/// allocating state for every rendition.
+	pub fn ensure_max_renditions(&self, max: usize) -> Result<()> {
+		for (kind, count) in [
+			("video", self.video.renditions.len()),
+			("audio", self.audio.renditions.len()),
+		] {
+			if count > max {
+				return Err(crate::Error::TooManyRenditions { kind, count, max });
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Check that every rendition can be decoded.
+	///
+	/// The catalog is written by the publisher, so consumers can call this after each update
+	/// to reject a broken broadcast early, rather than failing to configure a decoder later.
+	/// Returns every problem found, in the order of [Self::renditions].
+	pub fn validate(&self) -> std::result::Result<(), Vec<CatalogWarning>> {
+		let mut warnings = Vec::new();
+		for (name, rendition) in self.renditions() {
+			match rendition {
+				Rendition::Video(config) => config.validate(name, &mut warnings),
+				Rendition::Audio(config) => config.validate(name, &mut warnings),
+			}
+		}
+
+		if warnings.is_empty() { Ok(()) } else { Err(warnings) }
+	}
+
+	/// Iterate over every rendition with its track name: video first, then audio.
+	///
+	/// Each section is sorted by name, so the order is stable for a given catalog.
+	pub fn renditions(&self) -> impl Iterator<Item = (&str, Rendition<'_>)> {
+		let video = self
+			.video
+			.renditions
+			.iter()
+			.map(|(name, config)| (name.as_str(), Rendition::Video(config)));
+		let audio = self
+			.audio
+			.renditions
+			.iter()
+			.map(|(name, config)| (name.as_str(), Rendition::Audio(config)));
+		video.chain(audio)
+	}
+
+	/// Return the `index`th rendition of the given kind, in the order of [Self::renditions].
+	pub fn rendition(&self, kind: RenditionKind, index: usize) -> Option<(&str, Rendition<'_>)> {
+		self.renditions()
+			.filter(|(_, rendition)| rendition.kind() == kind)
+			.nth(index)
+	}
+
+	/// Find the rendition for a track name, checking video before audio.
+	pub fn find(&self, name: &str) -> Option<Rendition<'_>> {
+		if let Some(config) = self.video.renditions.get(name) {
+			return Some(Rendition::Video(config));
+		}
+
+		self.audio.renditions.get(name).map(Rendition::Audio)
+	}
+}
+
+/// The kind of media carried by a [Rendition].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenditionKind {
+	Video,
+	Audio,
+}
+
+/// The configuration of a single rendition, borrowed from a [Catalog].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rendition<'a> {
+	Video(&'a VideoConfig),
+	Audio(&'a AudioConfig),
+}
+
+impl Rendition<'_> {
+	pub fn kind(&self) -> RenditionKind {
+		match self {
+			Self::Video(_) => RenditionKind::Video,
+			Self::Audio(_) => RenditionKind::Audio,
+		}
+	}
+
+	/// How the rendition's frames are packaged.
+	pub fn container(&self) -> &Container {
+		match self {
+			Self::Video(config) => &config.container,
+			Self::Audio(config) => &config.container,
 		}
 	}
 }
@@ -144,6 +299,7 @@ mod test {
 				optimize_for_latency: None,
 				container: Container::Legacy,
 				jitter: None,
+				lang: None,
 			},
 		);
 
@@ -158,6 +314,9 @@ mod test {
 				description: None,
 				container: Container::Legacy,
 				jitter: None,
+				target_loudness: None,
+				true_peak: None,
+				lang: None,
 			},
 		);
 
@@ -180,4 +339,325 @@ mod test {
 		let output = decoded.to_string().expect("failed to encode");
 		assert_eq!(encoded, output, "wrong encoded output");
 	}
+
+	#[test]
+	fn mode() {
+		// Catalogs without a mode are live, and live catalogs don't serialize it.
+		let catalog = Catalog::from_str("{}").expect("failed to decode");
+		assert_eq!(catalog.mode, Mode::Live);
+		assert_eq!(
+			catalog.to_string().unwrap(),
+			"{\"video\":{\"renditions\":{}},\"audio\":{\"renditions\":{}}}"
+		);
+
+		let catalog = Catalog::from_str(r#"{"mode":"recording"}"#).expect("failed to decode");
+		assert_eq!(catalog.mode, Mode::Recording);
+		assert!(catalog.to_string().unwrap().contains(r#""mode":"recording""#));
+
+		assert!(Catalog::from_str(r#"{"mode":"vod"}"#).is_err());
+	}
+
+	#[test]
+	fn loudness() {
+		let encoded = r#"{"audio":{"renditions":{"audio":{"codec":"opus","sampleRate":48000,"numberOfChannels":2,"container":{"kind":"legacy"},"targetLoudness":-23.0,"truePeak":-1.5}}}}"#;
+
+		let catalog = Catalog::from_str(encoded).expect("failed to decode");
+		let config = &catalog.audio.renditions["audio"];
+		assert_eq!(config.target_loudness, Some(-23.0));
+		assert_eq!(config.true_peak, Some(-1.5));
+
+		let output = catalog.to_string().expect("failed to encode");
+		assert!(output.contains(r#""targetLoudness":-23.0,"truePeak":-1.5"#));
+	}
+
+	#[test]
+	fn max_renditions() {
+		let mut catalog = Catalog::default();
+		for i in 0..=Catalog::DEFAULT_MAX_RENDITIONS {
+			catalog.audio.renditions.insert(
+				format!("audio{i}"),
+				AudioConfig {
+					codec: Opus,
+					sample_rate: 48_000,
+					channel_count: 2,
+					bitrate: None,
+					description: None,
+					container: Container::Legacy,
+					jitter: None,
+					target_loudness: None,
+					true_peak: None,
+					lang: None,
+				},
+			);
+		}
+
+		assert!(matches!(
+			catalog.ensure_max_renditions(Catalog::DEFAULT_MAX_RENDITIONS),
+			Err(crate::Error::TooManyRenditions { kind: "audio", .. })
+		));
+		assert!(
+			catalog
+				.ensure_max_renditions(Catalog::DEFAULT_MAX_RENDITIONS + 1)
+				.is_ok()
+		);
+	}
+
+	#[test]
+	fn insert_named() {
+		let config = AudioConfig {
+			codec: Opus,
+			sample_rate: 48_000,
+			channel_count: 2,
+			bitrate: None,
+			description: None,
+			container: Container::Legacy,
+			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
+		};
+
+		let mut catalog = Catalog::default();
+		catalog.audio.insert("mic", config.clone()).unwrap();
+
+		// Taken names are an error rather than a silent overwrite.
+		assert!(matches!(
+			catalog.audio.insert("mic", config.clone()),
+			Err(crate::Error::Duplicate(name)) if name == "mic"
+		));
+
+		// A removed name can be reused.
+		assert!(catalog.audio.remove("mic").is_some());
+		catalog.audio.insert("mic", config).unwrap();
+		assert!(catalog.audio.renditions.contains_key("mic"));
+	}
+
+	#[test]
+	fn renditions() {
+		let video = |height: u32| VideoConfig {
+			codec: H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: false,
+			}
+			.into(),
+			description: None,
+			coded_width: Some(height * 16 / 9),
+			coded_height: Some(height),
+			display_ratio_width: None,
+			display_ratio_height: None,
+			bitrate: None,
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Legacy,
+			jitter: None,
+			lang: None,
+		};
+		let audio = AudioConfig {
+			codec: Opus,
+			sample_rate: 48_000,
+			channel_count: 2,
+			bitrate: None,
+			description: None,
+			container: Container::Legacy,
+			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
+		};
+
+		let mut catalog = Catalog::default();
+		catalog.video.insert("720p", video(720)).unwrap();
+		catalog.video.insert("1080p", video(1080)).unwrap();
+		catalog.audio.insert("fr", audio.clone()).unwrap();
+		catalog.audio.insert("en", audio.clone()).unwrap();
+
+		// Video then audio, each sorted by name regardless of insertion order.
+		let names: Vec<_> = catalog
+			.renditions()
+			.map(|(name, rendition)| (rendition.kind(), name))
+			.collect();
+		assert_eq!(
+			names,
+			[
+				(RenditionKind::Video, "1080p"),
+				(RenditionKind::Video, "720p"),
+				(RenditionKind::Audio, "en"),
+				(RenditionKind::Audio, "fr"),
+			]
+		);
+
+		// Indexes count within a kind.
+		let (name, rendition) = catalog.rendition(RenditionKind::Audio, 1).unwrap();
+		assert_eq!(name, "fr");
+		assert_eq!(rendition, Rendition::Audio(&audio));
+		assert!(catalog.rendition(RenditionKind::Audio, 2).is_none());
+
+		let Some(Rendition::Video(config)) = catalog.find("720p") else {
+			panic!("expected a video rendition");
+		};
+		assert_eq!(config.coded_height, Some(720));
+		assert_eq!(catalog.find("en").unwrap().container(), &Container::Legacy);
+		assert!(catalog.find("missing").is_none());
+	}
+
+	fn avc1() -> VideoConfig {
+		VideoConfig {
+			codec: H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: false,
+			}
+			.into(),
+			description: Some(bytes::Bytes::from_static(&[0x01, 0x64, 0x00, 0x1f])),
+			coded_width: Some(1280),
+			coded_height: Some(720),
+			display_ratio_width: None,
+			display_ratio_height: None,
+			bitrate: None,
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Legacy,
+			jitter: None,
+			lang: None,
+		}
+	}
+
+	fn opus() -> AudioConfig {
+		AudioConfig {
+			codec: Opus,
+			sample_rate: 48_000,
+			channel_count: 2,
+			bitrate: None,
+			description: None,
+			container: Container::Legacy,
+			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
+		}
+	}
+
+	fn validate_video(config: VideoConfig) -> std::result::Result<(), Vec<CatalogWarning>> {
+		let mut catalog = Catalog::default();
+		catalog.video.insert("video", config).unwrap();
+		catalog.validate()
+	}
+
+	fn validate_audio(config: AudioConfig) -> std::result::Result<(), Vec<CatalogWarning>> {
+		let mut catalog = Catalog::default();
+		catalog.audio.insert("audio", config).unwrap();
+		catalog.validate()
+	}
+
+	#[test]
+	fn validate_ok() {
+		let mut catalog = Catalog::default();
+		catalog.video.insert("video", avc1()).unwrap();
+		catalog.audio.insert("audio", opus()).unwrap();
+		assert_eq!(catalog.validate(), Ok(()));
+	}
+
+	#[test]
+	fn validate_missing_description() {
+		let config = VideoConfig {
+			description: None,
+			..avc1()
+		};
+		assert_eq!(
+			validate_video(config.clone()),
+			Err(vec![CatalogWarning::MissingDescription {
+				track: "video".to_string(),
+				codec: "avc1.64001f".to_string(),
+			}])
+		);
+
+		// Inline parameter sets or a CMAF init segment don't need a description.
+		let inline = VideoConfig {
+			codec: "avc3.64001f".parse().unwrap(),
+			..config.clone()
+		};
+		assert_eq!(validate_video(inline), Ok(()));
+
+		let cmaf = VideoConfig {
+			container: Container::Cmaf {
+				init: bytes::Bytes::from_static(b"init"),
+			},
+			..config
+		};
+		assert_eq!(validate_video(cmaf), Ok(()));
+	}
+
+	#[test]
+	fn validate_unknown_codec() {
+		let video = VideoConfig {
+			codec: "theora".parse().unwrap(),
+			..avc1()
+		};
+		assert!(matches!(
+			validate_video(video).unwrap_err().as_slice(),
+			[CatalogWarning::UnknownCodec { codec, .. }] if codec == "theora"
+		));
+
+		let audio = AudioConfig {
+			codec: "flac".parse().unwrap(),
+			..opus()
+		};
+		assert!(matches!(
+			validate_audio(audio).unwrap_err().as_slice(),
+			[CatalogWarning::UnknownCodec { codec, .. }] if codec == "flac"
+		));
+	}
+
+	#[test]
+	fn validate_dimensions() {
+		let config = VideoConfig {
+			coded_height: None,
+			display_ratio_width: Some(16),
+			display_ratio_height: Some(0),
+			..avc1()
+		};
+		let fields: Vec<_> = validate_video(config)
+			.unwrap_err()
+			.into_iter()
+			.map(|warning| match warning {
+				CatalogWarning::InvalidDimensions { field, .. } => field,
+				warning => panic!("unexpected warning: {warning}"),
+			})
+			.collect();
+		assert_eq!(fields, ["coded size", "display ratio"]);
+	}
+
+	#[test]
+	fn validate_audio_params() {
+		let config = AudioConfig {
+			sample_rate: 0,
+			channel_count: 0,
+			..opus()
+		};
+		assert_eq!(
+			validate_audio(config),
+			Err(vec![
+				CatalogWarning::InvalidSampleRate {
+					track: "audio".to_string(),
+					sample_rate: 0,
+				},
+				CatalogWarning::InvalidChannelCount {
+					track: "audio".to_string(),
+					channel_count: 0,
+				},
+			])
+		);
+
+		let config = AudioConfig {
+			sample_rate: 10_000_000,
+			..opus()
+		};
+		assert!(matches!(
+			validate_audio(config).unwrap_err().as_slice(),
+			[CatalogWarning::InvalidSampleRate { .. }]
+		));
+	}
 }
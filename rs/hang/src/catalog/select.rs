@@ -0,0 +1,364 @@
+use super::{Audio, Video, VideoConfig};
+
+/// Picks which audio and video renditions to play from a catalog.
+///
+/// Renditions are first filtered to the supported codecs, see [Self::with_codecs],
+/// and video to those fitting [Self::with_max_resolution].
+///
+/// [Self::best] picks the top video rendition, while [Self::select] picks the one that fits
+/// a bandwidth estimate without oscillating near thresholds. Video renditions are ranked by
+/// [`VideoConfig::bitrate`], then by coded size. Switching up requires the estimate to exceed
+/// the higher bitrate by the hysteresis margin, while the current rendition is kept until the
+/// estimate drops below its bitrate. An estimate hovering around a threshold therefore doesn't
+/// flip between two renditions.
+///
+/// The estimate is in bits per second, typically from
+/// [`BandwidthConsumer::peek`](moq_lite::BandwidthConsumer::peek) or supplied by the application.
+///
+/// ```
+/// # let catalog = hang::Catalog::default();
+/// let mut selector = hang::catalog::RenditionSelector::new(0.2).with_codecs(["avc1", "opus"]);
+/// let video = selector.select(&catalog.video, Some(2_000_000));
+/// let audio = selector.select_audio(&catalog.audio);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenditionSelector {
+	hysteresis: f64,
+	max_resolution: Option<(u32, u32)>,
+	codecs: Option<Vec<String>>,
+	current: Option<String>,
+}
+
+impl RenditionSelector {
+	/// Create a selector that switches up once the estimate exceeds a bitrate by `hysteresis`,
+	/// as a fraction: 0.2 needs 20% headroom.
+	pub fn new(hysteresis: f64) -> Self {
+		Self {
+			hysteresis: hysteresis.max(0.0),
+			max_resolution: None,
+			codecs: None,
+			current: None,
+		}
+	}
+
+	/// Only select video renditions whose coded size fits within `width` x `height`.
+	///
+	/// If no rendition fits, the smallest one is selected instead.
+	pub fn with_max_resolution(mut self, width: u32, height: u32) -> Self {
+		self.max_resolution = Some((width, height));
+		self
+	}
+
+	/// Only select renditions using one of these codecs; by default every codec is accepted.
+	///
+	/// A codec matches a rendition's codec string exactly or as a prefix ending at a `.`,
+	/// so `"avc1"` accepts any H.264 rendition while `"avc1.64001f"` only accepts that
+	/// profile and level.
+	pub fn with_codecs<I, S>(mut self, codecs: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.codecs = Some(codecs.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// The video rendition chosen by the last [Self::select] or [Self::best], if any.
+	pub fn current(&self) -> Option<&str> {
+		self.current.as_deref()
+	}
+
+	/// Choose the top video rendition regardless of bandwidth, remembering it for next time.
+	///
+	/// Returns `None` if no rendition uses a supported codec.
+	pub fn best(&mut self, video: &Video) -> Option<&str> {
+		self.current = self.ranked(video).last().map(|(name, _)| (*name).clone());
+		self.current()
+	}
+
+	/// Choose a video rendition for the given bandwidth estimate, remembering it for next time.
+	///
+	/// An unknown estimate keeps the current rendition, or starts at the lowest.
+	/// If nothing fits, the lowest rendition is chosen.
+	/// Renditions without a bitrate are only used when none advertise one, picking the largest.
+	/// Returns `None` if no rendition uses a supported codec.
+	pub fn select(&mut self, video: &Video, bandwidth: Option<u64>) -> Option<&str> {
+		let ranked = self.ranked(video);
+
+		if ranked.first().is_none_or(|(_, config)| config.bitrate.is_none()) {
+			return self.best(video);
+		}
+
+		let current = self
+			.current
+			.as_ref()
+			.and_then(|name| ranked.iter().position(|(candidate, _)| *candidate == name));
+		let lowest = 0;
+
+		let index = match bandwidth {
+			None => current.unwrap_or(lowest),
+			Some(bandwidth) => {
+				let bandwidth = bandwidth as f64;
+				let bitrate = |index: usize| ranked[index].1.bitrate.unwrap_or(0) as f64;
+
+				// The highest rendition with enough headroom to switch to.
+				let upgrade = (0..ranked.len())
+					.rev()
+					.find(|&i| bitrate(i) * (1.0 + self.hysteresis) <= bandwidth);
+
+				match current {
+					// Hold the current rendition while it still fits, unless there's room to step up.
+					Some(current) if bitrate(current) <= bandwidth => upgrade.map_or(current, |up| up.max(current)),
+					// Step down to the highest rendition that fits.
+					Some(_) => (0..ranked.len())
+						.rev()
+						.find(|&i| bitrate(i) <= bandwidth)
+						.unwrap_or(lowest),
+					// Start conservatively, as if stepping up from nothing.
+					None => upgrade.unwrap_or(lowest),
+				}
+			}
+		};
+
+		self.current = Some(ranked[index].0.clone());
+		self.current()
+	}
+
+	/// Choose the audio rendition with the highest bitrate among the supported codecs.
+	pub fn select_audio<'a>(&self, audio: &'a Audio) -> Option<&'a str> {
+		audio
+			.renditions
+			.iter()
+			.filter(|(_, config)| self.supports(&config.codec.to_string()))
+			.max_by_key(|(_, config)| (config.bitrate, config.sample_rate, config.channel_count))
+			.map(|(name, _)| name.as_str())
+	}
+
+	// The supported video renditions that fit, lowest first.
+	fn ranked<'a>(&self, video: &'a Video) -> Vec<(&'a String, &'a VideoConfig)> {
+		let supported = video
+			.renditions
+			.iter()
+			.filter(|(_, config)| self.supports(&config.codec.to_string()));
+
+		let fits = |config: &VideoConfig| match self.max_resolution {
+			Some((width, height)) => {
+				config.coded_width.unwrap_or(0) <= width && config.coded_height.unwrap_or(0) <= height
+			}
+			None => true,
+		};
+
+		let mut ranked: Vec<_> = supported.clone().filter(|(_, config)| fits(config)).collect();
+		if ranked.is_empty() {
+			// Nothing fits, so fall back to the smallest.
+			ranked.extend(supported.min_by_key(|(_, config)| (area(config), config.bitrate)));
+		}
+
+		if ranked.iter().any(|(_, config)| config.bitrate.is_some()) {
+			ranked.retain(|(_, config)| config.bitrate.is_some());
+			ranked.sort_by_key(|(_, config)| (config.bitrate, area(config)));
+		} else {
+			ranked.sort_by_key(|(_, config)| area(config));
+		}
+
+		ranked
+	}
+
+	fn supports(&self, codec: &str) -> bool {
+		let Some(codecs) = &self.codecs else {
+			return true;
+		};
+
+		codecs.iter().any(|prefix| {
+			codec
+				.strip_prefix(prefix.as_str())
+				.is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+		})
+	}
+}
+
+impl Default for RenditionSelector {
+	/// A selector with 20% hysteresis that accepts every codec and resolution.
+	fn default() -> Self {
+		Self::new(0.2)
+	}
+}
+
+fn area(config: &VideoConfig) -> u64 {
+	config.coded_width.unwrap_or(0) as u64 * config.coded_height.unwrap_or(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::catalog::{AudioCodec, AudioConfig, Container, H264};
+
+	fn rendition(height: u32, bitrate: Option<u64>) -> VideoConfig {
+		VideoConfig {
+			codec: H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: false,
+			}
+			.into(),
+			description: None,
+			coded_width: Some(height * 16 / 9),
+			coded_height: Some(height),
+			display_ratio_width: None,
+			display_ratio_height: None,
+			bitrate,
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Legacy,
+			jitter: None,
+			lang: None,
+		}
+	}
+
+	fn ladder() -> Video {
+		let mut video = Video::default();
+		video.insert("360p", rendition(360, Some(500_000))).unwrap();
+		video.insert("720p", rendition(720, Some(1_500_000))).unwrap();
+		video.insert("1080p", rendition(1080, Some(3_000_000))).unwrap();
+		video
+	}
+
+	#[test]
+	fn steps_up_with_headroom() {
+		let video = ladder();
+		let mut abr = RenditionSelector::new(0.2);
+
+		assert_eq!(abr.select(&video, Some(1_000_000)), Some("360p"));
+		// Enough for 720p, but not with 20% headroom.
+		assert_eq!(abr.select(&video, Some(1_600_000)), Some("360p"));
+		assert_eq!(abr.select(&video, Some(1_800_000)), Some("720p"));
+		assert_eq!(abr.select(&video, Some(3_500_000)), Some("720p"));
+		assert_eq!(abr.select(&video, Some(3_600_000)), Some("1080p"));
+
+		// A big jump skips straight to the top.
+		let mut abr = RenditionSelector::new(0.2);
+		assert_eq!(abr.select(&video, Some(500_000)), Some("360p"));
+		assert_eq!(abr.select(&video, Some(10_000_000)), Some("1080p"));
+	}
+
+	#[test]
+	fn steps_down_with_hysteresis() {
+		let video = ladder();
+		let mut abr = RenditionSelector::new(0.2);
+		assert_eq!(abr.select(&video, Some(4_000_000)), Some("1080p"));
+
+		// Held while the current rendition still fits.
+		assert_eq!(abr.select(&video, Some(3_100_000)), Some("1080p"));
+		assert_eq!(abr.select(&video, Some(2_900_000)), Some("720p"));
+
+		// Hovering around the threshold doesn't flip back.
+		assert_eq!(abr.select(&video, Some(3_100_000)), Some("720p"));
+		assert_eq!(abr.select(&video, Some(2_900_000)), Some("720p"));
+
+		// Nothing fits, so fall back to the lowest.
+		assert_eq!(abr.select(&video, Some(100_000)), Some("360p"));
+	}
+
+	#[test]
+	fn unknown_bandwidth_keeps_current() {
+		let mut video = ladder();
+		let mut abr = RenditionSelector::new(0.2);
+
+		assert_eq!(abr.select(&video, None), Some("360p"));
+		assert_eq!(abr.select(&video, Some(2_000_000)), Some("720p"));
+		assert_eq!(abr.select(&video, None), Some("720p"));
+		assert_eq!(abr.current(), Some("720p"));
+
+		// The current rendition was removed from the catalog.
+		video.remove("720p");
+		assert_eq!(abr.select(&video, None), Some("360p"));
+	}
+
+	#[test]
+	fn without_bitrates() {
+		let mut video = Video::default();
+		assert_eq!(RenditionSelector::new(0.2).select(&video, Some(1_000_000)), None);
+
+		video.insert("360p", rendition(360, None)).unwrap();
+		video.insert("720p", rendition(720, None)).unwrap();
+		assert_eq!(RenditionSelector::new(0.2).select(&video, Some(1_000)), Some("720p"));
+	}
+
+	#[test]
+	fn best_fits_max_resolution() {
+		let mut video = Video::default();
+		video.insert("360p", rendition(360, None)).unwrap();
+		video.insert("720p", rendition(720, None)).unwrap();
+		video.insert("1080p", rendition(1080, None)).unwrap();
+
+		assert_eq!(RenditionSelector::default().best(&video), Some("1080p"));
+		assert_eq!(
+			RenditionSelector::default().with_max_resolution(1280, 720).best(&video),
+			Some("720p")
+		);
+		// Nothing fits, so fall back to the smallest.
+		assert_eq!(
+			RenditionSelector::default().with_max_resolution(320, 180).best(&video),
+			Some("360p")
+		);
+		assert_eq!(RenditionSelector::default().best(&Video::default()), None);
+	}
+
+	#[test]
+	fn supported_codecs() {
+		let mut av1 = rendition(2160, None);
+		av1.codec = "av01.0.08M.08".parse().unwrap();
+
+		let mut video = Video::default();
+		video.insert("720p", rendition(720, None)).unwrap();
+		video.insert("1080p", rendition(1080, None)).unwrap();
+		video.insert("4k", av1).unwrap();
+
+		let best = |codecs: &[&str]| {
+			RenditionSelector::default()
+				.with_codecs(codecs.iter().copied())
+				.best(&video)
+				.map(str::to_string)
+		};
+
+		assert_eq!(best(&["av01", "avc1"]).as_deref(), Some("4k"));
+		assert_eq!(best(&["avc1"]).as_deref(), Some("1080p"));
+		assert_eq!(best(&["avc1.64001f"]).as_deref(), Some("1080p"));
+
+		// A partial codec component doesn't match.
+		assert_eq!(best(&["avc"]), None);
+		assert_eq!(best(&["vp09"]), None);
+	}
+
+	#[test]
+	fn selects_highest_bitrate_audio() {
+		let audio_rendition = |bitrate| AudioConfig {
+			codec: AudioCodec::Opus,
+			sample_rate: 48_000,
+			channel_count: 2,
+			bitrate: Some(bitrate),
+			description: None,
+			container: Container::Legacy,
+			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
+		};
+
+		let mut audio = Audio::default();
+		audio.insert("low", audio_rendition(32_000)).unwrap();
+		audio.insert("high", audio_rendition(128_000)).unwrap();
+
+		assert_eq!(RenditionSelector::default().select_audio(&audio), Some("high"));
+		assert_eq!(
+			RenditionSelector::default().with_codecs(["opus"]).select_audio(&audio),
+			Some("high")
+		);
+		assert_eq!(
+			RenditionSelector::default().with_codecs(["mp4a"]).select_audio(&audio),
+			None
+		);
+	}
+}
@@ -20,7 +20,10 @@ pub enum VideoCodec {
 	/// AV1 codec with profile and level information
 	AV1(AV1),
 
-	/// VP8 codec (no additional parameters)
+	/// VP8 codec, with no additional parameters.
+	///
+	/// The WebCodecs codec string is always `vp8`, so the profile and color fields of
+	/// a `vpcC` box have nowhere to go and are dropped on import.
 	#[display("vp8")]
 	VP8,
 
@@ -29,6 +32,18 @@ pub enum VideoCodec {
 	Unknown(String),
 }
 
+impl VideoCodec {
+	/// Returns true if the parameter sets are out-of-band (avc1, hvc1), so the decoder
+	/// can't be configured without a description.
+	pub fn requires_description(&self) -> bool {
+		match self {
+			Self::H264(h264) => !h264.inline,
+			Self::H265(h265) => !h265.in_band,
+			_ => false,
+		}
+	}
+}
+
 impl FromStr for VideoCodec {
 	type Err = Error;
 
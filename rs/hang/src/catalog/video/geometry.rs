@@ -0,0 +1,159 @@
+use super::{Rotation, Video, VideoConfig};
+
+/// The picture a player would show for decoded frames, after the catalog's transforms.
+///
+/// Frames are first stretched to the display aspect ratio, then rotated clockwise, then
+/// flipped horizontally if [`Self::flip`] is set, matching WebCodecs' `VideoFrame`.
+/// See [`Video::geometry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Geometry {
+	/// The width to display, after stretching and rotating.
+	pub width: u32,
+	/// The height to display, after stretching and rotating.
+	pub height: u32,
+	/// The clockwise rotation to apply.
+	pub rotation: Rotation,
+	/// Whether to flip horizontally after rotating.
+	pub flip: bool,
+}
+
+impl Geometry {
+	/// Returns true if the rotation is a quarter turn, so the decoded width becomes the height.
+	pub fn transposed(&self) -> bool {
+		matches!(self.rotation, Rotation::Deg90 | Rotation::Deg270)
+	}
+
+	/// Returns true if the frames can be displayed as decoded.
+	pub fn is_identity(&self) -> bool {
+		self.rotation == Rotation::Deg0 && !self.flip
+	}
+}
+
+impl Video {
+	/// Compute the display geometry of frames from the rendition, decoded at `width` x `height`.
+	///
+	/// The display aspect ratio only ever stretches a dimension, never shrinks one, and is
+	/// ignored if either side is zero. [`Self::display`] is a render size hint and isn't applied.
+	pub fn geometry(&self, config: &VideoConfig, width: u32, height: u32) -> Geometry {
+		let (mut width, mut height) = (width, height);
+
+		if let (Some(ratio_width), Some(ratio_height)) = (config.display_ratio_width, config.display_ratio_height)
+			&& ratio_width > 0
+			&& ratio_height > 0
+		{
+			let (ratio_width, ratio_height) = (ratio_width as u64, ratio_height as u64);
+
+			// Compare width / height against the ratio without dividing.
+			if (width as u64) * ratio_height < (height as u64) * ratio_width {
+				width = div_round(height as u64 * ratio_width, ratio_height);
+			} else {
+				height = div_round(width as u64 * ratio_height, ratio_width);
+			}
+		}
+
+		let rotation = self.rotation.unwrap_or_default();
+		if matches!(rotation, Rotation::Deg90 | Rotation::Deg270) {
+			std::mem::swap(&mut width, &mut height);
+		}
+
+		Geometry {
+			width,
+			height,
+			rotation,
+			flip: self.flip.unwrap_or_default(),
+		}
+	}
+}
+
+fn div_round(numerator: u64, denominator: u64) -> u32 {
+	((numerator + denominator / 2) / denominator)
+		.try_into()
+		.unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::catalog::{Container, H264};
+
+	fn config(ratio: Option<(u32, u32)>) -> VideoConfig {
+		VideoConfig {
+			codec: H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: false,
+			}
+			.into(),
+			description: None,
+			coded_width: Some(1440),
+			coded_height: Some(1080),
+			display_ratio_width: ratio.map(|ratio| ratio.0),
+			display_ratio_height: ratio.map(|ratio| ratio.1),
+			bitrate: None,
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Legacy,
+			jitter: None,
+			lang: None,
+		}
+	}
+
+	#[test]
+	fn identity() {
+		let geometry = Video::default().geometry(&config(None), 1440, 1080);
+		assert_eq!((geometry.width, geometry.height), (1440, 1080));
+		assert!(geometry.is_identity());
+		assert!(!geometry.transposed());
+	}
+
+	#[test]
+	fn rotation_swaps_dimensions() {
+		for (rotation, expected) in [
+			(Rotation::Deg90, (1080, 1440)),
+			(Rotation::Deg180, (1440, 1080)),
+			(Rotation::Deg270, (1080, 1440)),
+		] {
+			let video = Video {
+				rotation: Some(rotation),
+				flip: Some(true),
+				..Default::default()
+			};
+
+			let geometry = video.geometry(&config(None), 1440, 1080);
+			assert_eq!((geometry.width, geometry.height), expected, "{rotation:?}");
+			assert_eq!(geometry.rotation, rotation);
+			assert!(geometry.flip);
+		}
+	}
+
+	#[test]
+	fn display_ratio_stretches() {
+		let video = Video::default();
+
+		// Anamorphic 1440x1080 displayed at 16:9 widens to 1920x1080.
+		let geometry = video.geometry(&config(Some((16, 9))), 1440, 1080);
+		assert_eq!((geometry.width, geometry.height), (1920, 1080));
+
+		// A narrower ratio heightens instead: 1440x1080 at 1:1 becomes 1440x1440.
+		let geometry = video.geometry(&config(Some((1, 1))), 1440, 1080);
+		assert_eq!((geometry.width, geometry.height), (1440, 1440));
+
+		// Invalid ratios are ignored.
+		let geometry = video.geometry(&config(Some((16, 0))), 1440, 1080);
+		assert_eq!((geometry.width, geometry.height), (1440, 1080));
+	}
+
+	#[test]
+	fn display_ratio_before_rotation() {
+		let video = Video {
+			rotation: Some(Rotation::Deg90),
+			..Default::default()
+		};
+
+		let geometry = video.geometry(&config(Some((16, 9))), 1440, 1080);
+		assert_eq!((geometry.width, geometry.height), (1080, 1920));
+		assert!(geometry.transposed());
+	}
+}
@@ -1,13 +1,17 @@
 mod av1;
 mod codec;
+mod geometry;
 mod h264;
 mod h265;
+mod rotation;
 mod vp9;
 
 pub use av1::*;
 pub use codec::*;
+pub use geometry::*;
 pub use h264::*;
 pub use h265::*;
+pub use rotation::*;
 pub use vp9::*;
 
 use std::collections::{BTreeMap, btree_map};
@@ -16,7 +20,7 @@ use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, hex::Hex};
 
-use crate::catalog::Container;
+use crate::catalog::{CatalogWarning, Container};
 
 /// Information about a video track in the catalog.
 ///
@@ -37,10 +41,10 @@ pub struct Video {
 	#[serde(default)]
 	pub display: Option<Display>,
 
-	/// The rotation of the video in degrees.
+	/// The clockwise rotation of the video.
 	/// Default: 0
 	#[serde(default)]
-	pub rotation: Option<f64>,
+	pub rotation: Option<Rotation>,
 
 	/// If true, the decoder will flip the video horizontally
 	/// Default: false
@@ -50,6 +54,9 @@ pub struct Video {
 
 impl Video {
 	/// Insert a track config, returning an error if the name already exists.
+	///
+	/// The name is used as-is, so a muxer can keep source track identifiers stable across
+	/// catalog updates, including when a rendition is removed and added again.
 	pub fn insert(&mut self, name: &str, config: VideoConfig) -> crate::Result<()> {
 		let btree_map::Entry::Vacant(entry) = self.renditions.entry(name.to_string()) else {
 			return Err(crate::Error::Duplicate(name.to_string()));
@@ -140,4 +147,47 @@ pub struct VideoConfig {
 	/// - If frames are buffered into 2s segments, this would be 2s.
 	#[serde(default)]
 	pub jitter: Option<moq_lite::Time>,
+
+	/// The language of the video as a BCP-47 tag (ex. `en`), if known.
+	/// Useful for renditions with burned-in text or sign language.
+	#[serde(default, deserialize_with = "crate::catalog::lang::deserialize_lang")]
+	pub lang: Option<String>,
+}
+
+impl VideoConfig {
+	/// Check that the rendition can be decoded, appending any problems to `warnings`.
+	pub(crate) fn validate(&self, track: &str, warnings: &mut Vec<CatalogWarning>) {
+		if let VideoCodec::Unknown(codec) = &self.codec {
+			warnings.push(CatalogWarning::UnknownCodec {
+				track: track.to_string(),
+				codec: codec.clone(),
+			});
+		}
+
+		// A CMAF init segment carries the parameter sets instead.
+		if self.codec.requires_description() && self.description.is_none() && self.container == Container::Legacy {
+			warnings.push(CatalogWarning::MissingDescription {
+				track: track.to_string(),
+				codec: self.codec.to_string(),
+			});
+		}
+
+		for (field, width, height) in [
+			("coded size", self.coded_width, self.coded_height),
+			("display ratio", self.display_ratio_width, self.display_ratio_height),
+		] {
+			let valid = match (width, height) {
+				(Some(width), Some(height)) => width > 0 && height > 0,
+				(None, None) => true,
+				_ => false,
+			};
+
+			if !valid {
+				warnings.push(CatalogWarning::InvalidDimensions {
+					track: track.to_string(),
+					field,
+				});
+			}
+		}
+	}
 }
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A clockwise rotation to apply when rendering the video.
+///
+/// Only quarter turns are supported, matching WebCodecs' `VideoDecoderConfig.rotation`.
+/// On the wire this is the number of degrees, so any other value is rejected on deserialize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+	#[default]
+	Deg0,
+	Deg90,
+	Deg180,
+	Deg270,
+}
+
+impl Rotation {
+	/// The rotation in degrees.
+	pub fn degrees(self) -> u16 {
+		match self {
+			Self::Deg0 => 0,
+			Self::Deg90 => 90,
+			Self::Deg180 => 180,
+			Self::Deg270 => 270,
+		}
+	}
+}
+
+impl TryFrom<f64> for Rotation {
+	type Error = crate::Error;
+
+	fn try_from(degrees: f64) -> Result<Self, Self::Error> {
+		match degrees {
+			0.0 => Ok(Self::Deg0),
+			90.0 => Ok(Self::Deg90),
+			180.0 => Ok(Self::Deg180),
+			270.0 => Ok(Self::Deg270),
+			_ => Err(crate::Error::InvalidRotation(degrees)),
+		}
+	}
+}
+
+impl Serialize for Rotation {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_u16(self.degrees())
+	}
+}
+
+impl<'de> Deserialize<'de> for Rotation {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		// Older catalogs used a float, so accept `90.0` as well as `90`.
+		let degrees = f64::deserialize(deserializer)?;
+		Self::try_from(degrees).map_err(serde::de::Error::custom)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn roundtrip() {
+		for rotation in [Rotation::Deg0, Rotation::Deg90, Rotation::Deg180, Rotation::Deg270] {
+			let json = serde_json::to_string(&rotation).unwrap();
+			assert_eq!(json, rotation.degrees().to_string());
+			assert_eq!(serde_json::from_str::<Rotation>(&json).unwrap(), rotation);
+		}
+	}
+
+	#[test]
+	fn float() {
+		assert_eq!(serde_json::from_str::<Rotation>("90.0").unwrap(), Rotation::Deg90);
+	}
+
+	#[test]
+	fn invalid() {
+		for json in ["45", "-17", "360", "-90", "\"90\""] {
+			assert!(serde_json::from_str::<Rotation>(json).is_err(), "{json}");
+		}
+	}
+}
@@ -0,0 +1,25 @@
+/// A problem with a rendition that would stop it from being decoded, found by
+/// [`Catalog::validate`](super::Catalog::validate).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CatalogWarning {
+	/// The codec keeps its parameter sets out-of-band, but there's no description or init segment.
+	#[error("{track}: {codec} requires a description")]
+	MissingDescription { track: String, codec: String },
+
+	/// The codec string wasn't recognized, so a decoder can't be configured.
+	#[error("{track}: unknown codec: {codec}")]
+	UnknownCodec { track: String, codec: String },
+
+	/// Only one of a width/height pair is set, or either is zero.
+	#[error("{track}: invalid {field}")]
+	InvalidDimensions { track: String, field: &'static str },
+
+	/// The audio sample rate is zero or implausibly high.
+	#[error("{track}: invalid sample rate: {sample_rate}")]
+	InvalidSampleRate { track: String, sample_rate: u32 },
+
+	/// The audio channel count is zero or implausibly high.
+	#[error("{track}: invalid channel count: {channel_count}")]
+	InvalidChannelCount { track: String, channel_count: u32 },
+}
@@ -5,11 +5,17 @@ use crate::Error;
 
 pub type Timestamp = moq_lite::Timescale<1_000_000>;
 
+/// The object extension carrying the capture timestamp, as in the LOC draft.
+///
+/// The value is the wall clock time in microseconds since the Unix epoch.
+pub const CAPTURE_TIMESTAMP_EXTENSION: u64 = 0x02;
+
 /// A media frame with a timestamp and codec-specific payload.
 ///
 /// Frames are the fundamental unit of media data in hang. Each frame contains:
 /// - A timestamp when they should be rendered.
 /// - A codec-specific payload.
+/// - An optional wall clock time when they were captured.
 #[derive(Clone, Debug)]
 pub struct Frame {
 	/// The presentation timestamp for this frame.
@@ -25,6 +31,13 @@ pub struct Frame {
 	/// The debug implementation shows only the payload length for brevity.
 	#[debug("{} bytes", payload.len())]
 	pub payload: Bytes,
+
+	/// The wall clock time this frame was captured, in microseconds since the Unix epoch.
+	///
+	/// Set by live sources to measure glass-to-glass latency; importers leave it unset.
+	/// It's carried as the [CAPTURE_TIMESTAMP_EXTENSION] object extension, so frames without
+	/// it cost nothing extra. Only the IETF transport delivers extensions; moq-lite drops them.
+	pub capture: Option<Timestamp>,
 }
 
 impl Frame {
@@ -36,7 +49,16 @@ impl Frame {
 
 		let size = header.len() + self.payload.len();
 
-		let mut chunked = group.create_frame(size.into())?;
+		let mut info = moq_lite::Frame::from(size);
+		if let Some(capture) = self.capture {
+			let micros = capture.as_micros() as u64;
+			info.extensions.push((
+				CAPTURE_TIMESTAMP_EXTENSION,
+				Bytes::copy_from_slice(&micros.to_be_bytes()),
+			));
+		}
+
+		let mut chunked = group.create_frame(info)?;
 		chunked.write(header.freeze())?;
 		chunked.write(self.payload.clone())?;
 		chunked.finish()?;
@@ -49,6 +71,70 @@ impl Frame {
 		let timestamp = Timestamp::decode(&mut buf)?;
 		let payload = buf.copy_to_bytes(buf.remaining());
 
-		Ok(Self { timestamp, payload })
+		Ok(Self {
+			timestamp,
+			payload,
+			capture: None,
+		})
+	}
+
+	/// Decode a frame from its moq-lite header and raw bytes, including the capture timestamp.
+	///
+	/// Unknown extensions are ignored.
+	pub fn decode_with_info(info: &moq_lite::Frame, buf: impl Buf) -> Result<Self, Error> {
+		let mut frame = Self::decode(buf)?;
+
+		if let Some((_, value)) = info
+			.extensions
+			.iter()
+			.find(|(kind, _)| *kind == CAPTURE_TIMESTAMP_EXTENSION)
+		{
+			// Integer extensions are stored big-endian, possibly shorter than 8 bytes.
+			let micros = value.iter().fold(0u64, |int, byte| (int << 8) | *byte as u64);
+			frame.capture = Some(Timestamp::from_micros(micros)?);
+		}
+
+		Ok(frame)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[tokio::test]
+	async fn capture_round_trip() {
+		let mut group = moq_lite::Group { sequence: 0 }.produce();
+
+		let captured = Frame {
+			timestamp: Timestamp::from_micros(1_000).unwrap(),
+			payload: Bytes::from_static(b"captured"),
+			capture: Some(Timestamp::from_micros(1_760_000_000_000_000).unwrap()),
+		};
+		let plain = Frame {
+			timestamp: Timestamp::from_micros(2_000).unwrap(),
+			payload: Bytes::from_static(b"plain"),
+			capture: None,
+		};
+		captured.encode(&mut group).unwrap();
+		plain.encode(&mut group).unwrap();
+		group.finish().unwrap();
+
+		let mut consumer = group.consume();
+
+		let (info, data) = consumer.read_frame_info().await.unwrap().unwrap();
+		let frame = Frame::decode_with_info(&info, data).unwrap();
+		assert_eq!(frame.timestamp, captured.timestamp);
+		assert_eq!(frame.payload, captured.payload);
+		assert_eq!(frame.capture, captured.capture);
+
+		// Frames without a capture timestamp carry no extension at all.
+		let (info, data) = consumer.read_frame_info().await.unwrap().unwrap();
+		assert!(info.extensions.is_empty());
+		let frame = Frame::decode_with_info(&info, data).unwrap();
+		assert_eq!(frame.payload, plain.payload);
+		assert_eq!(frame.capture, None);
+
+		assert!(consumer.read_frame_info().await.unwrap().is_none());
 	}
 }
@@ -1,3 +1,5 @@
 mod frame;
+mod text;
 
 pub use frame::*;
+pub use text::*;
@@ -0,0 +1,238 @@
+use std::fmt::Write;
+
+use bytes::{Buf, Bytes, BytesMut};
+
+use super::Timestamp;
+use crate::Error;
+
+/// A timed text cue, such as a caption or subtitle line.
+///
+/// Cues are carried on their own track, one moq-lite frame per group. Each frame uses
+/// the same layout as a Legacy media frame: a VarInt timestamp prefix (the start of the
+/// first cue) followed by one or more WebVTT cue blocks. WebVTT timings have millisecond
+/// precision, so anything finer is truncated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cue {
+	/// When the cue should be shown.
+	pub start: Timestamp,
+
+	/// When the cue should be hidden.
+	pub end: Timestamp,
+
+	/// The cue payload, which may span multiple lines but not contain a blank one.
+	pub text: String,
+}
+
+impl Cue {
+	pub fn new(start: Timestamp, end: Timestamp, text: impl Into<String>) -> Self {
+		Self {
+			start,
+			end,
+			text: text.into(),
+		}
+	}
+
+	/// Encode a list of cues as the payload of a single frame.
+	pub fn encode_list(cues: &[Cue]) -> Result<Bytes, Error> {
+		let first = cues.first().ok_or_else(|| invalid("no cues"))?;
+
+		let mut buf = BytesMut::new();
+		first.start.encode(&mut buf).map_err(moq_lite::Error::from)?;
+
+		let mut vtt = String::new();
+		for cue in cues {
+			if cue.end < cue.start {
+				return Err(invalid("cue ends before it starts"));
+			}
+			if cue.text.contains("-->") || cue.text.lines().any(|line| line.trim().is_empty()) {
+				return Err(invalid("cue text can't contain blank lines or \"-->\""));
+			}
+
+			if !vtt.is_empty() {
+				vtt.push('\n');
+			}
+			let _ = writeln!(vtt, "{} --> {}", format_time(cue.start), format_time(cue.end));
+			let _ = writeln!(vtt, "{}", cue.text);
+		}
+
+		buf.extend_from_slice(vtt.as_bytes());
+		Ok(buf.freeze())
+	}
+
+	/// Decode the cues from the payload of a single frame.
+	///
+	/// Cue identifiers and settings are accepted but discarded.
+	pub fn decode_list(mut buf: impl Buf) -> Result<Vec<Cue>, Error> {
+		// Only the cue timings are authoritative; the prefix is for Legacy compatibility.
+		let _timestamp = Timestamp::decode(&mut buf)?;
+
+		let payload = buf.copy_to_bytes(buf.remaining());
+		let vtt = std::str::from_utf8(&payload).map_err(|_| invalid("not UTF-8"))?;
+		let vtt = vtt.replace("\r\n", "\n");
+
+		let mut cues = Vec::new();
+		for block in vtt.split("\n\n").filter(|block| !block.trim().is_empty()) {
+			let mut lines = block.lines().skip_while(|line| line.is_empty());
+
+			// An optional identifier precedes the timings.
+			let mut timing = lines.next().unwrap_or_default();
+			if !timing.contains("-->") {
+				timing = lines.next().ok_or_else(|| invalid("missing cue timings"))?;
+			}
+
+			let (start, rest) = timing.split_once("-->").ok_or_else(|| invalid("missing cue timings"))?;
+			let end = rest.split_whitespace().next().unwrap_or_default();
+
+			let text = lines.collect::<Vec<_>>().join("\n");
+			cues.push(Cue::new(parse_time(start.trim())?, parse_time(end)?, text));
+		}
+
+		if cues.is_empty() {
+			return Err(invalid("no cues"));
+		}
+
+		Ok(cues)
+	}
+}
+
+/// Writes timed text cues to a track, one group per frame.
+pub struct TextProducer {
+	track: moq_lite::TrackProducer,
+}
+
+impl TextProducer {
+	pub fn new(track: moq_lite::TrackProducer) -> Self {
+		Self { track }
+	}
+
+	/// Write a single cue as its own frame.
+	pub fn write(&mut self, start: Timestamp, end: Timestamp, text: &str) -> Result<(), Error> {
+		self.write_cues(&[Cue::new(start, end, text)])
+	}
+
+	/// Write a list of cues as a single frame, shown together.
+	pub fn write_cues(&mut self, cues: &[Cue]) -> Result<(), Error> {
+		self.track.write_frame(Cue::encode_list(cues)?)?;
+		Ok(())
+	}
+
+	/// Mark the track as finished.
+	pub fn finish(&mut self) -> Result<(), Error> {
+		self.track.finish()?;
+		Ok(())
+	}
+}
+
+/// Reads timed text cues from a track written by a [`TextProducer`].
+pub struct TextConsumer {
+	track: moq_lite::TrackConsumer,
+}
+
+impl TextConsumer {
+	pub fn new(track: moq_lite::TrackConsumer) -> Self {
+		Self { track }
+	}
+
+	/// Return the cues from the next frame, or `None` when the track is finished.
+	pub async fn read(&mut self) -> Result<Option<Vec<Cue>>, Error> {
+		match self.track.read_frame().await? {
+			Some(frame) => Ok(Some(Cue::decode_list(frame)?)),
+			None => Ok(None),
+		}
+	}
+}
+
+fn invalid(reason: &str) -> Error {
+	Error::InvalidWebVtt(reason.to_string())
+}
+
+/// Format a timestamp as `hh:mm:ss.ttt`.
+fn format_time(time: Timestamp) -> String {
+	let millis = time.as_millis();
+	let (hours, millis) = (millis / 3_600_000, millis % 3_600_000);
+	let (minutes, millis) = (millis / 60_000, millis % 60_000);
+	let (seconds, millis) = (millis / 1000, millis % 1000);
+	format!("{hours:02}:{minutes:02}:{seconds:02}.{millis:03}")
+}
+
+/// Parse a timestamp formatted as `hh:mm:ss.ttt` or `mm:ss.ttt`.
+fn parse_time(s: &str) -> Result<Timestamp, Error> {
+	let err = || invalid("invalid cue timestamp");
+
+	let (rest, millis) = s.split_once('.').ok_or_else(err)?;
+	if millis.len() != 3 {
+		return Err(err());
+	}
+	let millis: u64 = millis.parse().map_err(|_| err())?;
+
+	let mut parts = rest.rsplit(':');
+	let seconds: u64 = parts.next().ok_or_else(err)?.parse().map_err(|_| err())?;
+	let minutes: u64 = parts.next().ok_or_else(err)?.parse().map_err(|_| err())?;
+	let hours: u64 = match parts.next() {
+		Some(hours) => hours.parse().map_err(|_| err())?,
+		None => 0,
+	};
+	if parts.next().is_some() || minutes >= 60 || seconds >= 60 {
+		return Err(err());
+	}
+
+	let total = ((hours * 60 + minutes) * 60 + seconds) * 1000 + millis;
+	Ok(Timestamp::from_millis(total)?)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn ms(millis: u64) -> Timestamp {
+		Timestamp::from_millis(millis).unwrap()
+	}
+
+	#[test]
+	fn roundtrip() {
+		let cues = vec![
+			Cue::new(ms(1_000), ms(3_500), "Hello"),
+			Cue::new(ms(3_723_004), ms(3_725_000), "<v Bob>Two\nlines"),
+		];
+
+		let frame = Cue::encode_list(&cues).unwrap();
+		assert!(frame.ends_with(b"01:02:03.004 --> 01:02:05.000\n<v Bob>Two\nlines\n"));
+		assert_eq!(Cue::decode_list(frame).unwrap(), cues);
+	}
+
+	#[test]
+	fn decode_ignores_identifiers_and_settings() {
+		let mut buf = BytesMut::new();
+		ms(5_000).encode(&mut buf).unwrap();
+		buf.extend_from_slice(b"intro\r\n00:05.000 --> 00:06.250 align:start line:0\r\nHi there\r\n\r\n");
+
+		let cues = Cue::decode_list(buf.freeze()).unwrap();
+		assert_eq!(cues, vec![Cue::new(ms(5_000), ms(6_250), "Hi there")]);
+	}
+
+	#[test]
+	fn encode_rejects_invalid() {
+		assert!(Cue::encode_list(&[]).is_err());
+		assert!(Cue::encode_list(&[Cue::new(ms(2), ms(1), "backwards")]).is_err());
+		assert!(Cue::encode_list(&[Cue::new(ms(1), ms(2), "blank\n\nline")]).is_err());
+	}
+
+	#[tokio::test]
+	async fn producer_consumer() {
+		let track = moq_lite::Track::new("captions").produce();
+		let mut consumer = TextConsumer::new(track.consume());
+		let mut producer = TextProducer::new(track);
+
+		producer.write(ms(0), ms(1_000), "first").unwrap();
+		producer.write(ms(1_000), ms(2_000), "second").unwrap();
+		producer.finish().unwrap();
+
+		let first = consumer.read().await.unwrap().unwrap();
+		assert_eq!(first, vec![Cue::new(ms(0), ms(1_000), "first")]);
+
+		let second = consumer.read().await.unwrap().unwrap();
+		assert_eq!(second, vec![Cue::new(ms(1_000), ms(2_000), "second")]);
+
+		assert_eq!(consumer.read().await.unwrap(), None);
+	}
+}
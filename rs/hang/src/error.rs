@@ -47,13 +47,29 @@ pub enum Error {
 	#[error("empty group")]
 	EmptyGroup,
 
+	/// A timed text frame is not valid WebVTT.
+	#[error("invalid WebVTT: {0}")]
+	InvalidWebVtt(String),
+
 	/// The format is not recognized.
 	#[error("unknown format: {0}")]
 	UnknownFormat(String),
 
+	/// The rotation is not a multiple of 90 degrees in the range 0-270.
+	#[error("invalid rotation: {0}")]
+	InvalidRotation(f64),
+
 	/// A track with this name already exists in the catalog.
 	#[error("duplicate track: {0}")]
 	Duplicate(String),
+
+	/// The catalog has more renditions of one kind than the consumer allows.
+	#[error("too many {kind} renditions: {count} > {max}")]
+	TooManyRenditions {
+		kind: &'static str,
+		count: usize,
+		max: usize,
+	},
 }
 
 /// A Result type alias for hang operations.
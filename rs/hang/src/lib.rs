@@ -11,6 +11,9 @@
 //! - **Legacy**: A timestamp followed by the codec payload.
 //! - **CMAF**: Fragmented MP4 container (moof+mdat pair)
 //!
+//! Timed text, such as captions, is carried on its own track as WebVTT cues with the Legacy
+//! timestamp prefix. See [container::TextProducer] and [container::TextConsumer].
+//!
 //! See the [moq-mux](https://crates.io/crates/moq-mux) crate for importing existing media formats into hang broadcasts.
 mod error;
 
@@ -1,3 +1,4 @@
+use hang::catalog::{Rendition, RenditionKind};
 use std::ffi::c_char;
 use tokio::sync::oneshot;
 
@@ -120,17 +121,13 @@ impl Consume {
 	pub fn video_config(&mut self, catalog: Id, index: usize, dst: &mut moq_video_config) -> Result<(), Error> {
 		let consume = self.catalog.get(catalog).ok_or(Error::CatalogNotFound)?;
 
-		let (rendition, config) = consume
-			.catalog
-			.video
-			.renditions
-			.iter()
-			.nth(index)
-			.ok_or(Error::NoIndex)?;
+		let Some((rendition, Rendition::Video(config))) = consume.catalog.rendition(RenditionKind::Video, index) else {
+			return Err(Error::NoIndex);
+		};
 		let codec = consume.video_codec.get(index).ok_or(Error::NoIndex)?;
 
 		*dst = moq_video_config {
-			name: rendition.as_str().as_ptr() as *const c_char,
+			name: rendition.as_ptr() as *const c_char,
 			name_len: rendition.len(),
 			codec: codec.as_str().as_ptr() as *const c_char,
 			codec_len: codec.len(),
@@ -158,17 +155,13 @@ impl Consume {
 	pub fn audio_config(&mut self, catalog: Id, index: usize, dst: &mut moq_audio_config) -> Result<(), Error> {
 		let consume = self.catalog.get(catalog).ok_or(Error::CatalogNotFound)?;
 
-		let (rendition, config) = consume
-			.catalog
-			.audio
-			.renditions
-			.iter()
-			.nth(index)
-			.ok_or(Error::NoIndex)?;
+		let Some((rendition, Rendition::Audio(config))) = consume.catalog.rendition(RenditionKind::Audio, index) else {
+			return Err(Error::NoIndex);
+		};
 		let codec = consume.audio_codec.get(index).ok_or(Error::NoIndex)?;
 
 		*dst = moq_audio_config {
-			name: rendition.as_str().as_ptr() as *const c_char,
+			name: rendition.as_ptr() as *const c_char,
 			name_len: rendition.len(),
 			codec: codec.as_str().as_ptr() as *const c_char,
 			codec_len: codec.len(),
@@ -207,41 +200,7 @@ impl Consume {
 		latency: std::time::Duration,
 		on_frame: OnStatus,
 	) -> Result<Id, Error> {
-		let consume = self.catalog.get(catalog).ok_or(Error::CatalogNotFound)?;
-		let rendition = consume
-			.catalog
-			.video
-			.renditions
-			.keys()
-			.nth(index)
-			.ok_or(Error::NoIndex)?;
-
-		let track = consume.broadcast.subscribe_track(&moq_lite::Track {
-			name: rendition.clone(),
-			priority: 1, // TODO: Remove priority
-		})?;
-		let track = moq_mux::container::Consumer::new(track, moq_mux::container::Hang::Legacy).with_latency(latency);
-
-		let channel = oneshot::channel();
-		let entry = TaskEntry {
-			close: channel.0,
-			callback: on_frame,
-		};
-		let id = self.track_task.insert(Some(entry))?;
-
-		tokio::spawn(async move {
-			let res = tokio::select! {
-				res = Self::run_track(id, track) => res,
-				_ = channel.1 => Ok(()),
-			};
-
-			// The lock is dropped before the callback is invoked.
-			if let Some(entry) = State::lock().consume.track_task.remove(id).flatten() {
-				entry.callback.call(res);
-			}
-		});
-
-		Ok(id)
+		self.ordered(catalog, RenditionKind::Video, index, latency, on_frame)
 	}
 
 	pub fn audio_ordered(
@@ -250,19 +209,32 @@ impl Consume {
 		index: usize,
 		latency: std::time::Duration,
 		on_frame: OnStatus,
+	) -> Result<Id, Error> {
+		self.ordered(catalog, RenditionKind::Audio, index, latency, on_frame)
+	}
+
+	fn ordered(
+		&mut self,
+		catalog: Id,
+		kind: RenditionKind,
+		index: usize,
+		latency: std::time::Duration,
+		on_frame: OnStatus,
 	) -> Result<Id, Error> {
 		let consume = self.catalog.get(catalog).ok_or(Error::CatalogNotFound)?;
-		let rendition = consume
-			.catalog
-			.audio
-			.renditions
-			.keys()
-			.nth(index)
-			.ok_or(Error::NoIndex)?;
+		let (rendition, _) = consume.catalog.rendition(kind, index).ok_or(Error::NoIndex)?;
+
+		let priority = match kind {
+			RenditionKind::Video => moq_lite::Priority::low(),
+			RenditionKind::Audio => moq_lite::Priority::high(),
+		};
 
 		let track = consume.broadcast.subscribe_track(&moq_lite::Track {
-			name: rendition.clone(),
-			priority: 2, // TODO: Remove priority
+			name: rendition.to_string(),
+			priority,
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
 		})?;
 		let track = moq_mux::container::Consumer::new(track, moq_mux::container::Hang::Legacy).with_latency(latency);
 
@@ -99,10 +99,7 @@ async fn handle_viewer_commands(
 	broadcast: moq_lite::BroadcastConsumer,
 	cmd_tx: &tokio::sync::mpsc::Sender<Command>,
 ) -> anyhow::Result<()> {
-	let command_track = moq_lite::Track {
-		name: "command".to_string(),
-		priority: 0,
-	};
+	let command_track = moq_lite::Track::new("command");
 
 	let mut track = broadcast.subscribe_track(&command_track)?;
 
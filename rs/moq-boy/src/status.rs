@@ -42,7 +42,10 @@ impl StatusPublisher {
 	pub fn new(broadcast: &mut moq_lite::BroadcastProducer) -> anyhow::Result<Self> {
 		let track = moq_lite::Track {
 			name: "status".to_string(),
-			priority: 10,
+			priority: moq_lite::Priority(10),
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
 		};
 		let producer = broadcast.create_track(track)?;
 
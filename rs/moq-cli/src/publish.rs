@@ -29,6 +29,18 @@ impl PublishDecoder {
 			Self::Hls(_) => unreachable!(),
 		}
 	}
+
+	/// Flush the final frame and group, then close the tracks at EOF (Avc3 or Fmp4 only).
+	fn finish(&mut self, buffer: &mut bytes::BytesMut) -> anyhow::Result<()> {
+		match self {
+			Self::Avc3(d) => {
+				d.finalize(buffer)?;
+				d.finish()
+			}
+			Self::Fmp4(d) => d.finish(),
+			Self::Hls(_) => unreachable!(),
+		}
+	}
 }
 
 pub struct Publish {
@@ -47,7 +59,10 @@ impl Publish {
 				PublishDecoder::Avc3(Box::new(avc3))
 			}
 			PublishFormat::Fmp4 => {
-				let fmp4 = import::Fmp4::new(broadcast.clone(), catalog.clone());
+				let mut fmp4 = import::Fmp4::new(broadcast.clone(), catalog.clone());
+				if stdin_is_file() {
+					fmp4 = fmp4.with_mode(hang::catalog::Mode::Recording);
+				}
 				PublishDecoder::Fmp4(Box::new(fmp4))
 			}
 			PublishFormat::Hls { playlist } => {
@@ -78,10 +93,29 @@ impl Publish {
 			loop {
 				let n = tokio::io::AsyncReadExt::read_buf(&mut stdin, &mut buffer).await?;
 				if n == 0 {
-					return Ok(());
+					return self.decoder.finish(&mut buffer);
 				}
 				self.decoder.decode_buf(&mut buffer)?;
 			}
 		}
 	}
 }
+
+/// Whether stdin is redirected from a regular file, rather than a pipe or terminal.
+///
+/// A file is a recording that's already complete, while a pipe is usually a live encoder.
+#[cfg(unix)]
+fn stdin_is_file() -> bool {
+	use std::os::fd::AsFd;
+
+	std::io::stdin()
+		.as_fd()
+		.try_clone_to_owned()
+		.and_then(|fd| std::fs::File::from(fd).metadata())
+		.is_ok_and(|metadata| metadata.is_file())
+}
+
+#[cfg(not(unix))]
+fn stdin_is_file() -> bool {
+	false
+}
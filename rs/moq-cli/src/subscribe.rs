@@ -13,7 +13,7 @@ pub struct SubscribeArgs {
 	#[arg(long)]
 	pub output: OutputFormat,
 
-	/// Maximum latency in milliseconds before skipping groups
+	/// Maximum latency in milliseconds before skipping groups of a live broadcast; recordings never skip
 	#[arg(long, default_value = "500")]
 	pub max_latency: u64,
 }
@@ -25,15 +25,12 @@ pub async fn run_web(
 	}
 
 	let fingerprint_handler = move || async move {
-		// Get the first certificate's fingerprint.
-		// TODO serve all of them so we can support multiple signature algorithms.
+		// Serve every certificate's fingerprint, one per line.
 		tls_info
 			.read()
 			.expect("tls_info read lock poisoned")
 			.fingerprints
-			.first()
-			.expect("missing certificate")
-			.clone()
+			.join("\n")
 	};
 
 	let mut app = Router::new()
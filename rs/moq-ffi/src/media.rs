@@ -123,7 +123,7 @@ pub fn convert_catalog(catalog: &hang::catalog::Catalog) -> MoqCatalog {
 		video,
 		audio,
 		display,
-		rotation: catalog.video.rotation,
+		rotation: catalog.video.rotation.map(|rotation| rotation.degrees().into()),
 		flip: catalog.video.flip,
 	}
 }
@@ -1,6 +1,6 @@
 use crate::{
-	ALPN_14, ALPN_15, ALPN_16, ALPN_17, ALPN_LITE, ALPN_LITE_03, ALPN_LITE_04, Error, NEGOTIATED, OriginConsumer,
-	OriginProducer, Session, Version, Versions,
+	ALPN_14, ALPN_15, ALPN_16, ALPN_17, ALPN_LITE, ALPN_LITE_03, ALPN_LITE_04, ALPN_LITE_05, Error, NEGOTIATED,
+	OriginConsumer, OriginProducer, PublishStats, Session, Version, Versions,
 	coding::{self, Decode, Encode, Stream},
 	ietf, lite, setup,
 };
@@ -11,6 +11,7 @@ pub struct Client {
 	publish: Option<OriginConsumer>,
 	consume: Option<OriginProducer>,
 	versions: Versions,
+	stats: PublishStats,
 }
 
 impl Client {
@@ -41,6 +42,12 @@ impl Client {
 		self
 	}
 
+	/// Count the subscriptions and groups served to the peer.
+	pub fn with_stats(mut self, stats: PublishStats) -> Self {
+		self.stats = stats;
+		self
+	}
+
 	/// Perform the MoQ handshake as a client negotiating the version.
 	pub async fn connect<S: web_transport_trait::Session>(&self, session: S) -> Result<Session, Error> {
 		if self.publish.is_none() && self.consume.is_none() {
@@ -63,6 +70,7 @@ impl Client {
 					None,
 					true,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					ietf::Version::Draft17,
 				)?;
@@ -91,6 +99,22 @@ impl Client {
 					.ok_or(Error::Version)?;
 				(v, v.into())
 			}
+			Some(ALPN_LITE_05) => {
+				self.versions
+					.select(Version::Lite(lite::Version::Lite05))
+					.ok_or(Error::Version)?;
+
+				let recv_bw = lite::start(
+					session.clone(),
+					None,
+					self.publish.clone(),
+					self.stats.clone(),
+					self.consume.clone(),
+					lite::Version::Lite05,
+				)?;
+
+				return Ok(Session::new(session, lite::Version::Lite05.into(), recv_bw));
+			}
 			Some(ALPN_LITE_04) => {
 				self.versions
 					.select(Version::Lite(lite::Version::Lite04))
@@ -100,6 +124,7 @@ impl Client {
 					session.clone(),
 					None,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					lite::Version::Lite04,
 				)?;
@@ -116,6 +141,7 @@ impl Client {
 					session.clone(),
 					None,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					lite::Version::Lite03,
 				)?;
@@ -161,6 +187,7 @@ impl Client {
 					session.clone(),
 					Some(stream),
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					v,
 				)?
@@ -179,6 +206,7 @@ impl Client {
 					request_id_max,
 					true,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					v,
 				)?;
@@ -193,176 +221,10 @@ impl Client {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use std::{
-		collections::VecDeque,
-		sync::{Arc, Mutex},
-	};
 
 	use crate::coding::{Decode, Encode};
-	use bytes::{BufMut, Bytes};
-
-	#[derive(Debug, Clone, Default)]
-	struct FakeError;
-
-	impl std::fmt::Display for FakeError {
-		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-			write!(f, "fake transport error")
-		}
-	}
-
-	impl std::error::Error for FakeError {}
-
-	impl web_transport_trait::Error for FakeError {
-		fn session_error(&self) -> Option<(u32, String)> {
-			Some((0, "closed".to_string()))
-		}
-	}
-
-	#[derive(Clone, Default)]
-	struct FakeSession {
-		state: Arc<FakeSessionState>,
-	}
-
-	#[derive(Default)]
-	struct FakeSessionState {
-		protocol: Option<&'static str>,
-		control_stream: Mutex<Option<(FakeSendStream, FakeRecvStream)>>,
-		close_events: Mutex<Vec<(u32, String)>>,
-		close_notify: tokio::sync::Notify,
-		control_writes: Arc<Mutex<Vec<u8>>>,
-	}
-
-	impl FakeSession {
-		fn new(protocol: Option<&'static str>, server_control_bytes: Vec<u8>) -> Self {
-			let writes = Arc::new(Mutex::new(Vec::new()));
-			let send = FakeSendStream { writes: writes.clone() };
-			let recv = FakeRecvStream {
-				data: VecDeque::from(server_control_bytes),
-			};
-			let state = FakeSessionState {
-				protocol,
-				control_stream: Mutex::new(Some((send, recv))),
-				close_events: Mutex::new(Vec::new()),
-				close_notify: tokio::sync::Notify::new(),
-				control_writes: writes,
-			};
-			Self { state: Arc::new(state) }
-		}
-
-		fn control_writes(&self) -> Vec<u8> {
-			self.state.control_writes.lock().unwrap().clone()
-		}
-
-		async fn wait_for_first_close(&self) -> (u32, String) {
-			loop {
-				let notified = self.state.close_notify.notified();
-				if let Some(close) = self.state.close_events.lock().unwrap().first().cloned() {
-					return close;
-				}
-				notified.await;
-			}
-		}
-	}
-
-	impl web_transport_trait::Session for FakeSession {
-		type SendStream = FakeSendStream;
-		type RecvStream = FakeRecvStream;
-		type Error = FakeError;
-
-		async fn accept_uni(&self) -> Result<Self::RecvStream, Self::Error> {
-			std::future::pending().await
-		}
-
-		async fn accept_bi(&self) -> Result<(Self::SendStream, Self::RecvStream), Self::Error> {
-			std::future::pending().await
-		}
-
-		async fn open_bi(&self) -> Result<(Self::SendStream, Self::RecvStream), Self::Error> {
-			self.state.control_stream.lock().unwrap().take().ok_or(FakeError)
-		}
-
-		async fn open_uni(&self) -> Result<Self::SendStream, Self::Error> {
-			std::future::pending().await
-		}
-
-		fn send_datagram(&self, _payload: Bytes) -> Result<(), Self::Error> {
-			Ok(())
-		}
-
-		async fn recv_datagram(&self) -> Result<Bytes, Self::Error> {
-			std::future::pending().await
-		}
-
-		fn max_datagram_size(&self) -> usize {
-			1200
-		}
-
-		fn protocol(&self) -> Option<&str> {
-			self.state.protocol
-		}
-
-		fn close(&self, code: u32, reason: &str) {
-			self.state.close_events.lock().unwrap().push((code, reason.to_string()));
-			self.state.close_notify.notify_waiters();
-		}
-
-		async fn closed(&self) -> Self::Error {
-			self.state.close_notify.notified().await;
-			FakeError
-		}
-	}
-
-	#[derive(Clone, Default)]
-	struct FakeSendStream {
-		writes: Arc<Mutex<Vec<u8>>>,
-	}
-
-	impl web_transport_trait::SendStream for FakeSendStream {
-		type Error = FakeError;
-
-		async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
-			self.writes.lock().unwrap().put_slice(buf);
-			Ok(buf.len())
-		}
-
-		fn set_priority(&mut self, _order: u8) {}
-
-		fn finish(&mut self) -> Result<(), Self::Error> {
-			Ok(())
-		}
-
-		fn reset(&mut self, _code: u32) {}
-
-		async fn closed(&mut self) -> Result<(), Self::Error> {
-			Ok(())
-		}
-	}
-
-	struct FakeRecvStream {
-		data: VecDeque<u8>,
-	}
-
-	impl web_transport_trait::RecvStream for FakeRecvStream {
-		type Error = FakeError;
-
-		async fn read(&mut self, dst: &mut [u8]) -> Result<Option<usize>, Self::Error> {
-			if self.data.is_empty() {
-				return Ok(None);
-			}
-
-			let size = dst.len().min(self.data.len());
-			for slot in dst.iter_mut().take(size) {
-				*slot = self.data.pop_front().unwrap();
-			}
-			Ok(Some(size))
-		}
-
-		fn stop(&mut self, _code: u32) {}
-
-		async fn closed(&mut self) -> Result<(), Self::Error> {
-			Ok(())
-		}
-	}
+	use crate::fake::FakeSession;
+	use bytes::Bytes;
 
 	fn mock_server_setup(negotiated: Version) -> Vec<u8> {
 		let mut encoded = Vec::new();
@@ -424,4 +286,26 @@ mod tests {
 	async fn no_alpn_falls_back_to_draft14_and_switches_version_post_setup() {
 		run_alpn_lite_fallback_case(None).await;
 	}
+
+	#[tokio::test(start_paused = true)]
+	async fn max_datagram_size_reflects_transport() {
+		let version = Version::Lite(lite::Version::Lite01);
+
+		let fake = FakeSession::new(Some(ALPN_LITE), mock_server_setup(version)).with_max_datagram_size(1350);
+		let session = Client::new()
+			.with_versions([version].into())
+			.connect(fake)
+			.await
+			.unwrap();
+		assert_eq!(session.max_datagram_size(), Some(1350));
+
+		// Zero means the peer didn't negotiate datagrams.
+		let fake = FakeSession::new(Some(ALPN_LITE), mock_server_setup(version)).with_max_datagram_size(0);
+		let session = Client::new()
+			.with_versions([version].into())
+			.connect(fake)
+			.await
+			.unwrap();
+		assert_eq!(session.max_datagram_size(), None);
+	}
 }
@@ -19,6 +19,9 @@ pub enum DecodeError {
 	#[error("long buffer")]
 	Long,
 
+	#[error("too long")]
+	TooLong,
+
 	#[error("invalid string")]
 	InvalidString(#[from] FromUtf8Error),
 
@@ -0,0 +1,230 @@
+//! An in-memory transport for unit tests, recording everything written to it.
+
+use std::{
+	collections::VecDeque,
+	sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FakeError;
+
+impl std::fmt::Display for FakeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "fake transport error")
+	}
+}
+
+impl std::error::Error for FakeError {}
+
+impl web_transport_trait::Error for FakeError {
+	fn session_error(&self) -> Option<(u32, String)> {
+		Some((0, "closed".to_string()))
+	}
+}
+
+/// A session whose uni streams all share one [FakeSendStream].
+///
+/// The control stream, if any, is handed out by the first `open_bi`.
+#[derive(Clone)]
+pub(crate) struct FakeSession {
+	/// Every uni stream opened by the session.
+	pub(crate) uni: FakeSendStream,
+
+	/// Every datagram sent by the session.
+	pub(crate) datagrams: Arc<Mutex<Vec<Bytes>>>,
+
+	protocol: Option<&'static str>,
+	max_datagram_size: usize,
+	control_stream: Arc<Mutex<Option<(FakeSendStream, FakeRecvStream)>>>,
+	control_writes: FakeSendStream,
+	close_events: Arc<Mutex<Vec<(u32, String)>>>,
+	close_notify: Arc<tokio::sync::Notify>,
+}
+
+impl Default for FakeSession {
+	fn default() -> Self {
+		Self {
+			uni: FakeSendStream::default(),
+			datagrams: Default::default(),
+			protocol: None,
+			max_datagram_size: 1200,
+			control_stream: Default::default(),
+			control_writes: FakeSendStream::default(),
+			close_events: Default::default(),
+			close_notify: Default::default(),
+		}
+	}
+}
+
+impl FakeSession {
+	/// A session negotiated with `protocol`, whose control stream reads `server_control_bytes`.
+	pub(crate) fn new(protocol: Option<&'static str>, server_control_bytes: Vec<u8>) -> Self {
+		let send = FakeSendStream::default();
+		let recv = FakeRecvStream::new(server_control_bytes);
+
+		Self {
+			protocol,
+			control_stream: Arc::new(Mutex::new(Some((send.clone(), recv)))),
+			control_writes: send,
+			..Default::default()
+		}
+	}
+
+	pub(crate) fn with_max_datagram_size(mut self, size: usize) -> Self {
+		self.max_datagram_size = size;
+		self
+	}
+
+	/// Everything written to the control stream so far.
+	pub(crate) fn control_writes(&self) -> Vec<u8> {
+		self.control_writes.written().to_vec()
+	}
+
+	pub(crate) async fn wait_for_first_close(&self) -> (u32, String) {
+		loop {
+			let notified = self.close_notify.notified();
+			if let Some(close) = self.close_events.lock().unwrap().first().cloned() {
+				return close;
+			}
+			notified.await;
+		}
+	}
+}
+
+impl web_transport_trait::Session for FakeSession {
+	type SendStream = FakeSendStream;
+	type RecvStream = FakeRecvStream;
+	type Error = FakeError;
+
+	async fn accept_uni(&self) -> Result<Self::RecvStream, Self::Error> {
+		std::future::pending().await
+	}
+
+	async fn accept_bi(&self) -> Result<(Self::SendStream, Self::RecvStream), Self::Error> {
+		std::future::pending().await
+	}
+
+	async fn open_bi(&self) -> Result<(Self::SendStream, Self::RecvStream), Self::Error> {
+		self.control_stream.lock().unwrap().take().ok_or(FakeError)
+	}
+
+	async fn open_uni(&self) -> Result<Self::SendStream, Self::Error> {
+		Ok(self.uni.clone())
+	}
+
+	fn send_datagram(&self, payload: Bytes) -> Result<(), Self::Error> {
+		self.datagrams.lock().unwrap().push(payload);
+		Ok(())
+	}
+
+	async fn recv_datagram(&self) -> Result<Bytes, Self::Error> {
+		std::future::pending().await
+	}
+
+	fn max_datagram_size(&self) -> usize {
+		self.max_datagram_size
+	}
+
+	fn protocol(&self) -> Option<&str> {
+		self.protocol
+	}
+
+	fn close(&self, code: u32, reason: &str) {
+		self.close_events.lock().unwrap().push((code, reason.to_string()));
+		self.close_notify.notify_waiters();
+	}
+
+	async fn closed(&self) -> Self::Error {
+		self.close_notify.notified().await;
+		FakeError
+	}
+}
+
+/// Records every write call made on the stream, which stays open until finished.
+///
+/// Clones share the recorded writes, but not whether the stream is finished.
+#[derive(Clone, Default)]
+pub(crate) struct FakeSendStream {
+	pub(crate) writes: Arc<Mutex<Vec<Vec<u8>>>>,
+	finished: bool,
+}
+
+impl FakeSendStream {
+	/// Every write so far, concatenated.
+	pub(crate) fn written(&self) -> Bytes {
+		self.writes.lock().unwrap().concat().into()
+	}
+}
+
+impl web_transport_trait::SendStream for FakeSendStream {
+	type Error = FakeError;
+
+	async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+		self.writes.lock().unwrap().push(buf.to_vec());
+		Ok(buf.len())
+	}
+
+	fn set_priority(&mut self, _order: u8) {}
+
+	fn finish(&mut self) -> Result<(), Self::Error> {
+		self.finished = true;
+		Ok(())
+	}
+
+	fn reset(&mut self, _code: u32) {}
+
+	async fn closed(&mut self) -> Result<(), Self::Error> {
+		match self.finished {
+			true => Ok(()),
+			false => std::future::pending().await,
+		}
+	}
+}
+
+/// Reads back the given bytes and then ends, or stays open forever when [Self::pending].
+pub(crate) struct FakeRecvStream {
+	data: Option<VecDeque<u8>>,
+}
+
+impl FakeRecvStream {
+	pub(crate) fn new(data: Vec<u8>) -> Self {
+		Self {
+			data: Some(data.into()),
+		}
+	}
+
+	pub(crate) fn pending() -> Self {
+		Self { data: None }
+	}
+}
+
+impl web_transport_trait::RecvStream for FakeRecvStream {
+	type Error = FakeError;
+
+	async fn read(&mut self, dst: &mut [u8]) -> Result<Option<usize>, Self::Error> {
+		let Some(data) = self.data.as_mut() else {
+			return std::future::pending().await;
+		};
+
+		if data.is_empty() {
+			return Ok(None);
+		}
+
+		let size = dst.len().min(data.len());
+		for slot in dst.iter_mut().take(size) {
+			*slot = data.pop_front().unwrap();
+		}
+		Ok(Some(size))
+	}
+
+	fn stop(&mut self, _code: u32) {}
+
+	async fn closed(&mut self) -> Result<(), Self::Error> {
+		match self.data {
+			Some(_) => Ok(()),
+			None => std::future::pending().await,
+		}
+	}
+}
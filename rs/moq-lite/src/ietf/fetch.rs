@@ -328,6 +328,62 @@ impl Decode<Version> for FetchHeader {
 	}
 }
 
+/// An object on a FETCH data stream, following the [FetchHeader].
+///
+/// Unlike subgroup objects, each one carries its full location since a fetch spans groups.
+/// The payload follows immediately; an empty payload is followed by an object status instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchObject {
+	pub group_id: u64,
+	pub subgroup_id: u64,
+	pub object_id: u64,
+	pub publisher_priority: u8,
+	pub payload_length: u64,
+}
+
+impl Encode<Version> for FetchObject {
+	fn encode<W: bytes::BufMut>(&self, w: &mut W, version: Version) -> Result<(), EncodeError> {
+		self.group_id.encode(w, version)?;
+		self.subgroup_id.encode(w, version)?;
+		self.object_id.encode(w, version)?;
+		self.publisher_priority.encode(w, version)?;
+		0u64.encode(w, version)?; // no extensions
+		self.payload_length.encode(w, version)?;
+		if self.payload_length == 0 {
+			0u64.encode(w, version)?; // object status: normal
+		}
+		Ok(())
+	}
+}
+
+impl Decode<Version> for FetchObject {
+	fn decode<B: bytes::Buf>(buf: &mut B, version: Version) -> Result<Self, DecodeError> {
+		let group_id = u64::decode(buf, version)?;
+		let subgroup_id = u64::decode(buf, version)?;
+		let object_id = u64::decode(buf, version)?;
+		let publisher_priority = u8::decode(buf, version)?;
+
+		let extensions = usize::decode(buf, version)?;
+		if buf.remaining() < extensions {
+			return Err(DecodeError::Short);
+		}
+		buf.advance(extensions);
+
+		let payload_length = u64::decode(buf, version)?;
+		if payload_length == 0 {
+			let _status = u64::decode(buf, version)?;
+		}
+
+		Ok(Self {
+			group_id,
+			subgroup_id,
+			object_id,
+			publisher_priority,
+			payload_length,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -495,4 +551,25 @@ mod tests {
 		assert!(!decoded.end_of_track);
 		assert_eq!(decoded.end_location, Location { group: 5, object: 3 });
 	}
+
+	#[test]
+	fn test_fetch_object_round_trip() {
+		for payload_length in [0, 42] {
+			let msg = FetchObject {
+				group_id: 7,
+				subgroup_id: 0,
+				object_id: 3,
+				publisher_priority: 128,
+				payload_length,
+			};
+
+			let mut buf = BytesMut::new();
+			msg.encode(&mut buf, Version::Draft14).unwrap();
+			let mut buf = buf.freeze();
+			let decoded = FetchObject::decode(&mut buf, Version::Draft14).unwrap();
+
+			assert_eq!(decoded, msg);
+			assert!(buf.is_empty());
+		}
+	}
 }
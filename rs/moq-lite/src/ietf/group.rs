@@ -1,3 +1,5 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
 use crate::coding::{Decode, DecodeError, Encode, EncodeError};
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -207,10 +209,240 @@ impl Decode<Version> for GroupHeader {
 	}
 }
 
+/// Maximum number of extension headers on a single object.
+const MAX_EXTENSIONS: usize = 64;
+
+/// Maximum size in bytes of the extension block on a single object.
+const MAX_EXTENSIONS_SIZE: usize = (1 << 16) - 1;
+
+/// Object extension carrying [`crate::Frame::priority`], so relays keep the intra-group priority.
+///
+/// Even, so the value is an integer. Omitted for the default priority of 0.
+pub const EXTENSION_FRAME_PRIORITY: u64 = 0x4d50;
+
+/// The extension headers on an object, as `(type, value)` pairs.
+///
+/// Encoded as a length-prefixed block of Key-Value-Pairs: odd types carry length-prefixed
+/// bytes, while even types carry an integer, stored as its 8-byte big-endian value.
+/// Draft-16+ delta-encodes the types, so they're sent in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectExtensions(pub Vec<(u64, Bytes)>);
+
+impl ObjectExtensions {
+	/// The extensions to send for a frame, including its priority when it isn't the default.
+	pub fn from_frame(frame: &crate::Frame) -> Self {
+		let mut extensions = frame.extensions.clone();
+		if frame.priority != 0 {
+			extensions.push((EXTENSION_FRAME_PRIORITY, Bytes::copy_from_slice(&[frame.priority])));
+		}
+		Self(extensions)
+	}
+
+	/// Build the received frame, moving the priority extension into [`crate::Frame::priority`].
+	pub fn into_frame(self, size: u64) -> crate::Frame {
+		let mut priority = 0;
+		let extensions = self
+			.0
+			.into_iter()
+			.filter(|(kind, value)| {
+				if *kind != EXTENSION_FRAME_PRIORITY {
+					return true;
+				}
+				let int = value.iter().fold(0u64, |int, byte| (int << 8) | *byte as u64);
+				priority = int.min(u8::MAX as u64) as u8;
+				false
+			})
+			.collect();
+
+		crate::Frame {
+			size,
+			priority,
+			extensions,
+		}
+	}
+}
+
+impl Encode<Version> for ObjectExtensions {
+	fn encode<W: BufMut>(&self, w: &mut W, version: Version) -> Result<(), EncodeError> {
+		let mut extensions: Vec<_> = self.0.iter().collect();
+		extensions.sort_by_key(|(kind, _)| *kind);
+
+		let mut buf = BytesMut::new();
+		let mut prev: u64 = 0;
+		for (kind, value) in extensions {
+			match version {
+				Version::Draft14 | Version::Draft15 => kind.encode(&mut buf, version)?,
+				Version::Draft16 | Version::Draft17 => (kind - prev).encode(&mut buf, version)?,
+			}
+			prev = *kind;
+
+			if kind % 2 == 0 {
+				// Shorter big-endian values are accepted too.
+				if value.len() > 8 {
+					return Err(EncodeError::InvalidState);
+				}
+				let int = value.iter().fold(0u64, |int, byte| (int << 8) | *byte as u64);
+				int.encode(&mut buf, version)?;
+			} else {
+				value.encode(&mut buf, version)?;
+			}
+		}
+
+		if buf.len() > MAX_EXTENSIONS_SIZE {
+			return Err(EncodeError::BoundsExceeded);
+		}
+
+		buf.freeze().encode(w, version)
+	}
+}
+
+impl Decode<Version> for ObjectExtensions {
+	fn decode<R: Buf>(r: &mut R, version: Version) -> Result<Self, DecodeError> {
+		// Check the peer-chosen size before buffering the block.
+		let size = usize::decode(r, version)?;
+		if size > MAX_EXTENSIONS_SIZE {
+			return Err(DecodeError::BoundsExceeded);
+		}
+		if r.remaining() < size {
+			return Err(DecodeError::Short);
+		}
+		let mut buf = r.copy_to_bytes(size);
+
+		// The block is complete, so running out of bytes inside it is malformed.
+		let malformed = |err| match err {
+			DecodeError::Short => DecodeError::InvalidValue,
+			err => err,
+		};
+
+		let mut extensions = Vec::new();
+		let mut prev: u64 = 0;
+		while buf.has_remaining() {
+			if extensions.len() >= MAX_EXTENSIONS {
+				return Err(DecodeError::TooMany);
+			}
+
+			let kind = u64::decode(&mut buf, version).map_err(malformed)?;
+			let kind = match version {
+				Version::Draft14 | Version::Draft15 => kind,
+				Version::Draft16 | Version::Draft17 => prev.checked_add(kind).ok_or(DecodeError::BoundsExceeded)?,
+			};
+			prev = kind;
+
+			let value = if kind % 2 == 0 {
+				let int = u64::decode(&mut buf, version).map_err(malformed)?;
+				Bytes::copy_from_slice(&int.to_be_bytes())
+			} else {
+				Bytes::decode(&mut buf, version).map_err(malformed)?
+			};
+
+			extensions.push((kind, value));
+		}
+
+		Ok(Self(extensions))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	fn round_trip(extensions: Vec<(u64, Bytes)>, version: Version) -> ObjectExtensions {
+		let extensions = ObjectExtensions(extensions);
+		let mut buf = BytesMut::new();
+		extensions.encode(&mut buf, version).unwrap();
+
+		let mut buf = buf.freeze();
+		let decoded = ObjectExtensions::decode(&mut buf, version).unwrap();
+		assert!(buf.is_empty());
+		decoded
+	}
+
+	#[test]
+	fn extensions_round_trip_empty() {
+		for version in [Version::Draft14, Version::Draft16] {
+			assert_eq!(round_trip(Vec::new(), version), ObjectExtensions::default());
+		}
+	}
+
+	#[test]
+	fn extensions_round_trip_single() {
+		for version in [Version::Draft14, Version::Draft16] {
+			let extensions = vec![(0x3, Bytes::from_static(b"timestamp"))];
+			assert_eq!(round_trip(extensions.clone(), version).0, extensions);
+		}
+	}
+
+	#[test]
+	fn extensions_round_trip_multiple() {
+		let timestamp = Bytes::copy_from_slice(&1_700_000_000u64.to_be_bytes());
+
+		for version in [Version::Draft14, Version::Draft15, Version::Draft16, Version::Draft17] {
+			// Encoding sorts by type, which the delta-encoded drafts require.
+			let extensions = vec![
+				(0x5, Bytes::from_static(b"second")),
+				(0x2, timestamp.clone()),
+				(0x3, Bytes::from_static(b"first")),
+			];
+			let decoded = round_trip(extensions.clone(), version);
+
+			let mut expected = extensions;
+			expected.sort_by_key(|(kind, _)| *kind);
+			assert_eq!(decoded.0, expected);
+		}
+	}
+
+	#[test]
+	fn extensions_reject_oversized_integer() {
+		let extensions = ObjectExtensions(vec![(0x2, Bytes::from_static(b"not an integer"))]);
+		assert!(extensions.encode(&mut BytesMut::new(), Version::Draft14).is_err());
+	}
+
+	#[test]
+	fn extensions_truncated_block_is_malformed() {
+		// A block claiming an odd type with a value longer than the block.
+		let mut buf = Bytes::from_static(&[0x02, 0x01, 0x05]);
+		assert!(matches!(
+			ObjectExtensions::decode(&mut buf, Version::Draft14),
+			Err(DecodeError::InvalidValue)
+		));
+	}
+
+	#[test]
+	fn extensions_reject_oversized_block() {
+		// The size alone is enough to reject the block, without waiting for the bytes.
+		let mut buf = BytesMut::new();
+		(MAX_EXTENSIONS_SIZE + 1).encode(&mut buf, Version::Draft14).unwrap();
+		assert!(matches!(
+			ObjectExtensions::decode(&mut buf.freeze(), Version::Draft14),
+			Err(DecodeError::BoundsExceeded)
+		));
+
+		let extensions = ObjectExtensions(vec![(0x3, Bytes::from(vec![0u8; MAX_EXTENSIONS_SIZE]))]);
+		assert!(matches!(
+			extensions.encode(&mut BytesMut::new(), Version::Draft14),
+			Err(EncodeError::BoundsExceeded)
+		));
+	}
+
+	#[test]
+	fn extensions_carry_frame_priority() {
+		let frame = crate::Frame {
+			size: 3,
+			priority: 2,
+			extensions: vec![(0x3, Bytes::from_static(b"timestamp"))],
+		};
+
+		for version in [Version::Draft14, Version::Draft16] {
+			let decoded = round_trip(ObjectExtensions::from_frame(&frame).0, version).into_frame(3);
+			assert_eq!(decoded.priority, 2);
+			assert_eq!(decoded.extensions, frame.extensions);
+		}
+
+		// The default priority adds nothing to the wire.
+		let frame = crate::Frame::from(3usize);
+		assert_eq!(ObjectExtensions::from_frame(&frame), ObjectExtensions::default());
+	}
+
 	// Test table from draft-ietf-moq-transport-14 Section 10.4.2 Table 7
 	#[test]
 	fn test_group_flags_spec_table() {
@@ -5,30 +5,43 @@ use web_async::FuturesExt;
 use web_transport_trait::SendStream;
 
 use crate::{
-	AsPath, Error, Origin, OriginConsumer, Track, TrackConsumer,
+	AsPath, Error, Origin, OriginConsumer, Priority, PublishStats, Track, TrackConsumer, TrackStats,
 	coding::{Stream, Writer},
-	ietf::{self, Control, FetchHeader, FetchType, FilterType, GroupOrder, Location, RequestId},
+	ietf::{self, Control, FetchHeader, FetchObject, FetchType, FilterType, GroupOrder, Location, RequestId},
 	model::GroupConsumer,
 };
 
+use web_async::Lock;
+
 use super::{Message, Version};
 
 #[derive(Clone)]
 pub(super) struct Publisher<S: web_transport_trait::Session> {
 	session: S,
 	origin: OriginConsumer,
+	stats: PublishStats,
 	control: Control,
 	version: Version,
+	// Active subscriptions, so a joining FETCH can find the track it refers to.
+	subscribes: Lock<HashMap<RequestId, TrackConsumer>>,
 }
 
 impl<S: web_transport_trait::Session> Publisher<S> {
-	pub fn new(session: S, origin: Option<OriginConsumer>, control: Control, version: Version) -> Self {
+	pub fn new(
+		session: S,
+		origin: Option<OriginConsumer>,
+		stats: PublishStats,
+		control: Control,
+		version: Version,
+	) -> Self {
 		let origin = origin.unwrap_or_else(|| Origin::random().produce().consume());
 		Self {
 			session,
 			origin,
+			stats,
 			control,
 			version,
+			subscribes: Default::default(),
 		}
 	}
 
@@ -77,7 +90,16 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 				});
 			}
 			ietf::TrackStatus::ID => {
-				tracing::warn!("TrackStatus not supported");
+				let msg = ietf::TrackStatus::decode_msg(&mut data, this.version)?;
+				if !data.is_empty() {
+					return Err(Error::WrongSize);
+				}
+				tracing::debug!(message = ?msg, "received track_status");
+				web_async::spawn(async move {
+					if let Err(err) = this.run_track_status_stream(stream, msg).await {
+						tracing::debug!(%err, "track_status stream error");
+					}
+				});
 			}
 			_ => {
 				tracing::warn!(id, "unexpected bidi stream type for publisher");
@@ -93,10 +115,7 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 			FilterType::AbsoluteStart | FilterType::AbsoluteRange => {
 				tracing::warn!(?msg, "absolute subscribe not supported, ignoring");
 			}
-			FilterType::NextGroup => {
-				tracing::warn!(?msg, "next group subscribe not supported, ignoring");
-			}
-			FilterType::LargestObject => {}
+			FilterType::NextGroup | FilterType::LargestObject => {}
 		};
 
 		let request_id = msg.request_id;
@@ -115,11 +134,14 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 
 		let track = Track {
 			name: msg.track_name.to_string(),
-			priority: msg.subscriber_priority,
+			priority: Priority::from_ietf(msg.subscriber_priority),
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
 		};
 
-		let track = match broadcast.subscribe_track(&track) {
-			Ok(track) => track,
+		let mut track = match broadcast.subscribe_track(&track) {
+			Ok(track) => track.with_cache_stats(self.stats.cache()),
 			Err(err) => {
 				self.write_subscribe_error(&mut stream.writer, request_id, 404, &err.to_string())
 					.await?;
@@ -127,6 +149,11 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 			}
 		};
 
+		apply_filter(&mut track, msg.filter_type);
+
+		self.stats.add_subscribe();
+		let stats = self.stats.track(&absolute, &track_name);
+
 		// Send SubscribeOk on the stream
 		stream.writer.encode(&ietf::SubscribeOk::ID).await?;
 		stream
@@ -140,13 +167,17 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 			})
 			.await?;
 
+		self.subscribes.lock().insert(request_id, track.clone());
+
 		// Run the track, cancelling on reader close (Unsubscribe or stream close)
 		let res = tokio::select! {
-			res = self.run_track(track, request_id) => res,
+			res = self.run_track(track, request_id, &stats) => res,
 			_ = stream.reader.closed() => Ok(()),
 			_ = self.session.closed() => Ok(()),
 		};
 
+		self.subscribes.lock().remove(&request_id);
+
 		// Send PublishDone
 		let (status_code, reason) = match &res {
 			Ok(()) => (200, "OK"),
@@ -171,6 +202,68 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		res
 	}
 
+	/// Handle a TRACK_STATUS on its bidi stream, replying with the current state of the track.
+	async fn run_track_status_stream(
+		self,
+		mut stream: Stream<S, Version>,
+		msg: ietf::TrackStatus<'_>,
+	) -> Result<(), Error> {
+		let track = Track::new(msg.track_name.to_string());
+		let track = self
+			.origin
+			.get_broadcast(&msg.track_namespace)
+			.and_then(|broadcast| broadcast.subscribe_track(&track).ok());
+
+		let (status_code, last_group_id) = match &track {
+			None => (ietf::TrackStatusCode::NotFound, 0),
+			Some(track) if track.closed().now_or_never().is_some() => {
+				(ietf::TrackStatusCode::Ended, track.latest().unwrap_or(0))
+			}
+			Some(track) => (ietf::TrackStatusCode::InProgress, track.latest().unwrap_or(0)),
+		};
+
+		tracing::debug!(broadcast = %self.origin.absolute(&msg.track_namespace), track = %msg.track_name, ?status_code, "track status");
+
+		match self.version {
+			Version::Draft14 => {
+				stream.writer.encode(&ietf::TrackStatusOk::ID).await?;
+				stream
+					.writer
+					.encode(&ietf::TrackStatusOk {
+						track_namespace: msg.track_namespace,
+						track_name: msg.track_name,
+						status_code,
+						last_group_id,
+						// Objects aren't tracked per group.
+						last_object_id: 0,
+					})
+					.await?;
+			}
+			_ if track.is_none() => {
+				// v15+ has no status code, so report a missing track like a failed subscribe.
+				self.write_subscribe_error(&mut stream.writer, msg.request_id, 404, "Track not found")
+					.await?;
+			}
+			Version::Draft15 | Version::Draft16 => {
+				stream.writer.encode(&ietf::RequestOk::ID).await?;
+				stream
+					.writer
+					.encode(&ietf::RequestOk {
+						request_id: Some(msg.request_id),
+					})
+					.await?;
+			}
+			Version::Draft17 => {
+				stream.writer.encode(&ietf::RequestOk::ID).await?;
+				stream.writer.encode(&ietf::RequestOk { request_id: None }).await?;
+			}
+		}
+
+		stream.writer.finish().ok();
+
+		Ok(())
+	}
+
 	/// Write a subscribe error on the bidi stream writer.
 	async fn write_subscribe_error(
 		&self,
@@ -217,7 +310,12 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 	}
 
 	/// Serve a track using FuturesUnordered for unlimited concurrent groups.
-	async fn run_track(&self, mut track: TrackConsumer, request_id: RequestId) -> Result<(), Error> {
+	async fn run_track(
+		&self,
+		mut track: TrackConsumer,
+		request_id: RequestId,
+		stats: &TrackStats,
+	) -> Result<(), Error> {
 		let mut tasks = FuturesUnordered::new();
 
 		loop {
@@ -233,6 +331,7 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 
 			let sequence = group.sequence;
 			tracing::debug!(subscribe = %request_id, track = %track.name, sequence, "serving group");
+			self.stats.add_group();
 
 			let msg = ietf::GroupHeader {
 				track_alias: request_id.0,
@@ -242,17 +341,33 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 				flags: Default::default(),
 			};
 
-			tasks.push(Self::run_group(self.session.clone(), msg, track.priority, group, self.version).map(|_| ()));
+			let session = self.session.clone();
+			let priority = track.priority.to_ietf();
+			tasks.push(
+				Self::run_group(session, msg, priority, group, stats, self.version).map(|res| {
+					if res.is_err() {
+						stats.add_dropped();
+					}
+				}),
+			);
 		}
 	}
 
 	async fn run_group(
 		session: S,
-		msg: ietf::GroupHeader,
+		mut msg: ietf::GroupHeader,
 		priority: u8,
 		mut group: GroupConsumer,
+		stats: &TrackStats,
 		version: Version,
 	) -> Result<(), Error> {
+		// The header declares whether objects carry extensions, so wait for the first frame.
+		// A non-default priority is sent as an extension too.
+		let mut next = group.next_frame().await?;
+		msg.flags.has_extensions = next
+			.as_ref()
+			.is_some_and(|frame| !frame.extensions.is_empty() || frame.priority != 0);
+
 		let mut stream = session.open_uni().await.map_err(Error::from_transport)?;
 		stream.set_priority(priority);
 
@@ -261,10 +376,13 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		stream.encode(&msg).await?;
 
 		loop {
-			let frame = tokio::select! {
-				biased;
-				_ = stream.closed() => return Err(Error::Cancel),
-				frame = group.next_frame() => frame,
+			let frame = match next.take() {
+				Some(frame) => Ok(Some(frame)),
+				None => tokio::select! {
+					biased;
+					_ = stream.closed() => return Err(Error::Cancel),
+					frame = group.next_frame() => frame,
+				},
 			};
 
 			let mut frame = match frame? {
@@ -275,13 +393,15 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 			// object id delta is always 0.
 			stream.encode(&0u64).await?;
 
-			// not using extensions.
 			if msg.flags.has_extensions {
-				stream.encode(&0u64).await?;
+				stream.encode(&ietf::ObjectExtensions::from_frame(&frame)).await?;
+			} else if !frame.extensions.is_empty() || frame.priority != 0 {
+				tracing::debug!(group = %msg.group_id, "dropping extensions; the group's first frame had none");
 			}
 
 			// Write the size of the frame.
 			stream.encode(&frame.size).await?;
+			stats.add_bytes(frame.size);
 
 			if frame.size == 0 {
 				// Have to write the object status too.
@@ -317,34 +437,39 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 
 	/// Handle a FETCH on its bidi stream.
 	async fn run_fetch_stream(self, mut stream: Stream<S, Version>, msg: ietf::Fetch<'_>) -> Result<(), Error> {
-		let _subscribe_id = match msg.fetch_type {
+		// The backfill for a joining fetch, as the subscribed track and the first group to serve.
+		let backfill = match msg.fetch_type {
 			FetchType::Standalone { .. } => {
 				self.write_fetch_error(&mut stream.writer, msg.request_id, 500, "not supported")
 					.await?;
 				return Ok(());
 			}
-			FetchType::RelativeJoining {
-				subscriber_request_id,
-				group_offset,
-			} => {
+			FetchType::RelativeJoining { group_offset, .. } => {
 				if group_offset != 0 {
 					self.write_fetch_error(&mut stream.writer, msg.request_id, 500, "not supported")
 						.await?;
 					return Ok(());
 				}
-				subscriber_request_id
+				None
 			}
-			FetchType::AbsoluteJoining { .. } => {
-				self.write_fetch_error(&mut stream.writer, msg.request_id, 500, "not supported")
-					.await?;
-				return Ok(());
+			FetchType::AbsoluteJoining {
+				subscriber_request_id,
+				group_id,
+			} => {
+				let track = self.subscribes.lock().get(&subscriber_request_id).cloned();
+				let Some(track) = track else {
+					self.write_fetch_error(&mut stream.writer, msg.request_id, 404, "subscribe not found")
+						.await?;
+					return Ok(());
+				};
+				Some((track, group_id))
 			}
 		};
 
 		// Send FetchOk/RequestOk
 		self.write_fetch_ok(&mut stream.writer, msg.request_id).await?;
 
-		// Create a uni stream with just a FetchHeader and FIN it
+		// Create a uni stream with a FetchHeader, followed by any backfilled groups.
 		let uni = self.session.open_uni().await.map_err(Error::from_transport)?;
 		let mut writer = Writer::new(uni, self.version);
 		writer.encode(&FetchHeader::TYPE).await?;
@@ -353,6 +478,11 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 				request_id: msg.request_id,
 			})
 			.await?;
+
+		if let Some((track, group_id)) = backfill {
+			write_fetch_groups(&mut writer, &track, group_id).await?;
+		}
+
 		writer.finish()?;
 		writer.closed().await?;
 
@@ -631,3 +761,355 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		}
 	}
 }
+
+/// Position a new subscription according to its filter.
+///
+/// `NextGroup` skips the group in progress at subscribe time, so the subscriber starts on a
+/// clean group boundary instead of mid-group without a keyframe. With no groups yet, the next
+/// group is simply the first one.
+fn apply_filter(track: &mut TrackConsumer, filter: FilterType) {
+	if filter == FilterType::NextGroup
+		&& let Some(latest) = track.latest()
+	{
+		track.start_at(latest + 1);
+	}
+}
+
+/// Write every cached group from `start` up to (but excluding) the live edge as FETCH objects.
+///
+/// The joined subscription delivers the latest group onward, so the fetch only backfills
+/// the groups before it. Groups that were never received or have been evicted are skipped.
+async fn write_fetch_groups<W: SendStream>(
+	writer: &mut Writer<W, Version>,
+	track: &TrackConsumer,
+	start: u64,
+) -> Result<(), Error> {
+	let Some(latest) = track.latest() else {
+		return Ok(());
+	};
+
+	// Only walk the groups in the cache: the range can be huge when `start` comes from the peer.
+	let groups = track.cached_groups(start..latest);
+	let missing = latest.saturating_sub(start) - groups.len() as u64;
+	track.record_cache(groups.len() as u64, missing);
+
+	for mut group in groups {
+		let sequence = group.sequence;
+		let mut object_id = 0;
+		while let Some(mut frame) = group.read_frame().await? {
+			writer
+				.encode(&FetchObject {
+					group_id: sequence,
+					subgroup_id: 0,
+					object_id,
+					publisher_priority: track.priority.to_ietf(),
+					payload_length: frame.len() as u64,
+				})
+				.await?;
+			writer.write_all(&mut frame).await?;
+			object_id += 1;
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use crate::coding::{Decode, Encode};
+	use crate::fake::{FakeRecvStream, FakeSendStream, FakeSession};
+
+	/// Encode a SUBSCRIBE and split it like the session dispatch does, returning the type and body.
+	fn subscribe_frame(version: Version, namespace: &str, request_id: u64) -> (u64, bytes::Bytes) {
+		let msg = ietf::Subscribe {
+			request_id: RequestId(request_id),
+			track_namespace: crate::Path::new(namespace),
+			track_name: "video".into(),
+			subscriber_priority: 0,
+			group_order: GroupOrder::Descending,
+			filter_type: FilterType::LargestObject,
+		};
+
+		let mut buf = bytes::BytesMut::new();
+		ietf::Subscribe::ID.encode(&mut buf, version).unwrap();
+		msg.encode(&mut buf, version).unwrap();
+
+		let mut buf = buf.freeze();
+		let id = u64::decode(&mut buf, version).unwrap();
+		let size = u16::decode(&mut buf, version).unwrap();
+		assert_eq!(buf.len(), size as usize);
+		(id, buf)
+	}
+
+	/// Hand a SUBSCRIBE to the publisher on a fresh bidi stream, returning what it writes back.
+	fn handle_subscribe(
+		publisher: &Publisher<FakeSession>,
+		version: Version,
+		namespace: &str,
+		request_id: u64,
+	) -> FakeSendStream {
+		let (id, data) = subscribe_frame(version, namespace, request_id);
+		assert_eq!(id, ietf::Subscribe::ID);
+
+		let send = FakeSendStream::default();
+		let writes = send.clone();
+		let stream = Stream {
+			writer: Writer::new(send, version),
+			reader: crate::coding::Reader::new(FakeRecvStream::pending(), version),
+		};
+
+		publisher.handle_stream(id, data, stream).unwrap();
+		writes
+	}
+
+	/// Wait until the buffer holds more than `len` bytes.
+	async fn wait_for_writes(writes: &FakeSendStream, len: usize) -> bytes::Bytes {
+		tokio::time::timeout(std::time::Duration::from_secs(1), async {
+			loop {
+				let buf = writes.written();
+				if buf.len() > len {
+					return buf;
+				}
+				tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+			}
+		})
+		.await
+		.expect("nothing written")
+	}
+
+	#[tokio::test]
+	async fn draft16_subscribe_stream() {
+		let version = Version::Draft16;
+
+		let origin = Origin::random().produce();
+		let mut broadcast = crate::Broadcast::new().produce();
+		let mut track = broadcast.create_track(Track::new("video")).unwrap();
+		origin.publish_broadcast("demo", broadcast.consume());
+
+		let session = FakeSession::default();
+		let uni = session.uni.clone();
+		let publisher = Publisher::new(
+			session,
+			Some(origin.consume()),
+			PublishStats::default(),
+			Control::new(None, false),
+			version,
+		);
+
+		let writes = handle_subscribe(&publisher, version, "demo", 2);
+
+		// SUBSCRIBE_OK is the first reply on the bidi stream, before any group is served.
+		let mut buf = wait_for_writes(&writes, 0).await;
+		assert!(uni.written().is_empty());
+		assert_eq!(u64::decode(&mut buf, version).unwrap(), ietf::SubscribeOk::ID);
+		let ok = ietf::SubscribeOk::decode(&mut buf, version).unwrap();
+		assert_eq!(ok.request_id, Some(RequestId(2)));
+		assert_eq!(ok.track_alias, 2);
+		assert!(buf.is_empty());
+
+		// Groups go out on their own uni streams, leaving the bidi stream untouched.
+		let len = writes.written().len();
+		track.write_frame(bytes::Bytes::from_static(b"frame")).unwrap();
+		wait_for_writes(&uni, 0).await;
+		assert_eq!(writes.written().len(), len);
+
+		// PUBLISH_DONE follows once the track ends.
+		track.finish().unwrap();
+		let mut buf = wait_for_writes(&writes, len).await.split_off(len);
+		assert_eq!(u64::decode(&mut buf, version).unwrap(), ietf::PublishDone::ID);
+		let done = ietf::PublishDone::decode(&mut buf, version).unwrap();
+		assert_eq!(done.request_id, Some(RequestId(2)));
+		assert_eq!(done.status_code, 200);
+		assert!(buf.is_empty());
+	}
+
+	#[tokio::test]
+	async fn draft16_subscribe_stream_not_found() {
+		let version = Version::Draft16;
+
+		let publisher = Publisher::new(
+			FakeSession::default(),
+			Some(Origin::random().produce().consume()),
+			PublishStats::default(),
+			Control::new(None, false),
+			version,
+		);
+
+		let writes = handle_subscribe(&publisher, version, "missing", 4);
+
+		// v16 rejects with the generic REQUEST_ERROR rather than SUBSCRIBE_ERROR.
+		let mut buf = wait_for_writes(&writes, 0).await;
+		assert_eq!(u64::decode(&mut buf, version).unwrap(), ietf::RequestError::ID);
+		let err = ietf::RequestError::decode(&mut buf, version).unwrap();
+		assert_eq!(err.request_id, Some(RequestId(4)));
+		assert_eq!(err.error_code, 404);
+	}
+
+	#[tokio::test]
+	async fn absolute_joining_fetch_backfills_from_group() {
+		let version = Version::Draft14;
+
+		let mut track = Track::new("test").produce();
+		let consumer = track.consume();
+		for sequence in 0..6u64 {
+			let mut group = track.append_group().unwrap();
+			group.write_frame(format!("group {sequence}")).unwrap();
+			group.write_frame(bytes::Bytes::new()).unwrap();
+			group.finish().unwrap();
+		}
+
+		let stream = FakeSendStream::default();
+		let writes = stream.clone();
+		let mut writer = Writer::new(stream, version);
+		write_fetch_groups(&mut writer, &consumer, 2).await.unwrap();
+
+		let mut buf = writes.written();
+		let mut objects = Vec::new();
+		while !buf.is_empty() {
+			let object = FetchObject::decode(&mut buf, version).unwrap();
+			let payload = buf.split_to(object.payload_length as usize);
+			objects.push((object.group_id, object.object_id, payload));
+		}
+
+		// Groups 2..5 are backfilled in order; group 5 is the live edge served by the subscription.
+		let expected: Vec<_> = (2..5u64)
+			.flat_map(|sequence| {
+				[
+					(sequence, 0, bytes::Bytes::from(format!("group {sequence}"))),
+					(sequence, 1, bytes::Bytes::new()),
+				]
+			})
+			.collect();
+		assert_eq!(objects, expected);
+	}
+
+	#[tokio::test]
+	async fn next_group_skips_group_in_progress() {
+		let mut track = Track::new("test").produce();
+		let mut group = track.append_group().unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"partial")).unwrap();
+
+		let mut consumer = track.consume();
+		apply_filter(&mut consumer, FilterType::NextGroup);
+
+		group.write_frame(bytes::Bytes::from_static(b"rest")).unwrap();
+		group.finish().unwrap();
+		track.write_frame(bytes::Bytes::from_static(b"next")).unwrap();
+		track.finish().unwrap();
+
+		let first = consumer.recv_group().await.unwrap().expect("next group");
+		assert_eq!(first.sequence, 1);
+		assert!(consumer.recv_group().await.unwrap().is_none());
+
+		// LargestObject still starts with the group in progress.
+		let mut consumer = track.consume();
+		apply_filter(&mut consumer, FilterType::LargestObject);
+		assert_eq!(consumer.recv_group().await.unwrap().unwrap().sequence, 0);
+	}
+
+	#[tokio::test]
+	async fn fetch_counts_cache_hits() {
+		let version = Version::Draft14;
+		let cache = crate::CacheStats::new();
+
+		// Group 2 hasn't been produced yet, so backfilling it is a miss.
+		let mut track = Track::new("test").produce();
+		let consumer = track.consume().with_cache_stats(cache.clone());
+		for sequence in [0, 1, 3] {
+			let mut group = track.create_group(crate::Group { sequence }).unwrap();
+			group.write_frame(bytes::Bytes::from_static(b"frame")).unwrap();
+			group.finish().unwrap();
+		}
+
+		let mut writer = Writer::new(FakeSendStream::default(), version);
+		write_fetch_groups(&mut writer, &consumer, 0).await.unwrap();
+
+		assert_eq!(cache.hits(), 2);
+		assert_eq!(cache.misses(), 1);
+	}
+
+	#[tokio::test]
+	async fn frame_priority_sent_in_order() {
+		let version = Version::Draft14;
+
+		let mut group = crate::Group { sequence: 0 }.produce();
+		for (payload, priority) in [(&b"base"[..], 2), (b"enhance", 0), (b"base2", 1)] {
+			let mut frame = group
+				.create_frame(crate::Frame {
+					size: payload.len() as u64,
+					priority,
+					..Default::default()
+				})
+				.unwrap();
+			frame.write(bytes::Bytes::copy_from_slice(payload)).unwrap();
+			frame.finish().unwrap();
+		}
+		group.finish().unwrap();
+
+		let session = FakeSession::default();
+		let writes = session.uni.clone();
+		let msg = ietf::GroupHeader {
+			track_alias: 1,
+			group_id: 0,
+			sub_group_id: 0,
+			publisher_priority: 0,
+			flags: Default::default(),
+		};
+		let stats = PublishStats::default().track("test", "test");
+		Publisher::run_group(session, msg, 0, group.consume(), &stats, version)
+			.await
+			.unwrap();
+
+		let mut buf = writes.written();
+		let header = ietf::GroupHeader::decode(&mut buf, version).unwrap();
+		assert!(header.flags.has_extensions);
+
+		// Frames keep their order on the wire, each tagged with its priority.
+		let mut frames = Vec::new();
+		while !buf.is_empty() {
+			assert_eq!(u64::decode(&mut buf, version).unwrap(), 0);
+			let extensions = ietf::ObjectExtensions::decode(&mut buf, version).unwrap();
+			let size = u64::decode(&mut buf, version).unwrap();
+			let frame = extensions.into_frame(size);
+			frames.push((buf.split_to(size as usize), frame.priority));
+		}
+		assert_eq!(
+			frames,
+			vec![
+				(bytes::Bytes::from_static(b"base"), 2),
+				(bytes::Bytes::from_static(b"enhance"), 0),
+				(bytes::Bytes::from_static(b"base2"), 1),
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn fetch_skips_sparse_sequences() {
+		let version = Version::Draft14;
+
+		// A huge gap between cached groups must not be walked one sequence at a time.
+		let mut track = Track::new("test").produce();
+		let consumer = track.consume();
+		for sequence in [3, 1 << 40, (1 << 40) + 1] {
+			let mut group = track.create_group(crate::Group { sequence }).unwrap();
+			group.write_frame(bytes::Bytes::from_static(b"frame")).unwrap();
+			group.finish().unwrap();
+		}
+
+		let stream = FakeSendStream::default();
+		let writes = stream.clone();
+		let mut writer = Writer::new(stream, version);
+		write_fetch_groups(&mut writer, &consumer, 0).await.unwrap();
+
+		let mut buf = writes.written();
+		let mut groups = Vec::new();
+		while !buf.is_empty() {
+			let object = FetchObject::decode(&mut buf, version).unwrap();
+			bytes::Buf::advance(&mut buf, object.payload_length as usize);
+			groups.push(object.group_id);
+		}
+		assert_eq!(groups, vec![3, 1 << 40]);
+	}
+}
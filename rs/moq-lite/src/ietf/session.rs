@@ -1,5 +1,5 @@
 use crate::{
-	Error, OriginConsumer, OriginProducer,
+	Error, OriginConsumer, OriginProducer, PublishStats,
 	coding::{Encode, Reader, Stream, Writer},
 	ietf::{self, FetchHeader, GroupFlags, RequestId},
 	setup,
@@ -7,12 +7,14 @@ use crate::{
 
 use super::{Control, Message, Publisher, Subscriber, Version, adapter::ControlStreamAdapter};
 
+#[allow(clippy::too_many_arguments)]
 pub fn start<S: web_transport_trait::Session>(
 	session: S,
 	setup: Option<Stream<S, Version>>,
 	request_id_max: Option<RequestId>,
 	client: bool,
 	publish: Option<OriginConsumer>,
+	stats: PublishStats,
 	subscribe: Option<OriginProducer>,
 	version: Version,
 ) -> Result<(), Error> {
@@ -26,8 +28,8 @@ pub fn start<S: web_transport_trait::Session>(
 				let control = Control::new(request_id_max, client);
 				let adapter = ControlStreamAdapter::new(session.clone(), tx, control.clone(), version);
 
-				let publisher = Publisher::new(adapter.clone(), publish, control.clone(), version);
-				let subscriber = Subscriber::new(adapter.clone(), subscribe, control, version);
+				let publisher = Publisher::new(adapter.clone(), publish, stats.clone(), control.clone(), version);
+				let subscriber = Subscriber::new(adapter.clone(), subscribe, stats, control, version);
 
 				let dispatch_session = adapter.clone();
 				let mut sub_ns = subscriber.clone();
@@ -71,8 +73,8 @@ pub fn start<S: web_transport_trait::Session>(
 				});
 
 				let control = Control::new(None, client);
-				let publisher = Publisher::new(session.clone(), publish, control.clone(), version);
-				let subscriber = Subscriber::new(session.clone(), subscribe, control, version);
+				let publisher = Publisher::new(session.clone(), publish, stats.clone(), control.clone(), version);
+				let subscriber = Subscriber::new(session.clone(), subscribe, stats, control, version);
 
 				let sub_ns_session = session.clone();
 				let mut sub_ns = subscriber.clone();
@@ -1,8 +1,8 @@
 use std::collections::{HashMap, hash_map::Entry};
 
 use crate::{
-	Broadcast, BroadcastDynamic, Error, Frame, FrameProducer, Group, GroupProducer, OriginProducer, Path, PathOwned,
-	Track, TrackProducer,
+	Broadcast, BroadcastDynamic, Error, FrameProducer, Group, GroupProducer, OriginProducer, Path, PathOwned, Priority,
+	PublishStats, Track, TrackProducer,
 	coding::{Reader, Stream},
 	ietf::{self, Control, FilterType, GroupOrder, RequestId},
 	model::BroadcastProducer,
@@ -43,16 +43,24 @@ struct BroadcastState {
 pub(super) struct Subscriber<S: web_transport_trait::Session> {
 	session: S,
 	origin: Option<OriginProducer>,
+	stats: PublishStats,
 	control: Control,
 	state: Lock<State>,
 	version: Version,
 }
 
 impl<S: web_transport_trait::Session> Subscriber<S> {
-	pub fn new(session: S, origin: Option<OriginProducer>, control: Control, version: Version) -> Self {
+	pub fn new(
+		session: S,
+		origin: Option<OriginProducer>,
+		stats: PublishStats,
+		control: Control,
+		version: Version,
+	) -> Self {
 		Self {
 			session,
 			origin,
+			stats,
 			control,
 			state: Default::default(),
 			version,
@@ -415,6 +423,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 			Entry::Vacant(entry) => {
 				let broadcast = Broadcast::new().produce();
 				origin.publish_broadcast(path.clone(), broadcast.consume());
+				self.stats.add_announce();
 				entry.insert(BroadcastState {
 					producer: broadcast.clone(),
 					count: 1,
@@ -464,7 +473,10 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 
 		let track = Track {
 			name: msg.track_name.to_string(),
-			priority: 0,
+			priority: Priority::MIN,
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
 		}
 		.produce();
 
@@ -633,7 +645,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 				request_id,
 				track_namespace: broadcast.to_owned(),
 				track_name: (&track.name).into(),
-				subscriber_priority: track.priority,
+				subscriber_priority: track.priority.to_ietf(),
 				group_order: GroupOrder::Descending,
 				filter_type: FilterType::LargestObject,
 			})
@@ -727,16 +739,16 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 				return Err(Error::Unsupported);
 			}
 
-			if group.flags.has_extensions {
-				let size: usize = stream.decode().await?;
-				stream.skip(size).await?;
-			}
+			let extensions = match group.flags.has_extensions {
+				true => stream.decode::<ietf::ObjectExtensions>().await?,
+				false => ietf::ObjectExtensions::default(),
+			};
 
 			let size: u64 = stream.decode().await?;
 			if size == 0 {
 				let status: u64 = stream.decode().await?;
 				if status == 0 {
-					let mut frame = producer.create_frame(Frame { size: 0 })?;
+					let mut frame = producer.create_frame(extensions.into_frame(0))?;
 					frame.finish()?;
 				} else if status == 3 && !group.flags.has_end {
 					break;
@@ -744,7 +756,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 					return Err(Error::Unsupported);
 				}
 			} else {
-				let mut frame = producer.create_frame(Frame { size })?;
+				let mut frame = producer.create_frame(extensions.into_frame(size))?;
 
 				if let Err(err) = self.run_frame(stream, frame.clone()).await {
 					let _ = frame.abort(err.clone());
@@ -80,7 +80,47 @@ impl Message for TrackStatus<'_> {
 	}
 }
 
-#[derive(Clone, Copy, Debug, TryFromPrimitive, IntoPrimitive)]
+/// TrackStatusOk message (0x0e), the v14 response to [TrackStatus].
+/// v15+ responds with REQUEST_OK instead.
+#[derive(Clone, Debug)]
+pub struct TrackStatusOk<'a> {
+	pub track_namespace: Path<'a>,
+	pub track_name: Cow<'a, str>,
+	pub status_code: TrackStatusCode,
+	pub last_group_id: u64,
+	pub last_object_id: u64,
+}
+
+impl Message for TrackStatusOk<'_> {
+	const ID: u64 = 0x0e;
+
+	fn encode_msg<W: bytes::BufMut>(&self, w: &mut W, version: Version) -> Result<(), EncodeError> {
+		encode_namespace(w, &self.track_namespace, version)?;
+		self.track_name.encode(w, version)?;
+		self.status_code.encode(w, version)?;
+		self.last_group_id.encode(w, version)?;
+		self.last_object_id.encode(w, version)?;
+		Ok(())
+	}
+
+	fn decode_msg<R: bytes::Buf>(r: &mut R, version: Version) -> Result<Self, DecodeError> {
+		let track_namespace = decode_namespace(r, version)?;
+		let track_name = Cow::<str>::decode(r, version)?;
+		let status_code = TrackStatusCode::decode(r, version)?;
+		let last_group_id = u64::decode(r, version)?;
+		let last_object_id = u64::decode(r, version)?;
+
+		Ok(Self {
+			track_namespace,
+			track_name,
+			status_code,
+			last_group_id,
+			last_object_id,
+		})
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u64)]
 pub enum TrackStatusCode {
 	InProgress = 0x00,
@@ -181,4 +221,53 @@ mod tests {
 		assert_eq!(decoded.track_namespace.as_str(), "test/ns");
 		assert_eq!(decoded.track_name, "video");
 	}
+
+	fn track_status_ok_round_trip(version: Version) {
+		let msg = TrackStatusOk {
+			track_namespace: Path::new("test/ns"),
+			track_name: "video".into(),
+			status_code: TrackStatusCode::InProgress,
+			last_group_id: 42,
+			last_object_id: 0,
+		};
+
+		let encoded = encode_message(&msg, version);
+		let decoded: TrackStatusOk = decode_message(&encoded, version).unwrap();
+
+		assert_eq!(decoded.track_namespace.as_str(), "test/ns");
+		assert_eq!(decoded.track_name, "video");
+		assert_eq!(decoded.status_code, TrackStatusCode::InProgress);
+		assert_eq!(decoded.last_group_id, 42);
+		assert_eq!(decoded.last_object_id, 0);
+	}
+
+	#[test]
+	fn test_track_status_ok_round_trip() {
+		for version in [Version::Draft14, Version::Draft15, Version::Draft16] {
+			track_status_ok_round_trip(version);
+		}
+	}
+
+	#[test]
+	fn test_track_status_ok_not_found() {
+		let msg = TrackStatusOk {
+			track_namespace: Path::new("missing"),
+			track_name: "video".into(),
+			status_code: TrackStatusCode::NotFound,
+			last_group_id: 0,
+			last_object_id: 0,
+		};
+
+		let encoded = encode_message(&msg, Version::Draft14);
+		let decoded: TrackStatusOk = decode_message(&encoded, Version::Draft14).unwrap();
+		assert_eq!(decoded.status_code, TrackStatusCode::NotFound);
+	}
+
+	#[test]
+	fn test_track_status_code_invalid() {
+		let mut buf = BytesMut::new();
+		0x10u64.encode(&mut buf, Version::Draft14).unwrap();
+		let res = TrackStatusCode::decode(&mut buf.freeze(), Version::Draft14);
+		assert!(matches!(res, Err(DecodeError::InvalidValue)));
+	}
 }
@@ -9,6 +9,18 @@ const MAX_PARAMS: u64 = 64;
 #[derive(Default, Debug, Clone)]
 pub struct Parameters(HashMap<u64, Vec<u8>>);
 
+impl Parameters {
+	/// Return the raw value of the given parameter, if present.
+	pub fn get(&self, kind: u64) -> Option<&[u8]> {
+		self.0.get(&kind).map(Vec::as_slice)
+	}
+
+	/// Set the raw value of the given parameter, replacing any previous value.
+	pub fn set(&mut self, kind: u64, value: Vec<u8>) {
+		self.0.insert(kind, value);
+	}
+}
+
 impl Decode<Version> for Parameters {
 	fn decode<R: bytes::Buf>(mut r: &mut R, version: Version) -> Result<Self, DecodeError> {
 		let mut map = HashMap::new();
@@ -1,24 +1,34 @@
+use std::task::Poll;
 use std::time::Duration;
 
-use futures::{FutureExt, StreamExt, stream::FuturesUnordered};
+use bytes::BytesMut;
+use futures::{StreamExt, stream::FuturesUnordered};
 use web_async::FuturesExt;
 use web_transport_trait::Stats;
 
 use crate::{
-	AsPath, BroadcastConsumer, Error, Origin, OriginConsumer, OriginList, Track, TrackConsumer,
-	coding::{Stream, Writer},
+	AsPath, BroadcastConsumer, Error, Origin, OriginConsumer, OriginList, PublishStats, Track, TrackConsumer,
+	TrackStats,
+	coding::{Encode, Stream, Writer},
 	lite::{
 		self,
 		priority::{PriorityHandle, PriorityQueue},
 	},
-	model::GroupConsumer,
+	model::{DeliveryReporter, GroupConsumer, GroupDelivery},
 };
 
 use super::Version;
 
+/// Frames up to this size are coalesced with their neighbors when already complete.
+const MAX_BATCH_FRAME: u64 = 1024;
+
+/// Flush the coalesced frames once they reach this size.
+const MAX_BATCH: usize = 16 * 1024;
+
 pub(super) struct Publisher<S: web_transport_trait::Session> {
 	session: S,
 	origin: OriginConsumer,
+	stats: PublishStats,
 	// The session-level origin id stamped onto outbound hop chains. Shared
 	// with the Subscriber so it can optionally filter out reflected announces.
 	self_origin: Origin,
@@ -27,12 +37,19 @@ pub(super) struct Publisher<S: web_transport_trait::Session> {
 }
 
 impl<S: web_transport_trait::Session> Publisher<S> {
-	pub fn new(session: S, origin: Option<OriginConsumer>, self_origin: Origin, version: Version) -> Self {
+	pub fn new(
+		session: S,
+		origin: Option<OriginConsumer>,
+		stats: PublishStats,
+		self_origin: Origin,
+		version: Version,
+	) -> Self {
 		// Default to a dummy origin that is immediately closed.
 		let origin = origin.unwrap_or_else(|| Origin::random().produce().consume());
 		Self {
 			session,
 			origin,
+			stats,
 			self_origin,
 			priority: Default::default(),
 			version,
@@ -245,11 +262,13 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		// already seen an announcement for it — synchronous lookup is appropriate here.
 		let broadcast = self.origin.get_broadcast(&subscribe.broadcast);
 		let priority = self.priority.clone();
+		let stats = self.stats.track(&absolute, &track);
 		let version = self.version;
 
 		let session = self.session.clone();
 		web_async::spawn(async move {
-			if let Err(err) = Self::run_subscribe(session, &mut stream, &subscribe, broadcast, priority, version).await
+			if let Err(err) =
+				Self::run_subscribe(session, &mut stream, &subscribe, broadcast, priority, stats, version).await
 			{
 				match &err {
 					// TODO better classify WebTransport errors.
@@ -275,20 +294,29 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		subscribe: &lite::Subscribe<'_>,
 		consumer: Option<BroadcastConsumer>,
 		priority: PriorityQueue,
+		stats: TrackStats,
 		version: Version,
 	) -> Result<(), Error> {
 		let track = Track {
 			name: subscribe.track.to_string(),
-			priority: subscribe.priority,
+			priority: subscribe.priority.into(),
+			// The filter, datagram delivery and group order are applied locally in run_track so
+			// the shared upstream stays unfiltered.
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
 		};
 
 		let broadcast = consumer.ok_or(Error::NotFound)?;
-		let track = broadcast.subscribe_track(&track)?;
+		let track = broadcast
+			.subscribe_track(&track)?
+			.with_cache_stats(stats.publish().cache());
+		stats.publish().add_subscribe();
 
 		// TODO wait until track.info() to get the *real* priority
 
 		let info = lite::SubscribeOk {
-			priority: track.priority,
+			priority: track.priority.into(),
 			ordered: false,
 			max_latency: std::time::Duration::ZERO,
 			start_group: None,
@@ -298,7 +326,7 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		stream.writer.encode(&lite::SubscribeResponse::Ok(info)).await?;
 
 		tokio::select! {
-			res = Self::run_track(session, track, subscribe, priority, version) => res?,
+			res = Self::run_track(session, track, subscribe, priority, &stats, version) => res?,
 			res = stream.reader.closed() => res?,
 		}
 
@@ -311,12 +339,20 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		mut track: TrackConsumer,
 		subscribe: &lite::Subscribe<'_>,
 		priority: PriorityQueue,
+		stats: &TrackStats,
 		version: Version,
 	) -> Result<(), Error> {
+		let delivery = track.delivery();
 		let mut tasks = FuturesUnordered::new();
 
-		// Start the consumer at the specified sequence, otherwise start at the latest group.
-		if let Some(start_group) = subscribe.start_group.or_else(|| track.latest()) {
+		// An ascending subscription replays from the specified sequence, otherwise the oldest
+		// cached group. A live (descending) subscription starts at the latest group instead.
+		// The legacy `ordered` flag is ignored: deployed subscribers always set it.
+		let start_group = match subscribe.ascending {
+			true => subscribe.start_group,
+			false => subscribe.start_group.or_else(|| track.latest()),
+		};
+		if let Some(start_group) = start_group {
 			track.start_at(start_group);
 		}
 
@@ -327,21 +363,124 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 					while tasks.next().await.is_some() {}
 					false
 				} => unreachable!(),
-				Some(group) = track.recv_group().transpose() => group,
+				// Ascending delivery walks sequence numbers forward, skipping late arrivals.
+				Some(group) = async {
+					match subscribe.ascending {
+						true => track.next_group().await,
+						false => track.recv_group().await,
+					}
+				}.transpose() => group,
 				else => return Ok(()),
 			}?;
 
 			let sequence = group.sequence;
+
+			if subscribe.clean_join && group.is_truncated() {
+				tracing::debug!(subscribe = %subscribe.id, track = %track.name, sequence, "skipping truncated group");
+				stats.add_dropped();
+				continue;
+			}
+
 			tracing::debug!(subscribe = %subscribe.id, track = %track.name, sequence, "serving group");
+			stats.publish().add_group();
 
 			let msg = lite::Group {
 				subscribe: subscribe.id,
 				sequence,
 			};
 
-			let priority = priority.insert(track.priority, sequence);
-			tasks.push(Self::serve_group(session.clone(), msg, priority, group, version).map(|_| ()));
+			// Newer groups are transmitted first by default; ascending subscriptions flip that.
+			let order = match subscribe.ascending {
+				true => u64::MAX - sequence,
+				false => sequence,
+			};
+			let priority = priority.insert(track.priority, order);
+			let datagrams = subscribe.datagrams;
+			tasks.push(Self::serve(
+				session.clone(),
+				msg,
+				priority,
+				group,
+				datagrams,
+				stats,
+				&delivery,
+				version,
+			));
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	async fn serve(
+		session: S,
+		msg: lite::Group,
+		priority: PriorityHandle,
+		group: GroupConsumer,
+		datagrams: bool,
+		stats: &TrackStats,
+		delivery: &DeliveryReporter,
+		version: Version,
+	) {
+		let res = match datagrams {
+			true => Self::serve_datagram(&session, &msg, &group, stats, version).await,
+			false => Ok(false),
+		};
+
+		let res = match res {
+			Ok(true) => Ok(()),
+			// Datagrams are never acknowledged, so only streams report their delivery.
+			Ok(false) => Self::serve_group(session, msg, priority, group, stats, version)
+				.await
+				.map(|group| delivery.report(group)),
+			Err(err) => Err(err),
+		};
+
+		if res.is_err() {
+			stats.add_dropped();
+		}
+	}
+
+	/// Send a single-frame group as a datagram: the group header followed by the frame payload.
+	///
+	/// Returns false if the group has more than one frame or doesn't fit, in which case it
+	/// should be served over a stream instead.
+	async fn serve_datagram(
+		session: &S,
+		msg: &lite::Group,
+		group: &GroupConsumer,
+		stats: &TrackStats,
+		version: Version,
+	) -> Result<bool, Error> {
+		let mut group = group.clone();
+
+		let Some(mut frame) = group.next_frame().await? else {
+			return Ok(false);
+		};
+
+		let mut datagram = BytesMut::new();
+		msg.encode(&mut datagram, version)?;
+
+		// Don't wait on a frame that could never fit.
+		if datagram.len() as u64 + frame.size > session.max_datagram_size() as u64 {
+			return Ok(false);
 		}
+
+		let payload = frame.read_all().await?;
+
+		// Only a group that's already finished is known to be a single frame.
+		// Waiting for the end would stall a group that stays open, so use a stream instead.
+		if !matches!(group.poll_finished(&conducer::Waiter::noop()), Poll::Ready(Ok(1))) {
+			return Ok(false);
+		}
+
+		datagram.extend_from_slice(&payload);
+		stats.add_bytes(datagram.len() as u64);
+		session
+			.send_datagram(datagram.freeze())
+			.map_err(Error::from_transport)?;
+
+		tracing::debug!(sequence = %msg.sequence, "sent group as datagram");
+
+		Ok(true)
 	}
 
 	async fn serve_group(
@@ -349,8 +488,12 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		msg: lite::Group,
 		mut priority: PriorityHandle,
 		mut group: GroupConsumer,
+		stats: &TrackStats,
 		version: Version,
-	) -> Result<(), Error> {
+	) -> Result<GroupDelivery, Error> {
+		let start = tokio::time::Instant::now();
+		let mut bytes = 0;
+
 		// TODO add a way to open in priority order.
 		let stream = session.open_uni().await.map_err(Error::from_transport)?;
 
@@ -359,15 +502,29 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 		stream.encode(&lite::DataType::Group).await?;
 		stream.encode(&msg).await?;
 
+		// Complete frames that are already available are coalesced into a single write.
+		// Each frame is still encoded as its own size-prefixed object.
+		let mut batch = BytesMut::new();
+
+		// Frames are always written in order: moq-lite has no frame index on the wire, so the
+		// subscriber can't restore decode order. Priority only decides which frames get dropped.
 		loop {
-			let frame = tokio::select! {
-				biased;
-				_ = stream.closed() => return Err(Error::Cancel),
-				frame = group.next_frame() => frame,
-				// Update the priority if it changes.
-				priority = priority.next() => {
-					stream.set_priority(priority);
-					continue;
+			let frame = match group.poll_next_frame(&conducer::Waiter::noop()) {
+				Poll::Ready(frame) => frame,
+				Poll::Pending => {
+					// Nothing else is ready, so flush what we have before waiting.
+					stream.write_all(&mut batch).await?;
+
+					tokio::select! {
+						biased;
+						_ = stream.closed() => return Err(Error::Cancel),
+						frame = group.next_frame() => frame,
+						// Update the priority if it changes.
+						priority = priority.next() => {
+							stream.set_priority(priority);
+							continue;
+						}
+					}
 				}
 			};
 
@@ -376,7 +533,31 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 				None => break,
 			};
 
+			// The payload is shared with every other subscriber; only the size header is encoded here.
+			if frame.size <= MAX_BATCH_FRAME
+				&& let Poll::Ready(payload) = frame.poll_read_all(&conducer::Waiter::noop())
+			{
+				let payload = payload?;
+				let start = batch.len();
+				frame.size.encode(&mut batch, version)?;
+				batch.extend_from_slice(&payload);
+
+				let written = (batch.len() - start) as u64;
+				stats.add_bytes(written);
+				bytes += written;
+
+				if batch.len() >= MAX_BATCH {
+					stream.write_all(&mut batch).await?;
+				}
+
+				continue;
+			}
+
+			// Large or incomplete frames are streamed chunk by chunk.
+			stream.write_all(&mut batch).await?;
 			stream.encode(&frame.size).await?;
+			stats.add_bytes(frame.size);
+			bytes += frame.size;
 
 			loop {
 				let chunk = tokio::select! {
@@ -397,11 +578,370 @@ impl<S: web_transport_trait::Session> Publisher<S> {
 			}
 		}
 
+		stream.write_all(&mut batch).await?;
 		stream.finish()?;
+
+		// The stream is closed once the peer has acknowledged everything.
+		let finished = tokio::time::Instant::now();
 		stream.closed().await?;
 
-		tracing::debug!(sequence = %msg.sequence, "finished group");
+		let delivery = GroupDelivery {
+			sequence: msg.sequence,
+			bytes,
+			elapsed: start.elapsed(),
+			drain: finished.elapsed(),
+		};
 
-		Ok(())
+		tracing::debug!(sequence = %msg.sequence, ?delivery.elapsed, ?delivery.drain, "finished group");
+
+		Ok(delivery)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use crate::Priority;
+	use crate::coding::Decode;
+	use crate::fake::FakeSession;
+	use crate::model::Group;
+
+	#[tokio::test]
+	async fn tiny_frames_are_batched() {
+		const FRAMES: usize = 64;
+		let version = Version::Lite04;
+
+		let mut group = Group { sequence: 3 }.produce();
+		for i in 0..FRAMES {
+			group.write_frame(vec![i as u8; 8]).unwrap();
+		}
+		group.finish().unwrap();
+
+		let session = FakeSession::default();
+		let writes = session.uni.writes.clone();
+		let priority = PriorityQueue::default().insert(Priority::MIN, 3);
+		let publish = PublishStats::default();
+		let stats = publish.track("demo", "video");
+		let msg = lite::Group {
+			subscribe: 1,
+			sequence: 3,
+		};
+
+		let delivery = Publisher::serve_group(session, msg, priority, group.consume(), &stats, version)
+			.await
+			.unwrap();
+
+		// Each frame is a one byte size prefix plus an 8 byte payload.
+		assert_eq!(publish.tracks()[0].bytes, FRAMES as u64 * 9);
+		assert_eq!(delivery.sequence, 3);
+		assert_eq!(delivery.bytes, FRAMES as u64 * 9);
+
+		let writes = writes.lock().unwrap();
+		assert!(
+			writes.len() < FRAMES,
+			"expected the burst to be batched, got {} writes",
+			writes.len()
+		);
+
+		// The wire format is unchanged: a header followed by one size-prefixed object per frame.
+		let mut buf = bytes::Bytes::from(writes.concat());
+		assert!(matches!(
+			lite::DataType::decode(&mut buf, version).unwrap(),
+			lite::DataType::Group
+		));
+		let header = lite::Group::decode(&mut buf, version).unwrap();
+		assert_eq!(header.sequence, 3);
+
+		for i in 0..FRAMES {
+			let size = u64::decode(&mut buf, version).unwrap();
+			assert_eq!(size, 8);
+			assert_eq!(&buf.split_to(8)[..], &[i as u8; 8]);
+		}
+		assert!(buf.is_empty());
+	}
+
+	#[tokio::test]
+	async fn pending_frames_sent_in_order() {
+		let version = Version::Lite04;
+
+		// The whole group is buffered before the stream opens, as if the stream had backed up.
+		let mut group = Group { sequence: 1 }.produce();
+		for (payload, priority) in [("key", 0), ("enhance0", 0), ("base0", 1), ("enhance1", 0), ("base1", 1)] {
+			let mut frame = group
+				.create_frame(crate::Frame {
+					size: payload.len() as u64,
+					priority,
+					..Default::default()
+				})
+				.unwrap();
+			frame.write(bytes::Bytes::from_static(payload.as_bytes())).unwrap();
+			frame.finish().unwrap();
+		}
+		group.finish().unwrap();
+
+		let session = FakeSession::default();
+		let writes = session.uni.writes.clone();
+		let priority = PriorityQueue::default().insert(Priority::MIN, 1);
+		let stats = PublishStats::default().track("demo", "video");
+		let msg = lite::Group {
+			subscribe: 1,
+			sequence: 1,
+		};
+
+		Publisher::serve_group(session, msg, priority, group.consume(), &stats, version)
+			.await
+			.unwrap();
+
+		let mut buf = bytes::Bytes::from(writes.lock().unwrap().concat());
+		lite::DataType::decode(&mut buf, version).unwrap();
+		lite::Group::decode(&mut buf, version).unwrap();
+
+		let mut frames = Vec::new();
+		while !buf.is_empty() {
+			let size = u64::decode(&mut buf, version).unwrap();
+			frames.push(buf.split_to(size as usize));
+		}
+
+		// Priority never reorders frames, since the subscriber relies on their position.
+		assert_eq!(frames, ["key", "enhance0", "base0", "enhance1", "base1"]);
+	}
+
+	#[tokio::test]
+	async fn fanout_shares_encoded_frames() {
+		const SUBSCRIBERS: usize = 100;
+		const FRAMES: usize = 16;
+		let version = Version::Lite04;
+
+		let mut group = Group { sequence: 7 }.produce();
+		for i in 0..FRAMES {
+			group.write_frame(vec![i as u8; 32]).unwrap();
+		}
+		group.finish().unwrap();
+
+		let mut outputs = Vec::with_capacity(SUBSCRIBERS);
+		for subscriber in 0..SUBSCRIBERS {
+			let session = FakeSession::default();
+			let writes = session.uni.writes.clone();
+			let stats = PublishStats::default().track("demo", "video");
+			// Each subscriber gets its own stream priority; only the payload is shared.
+			let priority = PriorityQueue::default().insert(Priority::from(subscriber as u8), 7);
+			let msg = lite::Group {
+				subscribe: subscriber as u64,
+				sequence: 7,
+			};
+
+			Publisher::serve_group(session, msg, priority, group.consume(), &stats, version)
+				.await
+				.unwrap();
+
+			let mut buf = bytes::Bytes::from(writes.lock().unwrap().concat());
+			lite::DataType::decode(&mut buf, version).unwrap();
+			let header = lite::Group::decode(&mut buf, version).unwrap();
+			assert_eq!(header.subscribe, subscriber as u64);
+			outputs.push(buf);
+		}
+
+		// Every subscriber received the same frames after its own group header.
+		assert!(outputs.iter().all(|output| output == &outputs[0]));
+
+		// The output is exactly the size-prefixed frames, back to back.
+		let mut consumer = group.consume();
+		let mut expected = bytes::BytesMut::new();
+		while let Some(payload) = consumer.read_frame().await.unwrap() {
+			(payload.len() as u64).encode(&mut expected, version).unwrap();
+			expected.extend_from_slice(&payload);
+		}
+		assert_eq!(&expected[..], &outputs[0][..]);
+	}
+
+	/// Run a subscription against a finished track with groups 0..5, returning the sequences served.
+	async fn serve_track(ascending: bool, start_group: Option<u64>) -> Vec<u64> {
+		let version = Version::Lite05;
+
+		let mut track = Track::new("test").produce();
+		let consumer = track.consume();
+		for _ in 0..5 {
+			let mut group = track.append_group().unwrap();
+			group.write_frame(bytes::Bytes::from_static(b"frame")).unwrap();
+			group.finish().unwrap();
+		}
+		track.finish().unwrap();
+
+		let session = FakeSession::default();
+		let writes = session.uni.writes.clone();
+		let subscribe = lite::Subscribe {
+			id: 1,
+			broadcast: "test".as_path(),
+			track: "test".into(),
+			priority: 0,
+			// Deployed subscribers always set this, so it must not change the group order.
+			ordered: true,
+			max_latency: Duration::ZERO,
+			start_group,
+			end_group: None,
+			clean_join: false,
+			datagrams: false,
+			ascending,
+		};
+
+		Publisher::run_track(
+			session,
+			consumer,
+			&subscribe,
+			PriorityQueue::default(),
+			&PublishStats::default().track("test", "test"),
+			version,
+		)
+		.await
+		.unwrap();
+
+		let mut buf = bytes::Bytes::from(writes.lock().unwrap().concat());
+		let mut sequences = Vec::new();
+		while !buf.is_empty() {
+			lite::DataType::decode(&mut buf, version).unwrap();
+			let header = lite::Group::decode(&mut buf, version).unwrap();
+			let size = u64::decode(&mut buf, version).unwrap();
+			assert_eq!(&buf.split_to(size as usize)[..], b"frame");
+			sequences.push(header.sequence);
+		}
+		sequences
+	}
+
+	#[tokio::test]
+	async fn ascending_serves_groups_in_order() {
+		assert_eq!(serve_track(true, None).await, vec![0, 1, 2, 3, 4]);
+		assert_eq!(serve_track(true, Some(2)).await, vec![2, 3, 4]);
+	}
+
+	#[tokio::test]
+	async fn descending_starts_at_latest() {
+		assert_eq!(serve_track(false, None).await, vec![4]);
+	}
+
+	#[tokio::test]
+	async fn datagrams_for_small_single_frame_groups() {
+		let version = Version::Lite05;
+
+		let mut track = Track::new("audio").produce();
+		let consumer = track.consume();
+
+		// Group 0: a single small frame, sent as a datagram.
+		track.write_frame(bytes::Bytes::from_static(b"opus")).unwrap();
+
+		// Group 1: two frames, which must use a stream.
+		let mut group = track.append_group().unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"first")).unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"second")).unwrap();
+		group.finish().unwrap();
+
+		// Group 2: a single frame too large for a datagram, which must use a stream.
+		track.write_frame(vec![0u8; 4096]).unwrap();
+		track.finish().unwrap();
+
+		let session = FakeSession::default();
+		let writes = session.uni.writes.clone();
+		let datagrams = session.datagrams.clone();
+		let subscribe = lite::Subscribe {
+			id: 1,
+			broadcast: "test".as_path(),
+			track: "audio".into(),
+			priority: 0,
+			ordered: true,
+			max_latency: Duration::ZERO,
+			start_group: None,
+			end_group: None,
+			clean_join: false,
+			datagrams: true,
+			ascending: true,
+		};
+
+		Publisher::run_track(
+			session,
+			consumer,
+			&subscribe,
+			PriorityQueue::default(),
+			&PublishStats::default().track("test", "test"),
+			version,
+		)
+		.await
+		.unwrap();
+
+		let datagrams = datagrams.lock().unwrap();
+		assert_eq!(datagrams.len(), 1);
+		let mut datagram = datagrams[0].clone();
+		let header = lite::Group::decode(&mut datagram, version).unwrap();
+		assert_eq!((header.subscribe, header.sequence), (1, 0));
+		assert_eq!(&datagram[..], b"opus");
+
+		let mut buf = bytes::Bytes::from(writes.lock().unwrap().concat());
+		let mut sequences = Vec::new();
+		while !buf.is_empty() {
+			lite::DataType::decode(&mut buf, version).unwrap();
+			let header = lite::Group::decode(&mut buf, version).unwrap();
+			sequences.push(header.sequence);
+			let frames = if header.sequence == 1 { 2 } else { 1 };
+			for _ in 0..frames {
+				let size = u64::decode(&mut buf, version).unwrap();
+				bytes::Buf::advance(&mut buf, size as usize);
+			}
+		}
+		assert_eq!(sequences, vec![1, 2]);
+	}
+
+	#[tokio::test]
+	async fn datagrams_dont_wait_for_open_groups() {
+		let version = Version::Lite05;
+
+		let mut track = Track::new("audio").produce();
+		let consumer = track.consume();
+
+		// A single frame in a group that stays open, which must not block on the next frame.
+		let mut group = track.append_group().unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"opus")).unwrap();
+		track.finish().unwrap();
+
+		let session = FakeSession::default();
+		let writes = session.uni.writes.clone();
+		let datagrams = session.datagrams.clone();
+		let subscribe = lite::Subscribe {
+			id: 1,
+			broadcast: "test".as_path(),
+			track: "audio".into(),
+			priority: 0,
+			ordered: true,
+			max_latency: Duration::ZERO,
+			start_group: None,
+			end_group: None,
+			clean_join: false,
+			datagrams: true,
+			ascending: false,
+		};
+
+		let task = tokio::spawn(async move {
+			Publisher::run_track(
+				session,
+				consumer,
+				&subscribe,
+				PriorityQueue::default(),
+				&PublishStats::default().track("test", "test"),
+				version,
+			)
+			.await
+		});
+
+		// The frame goes out on a stream while the group is still open.
+		tokio::time::timeout(Duration::from_secs(1), async {
+			while writes.lock().unwrap().concat().windows(4).all(|w| w != b"opus") {
+				tokio::task::yield_now().await;
+			}
+		})
+		.await
+		.expect("frame was not written");
+
+		group.finish().unwrap();
+		task.await.unwrap().unwrap();
+
+		assert!(datagrams.lock().unwrap().is_empty());
 	}
 }
@@ -1,5 +1,5 @@
 use crate::{
-	BandwidthConsumer, BandwidthProducer, Error, Origin, OriginConsumer, OriginProducer, coding::Stream,
+	BandwidthConsumer, BandwidthProducer, Error, Origin, OriginConsumer, OriginProducer, PublishStats, coding::Stream,
 	lite::SessionInfo,
 };
 
@@ -11,6 +11,8 @@ pub fn start<S: web_transport_trait::Session>(
 	setup: Option<Stream<S, Version>>,
 	// We will publish any local broadcasts from this origin.
 	publish: Option<OriginConsumer>,
+	// Counters for what we publish and what the peer announces.
+	stats: PublishStats,
 	// We will consume any remote broadcasts, inserting them into this origin.
 	subscribe: Option<OriginProducer>,
 	// The version of the protocol to use.
@@ -32,8 +34,8 @@ pub fn start<S: web_transport_trait::Session>(
 	// announce hops, and the subscriber carries it so callers can opt into
 	// filtering out their own reflected announces.
 	let origin = Origin::random();
-	let publisher = Publisher::new(session.clone(), publish, origin, version);
-	let subscriber = Subscriber::new(session.clone(), subscribe, recv_bw_for_sub, origin, version);
+	let publisher = Publisher::new(session.clone(), publish, stats.clone(), origin, version);
+	let subscriber = Subscriber::new(session.clone(), subscribe, stats, recv_bw_for_sub, origin, version);
 
 	web_async::spawn(async move {
 		let res = tokio::select! {
@@ -5,7 +5,16 @@ use crate::{
 	coding::{Decode, DecodeError, Encode, EncodeError, Sizer},
 };
 
-use super::{Message, Version};
+use super::{Message, Parameters, Version};
+
+/// Subscribe parameter: only deliver groups that can be read from their first frame.
+const PARAM_CLEAN_JOIN: u64 = 0x01;
+
+/// Subscribe parameter: deliver single-frame groups as datagrams when they fit.
+const PARAM_DATAGRAMS: u64 = 0x02;
+
+/// Subscribe parameter: deliver groups oldest-first from the start group.
+const PARAM_ASCENDING: u64 = 0x03;
 
 /// Sent by the subscriber to request all future objects for the given track.
 ///
@@ -20,6 +29,22 @@ pub struct Subscribe<'a> {
 	pub max_latency: std::time::Duration,
 	pub start_group: Option<u64>,
 	pub end_group: Option<u64>,
+
+	/// Skip any group whose first frame is no longer available to the publisher.
+	///
+	/// Lite05+ only, encoded as a trailing parameter.
+	pub clean_join: bool,
+
+	/// Deliver single-frame groups as QUIC datagrams when they fit, falling back to streams.
+	///
+	/// Lite05+ only, encoded as a trailing parameter.
+	pub datagrams: bool,
+
+	/// Deliver groups oldest-first from the start group, instead of starting at the latest group.
+	///
+	/// Lite05+ only, encoded as a trailing parameter.
+	/// `ordered` can't be used for this because deployed subscribers always set it.
+	pub ascending: bool,
 }
 
 impl Message for Subscribe<'_> {
@@ -40,6 +65,15 @@ impl Message for Subscribe<'_> {
 			}
 		};
 
+		// Lite04 peers reject any trailing bytes, so the parameters are new in Lite05.
+		let params = match version {
+			Version::Lite01 | Version::Lite02 | Version::Lite03 | Version::Lite04 => Parameters::default(),
+			_ => Parameters::decode(r, version)?,
+		};
+		let clean_join = params.get(PARAM_CLEAN_JOIN).is_some();
+		let datagrams = params.get(PARAM_DATAGRAMS).is_some();
+		let ascending = params.get(PARAM_ASCENDING).is_some();
+
 		Ok(Self {
 			id,
 			broadcast,
@@ -49,6 +83,9 @@ impl Message for Subscribe<'_> {
 			max_latency,
 			start_group,
 			end_group,
+			clean_join,
+			datagrams,
+			ascending,
 		})
 	}
 
@@ -68,6 +105,23 @@ impl Message for Subscribe<'_> {
 			}
 		}
 
+		match version {
+			Version::Lite01 | Version::Lite02 | Version::Lite03 | Version::Lite04 => {}
+			_ => {
+				let mut params = Parameters::default();
+				if self.clean_join {
+					params.set(PARAM_CLEAN_JOIN, Vec::new());
+				}
+				if self.datagrams {
+					params.set(PARAM_DATAGRAMS, Vec::new());
+				}
+				if self.ascending {
+					params.set(PARAM_ASCENDING, Vec::new());
+				}
+				params.encode(w, version)?;
+			}
+		}
+
 		Ok(())
 	}
 }
@@ -326,3 +380,96 @@ impl Decode<Version> for SubscribeResponse {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bytes::BytesMut;
+
+	fn subscribe(clean_join: bool) -> Subscribe<'static> {
+		Subscribe {
+			id: 7,
+			broadcast: Path::new("test"),
+			track: "video".into(),
+			priority: 1,
+			ordered: false,
+			max_latency: std::time::Duration::ZERO,
+			start_group: None,
+			end_group: None,
+			clean_join,
+			datagrams: false,
+			ascending: false,
+		}
+	}
+
+	fn round_trip(msg: &Subscribe, version: Version) -> (Vec<u8>, Subscribe<'static>) {
+		let mut buf = BytesMut::new();
+		msg.encode(&mut buf, version).unwrap();
+		let encoded = buf.to_vec();
+
+		let mut buf = bytes::Bytes::from(encoded.clone());
+		let decoded = Subscribe::decode(&mut buf, version).unwrap();
+		assert!(buf.is_empty());
+
+		(encoded, decoded)
+	}
+
+	#[test]
+	fn clean_join_round_trip() {
+		let (_, decoded) = round_trip(&subscribe(true), Version::Lite05);
+		assert!(decoded.clean_join);
+
+		let (_, decoded) = round_trip(&subscribe(false), Version::Lite05);
+		assert!(!decoded.clean_join);
+	}
+
+	#[test]
+	fn lite04_unchanged() {
+		// Lite04 peers reject trailing bytes, so the parameters are never sent to them.
+		let (lite04, decoded) = round_trip(&subscribe(true), Version::Lite04);
+		let (lite03, _) = round_trip(&subscribe(true), Version::Lite03);
+		assert_eq!(lite04, lite03);
+		assert!(!decoded.clean_join);
+	}
+
+	#[test]
+	fn datagrams_round_trip() {
+		let msg = Subscribe {
+			datagrams: true,
+			..subscribe(true)
+		};
+
+		let (_, decoded) = round_trip(&msg, Version::Lite05);
+		assert!(decoded.datagrams);
+		assert!(decoded.clean_join);
+
+		let (_, decoded) = round_trip(&msg, Version::Lite04);
+		assert!(!decoded.datagrams);
+
+		let (_, decoded) = round_trip(&msg, Version::Lite03);
+		assert!(!decoded.datagrams);
+	}
+
+	#[test]
+	fn clean_join_ignored_before_lite05() {
+		let (_, decoded) = round_trip(&subscribe(true), Version::Lite03);
+		assert!(!decoded.clean_join);
+	}
+
+	#[test]
+	fn ascending_round_trip() {
+		let msg = Subscribe {
+			ascending: true,
+			..subscribe(false)
+		};
+
+		let (_, decoded) = round_trip(&msg, Version::Lite05);
+		assert!(decoded.ascending);
+
+		// The legacy ordered flag is unrelated.
+		assert!(!decoded.ordered);
+
+		let (_, decoded) = round_trip(&msg, Version::Lite04);
+		assert!(!decoded.ascending);
+	}
+}
@@ -7,8 +7,8 @@ use futures::{StreamExt, stream::FuturesUnordered};
 
 use crate::{
 	AsPath, BandwidthProducer, Broadcast, BroadcastDynamic, Error, Frame, FrameProducer, Group, GroupProducer,
-	OriginProducer, Path, PathOwned, TrackProducer,
-	coding::{Reader, Stream},
+	OriginProducer, Path, PathOwned, PublishStats, TrackProducer,
+	coding::{Decode, Reader, Stream},
 	lite,
 	model::BroadcastProducer,
 };
@@ -22,6 +22,7 @@ pub(super) struct Subscriber<S: web_transport_trait::Session> {
 	session: S,
 
 	origin: Option<OriginProducer>,
+	stats: PublishStats,
 	recv_bandwidth: Option<BandwidthProducer>,
 	// Session-level origin id shared with the Publisher. Kept so callers that
 	// want to filter reflected announces can reuse the same id; for now only
@@ -38,6 +39,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 	pub fn new(
 		session: S,
 		origin: Option<OriginProducer>,
+		stats: PublishStats,
 		recv_bandwidth: Option<BandwidthProducer>,
 		self_origin: crate::Origin,
 		version: Version,
@@ -45,6 +47,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 		Self {
 			session,
 			origin,
+			stats,
 			recv_bandwidth,
 			self_origin,
 			subscribes: Default::default(),
@@ -57,6 +60,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 		let bw = self.clone();
 		tokio::select! {
 			Err(err) = self.clone().run_announce() => Err(err),
+			Err(err) = self.clone().run_datagrams() => Err(err),
 			res = self.run_uni() => res,
 			Err(err) = bw.run_recv_bandwidth() => Err(err),
 		}
@@ -77,6 +81,43 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 		}
 	}
 
+	/// Receive single-frame groups delivered as datagrams (moq-lite-05+).
+	async fn run_datagrams(self) -> Result<(), Error> {
+		if matches!(
+			self.version,
+			Version::Lite01 | Version::Lite02 | Version::Lite03 | Version::Lite04
+		) {
+			return Ok(());
+		}
+
+		loop {
+			// A closed session is reported by run_uni; datagrams are best-effort.
+			let Ok(datagram) = self.session.recv_datagram().await else {
+				return Ok(());
+			};
+
+			if let Err(err) = self.recv_datagram(datagram) {
+				tracing::debug!(%err, "error receiving datagram");
+			}
+		}
+	}
+
+	fn recv_datagram(&self, mut datagram: bytes::Bytes) -> Result<(), Error> {
+		let hdr = lite::Group::decode(&mut datagram, self.version)?;
+
+		let mut group = {
+			let mut subs = self.subscribes.lock();
+			let track = subs.get_mut(&hdr.subscribe).ok_or(Error::Cancel)?;
+			track.create_group(Group { sequence: hdr.sequence })?
+		};
+
+		// The payload runs to the end of the datagram.
+		group.write_frame(datagram)?;
+		group.finish()?;
+
+		Ok(())
+	}
+
 	async fn run_uni_stream(mut self, mut stream: Reader<S::RecvStream, Version>) -> Result<(), Error> {
 		let kind = stream.decode().await?;
 
@@ -219,6 +260,7 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 			Entry::Occupied(_) => return Err(Error::Duplicate),
 			Entry::Vacant(entry) => entry.insert(broadcast.clone()),
 		};
+		self.stats.add_announce();
 
 		// Create the dynamic handler BEFORE publishing, so that consumers
 		// see dynamic >= 1 immediately when they receive the announcement.
@@ -272,11 +314,14 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 			id,
 			broadcast: path.as_path(),
 			track: (&track.name).into(),
-			priority: track.priority,
+			priority: track.priority.into(),
 			ordered: true,
 			max_latency: std::time::Duration::ZERO,
 			start_group: None,
 			end_group: None,
+			clean_join: track.clean_join,
+			datagrams: track.datagrams,
+			ascending: track.ascending,
 		};
 
 		tracing::info!(id, broadcast = %self.log_path(&path), track = %track.name, "subscribe started");
@@ -375,7 +420,10 @@ impl<S: web_transport_trait::Session> Subscriber<S> {
 		mut group: GroupProducer,
 	) -> Result<(), Error> {
 		while let Some(size) = stream.decode_maybe::<u64>().await? {
-			let mut frame = group.create_frame(Frame { size })?;
+			let mut frame = group.create_frame(Frame {
+				size,
+				..Default::default()
+			})?;
 
 			if let Err(err) = self.run_frame(stream, &mut frame).await {
 				let _ = frame.abort(err.clone());
@@ -7,6 +7,7 @@ pub enum Version {
 	Lite02,
 	Lite03,
 	Lite04,
+	Lite05,
 }
 
 impl fmt::Display for Version {
@@ -16,6 +17,7 @@ impl fmt::Display for Version {
 			Self::Lite02 => write!(f, "moq-lite-02"),
 			Self::Lite03 => write!(f, "moq-lite-03"),
 			Self::Lite04 => write!(f, "moq-lite-04"),
+			Self::Lite05 => write!(f, "moq-lite-05"),
 		}
 	}
 }
@@ -27,6 +29,7 @@ impl From<Version> for crate::Version {
 			Version::Lite02 => crate::Version::Lite(Version::Lite02),
 			Version::Lite03 => crate::Version::Lite(Version::Lite03),
 			Version::Lite04 => crate::Version::Lite(Version::Lite04),
+			Version::Lite05 => crate::Version::Lite(Version::Lite05),
 		}
 	}
 }
@@ -2,6 +2,7 @@
 //!
 //! A [BandwidthProducer] is used to set the current estimated bitrate, notifying consumers.
 //! A [BandwidthConsumer] can read the current estimate and wait for changes.
+//! A [Congestion] turns an estimate into a signal for dropping frames at the producer.
 
 use std::task::Poll;
 
@@ -126,3 +127,28 @@ impl BandwidthConsumer {
 		conducer::wait(|waiter| self.poll_changed(waiter)).await
 	}
 }
+
+/// Decides when a droppable track is congested, based on a bandwidth estimate.
+///
+/// Typically built from [`crate::Session::send_bandwidth`] and passed to
+/// [`crate::TrackProducer::with_congestion`].
+/// The track is congested while the estimate is known and below `min_bitrate`.
+#[derive(Clone)]
+pub struct Congestion {
+	bandwidth: BandwidthConsumer,
+	min_bitrate: u64,
+}
+
+impl Congestion {
+	/// Consider the track congested when `bandwidth` drops below `min_bitrate` bits per second.
+	pub fn new(bandwidth: BandwidthConsumer, min_bitrate: u64) -> Self {
+		Self { bandwidth, min_bitrate }
+	}
+
+	/// Returns true if the current estimate is below the minimum bitrate.
+	///
+	/// An unknown estimate is never considered congested.
+	pub fn is_congested(&self) -> bool {
+		self.bandwidth.peek().is_some_and(|bitrate| bitrate < self.min_bitrate)
+	}
+}
@@ -135,7 +135,7 @@ impl BroadcastProducer {
 		}
 		drop(state);
 
-		self.create_track(Track { name, priority: 0 })
+		self.create_track(Track::new(name))
 	}
 
 	/// Create a dynamic producer that handles on-demand track requests from consumers.
@@ -629,6 +629,27 @@ mod test {
 		);
 	}
 
+	#[tokio::test]
+	async fn requested_timeout() {
+		tokio::time::pause();
+
+		let mut broadcast = Broadcast::new().produce().dynamic();
+		let mut consumer = broadcast.consume().assert_subscribe_track(&Track::new("silent"));
+		let producer = broadcast.assert_request();
+
+		// The publisher never writes a group.
+		let timeout = std::time::Duration::from_secs(1);
+		let start = tokio::time::Instant::now();
+		let res = consumer.next_group_timeout(timeout).await;
+		assert!(matches!(res, Err(Error::Timeout)));
+		assert!(start.elapsed() >= timeout);
+
+		// Giving up on the track tears down the subscription.
+		assert!(producer.unused().now_or_never().is_none());
+		drop(consumer);
+		assert!(producer.unused().now_or_never().is_some());
+	}
+
 	// Cloning a `BroadcastDynamic` and dropping the clone must not flip
 	// `state.dynamic` to zero. The relay's lite subscriber clones the
 	// dynamic per spawned subscribe; if Clone skipped the increment, the
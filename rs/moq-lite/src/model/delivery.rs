@@ -0,0 +1,187 @@
+//! Delivery feedback for a track, reported by the publisher as groups are acknowledged.
+//!
+//! A [TrackDelivery] is obtained from [`crate::TrackProducer::delivery`] and reports a
+//! [GroupDelivery] each time a subscriber acknowledges a whole group, along with a smoothed
+//! throughput estimate. An encoder can use it to lower its bitrate when groups drain slowly.
+//!
+//! Estimates are best-effort and per-subscriber: every subscriber reports its own groups into
+//! the same channel, so with several subscribers the values interleave. Through a relay, the
+//! reports describe the hop to the relay rather than the relay's own subscribers.
+
+use std::{task::Poll, time::Duration};
+
+/// Weight of each new sample in the smoothed throughput, as `1 / THROUGHPUT_WEIGHT`.
+const THROUGHPUT_WEIGHT: u64 = 8;
+
+/// The delivery of a single group to a single subscriber.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GroupDelivery {
+	/// The sequence number of the group.
+	pub sequence: u64,
+	/// The number of bytes written for the group, excluding the stream header.
+	pub bytes: u64,
+	/// The time from opening the stream until the subscriber acknowledged the whole group.
+	pub elapsed: Duration,
+	/// The time from the last write until the subscriber acknowledged the whole group.
+	///
+	/// A growing drain time means data is queueing faster than it can be sent.
+	pub drain: Duration,
+}
+
+impl GroupDelivery {
+	/// The throughput of this group in bits per second, or `None` if it was instantaneous.
+	///
+	/// For a live track this is bounded by the rate the group was produced at,
+	/// so [`Self::drain`] is the better signal that delivery is falling behind.
+	pub fn throughput(&self) -> Option<u64> {
+		let nanos = self.elapsed.as_nanos();
+		if nanos == 0 {
+			return None;
+		}
+
+		let bits = self.bytes as u128 * 8 * 1_000_000_000;
+		Some((bits / nanos).try_into().unwrap_or(u64::MAX))
+	}
+}
+
+#[derive(Default)]
+struct State {
+	last: Option<GroupDelivery>,
+	throughput: Option<u64>,
+	/// The number of groups reported, used to detect new reports.
+	count: u64,
+}
+
+/// The publisher side of the delivery channel, held by every handle of a track.
+///
+/// Reports are dropped once the [`crate::TrackProducer`] is gone.
+#[derive(Clone)]
+pub(crate) struct DeliveryReporter {
+	state: conducer::Weak<State>,
+}
+
+impl DeliveryReporter {
+	/// Record an acknowledged group, updating the smoothed throughput.
+	pub fn report(&self, delivery: GroupDelivery) {
+		let Ok(mut state) = self.state.write() else {
+			return;
+		};
+
+		if let Some(sample) = delivery.throughput() {
+			state.throughput = Some(match state.throughput {
+				Some(prev) => prev - prev / THROUGHPUT_WEIGHT + sample / THROUGHPUT_WEIGHT,
+				None => sample,
+			});
+		}
+
+		state.last = Some(delivery);
+		state.count += 1;
+	}
+}
+
+/// Owns the delivery channel for a [`crate::TrackProducer`] and its clones.
+#[derive(Clone, Default)]
+pub(crate) struct DeliveryProducer {
+	state: conducer::Producer<State>,
+}
+
+impl DeliveryProducer {
+	pub fn reporter(&self) -> DeliveryReporter {
+		DeliveryReporter {
+			state: self.state.weak(),
+		}
+	}
+
+	pub fn consume(&self) -> TrackDelivery {
+		let state = self.state.consume();
+		let count = state.read().count;
+		TrackDelivery { state, count }
+	}
+}
+
+/// Delivery feedback for a track, allowing reads and async notifications of new reports.
+///
+/// See the [module documentation](self) for the caveats of these estimates.
+#[derive(Clone)]
+pub struct TrackDelivery {
+	state: conducer::Consumer<State>,
+	count: u64,
+}
+
+impl TrackDelivery {
+	/// The smoothed throughput of acknowledged groups in bits per second, if any were reported.
+	pub fn throughput(&self) -> Option<u64> {
+		self.state.read().throughput
+	}
+
+	/// The most recently acknowledged group, if any.
+	pub fn last(&self) -> Option<GroupDelivery> {
+		self.state.read().last
+	}
+
+	/// Poll for the next acknowledged group without blocking.
+	///
+	/// Reports that arrive between polls are coalesced into the latest one.
+	pub fn poll_next(&mut self, waiter: &conducer::Waiter) -> Poll<Option<GroupDelivery>> {
+		let count = self.count;
+
+		match self.state.poll(waiter, |state| match state.last {
+			Some(last) if state.count != count => Poll::Ready((state.count, last)),
+			_ => Poll::Pending,
+		}) {
+			Poll::Ready(Ok((count, last))) => {
+				self.count = count;
+				Poll::Ready(Some(last))
+			}
+			// The track producer was dropped.
+			Poll::Ready(Err(_)) => Poll::Ready(None),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+
+	/// Block until a group is acknowledged, returning its delivery.
+	/// Returns `None` once the track producer is dropped.
+	pub async fn next(&mut self) -> Option<GroupDelivery> {
+		conducer::wait(|waiter| self.poll_next(waiter)).await
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn delivery(sequence: u64, bytes: u64, elapsed: Duration) -> GroupDelivery {
+		GroupDelivery {
+			sequence,
+			bytes,
+			elapsed,
+			drain: Duration::ZERO,
+		}
+	}
+
+	#[tokio::test]
+	async fn reports_smoothed_throughput() {
+		let producer = DeliveryProducer::default();
+		let reporter = producer.reporter();
+		let mut consumer = producer.consume();
+
+		assert_eq!(consumer.throughput(), None);
+		assert!(consumer.poll_next(&conducer::Waiter::noop()).is_pending());
+
+		// 125 KB in a second is 1 Mb/s.
+		reporter.report(delivery(0, 125_000, Duration::from_secs(1)));
+		assert_eq!(consumer.next().await.unwrap().sequence, 0);
+		assert_eq!(consumer.throughput(), Some(1_000_000));
+
+		// A 9 Mb/s sample moves the estimate by an eighth of the difference.
+		reporter.report(delivery(1, 1_125_000, Duration::from_secs(1)));
+		reporter.report(delivery(2, 0, Duration::ZERO));
+		assert_eq!(consumer.next().await.unwrap().sequence, 2);
+		assert_eq!(consumer.throughput(), Some(2_000_000));
+
+		drop(producer);
+		reporter.report(delivery(3, 125_000, Duration::from_secs(1)));
+		assert_eq!(consumer.next().await, None);
+	}
+}
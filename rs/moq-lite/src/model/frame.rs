@@ -11,11 +11,26 @@ use crate::{Error, Result};
 ///
 /// Note that this is just the header.
 /// You use [FrameProducer] and [FrameConsumer] to deal with the frame payload, potentially chunked.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frame {
 	/// Total payload size in bytes. Declared up front so consumers can preallocate.
 	pub size: u64,
+	/// Importance within the group, used to pick what to drop under congestion.
+	///
+	/// Once a track is congested (see [`crate::TrackProducer::with_congestion`]), frames after
+	/// the first with the default priority of 0 are dropped. Frames with a higher priority,
+	/// such as base-layer slices, are still delivered unless a frame of equal or higher
+	/// priority was already dropped, since they may depend on it. Frames are never reordered.
+	/// The IETF transport carries it as an object extension; moq-lite drops it.
+	pub priority: u8,
+	/// Extension headers carried alongside the payload, as `(type, value)` pairs.
+	///
+	/// Only the IETF transport delivers them, for example a capture timestamp that
+	/// consumers can read without parsing the payload. moq-lite drops them.
+	/// Even types carry an integer, stored as its 8-byte big-endian value.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub extensions: Vec<(u64, Bytes)>,
 }
 
 impl Frame {
@@ -27,25 +42,37 @@ impl Frame {
 
 impl From<usize> for Frame {
 	fn from(size: usize) -> Self {
-		Self { size: size as u64 }
+		Self {
+			size: size as u64,
+			..Default::default()
+		}
 	}
 }
 
 impl From<u64> for Frame {
 	fn from(size: u64) -> Self {
-		Self { size }
+		Self {
+			size,
+			..Default::default()
+		}
 	}
 }
 
 impl From<u32> for Frame {
 	fn from(size: u32) -> Self {
-		Self { size: size as u64 }
+		Self {
+			size: size as u64,
+			..Default::default()
+		}
 	}
 }
 
 impl From<u16> for Frame {
 	fn from(size: u16) -> Self {
-		Self { size: size as u64 }
+		Self {
+			size: size as u64,
+			..Default::default()
+		}
 	}
 }
 
@@ -298,6 +325,9 @@ impl From<Frame> for FrameProducer {
 }
 
 /// Used to consume a frame's worth of data, streaming as bytes arrive.
+///
+/// Chunks are written into a single buffer sized up front, so every read is a zero-copy
+/// slice of it; a multi-chunk frame is never merged on read, even across consumers.
 #[derive(Clone)]
 pub struct FrameConsumer {
 	info: Frame,
@@ -437,7 +467,11 @@ mod test {
 
 	#[test]
 	fn single_chunk_roundtrip() {
-		let mut producer = Frame { size: 5 }.produce();
+		let mut producer = Frame {
+			size: 5,
+			..Default::default()
+		}
+		.produce();
 		producer.write(Bytes::from_static(b"hello")).unwrap();
 		producer.finish().unwrap();
 
@@ -448,7 +482,11 @@ mod test {
 
 	#[test]
 	fn multi_chunk_read_all() {
-		let mut producer = Frame { size: 10 }.produce();
+		let mut producer = Frame {
+			size: 10,
+			..Default::default()
+		}
+		.produce();
 		producer.write(Bytes::from_static(b"hello")).unwrap();
 		producer.write(Bytes::from_static(b"world")).unwrap();
 		producer.finish().unwrap();
@@ -460,7 +498,11 @@ mod test {
 
 	#[test]
 	fn read_chunk_sequential() {
-		let mut producer = Frame { size: 10 }.produce();
+		let mut producer = Frame {
+			size: 10,
+			..Default::default()
+		}
+		.produce();
 		producer.write(Bytes::from_static(b"hello")).unwrap();
 		// Each read_chunk returns whatever is new since the last call,
 		// which may span multiple writes.
@@ -479,7 +521,11 @@ mod test {
 
 	#[test]
 	fn read_all_chunks() {
-		let mut producer = Frame { size: 10 }.produce();
+		let mut producer = Frame {
+			size: 10,
+			..Default::default()
+		}
+		.produce();
 		producer.write(Bytes::from_static(b"hello")).unwrap();
 		producer.write(Bytes::from_static(b"world")).unwrap();
 		producer.finish().unwrap();
@@ -490,9 +536,39 @@ mod test {
 		assert_eq!(chunks[0], Bytes::from_static(b"helloworld"));
 	}
 
+	#[test]
+	fn multi_chunk_read_is_zero_copy() {
+		let mut producer = Frame {
+			size: 10,
+			..Default::default()
+		}
+		.produce();
+		producer.write(Bytes::from_static(b"hello")).unwrap();
+
+		let mut streaming = producer.consume();
+		let first = streaming.read_chunk().now_or_never().unwrap().unwrap().unwrap();
+
+		producer.write(Bytes::from_static(b"world")).unwrap();
+		producer.finish().unwrap();
+
+		let second = streaming.read_chunk().now_or_never().unwrap().unwrap().unwrap();
+		assert_eq!(second.as_ptr(), first[first.len()..].as_ptr());
+
+		// Reading the whole frame slices the same allocation instead of merging the chunks.
+		for _ in 0..10 {
+			let all = producer.consume().read_all().now_or_never().unwrap().unwrap();
+			assert_eq!(all, Bytes::from_static(b"helloworld"));
+			assert_eq!(all.as_ptr(), first.as_ptr());
+		}
+	}
+
 	#[test]
 	fn finish_checks_remaining() {
-		let mut producer = Frame { size: 5 }.produce();
+		let mut producer = Frame {
+			size: 5,
+			..Default::default()
+		}
+		.produce();
 		producer.write(Bytes::from_static(b"hi")).unwrap();
 		let err = producer.finish().unwrap_err();
 		assert!(matches!(err, Error::WrongSize));
@@ -500,14 +576,22 @@ mod test {
 
 	#[test]
 	fn write_too_many_bytes() {
-		let mut producer = Frame { size: 3 }.produce();
+		let mut producer = Frame {
+			size: 3,
+			..Default::default()
+		}
+		.produce();
 		let err = producer.write(Bytes::from_static(b"toolong")).unwrap_err();
 		assert!(matches!(err, Error::WrongSize));
 	}
 
 	#[test]
 	fn abort_propagates() {
-		let mut producer = Frame { size: 5 }.produce();
+		let mut producer = Frame {
+			size: 5,
+			..Default::default()
+		}
+		.produce();
 		let mut consumer = producer.consume();
 		producer.abort(Error::Cancel).unwrap();
 
@@ -517,7 +601,11 @@ mod test {
 
 	#[test]
 	fn empty_frame() {
-		let mut producer = Frame { size: 0 }.produce();
+		let mut producer = Frame {
+			size: 0,
+			..Default::default()
+		}
+		.produce();
 		producer.finish().unwrap();
 
 		let mut consumer = producer.consume();
@@ -527,7 +615,11 @@ mod test {
 
 	#[tokio::test]
 	async fn pending_then_ready() {
-		let mut producer = Frame { size: 5 }.produce();
+		let mut producer = Frame {
+			size: 5,
+			..Default::default()
+		}
+		.produce();
 		let mut consumer = producer.consume();
 
 		// Consumer blocks because no data yet.
@@ -543,7 +635,11 @@ mod test {
 	#[test]
 	fn buf_mut_roundtrip() {
 		// Exercise the BufMut path that the receive loop uses via `read_buf`.
-		let mut producer = Frame { size: 12 }.produce();
+		let mut producer = Frame {
+			size: 12,
+			..Default::default()
+		}
+		.produce();
 		assert_eq!(producer.remaining_mut(), 12);
 		producer.put_slice(b"hello");
 		assert_eq!(producer.remaining_mut(), 7);
@@ -559,14 +655,22 @@ mod test {
 	#[test]
 	#[should_panic(expected = "advance_mut past frame.size")]
 	fn buf_mut_advance_past_capacity_panics() {
-		let mut producer = Frame { size: 4 }.produce();
+		let mut producer = Frame {
+			size: 4,
+			..Default::default()
+		}
+		.produce();
 		// Safety violation on purpose: cnt > remaining_mut().
 		unsafe { producer.advance_mut(5) };
 	}
 
 	#[test]
 	fn read_chunk_streams_partial_writes() {
-		let mut producer = Frame { size: 6 }.produce();
+		let mut producer = Frame {
+			size: 6,
+			..Default::default()
+		}
+		.produce();
 		let mut consumer = producer.consume();
 
 		producer.write(Bytes::from_static(b"foo")).unwrap();
@@ -586,7 +690,11 @@ mod test {
 
 	#[test]
 	fn cloned_consumer_independent_cursor() {
-		let mut producer = Frame { size: 10 }.produce();
+		let mut producer = Frame {
+			size: 10,
+			..Default::default()
+		}
+		.produce();
 		let mut c1 = producer.consume();
 		producer.write(Bytes::from_static(b"hello")).unwrap();
 
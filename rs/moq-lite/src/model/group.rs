@@ -14,7 +14,7 @@ use bytes::Bytes;
 
 use crate::{Error, Result};
 
-use super::{Frame, FrameConsumer, FrameProducer};
+use super::{Congestion, Frame, FrameConsumer, FrameProducer, Timescale};
 
 /// Maximum total size of frames cached in a group before old frames are evicted.
 const MAX_GROUP_CACHE: u64 = 32 * 1024 * 1024; // 32 MB
@@ -87,6 +87,10 @@ struct GroupState {
 
 	// The error that caused the group to be aborted, if any.
 	abort: Option<Error>,
+
+	// The highest priority of any frame dropped due to congestion.
+	// Later frames at or below it are dropped too, since they may depend on it.
+	dropping: Option<u8>,
 }
 
 impl GroupState {
@@ -141,6 +145,9 @@ pub struct GroupProducer {
 
 	// The group header containing the sequence number.
 	info: Group,
+
+	// Drop frames after the first while congested, inherited from the track.
+	congestion: Option<Congestion>,
 }
 
 impl std::ops::Deref for GroupProducer {
@@ -157,9 +164,15 @@ impl GroupProducer {
 		Self {
 			info,
 			state: conducer::Producer::default(),
+			congestion: None,
 		}
 	}
 
+	pub(super) fn with_congestion(mut self, congestion: Option<Congestion>) -> Self {
+		self.congestion = congestion;
+		self
+	}
+
 	/// A helper method to write a frame from a single byte buffer.
 	///
 	/// If you want to write multiple chunks, use [Self::create_frame] to get a frame producer.
@@ -168,6 +181,7 @@ impl GroupProducer {
 		let data = frame.into();
 		let frame = Frame {
 			size: data.len() as u64,
+			..Default::default()
 		};
 		let mut frame = self.create_frame(frame)?;
 		frame.write(data)?;
@@ -183,11 +197,25 @@ impl GroupProducer {
 	}
 
 	/// Append a frame producer to the group.
+	///
+	/// If the track is congested (see [`crate::TrackProducer::with_congestion`]), any frame
+	/// after the first with a [`Frame::priority`] of 0 is silently dropped, along with every
+	/// later frame of equal or lower priority since they may depend on it.
+	/// The first frame (the keyframe) is always kept.
 	pub fn append_frame(&mut self, frame: FrameProducer) -> Result<()> {
 		let mut state = modify(&self.state)?;
 		if state.fin {
 			return Err(Error::Closed);
 		}
+
+		let first = state.offset == 0 && state.frames.is_empty();
+		let droppable = frame.priority == 0 && self.congestion.as_ref().is_some_and(Congestion::is_congested);
+		let dependent = state.dropping.is_some_and(|dropped| frame.priority <= dropped);
+		if !first && (droppable || dependent) {
+			state.dropping = state.dropping.max(Some(frame.priority));
+			return Ok(());
+		}
+
 		state.cache += frame.size;
 		state.frames.push_back(frame);
 		state.evict();
@@ -235,6 +263,9 @@ impl GroupProducer {
 	}
 
 	/// Block until there are no active consumers.
+	///
+	/// Use this to stop producing a group that every subscriber has skipped past.
+	/// Returns immediately if no consumers exist, or an error if the group is closed first.
 	pub async fn unused(&self) -> Result<()> {
 		self.state
 			.unused()
@@ -248,6 +279,7 @@ impl Clone for GroupProducer {
 		Self {
 			info: self.info.clone(),
 			state: self.state.clone(),
+			congestion: self.congestion.clone(),
 		}
 	}
 }
@@ -307,6 +339,44 @@ impl GroupConsumer {
 		self.poll(waiter, |state| state.poll_get_frame(index))
 	}
 
+	/// Block until the last frame at or before the given timestamp is known, returning it and its index.
+	///
+	/// Each frame must begin with a VarInt timestamp, like hang's Legacy container, which is decoded
+	/// to compare against `timestamp`. Frames are scanned in order until a later one is found or the
+	/// group ends, waiting for new frames while the group is still being produced.
+	/// Returns None if every remaining frame is after `timestamp`; evicted frames are skipped.
+	pub async fn frame_at<const SCALE: u64>(
+		&self,
+		timestamp: Timescale<SCALE>,
+	) -> Result<Option<(usize, FrameConsumer)>> {
+		let mut found = None;
+		let mut index = self.state.read().offset;
+
+		loop {
+			let frame = match self.get_frame(index).await {
+				Ok(Some(frame)) => frame,
+				Ok(None) => break,
+				// Frames were evicted mid-scan; resume from the oldest remaining one.
+				Err(Error::CacheFull) => {
+					index = self.state.read().offset;
+					continue;
+				}
+				Err(err) => return Err(err),
+			};
+
+			// Read a clone so the returned consumer starts at the beginning of the frame.
+			let mut data = frame.clone().read_all().await?;
+			if Timescale::<SCALE>::decode(&mut data)? > timestamp {
+				break;
+			}
+
+			found = Some((index, frame));
+			index += 1;
+		}
+
+		Ok(found)
+	}
+
 	/// Return a consumer for the next frame for chunked reading.
 	pub async fn next_frame(&mut self) -> Result<Option<FrameConsumer>> {
 		conducer::wait(|waiter| self.poll_next_frame(waiter)).await
@@ -341,6 +411,25 @@ impl GroupConsumer {
 		conducer::wait(|waiter| self.poll_read_frame(waiter)).await
 	}
 
+	/// Read the next frame's header and data all at once, without blocking.
+	///
+	/// Like [Self::poll_read_frame], but also returns the [Frame] header with its extensions.
+	pub fn poll_read_frame_info(&mut self, waiter: &conducer::Waiter) -> Poll<Result<Option<(Frame, Bytes)>>> {
+		let Some(mut frame) = ready!(self.poll(waiter, |state| state.poll_get_frame(self.index))?) else {
+			return Poll::Ready(Ok(None));
+		};
+
+		let data = ready!(frame.poll_read_all(waiter))?;
+		self.index += 1;
+
+		Poll::Ready(Ok(Some(((*frame).clone(), data))))
+	}
+
+	/// Read the next frame's header and data all at once.
+	pub async fn read_frame_info(&mut self) -> Result<Option<(Frame, Bytes)>> {
+		conducer::wait(|waiter| self.poll_read_frame_info(waiter)).await
+	}
+
 	/// Read all of the chunks of the next frame, without blocking.
 	pub fn poll_read_frame_chunks(&mut self, waiter: &conducer::Waiter) -> Poll<Result<Option<Vec<Bytes>>>> {
 		let Some(mut frame) = ready!(self.poll(waiter, |state| state.poll_get_frame(self.index))?) else {
@@ -367,6 +456,13 @@ impl GroupConsumer {
 	pub async fn finished(&mut self) -> Result<u64> {
 		conducer::wait(|waiter| self.poll_finished(waiter)).await
 	}
+
+	/// Returns true if frames were evicted from the front of the group.
+	///
+	/// A truncated group can no longer be read from its first frame.
+	pub fn is_truncated(&self) -> bool {
+		self.state.read().offset > 0
+	}
 }
 
 #[cfg(test)]
@@ -390,6 +486,87 @@ mod test {
 		assert!(end.is_none());
 	}
 
+	#[test]
+	fn unused_after_last_consumer() {
+		let mut track = crate::Track::new("test").produce();
+		let mut subscriber = track.consume();
+		let mut producer = track.append_group().unwrap();
+		producer.write_frame(Bytes::from_static(b"frame0")).unwrap();
+
+		let first = subscriber.assert_group();
+		let second = first.clone();
+		assert!(producer.unused().now_or_never().is_none());
+
+		drop(first);
+		assert!(producer.unused().now_or_never().is_none());
+
+		// The track's cache holds a producer, so it doesn't keep the group in use.
+		drop(second);
+		assert!(matches!(producer.unused().now_or_never(), Some(Ok(()))));
+
+		// A new consumer makes the group used again.
+		let _third = producer.consume();
+		assert!(producer.unused().now_or_never().is_none());
+	}
+
+	fn write_timestamped(producer: &mut GroupProducer, micros: u64, payload: &'static [u8]) {
+		let mut buf = bytes::BytesMut::new();
+		Timescale::<1_000_000>::from_micros(micros)
+			.unwrap()
+			.encode(&mut buf)
+			.unwrap();
+		buf.extend_from_slice(payload);
+		producer.write_frame(buf.freeze()).unwrap();
+	}
+
+	#[test]
+	fn frame_at_timestamp() {
+		let mut producer = Group { sequence: 0 }.produce();
+		write_timestamped(&mut producer, 1_000, b"a");
+		write_timestamped(&mut producer, 2_000, b"b");
+		write_timestamped(&mut producer, 3_000, b"c");
+		producer.finish().unwrap();
+
+		let consumer = producer.consume();
+		let at = |micros| {
+			let timestamp = Timescale::<1_000_000>::from_micros(micros).unwrap();
+			let found = consumer.frame_at(timestamp).now_or_never().unwrap().unwrap();
+			found.map(|(index, _)| index)
+		};
+
+		assert_eq!(at(500), None);
+		assert_eq!(at(1_000), Some(0));
+		assert_eq!(at(2_999), Some(1));
+		assert_eq!(at(10_000), Some(2));
+
+		// The returned consumer reads the whole frame, timestamp prefix included.
+		let timestamp = Timescale::<1_000_000>::from_micros(2_000).unwrap();
+		let (_, mut frame) = consumer.frame_at(timestamp).now_or_never().unwrap().unwrap().unwrap();
+		let data = frame.read_all().now_or_never().unwrap().unwrap();
+		assert_eq!(data.last(), Some(&b'b'));
+	}
+
+	#[test]
+	fn frame_at_waits_for_group() {
+		let mut producer = Group { sequence: 0 }.produce();
+		write_timestamped(&mut producer, 1_000, b"a");
+
+		let consumer = producer.consume();
+		let timestamp = Timescale::<1_000_000>::from_micros(5_000).unwrap();
+		let mut pending = Box::pin(consumer.frame_at(timestamp));
+
+		// A later frame may still arrive, so the answer isn't known yet.
+		assert!((&mut pending).now_or_never().is_none());
+
+		write_timestamped(&mut producer, 4_000, b"b");
+		assert!((&mut pending).now_or_never().is_none());
+
+		// The group ending settles it.
+		producer.finish().unwrap();
+		let (index, _) = pending.now_or_never().unwrap().unwrap().unwrap();
+		assert_eq!(index, 1);
+	}
+
 	#[test]
 	fn read_frame_all_at_once() {
 		let mut producer = Group { sequence: 0 }.produce();
@@ -404,7 +581,12 @@ mod test {
 	#[test]
 	fn read_frame_chunks() {
 		let mut producer = Group { sequence: 0 }.produce();
-		let mut frame = producer.create_frame(Frame { size: 10 }).unwrap();
+		let mut frame = producer
+			.create_frame(Frame {
+				size: 10,
+				..Default::default()
+			})
+			.unwrap();
 		frame.write(Bytes::from_static(b"hello")).unwrap();
 		frame.write(Bytes::from_static(b"world")).unwrap();
 		frame.finish().unwrap();
@@ -480,6 +662,7 @@ mod test {
 
 		// The first frame should have been evicted.
 		let consumer = producer.consume();
+		assert!(consumer.is_truncated());
 		let result = consumer.get_frame(0).now_or_never().unwrap();
 		assert!(matches!(result, Err(crate::Error::CacheFull)));
 
@@ -1,15 +1,19 @@
 mod bandwidth;
 mod broadcast;
+mod delivery;
 mod frame;
 mod group;
 mod origin;
+mod priority;
 mod time;
 mod track;
 
 pub use bandwidth::*;
 pub use broadcast::*;
+pub use delivery::*;
 pub use frame::*;
 pub use group::*;
 pub use origin::*;
+pub use priority::*;
 pub use time::*;
 pub use track::*;
@@ -565,6 +565,9 @@ pub struct OriginProducer {
 
 	// The prefix that is automatically stripped from all paths.
 	root: PathOwned,
+
+	// Broadcasts whose hop chain is longer than this are refused.
+	max_hops: usize,
 }
 
 impl std::ops::Deref for OriginProducer {
@@ -583,9 +586,19 @@ impl OriginProducer {
 			info,
 			nodes: OriginNodes::default(),
 			root: PathOwned::default(),
+			max_hops: MAX_HOPS,
 		}
 	}
 
+	/// Refuse broadcasts that have crossed more than `max` hops, limiting how far they propagate.
+	///
+	/// Every session a broadcast was announced over counts as a hop, including the one from the
+	/// publishing client. Defaults to (and is capped at) the protocol limit of 32.
+	pub fn with_max_hops(mut self, max: usize) -> Self {
+		self.max_hops = max.min(MAX_HOPS);
+		self
+	}
+
 	/// Create and publish a new broadcast, returning the producer.
 	///
 	/// This is a helper method when you only want to publish a broadcast to a single origin.
@@ -612,6 +625,10 @@ impl OriginProducer {
 			return false;
 		}
 
+		if broadcast.hops.len() > self.max_hops {
+			return false;
+		}
+
 		let (root, rest) = match self.nodes.get(&path) {
 			Some(root) => root,
 			None => return false,
@@ -641,6 +658,7 @@ impl OriginProducer {
 			info: self.info,
 			nodes: self.nodes.select(&prefixes)?,
 			root: self.root.clone(),
+			max_hops: self.max_hops,
 		})
 	}
 
@@ -672,6 +690,7 @@ impl OriginProducer {
 			info: self.info,
 			root: self.root.join(&prefix).to_owned(),
 			nodes: self.nodes.root(&prefix)?,
+			max_hops: self.max_hops,
 		})
 	}
 
@@ -704,6 +723,15 @@ pub struct OriginConsumer {
 
 	// A prefix that is automatically stripped from all paths.
 	root: PathOwned,
+
+	// Every active broadcast received from `updates`, regardless of the filter.
+	active: HashMap<PathOwned, BroadcastConsumer>,
+
+	// Optional prefixes (relative to root) that announcements must match to be returned.
+	filter: Option<Vec<PathOwned>>,
+
+	// Updates generated by a filter change, returned before anything new from `updates`.
+	pending: VecDeque<OriginAnnounce>,
 }
 
 impl std::ops::Deref for OriginConsumer {
@@ -734,6 +762,9 @@ impl OriginConsumer {
 			nodes,
 			updates: rx,
 			root,
+			active: HashMap::new(),
+			filter: None,
+			pending: VecDeque::new(),
 		}
 	}
 
@@ -745,7 +776,16 @@ impl OriginConsumer {
 	///
 	/// Note: The returned path is absolute and will always match this consumer's prefix.
 	pub async fn announced(&mut self) -> Option<OriginAnnounce> {
-		self.updates.recv().await
+		loop {
+			if let Some(update) = self.pending.pop_front() {
+				return Some(update);
+			}
+
+			let update = self.updates.recv().await?;
+			if let Some(update) = self.receive(update) {
+				return Some(update);
+			}
+		}
 	}
 
 	/// Returns the next (un)announced broadcast and the absolute path without blocking.
@@ -753,7 +793,94 @@ impl OriginConsumer {
 	/// Returns None if there is no update available; NOT because the consumer is closed.
 	/// You have to use `is_closed` to check if the consumer is closed.
 	pub fn try_announced(&mut self) -> Option<OriginAnnounce> {
-		self.updates.try_recv().ok()
+		loop {
+			if let Some(update) = self.pending.pop_front() {
+				return Some(update);
+			}
+
+			let update = self.updates.try_recv().ok()?;
+			if let Some(update) = self.receive(update) {
+				return Some(update);
+			}
+		}
+	}
+
+	/// Only return announcements under `prefix`, in addition to any prefixes already added.
+	///
+	/// Unlike [`Self::scope`], the filter can change after creation without creating a new
+	/// consumer. The first prefix narrows an unfiltered consumer, so active broadcasts outside
+	/// of it are unannounced by the following calls to [`Self::announced`].
+	pub fn add_filter(&mut self, prefix: impl AsPath) {
+		let prefix = prefix.as_path().to_owned();
+
+		let mut filter = self.filter.clone().unwrap_or_default();
+		if !filter.contains(&prefix) {
+			filter.push(prefix);
+		}
+
+		self.set_filter(Some(filter));
+	}
+
+	/// Stop returning announcements under `prefix`, unannouncing any that are active.
+	///
+	/// Removing the last prefix leaves an empty filter that matches nothing; use
+	/// [`Self::clear_filter`] to see everything again.
+	pub fn remove_filter(&mut self, prefix: impl AsPath) {
+		let prefix = prefix.as_path();
+
+		let Some(mut filter) = self.filter.clone() else {
+			return;
+		};
+		filter.retain(|existing| existing != &prefix);
+
+		self.set_filter(Some(filter));
+	}
+
+	/// Remove the filter, announcing every active broadcast that it excluded.
+	pub fn clear_filter(&mut self) {
+		self.set_filter(None);
+	}
+
+	fn set_filter(&mut self, filter: Option<Vec<PathOwned>>) {
+		// Catch up first, so the diff below is against what the caller has (or will have) seen.
+		while let Ok(update) = self.updates.try_recv() {
+			if let Some(update) = self.receive(update) {
+				self.pending.push_back(update);
+			}
+		}
+
+		let before = std::mem::replace(&mut self.filter, filter);
+
+		let mut changed: Vec<_> = self
+			.active
+			.iter()
+			.filter(|(path, _)| Self::matches(&before, path) != Self::matches(&self.filter, path))
+			.collect();
+		changed.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+		for (path, broadcast) in changed {
+			match Self::matches(&self.filter, path) {
+				true => self.pending.push_back((path.clone(), Some(broadcast.clone()))),
+				false => self.pending.push_back((path.clone(), None)),
+			}
+		}
+	}
+
+	// Record an update from the origin, returning it if it passes the filter.
+	fn receive(&mut self, (path, broadcast): OriginAnnounce) -> Option<OriginAnnounce> {
+		match &broadcast {
+			Some(broadcast) => self.active.insert(path.clone(), broadcast.clone()),
+			None => self.active.remove(&path),
+		};
+
+		Self::matches(&self.filter, &path).then_some((path, broadcast))
+	}
+
+	fn matches(filter: &Option<Vec<PathOwned>>, path: &Path) -> bool {
+		match filter {
+			Some(prefixes) => prefixes.iter().any(|prefix| path.has_prefix(prefix)),
+			None => true,
+		}
 	}
 
 	/// Create another consumer with its own announcement cursor over the same origin.
@@ -862,7 +989,9 @@ impl Drop for OriginConsumer {
 
 impl Clone for OriginConsumer {
 	fn clone(&self) -> Self {
-		OriginConsumer::new(self.info, self.root.clone(), self.nodes.clone())
+		let mut consumer = OriginConsumer::new(self.info, self.root.clone(), self.nodes.clone());
+		consumer.filter = self.filter.clone();
+		consumer
 	}
 }
 
@@ -933,6 +1062,34 @@ mod tests {
 		assert_eq!(OriginList::try_from(over), Err(TooManyOrigins));
 	}
 
+	#[tokio::test]
+	async fn max_hops_refuses_longer_chains() {
+		let origin = Origin::random().produce().with_max_hops(2);
+		let mut consumer = origin.consume();
+
+		let chain = |len: usize| {
+			let hops = (0..len).map(|_| Origin::random()).collect::<Vec<_>>();
+			Broadcast {
+				hops: OriginList::try_from(hops).unwrap(),
+			}
+			.produce()
+		};
+
+		// A broadcast that crossed exactly the limit is still accepted.
+		let at_limit = chain(2);
+		assert!(origin.publish_broadcast("near", at_limit.consume()));
+		consumer.assert_next("near", &at_limit.consume());
+
+		// One more hop and it's refused, so it's never announced onwards.
+		let over = chain(3);
+		assert!(!origin.publish_broadcast("far", over.consume()));
+		consumer.assert_next_wait();
+
+		// Scoped producers keep the limit.
+		let scoped = origin.with_root("room").unwrap();
+		assert!(!scoped.publish_broadcast("far", over.consume()));
+	}
+
 	#[tokio::test]
 	async fn test_announce() {
 		tokio::time::pause();
@@ -1238,6 +1395,63 @@ mod tests {
 		consumer.assert_next("notallowed", &broadcast3.consume());
 	}
 
+	#[tokio::test]
+	async fn test_filter_narrow_unannounces() {
+		let origin = Origin::random().produce();
+		let foo = Broadcast::new().produce();
+		let bar = Broadcast::new().produce();
+		let baz = Broadcast::new().produce();
+
+		let mut consumer = origin.consume();
+		origin.publish_broadcast("foo/test", foo.consume());
+		origin.publish_broadcast("bar/test", bar.consume());
+		consumer.assert_next("foo/test", &foo.consume());
+		consumer.assert_next("bar/test", &bar.consume());
+
+		// Narrowing to "foo" unannounces everything else that's active.
+		consumer.add_filter("foo");
+		consumer.assert_next_none("bar/test");
+		consumer.assert_next_wait();
+
+		// New broadcasts outside the filter are hidden.
+		origin.publish_broadcast("baz/test", baz.consume());
+		consumer.assert_next_wait();
+
+		// Widening announces the hidden broadcasts again.
+		consumer.add_filter("baz");
+		consumer.assert_next("baz/test", &baz.consume());
+
+		// Removing a prefix unannounces only the paths under it.
+		consumer.remove_filter("foo");
+		consumer.assert_next_none("foo/test");
+		consumer.assert_next_wait();
+
+		// Clearing the filter brings back everything.
+		consumer.clear_filter();
+		consumer.assert_next("bar/test", &bar.consume());
+		consumer.assert_next("foo/test", &foo.consume());
+		consumer.assert_next_wait();
+	}
+
+	#[tokio::test]
+	async fn test_filter_unannounce_before_seen() {
+		let origin = Origin::random().produce();
+		let foo = Broadcast::new().produce();
+		let bar = Broadcast::new().produce();
+
+		let mut consumer = origin.consume();
+		origin.publish_broadcast("foo", foo.consume());
+		origin.publish_broadcast("bar", bar.consume());
+
+		// Updates not read yet are still delivered, followed by the unannounce, so the
+		// caller always sees a consistent toggle.
+		consumer.add_filter("foo");
+		consumer.assert_next("foo", &foo.consume());
+		consumer.assert_next("bar", &bar.consume());
+		consumer.assert_next_none("bar");
+		consumer.assert_next_wait();
+	}
+
 	#[tokio::test]
 	async fn test_consume_scope_multiple_prefixes() {
 		let origin = Origin::random().produce();
@@ -0,0 +1,58 @@
+use std::fmt;
+
+/// The delivery priority of a [`crate::Track`].
+///
+/// Higher values are more important. When bandwidth is constrained, groups from a higher
+/// priority track are transmitted before groups from a lower priority track, and within a
+/// track newer groups are transmitted before older ones.
+///
+/// The default is [`Priority::MIN`], the least important.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Priority(pub u8);
+
+impl Priority {
+	/// The least important priority.
+	pub const MIN: Self = Self(0);
+
+	/// The most important priority.
+	pub const MAX: Self = Self(u8::MAX);
+
+	/// A priority for data that can tolerate delay, such as video.
+	pub const fn low() -> Self {
+		Self(64)
+	}
+
+	/// A priority for latency-sensitive data, such as audio.
+	pub const fn high() -> Self {
+		Self(192)
+	}
+
+	/// Convert from an IETF priority, where lower values are more important.
+	pub(crate) const fn from_ietf(value: u8) -> Self {
+		Self(u8::MAX - value)
+	}
+
+	/// Convert to an IETF priority, where lower values are more important.
+	pub(crate) const fn to_ietf(self) -> u8 {
+		u8::MAX - self.0
+	}
+}
+
+impl From<u8> for Priority {
+	fn from(value: u8) -> Self {
+		Self(value)
+	}
+}
+
+impl From<Priority> for u8 {
+	fn from(priority: Priority) -> Self {
+		priority.0
+	}
+}
+
+impl fmt::Display for Priority {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
@@ -3,7 +3,7 @@
 //! A [TrackProducer] creates streams with a sequence number and priority.
 //! The sequence number is used to determine the order of streams, while the priority is used to determine which stream to transmit first.
 //! This may seem counter-intuitive, but is designed for live streaming where the newest streams may be higher priority.
-//! A cloned [TrackProducer] can be used to create streams in parallel, but will error if a duplicate sequence number is used.
+//! A cloned [TrackProducer] can be used to create streams in parallel; a duplicate sequence number is handled according to its [DuplicatePolicy].
 //!
 //! A [TrackConsumer] may not receive all streams in order or at all.
 //! These streams are meant to be transmitted over congested networks and the key to MoQ Transport is to not block on them.
@@ -12,9 +12,11 @@
 //!
 //! The track is closed with [Error] when all writers or readers are dropped.
 
-use crate::{Error, Result, coding};
+use crate::{CacheStats, Error, Result, coding};
 
-use super::{Group, GroupConsumer, GroupProducer};
+use super::{
+	Congestion, DeliveryProducer, DeliveryReporter, Group, GroupConsumer, GroupProducer, Priority, TrackDelivery,
+};
 
 use std::{
 	collections::{HashSet, VecDeque},
@@ -23,7 +25,8 @@ use std::{
 };
 
 /// Groups older than this are evicted from the track cache (unless they are the max_sequence group).
-// TODO: Replace with a configurable cache size.
+///
+/// Override per track with [`TrackProducer::with_cache`].
 const MAX_GROUP_AGE: Duration = Duration::from_secs(5);
 
 /// A track is a collection of groups, delivered out-of-order until expired.
@@ -33,15 +36,37 @@ pub struct Track {
 	/// Identifier within a broadcast. Unique per [`crate::Broadcast`].
 	pub name: String,
 	/// Delivery priority. Higher values preempt lower ones when bandwidth is constrained.
-	pub priority: u8,
+	pub priority: Priority,
+	/// Ask the publisher to skip any group that can't be served from its first frame.
+	///
+	/// Media tracks start each group with a keyframe, so this guarantees a decodable start on join.
+	/// Only honored by moq-lite-05+ publishers.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub clean_join: bool,
+	/// Ask the publisher to deliver single-frame groups as QUIC datagrams when they fit.
+	///
+	/// Datagrams are lossy: a dropped group is never retransmitted, so this suits small,
+	/// independently decodable frames such as Opus audio. Larger groups fall back to a stream.
+	/// Only honored by moq-lite-05+ publishers.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub datagrams: bool,
+	/// Ask the publisher to deliver groups oldest-first, starting from the oldest cached group.
+	///
+	/// Suits VOD playback and catch-up, where every group is wanted in order.
+	/// Only honored by moq-lite-05+ publishers; otherwise delivery starts at the latest group.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub ascending: bool,
 }
 
 impl Track {
-	/// Create a track with the given name and default priority (`0`).
+	/// Create a track with the given name and the default priority ([`Priority::MIN`]).
 	pub fn new<T: Into<String>>(name: T) -> Self {
 		Self {
 			name: name.into(),
-			priority: 0,
+			priority: Priority::MIN,
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
 		}
 	}
 
@@ -51,6 +76,20 @@ impl Track {
 	}
 }
 
+/// How [`TrackProducer::create_group`] handles a sequence number that already exists.
+///
+/// This matters when multiple sources feed the same track, such as redundant encoders.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+	/// Return [`Error::Duplicate`].
+	#[default]
+	Reject,
+	/// Keep the existing group and return a detached group that nobody will consume.
+	Ignore,
+	/// Abort the existing group with [`Error::Duplicate`] and deliver the new group in its place.
+	Replace,
+}
+
 #[derive(Default)]
 struct State {
 	/// Groups in arrival order. `None` entries are tombstones for evicted groups.
@@ -60,6 +99,10 @@ struct State {
 	max_sequence: Option<u64>,
 	final_sequence: Option<u64>,
 	abort: Option<Error>,
+	/// How long groups stay cached, defaulting to MAX_GROUP_AGE.
+	max_age: Option<Duration>,
+	/// The highest sequence evicted so far, used to report evicted groups as gone.
+	evicted: Option<u64>,
 }
 
 impl State {
@@ -145,6 +188,11 @@ impl State {
 			return Poll::Ready(Ok(None));
 		}
 
+		// Anything at or below an evicted group has expired, even if it never arrived.
+		if self.evicted.is_some_and(|evicted| sequence <= evicted) {
+			return Poll::Ready(Ok(None));
+		}
+
 		if let Some(err) = &self.abort {
 			return Poll::Ready(Err(err.clone()));
 		}
@@ -162,13 +210,15 @@ impl State {
 		}
 	}
 
-	/// Evict groups older than MAX_GROUP_AGE, never evicting the max_sequence group.
+	/// Evict groups older than the cache duration, never evicting the max_sequence group.
 	///
 	/// Groups are in arrival order, so we can stop early when we hit a non-expired,
 	/// non-max_sequence group (everything after it arrived even later).
 	/// When max_sequence is at the front, we skip past it and tombstone expired groups
 	/// behind it.
 	fn evict_expired(&mut self, now: tokio::time::Instant) {
+		let max_age = self.max_age.unwrap_or(MAX_GROUP_AGE);
+
 		for slot in self.groups.iter_mut() {
 			let Some((group, created_at)) = slot else { continue };
 
@@ -176,11 +226,12 @@ impl State {
 				continue;
 			}
 
-			if now.duration_since(*created_at) <= MAX_GROUP_AGE {
+			if now.duration_since(*created_at) <= max_age {
 				break;
 			}
 
 			self.duplicates.remove(&group.sequence);
+			self.evicted = self.evicted.max(Some(group.sequence));
 			*slot = None;
 		}
 
@@ -206,6 +257,9 @@ impl State {
 pub struct TrackProducer {
 	info: Track,
 	state: conducer::Producer<State>,
+	duplicates: DuplicatePolicy,
+	congestion: Option<Congestion>,
+	delivery: DeliveryProducer,
 }
 
 impl std::ops::Deref for TrackProducer {
@@ -222,12 +276,52 @@ impl TrackProducer {
 		Self {
 			info,
 			state: conducer::Producer::default(),
+			duplicates: DuplicatePolicy::default(),
+			congestion: None,
+			delivery: DeliveryProducer::default(),
+		}
+	}
+
+	/// Choose how [`Self::create_group`] handles a sequence number that already exists.
+	///
+	/// Defaults to [`DuplicatePolicy::Reject`]. Clones inherit the policy at the time they are made.
+	pub fn with_duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+		self.duplicates = policy;
+		self
+	}
+
+	/// Mark the track as droppable, skipping frames instead of queueing them under congestion.
+	///
+	/// While `congestion` reports the transport as congested, groups created by this producer
+	/// keep their first frame (the keyframe) but drop the rest, so the track stays live at a
+	/// reduced frame rate. Clones inherit the setting at the time they are made.
+	pub fn with_congestion(mut self, congestion: Congestion) -> Self {
+		self.congestion = Some(congestion);
+		self
+	}
+
+	/// Keep groups cached for `duration` instead of the default 5 seconds.
+	///
+	/// Older groups are evicted once a newer group is created, except for the latest one.
+	/// An evicted group is no longer returned by [`TrackConsumer::get_group`], which resolves
+	/// to `None` instead. The setting is shared by every clone of this producer.
+	pub fn with_cache(self, duration: Duration) -> Self {
+		if let Ok(mut state) = self.state.write() {
+			state.max_age = Some(duration);
 		}
+		self
+	}
+
+	/// Returns true if the track is droppable and currently congested.
+	pub fn is_congested(&self) -> bool {
+		self.congestion.as_ref().is_some_and(Congestion::is_congested)
 	}
 
 	/// Create a new group with the given sequence number.
+	///
+	/// If the sequence number already exists, the result depends on the [`DuplicatePolicy`].
 	pub fn create_group(&mut self, info: Group) -> Result<GroupProducer> {
-		let group = info.produce();
+		let group = info.produce().with_congestion(self.congestion.clone());
 
 		let mut state = self.modify()?;
 		if let Some(fin) = state.final_sequence
@@ -237,7 +331,21 @@ impl TrackProducer {
 		}
 
 		if !state.duplicates.insert(group.sequence) {
-			return Err(Error::Duplicate);
+			match self.duplicates {
+				DuplicatePolicy::Reject => return Err(Error::Duplicate),
+				DuplicatePolicy::Ignore => return Ok(group),
+				DuplicatePolicy::Replace => {
+					// Tombstone the old group; the new one is appended in arrival order below.
+					for slot in state.groups.iter_mut() {
+						if let Some((old, _)) = slot
+							&& old.sequence == group.sequence
+						{
+							old.abort(Error::Duplicate).ok();
+							*slot = None;
+						}
+					}
+				}
+			}
 		}
 
 		let now = tokio::time::Instant::now();
@@ -261,7 +369,7 @@ impl TrackProducer {
 			return Err(Error::Closed);
 		}
 
-		let group = Group { sequence }.produce();
+		let group = Group { sequence }.produce().with_congestion(self.congestion.clone());
 
 		let now = tokio::time::Instant::now();
 		state.duplicates.insert(sequence);
@@ -333,9 +441,18 @@ impl TrackProducer {
 			index: 0,
 			min_sequence: 0,
 			next_sequence: 0,
+			cache_stats: None,
+			delivery: self.delivery.reporter(),
 		}
 	}
 
+	/// Receive feedback as subscribers acknowledge groups, for adapting the bitrate.
+	///
+	/// Estimates are best-effort and per-subscriber; see [`TrackDelivery`] for the caveats.
+	pub fn delivery(&self) -> TrackDelivery {
+		self.delivery.consume()
+	}
+
 	/// Block until there are no active consumers.
 	pub async fn unused(&self) -> Result<()> {
 		self.state
@@ -373,6 +490,7 @@ impl TrackProducer {
 		TrackWeak {
 			info: self.info.clone(),
 			state: self.state.weak(),
+			delivery: self.delivery.reporter(),
 		}
 	}
 
@@ -388,6 +506,9 @@ impl Clone for TrackProducer {
 		Self {
 			info: self.info.clone(),
 			state: self.state.clone(),
+			duplicates: self.duplicates,
+			congestion: self.congestion.clone(),
+			delivery: self.delivery.clone(),
 		}
 	}
 }
@@ -403,6 +524,7 @@ impl From<Track> for TrackProducer {
 pub(crate) struct TrackWeak {
 	pub(crate) info: Track,
 	state: conducer::Weak<State>,
+	delivery: DeliveryReporter,
 }
 
 impl TrackWeak {
@@ -417,6 +539,8 @@ impl TrackWeak {
 			index: 0,
 			min_sequence: 0,
 			next_sequence: 0,
+			cache_stats: None,
+			delivery: self.delivery.clone(),
 		}
 	}
 
@@ -444,6 +568,10 @@ pub struct TrackConsumer {
 	/// One past the highest sequence returned by [`Self::next_group`].
 	/// Used only by that method to skip late arrivals; does not affect [`Self::recv_group`].
 	next_sequence: u64,
+	/// Records whether requested groups were already cached. Set by [`Self::with_cache_stats`].
+	cache_stats: Option<CacheStats>,
+	/// Reports acknowledged groups back to the [`TrackProducer`].
+	delivery: DeliveryReporter,
 }
 
 impl std::ops::Deref for TrackConsumer {
@@ -494,7 +622,7 @@ impl TrackConsumer {
 	/// be out of sequence due to network reordering or loss. Use [`Self::next_group`] if you
 	/// only want groups whose sequence number is higher than any previously returned.
 	pub async fn recv_group(&mut self) -> Result<Option<GroupConsumer>> {
-		conducer::wait(|waiter| self.poll_recv_group(waiter)).await
+		wait_cached(self.cache_stats.clone(), |waiter| self.poll_recv_group(waiter)).await
 	}
 
 	/// Poll for the next group with a higher sequence number than any previously returned.
@@ -522,7 +650,17 @@ impl TrackConsumer {
 	/// produces a monotonically increasing sequence at the cost of dropping out-of-order
 	/// groups. Use [`Self::recv_group`] to see every group in arrival order instead.
 	pub async fn next_group(&mut self) -> Result<Option<GroupConsumer>> {
-		conducer::wait(|waiter| self.poll_next_group(waiter)).await
+		wait_cached(self.cache_stats.clone(), |waiter| self.poll_next_group(waiter)).await
+	}
+
+	/// Like [`Self::next_group`], but returns [`Error::Timeout`] if no group arrives within `timeout`.
+	///
+	/// Useful to report a silent track instead of waiting forever. The consumer remains usable;
+	/// drop it (and any clones) to cancel the subscription.
+	pub async fn next_group_timeout(&mut self, timeout: Duration) -> Result<Option<GroupConsumer>> {
+		tokio::time::timeout(timeout, self.next_group())
+			.await
+			.map_err(|_| Error::Timeout)?
 	}
 
 	/// A helper that calls [`Self::poll_next_group`] and returns its first frame,
@@ -558,10 +696,38 @@ impl TrackConsumer {
 	/// Resolves to `Some(GroupConsumer)` once the group is in the cache.
 	/// Resolves to `None` only when `sequence` is at or past the track's
 	/// `final_sequence` (set by `finish()` / `finish_at()`), since such a
-	/// group can never be produced. Sequences below `final_sequence` still
-	/// wait, since older groups may still arrive out of order.
+	/// group can never be produced, or when it's at or below a group that was
+	/// already evicted from the cache (see [`TrackProducer::with_cache`]).
+	/// Other missing sequences still wait, since older groups may still arrive
+	/// out of order.
 	pub async fn get_group(&self, sequence: u64) -> Result<Option<GroupConsumer>> {
-		conducer::wait(|waiter| self.poll_get_group(waiter, sequence)).await
+		wait_cached(self.cache_stats.clone(), |waiter| self.poll_get_group(waiter, sequence)).await
+	}
+
+	/// Count every group requested through this consumer (and its clones) in `stats`.
+	///
+	/// A call to [`Self::recv_group`], [`Self::next_group`] or [`Self::get_group`] is a hit
+	/// when the group is already cached and a miss when it has to wait for the group.
+	pub fn with_cache_stats(mut self, stats: CacheStats) -> Self {
+		self.cache_stats = Some(stats);
+		self
+	}
+
+	/// A handle for reporting acknowledged groups back to the [`TrackProducer`].
+	pub(crate) fn delivery(&self) -> DeliveryReporter {
+		self.delivery.clone()
+	}
+
+	/// Record groups served without waiting, for callers that read the cache directly.
+	pub(crate) fn record_cache(&self, hits: u64, misses: u64) {
+		let Some(stats) = &self.cache_stats else {
+			return;
+		};
+
+		for _ in 0..hits {
+			stats.add_hit();
+		}
+		stats.add_misses(misses);
 	}
 
 	/// Poll for track closure, without blocking.
@@ -601,15 +767,61 @@ impl TrackConsumer {
 		self.state.read().max_sequence
 	}
 
+	/// Return the oldest sequence number still cached, or `None` if no groups are cached.
+	///
+	/// Groups older than this have been evicted and can no longer be fetched.
+	pub fn oldest(&self) -> Option<u64> {
+		let state = self.state.read();
+		state.groups.iter().flatten().map(|(group, _)| group.sequence).min()
+	}
+
+	/// Return the cached groups with a sequence in `range`, in ascending order.
+	///
+	/// Only groups already in the cache are returned, so this never waits and its cost doesn't
+	/// depend on the size of `range`.
+	pub fn cached_groups<R: std::ops::RangeBounds<u64>>(&self, range: R) -> Vec<GroupConsumer> {
+		let state = self.state.read();
+		let mut groups: Vec<_> = state
+			.groups
+			.iter()
+			.flatten()
+			.filter(|(group, _)| range.contains(&group.sequence))
+			.map(|(group, _)| group.consume())
+			.collect();
+		groups.sort_by_key(|group| group.sequence);
+		groups
+	}
+
 	/// Create a weak reference that doesn't prevent auto-close.
 	pub(crate) fn weak(&self) -> TrackWeak {
 		TrackWeak {
 			info: self.info.clone(),
 			state: self.state.weak(),
+			delivery: self.delivery.clone(),
 		}
 	}
 }
 
+// Wait for a group, counting it as a cache hit if it was available without waiting.
+async fn wait_cached<F>(stats: Option<CacheStats>, mut poll: F) -> Result<Option<GroupConsumer>>
+where
+	F: FnMut(&conducer::Waiter) -> Poll<Result<Option<GroupConsumer>>> + Unpin,
+{
+	let Some(stats) = stats else {
+		return conducer::wait(poll).await;
+	};
+
+	if let Poll::Ready(res) = poll(&conducer::Waiter::noop()) {
+		if matches!(res, Ok(Some(_))) {
+			stats.add_hit();
+		}
+		return res;
+	}
+
+	stats.add_miss();
+	conducer::wait(poll).await
+}
+
 #[cfg(test)]
 use futures::FutureExt;
 
@@ -706,6 +918,33 @@ mod test {
 		}
 	}
 
+	#[tokio::test]
+	async fn latest_and_oldest() {
+		tokio::time::pause();
+
+		let mut producer = Track::new("test").produce();
+		let consumer = producer.consume();
+		assert_eq!(consumer.latest(), None);
+		assert_eq!(consumer.oldest(), None);
+
+		producer.append_group().unwrap(); // seq 0
+		producer.append_group().unwrap(); // seq 1
+		producer.append_group().unwrap(); // seq 2
+		assert_eq!(consumer.latest(), Some(2));
+		assert_eq!(consumer.oldest(), Some(0));
+
+		// Reading doesn't consume anything.
+		let mut reader = producer.consume();
+		assert_eq!(reader.assert_group().sequence, 0);
+		assert_eq!(consumer.latest(), Some(2));
+
+		// Evicting the old groups advances the oldest.
+		tokio::time::advance(MAX_GROUP_AGE + Duration::from_secs(1)).await;
+		producer.append_group().unwrap(); // seq 3
+		assert_eq!(consumer.latest(), Some(3));
+		assert_eq!(consumer.oldest(), Some(3));
+	}
+
 	#[tokio::test]
 	async fn evict_keeps_max_sequence() {
 		tokio::time::pause();
@@ -743,6 +982,37 @@ mod test {
 		}
 	}
 
+	#[tokio::test]
+	async fn short_cache_evicts_groups() {
+		tokio::time::pause();
+
+		let mut producer = Track::new("test").produce().with_cache(Duration::from_millis(100));
+		let consumer = producer.consume();
+		producer.append_group().unwrap(); // seq 0
+
+		tokio::time::advance(Duration::from_millis(200)).await;
+		producer.append_group().unwrap(); // seq 1
+
+		// Seq 0 is evicted and reported as gone instead of pending.
+		assert!(consumer.get_group(0).await.unwrap().is_none());
+		assert!(consumer.get_group(1).await.unwrap().is_some());
+	}
+
+	#[tokio::test]
+	async fn long_cache_retains_groups() {
+		tokio::time::pause();
+
+		let mut producer = Track::new("test").produce().with_cache(Duration::from_secs(60));
+		let consumer = producer.consume();
+		producer.append_group().unwrap(); // seq 0
+
+		tokio::time::advance(MAX_GROUP_AGE + Duration::from_secs(1)).await;
+		producer.append_group().unwrap(); // seq 1
+
+		assert_eq!(live_groups(&producer.state.read()), 2);
+		assert!(consumer.get_group(0).await.unwrap().is_some());
+	}
+
 	#[tokio::test]
 	async fn consumer_skips_evicted_groups() {
 		tokio::time::pause();
@@ -1164,6 +1434,69 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn duplicate_policy_reject() {
+		let mut producer = Track::new("test").produce();
+		let mut other = producer.clone();
+
+		producer.create_group(Group { sequence: 3 }).unwrap();
+		assert!(matches!(
+			other.create_group(Group { sequence: 3 }),
+			Err(Error::Duplicate)
+		));
+	}
+
+	#[tokio::test]
+	async fn duplicate_policy_ignore() {
+		let mut producer = Track::new("test")
+			.produce()
+			.with_duplicate_policy(DuplicatePolicy::Ignore);
+		let mut other = producer.clone();
+		let mut consumer = producer.consume();
+
+		let mut first = producer.create_group(Group { sequence: 3 }).unwrap();
+		first.write_frame(bytes::Bytes::from_static(b"first")).unwrap();
+		first.finish().unwrap();
+
+		// The duplicate is accepted but never delivered.
+		let mut second = other.create_group(Group { sequence: 3 }).unwrap();
+		second.write_frame(bytes::Bytes::from_static(b"second")).unwrap();
+		second.finish().unwrap();
+
+		let mut group = consumer.assert_group();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "first");
+		consumer.assert_no_group();
+		assert_eq!(live_groups(&producer.state.read()), 1);
+	}
+
+	#[tokio::test]
+	async fn duplicate_policy_replace() {
+		let mut producer = Track::new("test")
+			.produce()
+			.with_duplicate_policy(DuplicatePolicy::Replace);
+		let mut other = producer.clone();
+		let mut consumer = producer.consume();
+
+		let first = producer.create_group(Group { sequence: 3 }).unwrap();
+		let mut stale = consumer.assert_group();
+
+		let mut second = other.create_group(Group { sequence: 3 }).unwrap();
+		second.write_frame(bytes::Bytes::from_static(b"second")).unwrap();
+		second.finish().unwrap();
+
+		// The old group is aborted and the replacement is delivered instead.
+		assert!(matches!(first.closed().await, Error::Duplicate));
+		assert!(matches!(stale.read_frame().await, Err(Error::Duplicate)));
+
+		let mut group = consumer.assert_group();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "second");
+		assert_eq!(live_groups(&producer.state.read()), 1);
+
+		let late = producer.consume();
+		let mut group = late.get_group(3).await.unwrap().unwrap();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "second");
+	}
+
 	#[test]
 	fn append_group_returns_bounds_exceeded_on_sequence_overflow() {
 		let mut producer = Track::new("test").produce();
@@ -1174,4 +1507,127 @@ mod test {
 
 		assert!(matches!(producer.append_group(), Err(Error::BoundsExceeded(_))));
 	}
+
+	#[tokio::test]
+	async fn congestion_drops_non_keyframes() {
+		let bandwidth = crate::BandwidthProducer::new();
+		let congestion = Congestion::new(bandwidth.consume(), 1_000_000);
+
+		let mut producer = Track::new("test").produce().with_congestion(congestion);
+		let mut consumer = producer.consume();
+
+		// Plenty of bandwidth: every frame is written.
+		bandwidth.set(Some(5_000_000)).unwrap();
+		assert!(!producer.is_congested());
+		let mut group = producer.append_group().unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"key0")).unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"delta0")).unwrap();
+
+		// Congested mid-group: the remaining frames are dropped.
+		bandwidth.set(Some(500_000)).unwrap();
+		assert!(producer.is_congested());
+		group.write_frame(bytes::Bytes::from_static(b"delta1")).unwrap();
+		group.finish().unwrap();
+
+		// A new group still keeps its keyframe.
+		let mut next = producer.append_group().unwrap();
+		next.write_frame(bytes::Bytes::from_static(b"key1")).unwrap();
+		next.write_frame(bytes::Bytes::from_static(b"delta2")).unwrap();
+
+		// Once recovered, frames after a dropped one stay dropped until the next group.
+		bandwidth.set(Some(5_000_000)).unwrap();
+		next.write_frame(bytes::Bytes::from_static(b"delta3")).unwrap();
+		next.finish().unwrap();
+
+		let mut group = consumer.assert_group();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "key0");
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "delta0");
+		assert_eq!(group.read_frame().await.unwrap(), None);
+
+		let mut group = consumer.assert_group();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "key1");
+		assert_eq!(group.read_frame().await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn congestion_keeps_priority_frames() {
+		let bandwidth = crate::BandwidthProducer::new();
+		let congestion = Congestion::new(bandwidth.consume(), 1_000_000);
+
+		let mut producer = Track::new("test").produce().with_congestion(congestion);
+		let mut consumer = producer.consume();
+
+		let write = |group: &mut GroupProducer, payload: &'static [u8], priority: u8| {
+			let mut frame = group
+				.create_frame(crate::Frame {
+					size: payload.len() as u64,
+					priority,
+					..Default::default()
+				})
+				.unwrap();
+			frame.write(bytes::Bytes::from_static(payload)).unwrap();
+			frame.finish().unwrap();
+		};
+
+		// Congested: enhancement slices (priority 0) are dropped, base slices (priority 1) are kept.
+		bandwidth.set(Some(500_000)).unwrap();
+		let mut group = producer.append_group().unwrap();
+		write(&mut group, b"base0", 1);
+		write(&mut group, b"enhance0", 0);
+		write(&mut group, b"base1", 1);
+		write(&mut group, b"enhance1", 0);
+
+		// Frames that may depend on a dropped frame stay dropped once recovered.
+		bandwidth.set(Some(5_000_000)).unwrap();
+		write(&mut group, b"enhance2", 0);
+		write(&mut group, b"base2", 1);
+		group.finish().unwrap();
+
+		let mut group = consumer.assert_group();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "base0");
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "base1");
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "base2");
+		assert_eq!(group.read_frame().await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn congestion_ignored_without_opt_in() {
+		let mut producer = Track::new("test").produce();
+		let mut consumer = producer.consume();
+		assert!(!producer.is_congested());
+
+		let mut group = producer.append_group().unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"key")).unwrap();
+		group.write_frame(bytes::Bytes::from_static(b"delta")).unwrap();
+		group.finish().unwrap();
+
+		let mut group = consumer.assert_group();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "key");
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "delta");
+	}
+
+	#[tokio::test]
+	async fn cache_stats() {
+		let stats = CacheStats::new();
+		let mut producer = Track::new("test").produce();
+		let mut consumer = producer.consume().with_cache_stats(stats.clone());
+
+		// A cached group is a hit.
+		producer.append_group().unwrap();
+		consumer.next_group().await.unwrap().unwrap();
+		assert_eq!((stats.hits(), stats.misses()), (1, 0));
+
+		// Waiting for the next group is a miss, even once it arrives.
+		{
+			let mut next = std::pin::pin!(consumer.next_group());
+			assert!((&mut next).now_or_never().is_none());
+			producer.append_group().unwrap();
+			next.await.unwrap().unwrap();
+		}
+		assert_eq!((stats.hits(), stats.misses()), (1, 1));
+
+		// Clones share the counters.
+		consumer.clone().get_group(1).await.unwrap().unwrap();
+		assert_eq!((stats.hits(), stats.misses()), (2, 1));
+	}
 }
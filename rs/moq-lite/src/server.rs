@@ -1,6 +1,6 @@
 use crate::{
-	ALPN_14, ALPN_15, ALPN_16, ALPN_17, ALPN_LITE, ALPN_LITE_03, ALPN_LITE_04, Error, NEGOTIATED, OriginConsumer,
-	OriginProducer, Session, Version, Versions,
+	ALPN_14, ALPN_15, ALPN_16, ALPN_17, ALPN_LITE, ALPN_LITE_03, ALPN_LITE_04, ALPN_LITE_05, Error, NEGOTIATED,
+	OriginConsumer, OriginProducer, PublishStats, Session, Version, Versions,
 	coding::{Decode, Encode, Stream},
 	ietf, lite, setup,
 };
@@ -11,6 +11,7 @@ pub struct Server {
 	publish: Option<OriginConsumer>,
 	consume: Option<OriginProducer>,
 	versions: Versions,
+	stats: PublishStats,
 }
 
 impl Server {
@@ -41,6 +42,12 @@ impl Server {
 		self
 	}
 
+	/// Count the subscriptions and groups served to the peer.
+	pub fn with_stats(mut self, stats: PublishStats) -> Self {
+		self.stats = stats;
+		self
+	}
+
 	/// Perform the MoQ handshake as a server for the given session.
 	pub async fn accept<S: web_transport_trait::Session>(&self, session: S) -> Result<Session, Error> {
 		if self.publish.is_none() && self.consume.is_none() {
@@ -61,6 +68,7 @@ impl Server {
 					None,
 					false,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					ietf::Version::Draft17,
 				)?;
@@ -89,6 +97,22 @@ impl Server {
 					.ok_or(Error::Version)?;
 				(v, v.into())
 			}
+			Some(ALPN_LITE_05) => {
+				self.versions
+					.select(Version::Lite(lite::Version::Lite05))
+					.ok_or(Error::Version)?;
+
+				let recv_bw = lite::start(
+					session.clone(),
+					None,
+					self.publish.clone(),
+					self.stats.clone(),
+					self.consume.clone(),
+					lite::Version::Lite05,
+				)?;
+
+				return Ok(Session::new(session, lite::Version::Lite05.into(), recv_bw));
+			}
 			Some(ALPN_LITE_04) => {
 				self.versions
 					.select(Version::Lite(lite::Version::Lite04))
@@ -98,6 +122,7 @@ impl Server {
 					session.clone(),
 					None,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					lite::Version::Lite04,
 				)?;
@@ -114,6 +139,7 @@ impl Server {
 					session.clone(),
 					None,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					lite::Version::Lite03,
 				)?;
@@ -163,6 +189,7 @@ impl Server {
 					session.clone(),
 					Some(stream),
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					v,
 				)?
@@ -181,6 +208,7 @@ impl Server {
 					request_id_max,
 					false,
 					self.publish.clone(),
+					self.stats.clone(),
 					self.consume.clone(),
 					v,
 				)?;
@@ -67,6 +67,43 @@ impl Session {
 		self.recv_bandwidth.clone()
 	}
 
+	/// Returns the maximum payload size of a datagram, as negotiated with the peer.
+	///
+	/// Returns `None` if the peer doesn't support datagrams. The size may change over the
+	/// lifetime of the session (e.g. after path MTU discovery), so query it when sending.
+	pub fn max_datagram_size(&self) -> Option<usize> {
+		match self.session.max_datagram_size() {
+			0 => None,
+			size => Some(size),
+		}
+	}
+
+	/// Returns a snapshot of the transport's connection statistics, for diagnostics.
+	pub fn stats(&self) -> SessionStats {
+		self.session.stats()
+	}
+
+	/// Returns the smoothed round-trip time estimate of the transport.
+	///
+	/// Returns `None` if the backend doesn't measure it, e.g. WebSocket.
+	pub fn rtt(&self) -> Option<Duration> {
+		self.stats().rtt
+	}
+
+	/// Returns the bytes sent by the transport, including retransmissions and framing.
+	///
+	/// Returns `None` if the backend doesn't count them, e.g. WebSocket.
+	pub fn bytes_sent(&self) -> Option<u64> {
+		self.stats().bytes_sent
+	}
+
+	/// Returns the bytes received by the transport, including framing.
+	///
+	/// Returns `None` if the backend doesn't count them, e.g. WebSocket.
+	pub fn bytes_received(&self) -> Option<u64> {
+		self.stats().bytes_received
+	}
+
 	/// Close the underlying transport session.
 	pub fn close(&mut self, err: Error) {
 		if self.closed {
@@ -91,6 +128,45 @@ impl Drop for Session {
 	}
 }
 
+/// A snapshot of the transport's connection statistics. See [`Session::stats`].
+///
+/// Each field is `None` if the backend doesn't measure it; WebSocket measures none of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SessionStats {
+	/// The smoothed round-trip time estimate.
+	pub rtt: Option<Duration>,
+	/// Bytes sent, including retransmissions and framing.
+	pub bytes_sent: Option<u64>,
+	/// Bytes received, including duplicates and framing.
+	pub bytes_received: Option<u64>,
+	/// Bytes detected as lost.
+	pub bytes_lost: Option<u64>,
+	/// Packets sent.
+	pub packets_sent: Option<u64>,
+	/// Packets received.
+	pub packets_received: Option<u64>,
+	/// Packets detected as lost.
+	pub packets_lost: Option<u64>,
+	/// The congestion controller's estimated send rate in bits per second, derived from the congestion window.
+	pub estimated_send_rate: Option<u64>,
+}
+
+impl<T: Stats> From<T> for SessionStats {
+	fn from(stats: T) -> Self {
+		Self {
+			rtt: stats.rtt(),
+			bytes_sent: stats.bytes_sent(),
+			bytes_received: stats.bytes_received(),
+			bytes_lost: stats.bytes_lost(),
+			packets_sent: stats.packets_sent(),
+			packets_received: stats.packets_received(),
+			packets_lost: stats.packets_lost(),
+			estimated_send_rate: stats.estimated_send_rate(),
+		}
+	}
+}
+
 /// Polls the QUIC congestion controller for estimated send rate.
 ///
 /// Exits as soon as the session closes so we don't pin the underlying connection
@@ -137,11 +213,21 @@ async fn run_send_bandwidth_inner<S: web_transport_trait::Session>(session: &S,
 
 // We use a wrapper type that is dyn-compatible to remove the generic bounds from Session.
 trait SessionInner: Send + Sync {
+	fn max_datagram_size(&self) -> usize;
+	fn stats(&self) -> SessionStats;
 	fn close(&self, code: u32, reason: &str);
 	fn closed(&self) -> Pin<Box<dyn Future<Output = String> + Send + '_>>;
 }
 
 impl<S: web_transport_trait::Session> SessionInner for S {
+	fn max_datagram_size(&self) -> usize {
+		S::max_datagram_size(self)
+	}
+
+	fn stats(&self) -> SessionStats {
+		S::stats(self).into()
+	}
+
 	fn close(&self, code: u32, reason: &str) {
 		S::close(self, code, reason);
 	}
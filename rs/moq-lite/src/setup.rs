@@ -9,6 +9,11 @@ use crate::{
 const CLIENT_SETUP: u8 = 0x20;
 const SERVER_SETUP: u8 = 0x21;
 
+/// The maximum size of a setup message body, enforced before buffering it.
+///
+/// Matches the u16 limit of the IETF encoding, so a moq-lite peer can't declare a huge u64 size.
+const MAX_SETUP_SIZE: u64 = u16::MAX as u64;
+
 /// Draft-17 unified SETUP message type (varint 0x2F00)
 pub(crate) const SETUP_V17: u64 = 0x2F00;
 
@@ -75,8 +80,23 @@ impl SetupVersion {
 			Version::Ietf(ietf::Version::Draft15) | Version::Ietf(ietf::Version::Draft16) => Self::Draft15Plus,
 			Version::Ietf(ietf::Version::Draft17) => Self::Draft17,
 			Version::Lite(lite::Version::Lite01) | Version::Lite(lite::Version::Lite02) => Self::LiteLegacy,
-			Version::Lite(lite::Version::Lite03 | lite::Version::Lite04) => Self::Unsupported,
+			Version::Lite(lite::Version::Lite03 | lite::Version::Lite04 | lite::Version::Lite05) => Self::Unsupported,
+		}
+	}
+
+	/// Decode the size prefix of a client or server setup message, rejecting oversized messages.
+	fn decode_size<R: bytes::Buf>(self, r: &mut R, v: Version) -> Result<usize, DecodeError> {
+		let size = match self {
+			Self::Draft14 | Self::Draft15Plus => u16::decode(r, v)? as u64,
+			Self::LiteLegacy => u64::decode(r, v)?,
+			Self::Draft17 | Self::Unsupported => return Err(DecodeError::Version),
+		};
+
+		if size > MAX_SETUP_SIZE {
+			return Err(DecodeError::TooLong);
 		}
+
+		Ok(size as usize)
 	}
 }
 
@@ -115,11 +135,7 @@ impl Decode<Version> for Client {
 			return Err(DecodeError::InvalidValue);
 		}
 
-		let size = match SetupVersion::from_version(v) {
-			SetupVersion::Draft14 | SetupVersion::Draft15Plus => u16::decode(r, v)? as usize,
-			SetupVersion::LiteLegacy => u64::decode(r, v)? as usize,
-			SetupVersion::Draft17 | SetupVersion::Unsupported => return Err(DecodeError::Version),
-		};
+		let size = SetupVersion::from_version(v).decode_size(r, v)?;
 
 		if r.remaining() < size {
 			return Err(DecodeError::Short);
@@ -219,11 +235,7 @@ impl Decode<Version> for Server {
 			return Err(DecodeError::InvalidValue);
 		}
 
-		let size = match SetupVersion::from_version(v) {
-			SetupVersion::Draft14 | SetupVersion::Draft15Plus => u16::decode(r, v)? as usize,
-			SetupVersion::LiteLegacy => u64::decode(r, v)? as usize,
-			SetupVersion::Draft17 | SetupVersion::Unsupported => return Err(DecodeError::Version),
-		};
+		let size = SetupVersion::from_version(v).decode_size(r, v)?;
 
 		if r.remaining() < size {
 			return Err(DecodeError::Short);
@@ -242,3 +254,44 @@ impl Decode<Version> for Server {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn oversized(kind: u8) -> Bytes {
+		let v = Version::Lite(lite::Version::Lite02);
+		let mut buf = Vec::new();
+		kind.encode(&mut buf, v).unwrap();
+		(1u64 << 40).encode(&mut buf, v).unwrap();
+		buf.into()
+	}
+
+	#[test]
+	fn client_rejects_oversized() {
+		let v = Version::Lite(lite::Version::Lite02);
+		let mut buf = oversized(CLIENT_SETUP);
+		assert!(matches!(Client::decode(&mut buf, v), Err(DecodeError::TooLong)));
+	}
+
+	#[test]
+	fn server_rejects_oversized() {
+		let v = Version::Lite(lite::Version::Lite02);
+		let mut buf = oversized(SERVER_SETUP);
+		assert!(matches!(Server::decode(&mut buf, v), Err(DecodeError::TooLong)));
+	}
+
+	#[test]
+	fn roundtrip_within_limit() {
+		let v = Version::Lite(lite::Version::Lite02);
+		let setup = Client {
+			versions: coding::Versions::from([v.into()]),
+			parameters: Bytes::new(),
+		};
+
+		let mut buf = Vec::new();
+		setup.encode(&mut buf, v).unwrap();
+		let decoded = Client::decode(&mut Bytes::from(buf), v).unwrap();
+		assert_eq!(decoded.versions, setup.versions);
+	}
+}
@@ -0,0 +1,257 @@
+use std::{
+	collections::HashMap,
+	sync::{
+		Arc, Mutex,
+		atomic::{AtomicU64, Ordering},
+	},
+};
+
+use crate::{AsPath, PathOwned};
+
+/// Counters for what sessions publish to their peers, and what their peers announce.
+///
+/// Cloning shares the counters, so a single handle can aggregate every session it is given to.
+/// Use [`Self::child`] to also count a single session on its own.
+/// See [`crate::Client::with_stats`] and [`crate::Server::with_stats`].
+#[derive(Clone, Debug, Default)]
+pub struct PublishStats {
+	inner: Arc<PublishStatsInner>,
+}
+
+#[derive(Debug, Default)]
+struct PublishStatsInner {
+	subscribes: AtomicU64,
+	groups: AtomicU64,
+	announces: AtomicU64,
+	cache: CacheStats,
+	tracks: Mutex<HashMap<(PathOwned, String), Arc<TrackCounters>>>,
+	// Every count is also added to the parent, which owns the per-track counters.
+	parent: Option<PublishStats>,
+}
+
+#[derive(Debug, Default)]
+struct TrackCounters {
+	subscribers: AtomicU64,
+	bytes: AtomicU64,
+	dropped: AtomicU64,
+}
+
+impl PublishStats {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create counters that also add to these ones, such as for a single session.
+	///
+	/// The child shares the cache and per-track counters with its parent.
+	pub fn child(&self) -> Self {
+		Self {
+			inner: Arc::new(PublishStatsInner {
+				cache: self.inner.cache.clone(),
+				parent: Some(self.clone()),
+				..Default::default()
+			}),
+		}
+	}
+
+	/// The total number of subscriptions accepted.
+	pub fn subscribes(&self) -> u64 {
+		self.inner.subscribes.load(Ordering::Relaxed)
+	}
+
+	/// The total number of groups sent to subscribers.
+	pub fn groups(&self) -> u64 {
+		self.inner.groups.load(Ordering::Relaxed)
+	}
+
+	/// The total number of broadcasts announced by peers.
+	pub fn announces(&self) -> u64 {
+		self.inner.announces.load(Ordering::Relaxed)
+	}
+
+	/// The group cache counters for every track served.
+	pub fn cache(&self) -> CacheStats {
+		self.inner.cache.clone()
+	}
+
+	/// A snapshot of every track with at least one active subscriber.
+	pub fn tracks(&self) -> Vec<TrackSnapshot> {
+		let tracks = self.root().inner.tracks.lock().unwrap();
+		tracks
+			.iter()
+			.map(|((broadcast, track), counters)| TrackSnapshot {
+				broadcast: broadcast.clone(),
+				track: track.clone(),
+				subscribers: counters.subscribers.load(Ordering::Relaxed),
+				bytes: counters.bytes.load(Ordering::Relaxed),
+				dropped: counters.dropped.load(Ordering::Relaxed),
+			})
+			.collect()
+	}
+
+	/// Record a subscription to the given track until the returned [`TrackStats`] is dropped.
+	///
+	/// The track appears in [`Self::tracks`] while it has at least one subscriber.
+	pub fn track(&self, broadcast: impl AsPath, track: &str) -> TrackStats {
+		let key = (broadcast.as_path().to_owned(), track.to_string());
+
+		let mut tracks = self.root().inner.tracks.lock().unwrap();
+		let counters = tracks.entry(key.clone()).or_default().clone();
+		counters.subscribers.fetch_add(1, Ordering::Relaxed);
+
+		TrackStats {
+			stats: self.clone(),
+			key,
+			counters,
+		}
+	}
+
+	pub(crate) fn add_subscribe(&self) {
+		self.each(|inner| &inner.subscribes);
+	}
+
+	pub(crate) fn add_group(&self) {
+		self.each(|inner| &inner.groups);
+	}
+
+	pub(crate) fn add_announce(&self) {
+		self.each(|inner| &inner.announces);
+	}
+
+	/// Increment the selected counter here and in every parent.
+	fn each(&self, counter: fn(&PublishStatsInner) -> &AtomicU64) {
+		let mut stats = Some(self);
+		while let Some(current) = stats {
+			counter(&current.inner).fetch_add(1, Ordering::Relaxed);
+			stats = current.inner.parent.as_ref();
+		}
+	}
+
+	/// The top-most parent, which owns the per-track counters.
+	fn root(&self) -> &Self {
+		match &self.inner.parent {
+			Some(parent) => parent.root(),
+			None => self,
+		}
+	}
+}
+
+/// Counters for groups served from a track's cache versus waited for.
+///
+/// Attach to a [`TrackConsumer`](crate::TrackConsumer) with
+/// [`with_cache_stats`](crate::TrackConsumer::with_cache_stats). Cloning shares the counters.
+#[derive(Clone, Debug, Default)]
+pub struct CacheStats {
+	inner: Arc<CacheStatsInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheStatsInner {
+	hits: AtomicU64,
+	misses: AtomicU64,
+}
+
+impl CacheStats {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The number of groups that were already cached when requested.
+	pub fn hits(&self) -> u64 {
+		self.inner.hits.load(Ordering::Relaxed)
+	}
+
+	/// The number of group requests that had to wait for the group to arrive.
+	pub fn misses(&self) -> u64 {
+		self.inner.misses.load(Ordering::Relaxed)
+	}
+
+	pub(crate) fn add_hit(&self) {
+		self.inner.hits.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn add_miss(&self) {
+		self.add_misses(1);
+	}
+
+	pub(crate) fn add_misses(&self, count: u64) {
+		self.inner.misses.fetch_add(count, Ordering::Relaxed);
+	}
+}
+
+/// Per-track counters for a single subscription. See [`PublishStats::track`].
+#[derive(Debug)]
+pub struct TrackStats {
+	stats: PublishStats,
+	key: (PathOwned, String),
+	counters: Arc<TrackCounters>,
+}
+
+impl TrackStats {
+	pub(crate) fn publish(&self) -> &PublishStats {
+		&self.stats
+	}
+
+	/// Record bytes sent to the subscriber.
+	pub fn add_bytes(&self, bytes: u64) {
+		self.counters.bytes.fetch_add(bytes, Ordering::Relaxed);
+	}
+
+	/// Record a group that was skipped or not delivered in full.
+	pub fn add_dropped(&self) {
+		self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+impl Drop for TrackStats {
+	fn drop(&mut self) {
+		let mut tracks = self.stats.root().inner.tracks.lock().unwrap();
+		if self.counters.subscribers.fetch_sub(1, Ordering::Relaxed) == 1 {
+			tracks.remove(&self.key);
+		}
+	}
+}
+
+/// The counters for a single track, as returned by [`PublishStats::tracks`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TrackSnapshot {
+	/// The absolute path of the broadcast.
+	pub broadcast: PathOwned,
+	/// The name of the track within the broadcast.
+	pub track: String,
+	/// The number of active subscriptions.
+	pub subscribers: u64,
+	/// The bytes sent to subscribers, across every active subscription.
+	pub bytes: u64,
+	/// The groups skipped or not delivered in full.
+	pub dropped: u64,
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn child_adds_to_parent() {
+		let parent = PublishStats::new();
+		let child = parent.child();
+		let other = parent.child();
+
+		child.add_subscribe();
+		child.add_announce();
+		other.add_group();
+
+		assert_eq!((child.subscribes(), child.announces(), child.groups()), (1, 1, 0));
+		assert_eq!((other.subscribes(), other.announces(), other.groups()), (0, 0, 1));
+		assert_eq!((parent.subscribes(), parent.announces(), parent.groups()), (1, 1, 1));
+
+		// Per-track counters live in the parent.
+		let track = child.track("demo", "video");
+		track.add_bytes(10);
+		assert_eq!(parent.tracks().len(), 1);
+		assert_eq!(other.tracks()[0].bytes, 10);
+		drop(track);
+		assert!(parent.tracks().is_empty());
+	}
+}
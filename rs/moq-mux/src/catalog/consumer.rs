@@ -8,52 +8,102 @@ use crate::Result;
 ///
 /// This wraps a `moq_lite::TrackConsumer` and automatically deserializes JSON
 /// catalog data to discover available audio and video tracks in a broadcast.
+///
+/// Each group starts with the full catalog and may be followed by JSON Merge Patch frames,
+/// which are applied to a running copy of the latest group's document.
+///
+/// Catalogs with more than [`Catalog::DEFAULT_MAX_RENDITIONS`] video or audio renditions are
+/// rejected with an error; see [`Self::with_max_renditions`].
 #[derive(Clone)]
 pub struct Consumer {
 	/// Access to the underlying track consumer.
 	pub track: moq_lite::TrackConsumer,
 	group: Option<moq_lite::GroupConsumer>,
+
+	// The document described by the frames read so far from the current group.
+	current: Option<serde_json::Value>,
+
+	// The maximum number of renditions of each kind.
+	max_renditions: usize,
 }
 
 impl Consumer {
 	/// Create a new catalog consumer from a MoQ track consumer.
 	pub fn new(track: moq_lite::TrackConsumer) -> Self {
-		Self { track, group: None }
+		Self {
+			track,
+			group: None,
+			current: None,
+			max_renditions: Catalog::DEFAULT_MAX_RENDITIONS,
+		}
+	}
+
+	/// Reject catalogs with more than `max` video or audio renditions.
+	pub fn with_max_renditions(mut self, max: usize) -> Self {
+		self.max_renditions = max;
+		self
 	}
 
 	/// Poll for the next catalog update.
 	pub fn poll_next(&mut self, waiter: &conducer::Waiter) -> Poll<Result<Option<Catalog>>> {
 		// Get the newest group from the track.
+		let mut ended = false;
 		while let Poll::Ready(group) = self.track.poll_next_group(waiter)? {
-			self.group = group;
-
-			// We got a None, meaning the track is done.
-			if self.group.is_none() {
-				return Poll::Ready(Ok(None));
+			match group {
+				Some(group) => {
+					self.group = Some(group);
+					self.current = None;
+				}
+				// The track is done, but any pending group is still delivered first.
+				None => {
+					ended = true;
+					break;
+				}
 			}
 		}
 
-		// If there's no current group, return pending.
-		let Some(group) = &mut self.group else {
-			return Poll::Pending;
-		};
+		// Apply every frame available in the current group, so we return the latest catalog.
+		let mut updated = false;
+		while let Some(group) = &mut self.group {
+			let Poll::Ready(frame) = group.poll_read_frame(waiter)? else {
+				break;
+			};
 
-		// Poll for frame from current group.
-		if let Poll::Ready(Some(frame)) = group.poll_read_frame(waiter)? {
-			self.group.take(); // We don't support deltas yet
+			let Some(frame) = frame else {
+				// The group is finished, so wait for the next one.
+				self.group = None;
+				break;
+			};
 
-			let catalog = Catalog::from_slice(&frame)?;
-			Poll::Ready(Ok(Some(catalog)))
-		} else {
-			self.group.take();
-			Poll::Pending
+			let value: serde_json::Value = serde_json::from_slice(&frame).map_err(hang::Error::from)?;
+			match &mut self.current {
+				// The first frame of each group is the full document.
+				None => self.current = Some(value),
+				Some(current) => hang::catalog::merge_patch(current, &value),
+			}
+			updated = true;
+		}
+
+		if updated && let Some(current) = &self.current {
+			let catalog = Catalog::from_value(current.clone())?;
+			catalog.ensure_max_renditions(self.max_renditions)?;
+			return Poll::Ready(Ok(Some(catalog)));
 		}
+
+		if ended && self.group.is_none() {
+			return Poll::Ready(Ok(None));
+		}
+
+		Poll::Pending
 	}
 
 	/// Get the next catalog update.
 	///
 	/// This method waits for the next catalog publication and returns the
 	/// catalog data. If there are no more updates, `None` is returned.
+	///
+	/// Updates are coalesced: a consumer that falls behind skips to the newest group
+	/// and returns only the latest catalog.
 	pub async fn next(&mut self) -> Result<Option<Catalog>> {
 		conducer::wait(|waiter| self.poll_next(waiter)).await
 	}
@@ -1,22 +1,47 @@
 use std::ops::{Deref, DerefMut};
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 use base64::Engine;
 
-/// Produces both a hang and MSF catalog track for a broadcast.
+/// Produces the hang, hang patch and MSF catalog tracks for a broadcast.
 ///
 /// The JSON catalog is updated when tracks are added/removed but is *not* automatically published.
 /// You'll have to call [`lock`](Self::lock) to update and publish the catalog.
-/// Both the hang (`catalog.json`) and MSF (`catalog`) tracks are published on drop of the guard.
+/// The hang (`catalog.json`), patch (`catalog.patch`) and MSF (`catalog`) tracks are published
+/// on drop of the guard.
+///
+/// The hang and MSF catalogs are published as a full document in a new group for each update.
+/// The patch track starts each group with the full document, followed by a JSON Merge Patch
+/// (RFC 7386) frame for each update, so late joiners can bootstrap from the latest group.
+/// A new patch group is started whenever a patch wouldn't be smaller than the full document.
+///
+/// For a catalog with many subscribers, [`with_latest_only`](Self::with_latest_only) trades the
+/// patches for a smaller cache: every update is a full document in its own group, and only the
+/// latest group is kept.
 #[derive(Clone)]
 pub struct Producer {
 	/// Access to the underlying hang catalog track producer.
 	pub hang_track: moq_lite::TrackProducer,
 
+	/// Access to the underlying hang catalog patch track producer.
+	pub patch_track: moq_lite::TrackProducer,
+
 	/// Access to the underlying MSF catalog track producer.
 	pub msf_track: moq_lite::TrackProducer,
 
-	current: Arc<Mutex<hang::Catalog>>,
+	current: Arc<Mutex<State>>,
+}
+
+struct State {
+	catalog: hang::Catalog,
+
+	// The patch track group receiving patches, and the document it currently describes.
+	group: Option<moq_lite::GroupProducer>,
+	published: Option<serde_json::Value>,
+
+	// Publish every update as a full document in a new group. See Producer::with_latest_only.
+	latest_only: bool,
 }
 
 impl Producer {
@@ -31,20 +56,43 @@ impl Producer {
 		catalog: hang::Catalog,
 	) -> Result<Self, moq_lite::Error> {
 		let hang_track = broadcast.create_track(hang::Catalog::default_track())?;
+		let patch_track = broadcast.create_track(hang::Catalog::patch_track())?;
 		let msf_track = broadcast.create_track(moq_lite::Track::new(moq_msf::DEFAULT_NAME))?;
 
 		Ok(Self {
 			hang_track,
+			patch_track,
 			msf_track,
-			current: Arc::new(Mutex::new(catalog)),
+			current: Arc::new(Mutex::new(State {
+				catalog,
+				group: None,
+				published: None,
+				latest_only: false,
+			})),
 		})
 	}
 
+	/// Publish every update as a full document in a new group, caching only the latest group.
+	///
+	/// Each group is self-contained, so a late joiner always receives the current catalog and a
+	/// [`Consumer`](super::Consumer) that falls behind skips straight to it, without the
+	/// history of patches. This bounds the memory each catalog track holds with thousands of
+	/// consumers. The cache is local to this producer: a relay forwarding the catalog applies
+	/// its own.
+	pub fn with_latest_only(mut self) -> Self {
+		self.current.lock().unwrap().latest_only = true;
+		self.hang_track = self.hang_track.with_cache(Duration::ZERO);
+		self.patch_track = self.patch_track.with_cache(Duration::ZERO);
+		self.msf_track = self.msf_track.with_cache(Duration::ZERO);
+		self
+	}
+
 	/// Get mutable access to the catalog, publishing it after any changes.
 	pub fn lock(&mut self) -> Guard<'_> {
 		Guard {
-			catalog: self.current.lock().unwrap(),
+			state: self.current.lock().unwrap(),
 			hang_track: &mut self.hang_track,
+			patch_track: &mut self.patch_track,
 			msf_track: &mut self.msf_track,
 			updated: false,
 		}
@@ -52,7 +100,7 @@ impl Producer {
 
 	/// Get a snapshot of the current catalog.
 	pub fn snapshot(&self) -> hang::Catalog {
-		self.current.lock().unwrap().clone()
+		self.current.lock().unwrap().catalog.clone()
 	}
 
 	/// Create a consumer for this catalog, receiving updates as they're published.
@@ -62,10 +110,26 @@ impl Producer {
 		Ok(super::Consumer::new(subscriber))
 	}
 
+	/// Create a consumer for the patch track, applying each update as a merge patch.
+	pub fn consume_patches(&self) -> Result<super::Consumer, moq_lite::Error> {
+		Ok(super::Consumer::new(self.patch_track.consume()))
+	}
+
 	/// Finish publishing to this catalog.
+	///
+	/// The producer is shared between importers, so finishing an already finished catalog is a no-op.
 	pub fn finish(&mut self) -> Result<(), moq_lite::Error> {
-		self.hang_track.finish()?;
-		self.msf_track.finish()?;
+		// Close the group receiving patches before the track itself.
+		if let Some(mut group) = self.current.lock().unwrap().group.take() {
+			let _ = group.finish();
+		}
+
+		for track in [&mut self.hang_track, &mut self.patch_track, &mut self.msf_track] {
+			match track.finish() {
+				Ok(()) | Err(moq_lite::Error::Closed) => {}
+				Err(err) => return Err(err),
+			}
+		}
 		Ok(())
 	}
 }
@@ -74,10 +138,11 @@ impl Producer {
 ///
 /// Obtained via [`Producer::lock`].
 ///
-/// On drop, both the hang and MSF catalog tracks are updated if the catalog was mutated.
+/// On drop, the hang, patch and MSF catalog tracks are updated if the catalog was mutated.
 pub struct Guard<'a> {
-	catalog: MutexGuard<'a, hang::Catalog>,
+	state: MutexGuard<'a, State>,
 	hang_track: &'a mut moq_lite::TrackProducer,
+	patch_track: &'a mut moq_lite::TrackProducer,
 	msf_track: &'a mut moq_lite::TrackProducer,
 	updated: bool,
 }
@@ -86,14 +151,14 @@ impl<'a> Deref for Guard<'a> {
 	type Target = hang::Catalog;
 
 	fn deref(&self) -> &Self::Target {
-		&self.catalog
+		&self.state.catalog
 	}
 }
 
 impl<'a> DerefMut for Guard<'a> {
 	fn deref_mut(&mut self) -> &mut Self::Target {
 		self.updated = true;
-		&mut self.catalog
+		&mut self.state.catalog
 	}
 }
 
@@ -105,13 +170,16 @@ impl Drop for Guard<'_> {
 
 		// Publish hang catalog
 		if let Ok(mut group) = self.hang_track.append_group() {
-			let frame = self.catalog.to_string().expect("invalid catalog");
+			let frame = self.state.catalog.to_string().expect("invalid catalog");
 			let _ = group.write_frame(frame);
 			let _ = group.finish();
 		}
 
+		// Publish hang catalog patch
+		publish_patch(&mut self.state, self.patch_track);
+
 		// Publish MSF catalog
-		let msf = to_msf(&self.catalog);
+		let msf = to_msf(&self.state.catalog);
 		if let Ok(mut group) = self.msf_track.append_group() {
 			let _ = group.write_frame(msf.to_string().expect("invalid MSF catalog"));
 			let _ = group.finish();
@@ -119,6 +187,37 @@ impl Drop for Guard<'_> {
 	}
 }
 
+/// Publish the hang catalog as a patch to the open group, or as a full document in a new group.
+fn publish_patch(state: &mut State, track: &mut moq_lite::TrackProducer) {
+	let json = state.catalog.to_value().expect("invalid catalog");
+	let full = state.catalog.to_string().expect("invalid catalog");
+
+	if let (Some(group), Some(published)) = (state.group.as_mut(), state.published.as_ref())
+		&& !state.latest_only
+	{
+		let Some(patch) = hang::catalog::merge_diff(published, &json) else {
+			// Nothing changed.
+			return;
+		};
+
+		let patch = patch.to_string();
+		if patch.len() < full.len() && group.write_frame(patch).is_ok() {
+			state.published = Some(json);
+			return;
+		}
+	}
+
+	if let Some(mut group) = state.group.take() {
+		let _ = group.finish();
+	}
+
+	if let Ok(mut group) = track.append_group() {
+		let _ = group.write_frame(full);
+		state.group = Some(group);
+		state.published = Some(json);
+	}
+}
+
 /// Convert a hang catalog to an MSF catalog.
 fn to_msf(catalog: &hang::Catalog) -> moq_msf::Catalog {
 	let mut tracks = Vec::new();
@@ -224,6 +323,7 @@ mod test {
 				optimize_for_latency: None,
 				container: Container::Legacy,
 				jitter: None,
+				lang: None,
 			},
 		);
 
@@ -238,6 +338,9 @@ mod test {
 				description: None,
 				container: Container::Legacy,
 				jitter: None,
+				target_loudness: None,
+				true_peak: None,
+				lang: None,
 			},
 		);
 
@@ -303,6 +406,7 @@ mod test {
 				optimize_for_latency: None,
 				container: Container::Legacy,
 				jitter: None,
+				lang: None,
 			},
 		);
 
@@ -357,6 +461,7 @@ mod test {
 						.into(),
 				},
 				jitter: None,
+				lang: None,
 			},
 		);
 
@@ -375,4 +480,130 @@ mod test {
 		assert_eq!(video.packaging, moq_msf::Packaging::Cmaf);
 		assert_eq!(video.init_data, Some("AAAYZ2Z0eXA=".to_string()));
 	}
+
+	fn opus() -> AudioConfig {
+		AudioConfig {
+			codec: AudioCodec::Opus,
+			sample_rate: 48_000,
+			channel_count: 2,
+			bitrate: None,
+			description: None,
+			container: Container::Legacy,
+			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn publish_patches() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut producer = Producer::new(&mut broadcast).unwrap();
+		let mut consumer = producer.consume_patches().unwrap();
+
+		producer.lock().audio.insert("a", opus()).unwrap();
+		let catalog = consumer.next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["a"]);
+
+		producer.lock().audio.insert("b", opus()).unwrap();
+		let catalog = consumer.next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["a", "b"]);
+
+		producer.lock().audio.remove("a");
+		let catalog = consumer.next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["b"]);
+
+		// A no-op update doesn't publish anything.
+		producer.lock().audio.remove("missing");
+
+		// Everything went into one group: the full document followed by minimal patches.
+		let mut track = producer.patch_track.consume();
+		let mut group = track.next_group().await.unwrap().unwrap();
+		assert_eq!(group.sequence, 0);
+
+		let full = group.read_frame().await.unwrap().unwrap();
+		assert!(hang::Catalog::from_slice(&full).is_ok());
+		let added = group.read_frame().await.unwrap().unwrap();
+		assert_eq!(
+			added,
+			r#"{"audio":{"renditions":{"b":{"codec":"opus","container":{"kind":"legacy"},"numberOfChannels":2,"sampleRate":48000}}}}"#
+		);
+		let removed = group.read_frame().await.unwrap().unwrap();
+		assert_eq!(removed, r#"{"audio":{"renditions":{"a":null}}}"#);
+
+		// A late joiner bootstraps from the latest group, applying the patches.
+		let catalog = producer.consume_patches().unwrap().next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["b"]);
+
+		// The hang catalog track still carries a full document in each group.
+		let mut track = producer.hang_track.consume();
+		let mut first = track.next_group().await.unwrap().unwrap();
+		assert_eq!(first.sequence, 0);
+		let full = first.read_frame().await.unwrap().unwrap();
+		let catalog = hang::Catalog::from_slice(&full).unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["a"]);
+		assert!(first.read_frame().await.unwrap().is_none());
+
+		producer.finish().unwrap();
+		assert!(group.read_frame().await.unwrap().is_none());
+		assert!(consumer.next().await.unwrap().is_none());
+	}
+
+	#[tokio::test]
+	async fn consume_too_many_renditions() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut producer = Producer::new(&mut broadcast).unwrap();
+		let mut consumer = producer.consume().unwrap().with_max_renditions(2);
+
+		producer.lock().audio.insert("a", opus()).unwrap();
+		producer.lock().audio.insert("b", opus()).unwrap();
+		let catalog = consumer.next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.len(), 2);
+
+		producer.lock().audio.insert("c", opus()).unwrap();
+		assert!(matches!(
+			consumer.next().await,
+			Err(crate::Error::Hang(hang::Error::TooManyRenditions {
+				kind: "audio",
+				count: 3,
+				max: 2
+			}))
+		));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn publish_latest_only() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut producer = Producer::new(&mut broadcast).unwrap().with_latest_only();
+		let mut consumer = producer.consume().unwrap();
+
+		for name in ["a", "b", "c"] {
+			producer.lock().audio.insert(name, opus()).unwrap();
+			tokio::time::advance(Duration::from_millis(1)).await;
+		}
+
+		// A consumer that fell behind jumps straight to the newest catalog.
+		let catalog = consumer.next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+		assert!(
+			tokio::time::timeout(Duration::from_secs(1), consumer.next())
+				.await
+				.is_err()
+		);
+
+		// Every update is a full document in its own group, and only the latest is cached.
+		let mut track = producer.hang_track.consume();
+		let mut group = track.next_group().await.unwrap().unwrap();
+		assert_eq!(group.sequence, 2);
+		let full = group.read_frame().await.unwrap().unwrap();
+		let catalog = hang::Catalog::from_slice(&full).unwrap();
+		assert_eq!(catalog.audio.renditions.len(), 3);
+		assert!(track.get_group(0).await.unwrap().is_none());
+
+		// A late joiner gets the current catalog.
+		producer.lock().audio.remove("a");
+		let catalog = producer.consume().unwrap().next().await.unwrap().unwrap();
+		assert_eq!(catalog.audio.renditions.keys().collect::<Vec<_>>(), ["b", "c"]);
+	}
 }
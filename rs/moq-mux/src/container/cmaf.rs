@@ -22,9 +22,6 @@ pub enum Error {
 	#[error("no tfdt in traf")]
 	NoTfdt,
 
-	#[error("PTS overflow")]
-	PtsOverflow,
-
 	#[error("no moof found in CMAF frame data")]
 	NoMoof,
 
@@ -39,6 +36,9 @@ pub enum Error {
 
 	#[error("multiple tracks in moov, use Trak instead")]
 	MultipleTracks,
+
+	#[error("track is not CMAF")]
+	NotCmaf,
 }
 
 /// CMAF container: encodes/decodes a single track's moof+mdat fragments.
@@ -142,8 +142,9 @@ pub(crate) fn decode(data: Bytes, timescale: u64) -> Result<Vec<Frame>, Error> {
 				return Ok(frames);
 			}
 
+			// Signed composition offsets may produce a negative PTS; clamp at zero.
 			let cts = entry.cts.unwrap_or_default() as i64;
-			let pts = dts.checked_add_signed(cts).ok_or(Error::PtsOverflow)?;
+			let pts = dts.saturating_add_signed(cts);
 			let timestamp = Timestamp::from_scale(pts, timescale)?;
 			let payload = Bytes::copy_from_slice(&mdat_data[offset..end]);
 			let flags = entry.flags.unwrap_or(0);
@@ -154,6 +155,7 @@ pub(crate) fn decode(data: Bytes, timescale: u64) -> Result<Vec<Frame>, Error> {
 				timestamp,
 				payload,
 				keyframe,
+				capture: None,
 			});
 
 			offset = end;
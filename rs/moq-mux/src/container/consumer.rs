@@ -20,7 +20,20 @@ use super::{Container, Frame, Timestamp};
 /// budget before being skipped.
 ///
 /// Set the latency with [`with_latency`](Self::with_latency) (builder) or
-/// [`set_latency`](Self::set_latency) (mid-stream).
+/// [`set_latency`](Self::set_latency) (mid-stream), and size it from the catalog's jitter hint with
+/// [`with_jitter`](Self::with_jitter).
+///
+/// ## Warmup
+///
+/// Players that want smooth playback can wait for [`ready`](Self::ready) before the first
+/// [`read`](Self::read), which resolves once the configured latency worth of frames (or one
+/// complete group) is buffered. [`buffered_duration`](Self::buffered_duration) reports
+/// progress, e.g. for a buffering indicator.
+///
+/// ## Skip stats
+///
+/// [`skipped`](Self::skipped) reports how many groups were skipped to stay within the latency
+/// budget, and how many arrived too late to be played, e.g. to surface rebuffering stats.
 pub struct Consumer<F: Container> {
 	track: moq_lite::TrackConsumer,
 
@@ -38,6 +51,27 @@ pub struct Consumer<F: Container> {
 
 	// The maximum buffer size before skipping a group.
 	latency: std::time::Duration,
+
+	// The groups skipped so far.
+	skipped: Skipped,
+}
+
+/// Groups a [`Consumer`] did not play, as reported by [`Consumer::skipped`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Skipped {
+	/// The number of group sequences skipped to stay within the latency budget.
+	///
+	/// Includes sequences that were never received.
+	pub groups: u64,
+
+	/// The sequences most recently skipped to stay within the latency budget.
+	pub last: Option<std::ops::Range<u64>>,
+
+	/// The number of groups that arrived after the consumer had moved past them.
+	///
+	/// A group that arrives after its sequence was skipped counts here as well.
+	pub late: u64,
 }
 
 impl<F: Container> Consumer<F> {
@@ -50,6 +84,7 @@ impl<F: Container> Consumer<F> {
 			pending: VecDeque::new(),
 			startup: true,
 			latency: std::time::Duration::ZERO,
+			skipped: Skipped::default(),
 		}
 	}
 
@@ -62,6 +97,31 @@ impl<F: Container> Consumer<F> {
 		self
 	}
 
+	/// Raise the latency tolerance to cover the catalog's `jitter` hint.
+	///
+	/// The publisher declares its worst-case reordering in
+	/// [`VideoConfig::jitter`](hang::catalog::VideoConfig::jitter) and
+	/// [`AudioConfig::jitter`](hang::catalog::AudioConfig::jitter); a smaller buffer would skip
+	/// groups that are merely late. The larger of the current latency and the hint wins, so call
+	/// this after [`with_latency`](Self::with_latency) or [`with_mode`](Self::with_mode).
+	pub fn with_jitter(mut self, jitter: Option<moq_lite::Time>) -> Self {
+		if let Some(jitter) = jitter {
+			self.latency = self.latency.max(jitter.into());
+		}
+		self
+	}
+
+	/// Pick the latency tolerance that suits the catalog's [`Mode`](hang::catalog::Mode).
+	///
+	/// Live broadcasts skip aggressively to stay at the live edge (zero latency), while
+	/// recordings never skip a group, since every frame is eventually available.
+	pub fn with_mode(self, mode: hang::catalog::Mode) -> Self {
+		self.with_latency(match mode {
+			hang::catalog::Mode::Live => std::time::Duration::ZERO,
+			hang::catalog::Mode::Recording => std::time::Duration::MAX,
+		})
+	}
+
 	/// Read the next frame from the track.
 	///
 	/// This method handles timestamp decoding, group ordering, and latency management
@@ -145,23 +205,11 @@ impl<F: Container> Consumer<F> {
 				}
 			}
 
-			// Find the max timestamp across all newer groups.
-			let mut max_timestamp = std::time::Duration::ZERO;
-			for group in self.pending.iter_mut().rev() {
-				if group.sequence <= self.current {
-					break;
-				}
-
-				if let Poll::Ready(Ok(ts)) = group.poll_max_timestamp(waiter, &self.format) {
-					max_timestamp = max_timestamp.max(ts.into());
-					break; // We know older groups won't be newer than this.
-				}
-			}
-
 			let should_skip = if min_idx.is_some() {
 				if let Some(oldest) = oldest_timestamp {
-					// Current group is blocking: skip if newer groups exceed latency threshold
-					max_timestamp.saturating_sub(oldest) >= self.latency
+					// Current group is blocking: skip if newer groups exceed latency threshold.
+					// With zero latency any newer frame exceeds it, so don't buffer the newest group to find out.
+					self.latency.is_zero() || self.poll_newest_timestamp(waiter).saturating_sub(oldest) >= self.latency
 				} else {
 					// Sequence gap: current group consumed but next sequence missing.
 					// Only skip if track is fully received (no more groups coming).
@@ -179,6 +227,8 @@ impl<F: Container> Consumer<F> {
 
 				tracing::debug!(old = self.current, new = new_current, "skipping slow groups");
 
+				self.skipped.groups += new_current - self.current;
+				self.skipped.last = Some(self.current..new_current);
 				self.current = new_current;
 				continue;
 			}
@@ -191,6 +241,74 @@ impl<F: Container> Consumer<F> {
 		}
 	}
 
+	/// Wait until enough frames are buffered to start playback.
+	///
+	/// Resolves once at least the configured latency worth of frames is buffered, a whole
+	/// group is buffered, or the track has ended. With zero latency, this is the first frame.
+	pub async fn ready(&mut self) -> Result<(), F::Error> {
+		conducer::wait(|waiter| self.poll_ready(waiter)).await
+	}
+
+	/// Poll-based implementation of [`ready`](Self::ready).
+	pub fn poll_ready(&mut self, waiter: &conducer::Waiter) -> Poll<Result<(), F::Error>> {
+		let finished = self.poll_read_finish(waiter)?.is_ready();
+
+		let mut buffered = false;
+		let mut complete = false;
+		for group in self.pending.iter_mut() {
+			// Errors are surfaced (and the group skipped) by poll_read.
+			let _ = group.buffer_all(waiter, &self.format);
+
+			buffered |= !group.buffered.is_empty();
+			complete |= group.complete && !group.buffered.is_empty();
+		}
+
+		if complete || (buffered && self.buffered_duration() >= self.latency) {
+			return Poll::Ready(Ok(()));
+		}
+
+		if finished {
+			// Nothing more is coming, so start with whatever we have.
+			return Poll::Ready(Ok(()));
+		}
+
+		Poll::Pending
+	}
+
+	/// The span of timestamps currently buffered but not yet returned by [`read`](Self::read).
+	///
+	/// Only counts frames that have already arrived; returns zero when nothing is buffered.
+	pub fn buffered_duration(&self) -> std::time::Duration {
+		let mut timestamps = self
+			.pending
+			.iter()
+			.flat_map(|group| group.buffered.iter())
+			.map(|frame| frame.timestamp);
+
+		let Some(first) = timestamps.next() else {
+			return std::time::Duration::ZERO;
+		};
+
+		let (min, max) = timestamps.fold((first, first), |(min, max), ts| (min.min(ts), max.max(ts)));
+		std::time::Duration::from(max).saturating_sub(min.into())
+	}
+
+	// The max timestamp across all groups newer than the current one, or zero if none have data.
+	fn poll_newest_timestamp(&mut self, waiter: &conducer::Waiter) -> std::time::Duration {
+		for group in self.pending.iter_mut().rev() {
+			if group.sequence <= self.current {
+				break;
+			}
+
+			if let Poll::Ready(Ok(ts)) = group.poll_max_timestamp(waiter, &self.format) {
+				// We know older groups won't be newer than this.
+				return ts.into();
+			}
+		}
+
+		std::time::Duration::ZERO
+	}
+
 	// Reads any new groups from the track until we're completely finished.
 	//
 	// Returns Pending until all groups have been consumed.
@@ -208,6 +326,7 @@ impl<F: Container> Consumer<F> {
 					current = ?self.current,
 					"skipping old group"
 				);
+				self.skipped.late += 1;
 				continue;
 			}
 
@@ -218,6 +337,14 @@ impl<F: Container> Consumer<F> {
 		}
 	}
 
+	/// The groups skipped so far, instead of being returned by [`read`](Self::read).
+	///
+	/// Groups discarded while choosing where to start, or because they failed to decode,
+	/// are not counted.
+	pub fn skipped(&self) -> &Skipped {
+		&self.skipped
+	}
+
 	/// Set the maximum latency tolerance.
 	pub fn set_latency(&mut self, latency: std::time::Duration) {
 		self.latency = latency;
@@ -252,6 +379,9 @@ struct GroupBuffer {
 
 	// The maximum timestamp in the group.
 	max_timestamp: Option<Timestamp>,
+
+	// True once every frame in the group has been buffered.
+	complete: bool,
 }
 
 impl GroupBuffer {
@@ -262,6 +392,7 @@ impl GroupBuffer {
 			buffered: VecDeque::new(),
 			max_timestamp: None,
 			min_timestamp: None,
+			complete: false,
 		}
 	}
 
@@ -286,6 +417,7 @@ impl GroupBuffer {
 	// Returns false if the group is finished.
 	fn buffer_once<F: Container>(&mut self, waiter: &conducer::Waiter, format: &F) -> Poll<Result<bool, F::Error>> {
 		let Some(frames) = ready!(format.poll_read(&mut self.group, waiter)?) else {
+			self.complete = true;
 			return Poll::Ready(Ok(false));
 		};
 
@@ -307,6 +439,7 @@ impl GroupBuffer {
 				timestamp: frame.timestamp,
 				payload: frame.payload,
 				keyframe,
+				capture: frame.capture,
 			});
 		}
 
@@ -402,6 +535,7 @@ mod tests {
 				timestamp,
 				payload: Bytes::from_static(&[0xDE, 0xAD]),
 				keyframe: false,
+				capture: None,
 			};
 			Hang::Legacy.write(&mut group, &[frame]).unwrap();
 		}
@@ -479,6 +613,71 @@ mod tests {
 		assert_eq!(frames.len(), 5);
 	}
 
+	// ---- Warmup ----
+
+	#[tokio::test]
+	async fn ready_waits_for_latency() {
+		tokio::time::pause();
+		let mut track = moq_lite::Track::new("test").produce();
+		let consumer_track = subscribe_default(&track);
+		let mut consumer = Consumer::new(consumer_track, Hang::Legacy).with_latency(Duration::from_millis(100));
+
+		// An unfinished group with 80ms of frames isn't enough.
+		let mut group = track.create_group(moq_lite::Group { sequence: 0 }).unwrap();
+		for f in 0..3u64 {
+			let frame = Frame {
+				timestamp: ts(f * 40_000),
+				payload: Bytes::from_static(&[0xDE, 0xAD]),
+				keyframe: false,
+				capture: None,
+			};
+			Hang::Legacy.write(&mut group, &[frame]).unwrap();
+		}
+
+		let ready = tokio::time::timeout(Duration::from_millis(50), consumer.ready()).await;
+		assert!(ready.is_err(), "ready before enough frames were buffered");
+		assert_eq!(consumer.buffered_duration(), Duration::from_millis(80));
+
+		// One more frame reaches the configured latency.
+		let frame = Frame {
+			timestamp: ts(120_000),
+			payload: Bytes::from_static(&[0xDE, 0xAD]),
+			keyframe: false,
+			capture: None,
+		};
+		Hang::Legacy.write(&mut group, &[frame]).unwrap();
+
+		tokio::time::timeout(Duration::from_millis(50), consumer.ready())
+			.await
+			.expect("ready timed out")
+			.unwrap();
+		assert_eq!(consumer.buffered_duration(), Duration::from_millis(120));
+
+		// Reading drains the buffer.
+		let frame = consumer.read().await.unwrap().unwrap();
+		assert_eq!(frame.timestamp, ts(0));
+		assert_eq!(consumer.buffered_duration(), Duration::from_millis(80));
+	}
+
+	#[tokio::test]
+	async fn ready_after_complete_group() {
+		tokio::time::pause();
+		let mut track = moq_lite::Track::new("test").produce();
+		let consumer_track = subscribe_default(&track);
+		let mut consumer = Consumer::new(consumer_track, Hang::Legacy).with_latency(Duration::from_secs(10));
+
+		let ready = tokio::time::timeout(Duration::from_millis(50), consumer.ready()).await;
+		assert!(ready.is_err(), "ready with nothing buffered");
+
+		// A whole group is enough to start, even if it's shorter than the latency.
+		write_group(&mut track, 0, &[ts(0), ts(33_000)]);
+
+		tokio::time::timeout(Duration::from_millis(50), consumer.ready())
+			.await
+			.expect("ready timed out")
+			.unwrap();
+	}
+
 	// ---- Latency Skipping ----
 
 	#[tokio::test]
@@ -498,6 +697,7 @@ mod tests {
 						timestamp: ts(f * 2_000),
 						payload: Bytes::from_static(&[0xDE, 0xAD]),
 						keyframe: false,
+						capture: None,
 					}],
 				)
 				.unwrap();
@@ -537,6 +737,7 @@ mod tests {
 					timestamp: ts(400_000),
 					payload: Bytes::from_static(&[0xDE, 0xAD]),
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -558,6 +759,103 @@ mod tests {
 		finisher.await.expect("finisher task panicked");
 	}
 
+	#[test]
+	fn zero_latency_reads_without_waiting() {
+		let mut track = moq_lite::Track::new("test").produce();
+		let mut consumer = Consumer::new(subscribe_default(&track), Hang::Legacy);
+		let waiter = conducer::Waiter::noop();
+
+		let frame = |timestamp| Frame {
+			timestamp,
+			payload: Bytes::from_static(&[0xDE, 0xAD]),
+			keyframe: false,
+			capture: None,
+		};
+
+		// A frame in an open group is returned as soon as it arrives.
+		let mut group0 = track.create_group(moq_lite::Group { sequence: 0 }).unwrap();
+		Hang::Legacy.write(&mut group0, &[frame(ts(0))]).unwrap();
+		let Poll::Ready(Ok(Some(read))) = consumer.poll_read(&waiter) else {
+			panic!("frame wasn't ready");
+		};
+		assert_eq!(read.timestamp, ts(0));
+		assert!(consumer.poll_read(&waiter).is_pending());
+
+		// A newer group takes over immediately, without buffering the rest of it.
+		let mut group1 = track.create_group(moq_lite::Group { sequence: 1 }).unwrap();
+		Hang::Legacy
+			.write(&mut group1, &[frame(ts(100_000)), frame(ts(110_000))])
+			.unwrap();
+		let Poll::Ready(Ok(Some(read))) = consumer.poll_read(&waiter) else {
+			panic!("newer frame wasn't ready");
+		};
+		assert_eq!(read.timestamp, ts(100_000));
+		assert_eq!(consumer.skipped().groups, 1);
+		assert_eq!(consumer.pending.front().unwrap().buffered.len(), 0);
+	}
+
+	#[tokio::test]
+	async fn mode_chooses_latency() {
+		let track = moq_lite::Track::new("test").produce();
+
+		let live = Consumer::new(subscribe_default(&track), Hang::Legacy).with_mode(hang::catalog::Mode::Live);
+		assert_eq!(live.latency, Duration::ZERO);
+
+		let recording =
+			Consumer::new(subscribe_default(&track), Hang::Legacy).with_mode(hang::catalog::Mode::Recording);
+		assert_eq!(recording.latency, Duration::MAX);
+	}
+
+	#[tokio::test]
+	async fn recording_mode_never_skips() {
+		tokio::time::pause();
+		let mut track = moq_lite::Track::new("test").produce();
+		let consumer_track = subscribe_default(&track);
+		let mut consumer = Consumer::new(consumer_track, Hang::Legacy).with_mode(hang::catalog::Mode::Recording);
+
+		// Group 0 stalls while newer groups are available far ahead of it.
+		let mut group0 = track.create_group(moq_lite::Group { sequence: 0 }).unwrap();
+		Hang::Legacy
+			.write(
+				&mut group0,
+				&[Frame {
+					timestamp: ts(0),
+					payload: Bytes::from_static(&[0xDE, 0xAD]),
+					keyframe: false,
+					capture: None,
+				}],
+			)
+			.unwrap();
+		for g in 1..4u64 {
+			write_group(&mut track, g, &[ts(g * 10_000_000)]);
+		}
+		track.finish().unwrap();
+
+		let finisher = tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_millis(50)).await;
+			Hang::Legacy
+				.write(
+					&mut group0,
+					&[Frame {
+						timestamp: ts(33_000),
+						payload: Bytes::from_static(&[0xDE, 0xAD]),
+						keyframe: false,
+						capture: None,
+					}],
+				)
+				.unwrap();
+			group0.finish().unwrap();
+		});
+
+		let frames = read_all(&mut consumer).await.unwrap();
+		let timestamps: Vec<_> = frames.iter().map(|f| f.timestamp).collect();
+		assert_eq!(
+			timestamps,
+			vec![ts(0), ts(33_000), ts(10_000_000), ts(20_000_000), ts(30_000_000)]
+		);
+		finisher.await.expect("finisher task panicked");
+	}
+
 	#[tokio::test]
 	async fn latency_skip_correctness() {
 		tokio::time::pause();
@@ -573,6 +871,7 @@ mod tests {
 					timestamp: ts(0),
 					payload: Bytes::from_static(&[0xDE, 0xAD]),
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -615,6 +914,7 @@ mod tests {
 					timestamp: ts(0),
 					payload: Bytes::from_static(&[0xDE, 0xAD]),
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -808,6 +1108,7 @@ mod tests {
 					payload: Bytes::from(payload_bytes.clone()),
 
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -826,6 +1127,36 @@ mod tests {
 		assert_eq!(received, payload_bytes);
 	}
 
+	#[tokio::test]
+	async fn frame_capture_preserved() {
+		let mut track = moq_lite::Track::new("test").produce();
+		let consumer_track = subscribe_default(&track);
+		let mut consumer = Consumer::new(consumer_track, Hang::Legacy).with_latency(Duration::from_millis(500));
+
+		let capture = ts(1_760_000_000_000_000);
+		let mut group = track.create_group(moq_lite::Group { sequence: 0 }).unwrap();
+		for capture in [Some(capture), None] {
+			Hang::Legacy
+				.write(
+					&mut group,
+					&[Frame {
+						timestamp: ts(0),
+						payload: Bytes::from_static(&[0x01]),
+						keyframe: false,
+						capture,
+					}],
+				)
+				.unwrap();
+		}
+		group.finish().unwrap();
+		track.finish().unwrap();
+
+		let frames = read_all(&mut consumer).await.unwrap();
+		assert_eq!(frames.len(), 2);
+		assert_eq!(frames[0].capture, Some(capture));
+		assert_eq!(frames[1].capture, None);
+	}
+
 	// ---- Regression ----
 
 	#[tokio::test]
@@ -843,6 +1174,7 @@ mod tests {
 					timestamp: ts(0),
 					payload: Bytes::from_static(&[0xDE, 0xAD]),
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -907,6 +1239,25 @@ mod tests {
 		assert!(consumer.read().await.unwrap().is_none());
 	}
 
+	#[test]
+	fn with_jitter_raises_latency() {
+		let track = moq_lite::Track::new("test").produce();
+
+		let consumer = Consumer::new(track.consume(), Hang::Legacy)
+			.with_latency(Duration::from_millis(500))
+			.with_jitter(Some(moq_lite::Time::from_secs_unchecked(2)));
+		assert_eq!(consumer.latency, Duration::from_secs(2));
+
+		// A smaller hint doesn't shrink an explicit latency.
+		let consumer = Consumer::new(track.consume(), Hang::Legacy)
+			.with_latency(Duration::from_secs(3))
+			.with_jitter(Some(moq_lite::Time::from_secs_unchecked(2)));
+		assert_eq!(consumer.latency, Duration::from_secs(3));
+
+		let consumer = Consumer::new(track.consume(), Hang::Legacy).with_jitter(None);
+		assert_eq!(consumer.latency, Duration::ZERO);
+	}
+
 	#[tokio::test]
 	async fn max_timestamp_tracks_through_bframes() {
 		tokio::time::pause();
@@ -925,6 +1276,7 @@ mod tests {
 						timestamp,
 						payload: Bytes::from_static(&[0xDE, 0xAD]),
 						keyframe: false,
+						capture: None,
 					}],
 				)
 				.unwrap();
@@ -976,6 +1328,7 @@ mod tests {
 					timestamp: ts(300_000),
 					payload: Bytes::from_static(&[0xDE, 0xAD]),
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -989,6 +1342,7 @@ mod tests {
 						timestamp: ts(400_000),
 						payload: Bytes::from_static(&[0xBE, 0xEF]),
 						keyframe: false,
+						capture: None,
 					}],
 				)
 				.unwrap();
@@ -1062,6 +1416,7 @@ mod tests {
 					payload: Bytes::from_static(&[0xAA]),
 
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -1097,6 +1452,7 @@ mod tests {
 					payload: Bytes::from_static(&[0xAA]),
 
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -1134,6 +1490,7 @@ mod tests {
 					timestamp: ts(0),
 					payload: Bytes::from_static(&[0xDE, 0xAD]),
 					keyframe: false,
+					capture: None,
 				}],
 			)
 			.unwrap();
@@ -1152,6 +1509,44 @@ mod tests {
 		finisher.await.unwrap();
 	}
 
+	#[tokio::test]
+	async fn skipped_counts_slow_and_late_groups() {
+		tokio::time::pause();
+		let mut track = moq_lite::Track::new("test").produce();
+		let consumer_track = subscribe_default(&track);
+		let mut consumer = Consumer::new(consumer_track, Hang::Legacy).with_latency(Duration::from_millis(100));
+
+		// Group 5 stalls after one frame, while group 7 is 200ms ahead and group 6 never arrives.
+		let mut group5 = track.create_group(moq_lite::Group { sequence: 5 }).unwrap();
+		Hang::Legacy
+			.write(
+				&mut group5,
+				&[Frame {
+					timestamp: ts(0),
+					payload: Bytes::from_static(&[0xDE, 0xAD]),
+					keyframe: false,
+					capture: None,
+				}],
+			)
+			.unwrap();
+		write_group(&mut track, 7, &[ts(200_000)]);
+
+		assert_eq!(consumer.read().await.unwrap().unwrap().timestamp, ts(0));
+		assert_eq!(consumer.read().await.unwrap().unwrap().timestamp, ts(200_000));
+		assert_eq!(consumer.skipped().groups, 2);
+		assert_eq!(consumer.skipped().last, Some(5..7));
+		assert_eq!(consumer.skipped().late, 0);
+
+		// Group 6 finally shows up, after the consumer moved past it.
+		write_group(&mut track, 6, &[ts(100_000)]);
+		track.finish().unwrap();
+
+		assert!(consumer.read().await.unwrap().is_none());
+		assert_eq!(consumer.skipped().groups, 2);
+		assert_eq!(consumer.skipped().late, 1);
+		drop(group5);
+	}
+
 	/// Regression: when the current group is fully consumed and the next sequence
 	/// is missing (gap), the consumer should skip to the next available group
 	/// once the track is fully received, rather than hanging forever.
@@ -1251,6 +1646,7 @@ mod tests {
 				timestamp: ts(i * 33_333),
 				payload: Bytes::from_static(&[0xDE, 0xAD]),
 				keyframe: false,
+				capture: None,
 			};
 			Hang::Legacy.write(&mut group, &[frame]).unwrap();
 		}
@@ -47,6 +47,7 @@ impl Container for Hang {
 					let hang_frame = hang::container::Frame {
 						timestamp: frame.timestamp,
 						payload: frame.payload.clone(),
+						capture: frame.capture,
 					};
 					hang_frame.encode(group)?;
 				}
@@ -65,11 +66,11 @@ impl Container for Hang {
 			Self::Legacy => {
 				use std::task::ready;
 
-				let Some(data) = ready!(group.poll_read_frame(waiter).map_err(hang::Error::from)?) else {
+				let Some((info, data)) = ready!(group.poll_read_frame_info(waiter).map_err(hang::Error::from)?) else {
 					return Poll::Ready(Ok(None));
 				};
 
-				let mut hang_frame = hang::container::Frame::decode(data)?;
+				let mut hang_frame = hang::container::Frame::decode_with_info(&info, data)?;
 				let payload = hang_frame.payload.copy_to_bytes(hang_frame.payload.remaining());
 
 				Poll::Ready(Ok(Some(vec![Frame {
@@ -77,6 +78,7 @@ impl Container for Hang {
 					payload,
 					// Legacy can't determine from data; consumer infers from group position.
 					keyframe: false,
+					capture: hang_frame.capture,
 				}])))
 			}
 			Self::Cmaf(cmaf) => cmaf.poll_read(group, waiter).map(|r| r.map_err(Into::into)),
@@ -22,14 +22,14 @@ mod hang;
 mod producer;
 
 pub use cmaf::{Cmaf, Error as CmafError};
-pub use consumer::Consumer;
+pub use consumer::{Consumer, Skipped};
 pub use hang::Hang;
 pub use producer::Producer;
 
 /// Microsecond presentation timestamp, the canonical timebase for media frames in moq-mux.
 pub type Timestamp = moq_lite::Timescale<1_000_000>;
 
-/// A decoded media frame: timestamp, payload bytes, keyframe flag.
+/// A decoded media frame: timestamp, payload bytes, keyframe flag and optional capture time.
 ///
 /// `payload` is the raw codec bitstream — what gets decoded by the eventual player.
 /// The exact format depends on the codec (Annex B for H.264 / H.265, OBU for AV1, etc.).
@@ -50,6 +50,13 @@ pub struct Frame {
 	/// In the Legacy wire format, keyframes are inferred from group boundaries (the first
 	/// frame of a group is a keyframe). In CMAF, the trun sample-flags carry the truth.
 	pub keyframe: bool,
+
+	/// Wall clock capture time in microseconds since the Unix epoch, if the source set one.
+	///
+	/// Unlike [`Self::timestamp`], this is comparable against the local clock to compute
+	/// glass-to-glass latency. Only the Legacy wire format carries it, as an object extension
+	/// that moq-lite sessions drop; see [`hang::container::Frame::capture`].
+	pub capture: Option<Timestamp>,
 }
 
 /// Encode/decode media frames over a moq-lite group.
@@ -158,6 +158,7 @@ mod tests {
 			timestamp: Timestamp::from_micros(timestamp_us).unwrap(),
 			payload: Bytes::from_static(&[0xDE, 0xAD]),
 			keyframe,
+			capture: None,
 		}
 	}
 
@@ -17,6 +17,10 @@ pub enum Error {
 	/// Error parsing or building CMAF moof+mdat fragments.
 	#[error("cmaf: {0}")]
 	Cmaf(#[from] crate::container::CmafError),
+
+	/// No rendition in the catalog uses a supported codec.
+	#[error("no supported rendition")]
+	NoRendition,
 }
 
 /// A Result type alias for moq-mux operations.
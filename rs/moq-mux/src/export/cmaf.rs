@@ -0,0 +1,186 @@
+use std::task::{Poll, ready};
+
+use bytes::{Bytes, BytesMut};
+use hang::catalog::Container;
+
+use crate::container::{Cmaf, CmafError};
+
+/// Read a CMAF track as a continuous fMP4 byte stream, without re-encoding.
+///
+/// The track must use [`Container::Cmaf`], as produced by the fMP4 importer. The first
+/// call to [`next`](Self::next) returns the init segment (ftyp + moov) from the catalog,
+/// and each later call returns one moq-lite frame of moof+mdat bytes exactly as they
+/// were published. Frames written in several chunks (e.g. moof and mdat separately) are
+/// reassembled before they're returned.
+///
+/// The output can be appended directly to an MSE `SourceBuffer`.
+pub struct CmafConsumer {
+	track: moq_lite::TrackConsumer,
+	group: Option<moq_lite::GroupConsumer>,
+
+	/// The init segment, until it has been returned.
+	init: Option<Bytes>,
+
+	track_id: u32,
+	timescale: u64,
+}
+
+impl CmafConsumer {
+	/// Create a consumer for `track`, described by the catalog `container`.
+	///
+	/// Fails if the container isn't CMAF or the init segment doesn't hold exactly one track.
+	pub fn new(track: moq_lite::TrackConsumer, container: &Container) -> Result<Self, crate::Error> {
+		let Container::Cmaf { init } = container else {
+			return Err(CmafError::NotCmaf.into());
+		};
+
+		let cmaf = Cmaf::from_init(init)?;
+		let trak = cmaf.trak();
+
+		Ok(Self {
+			track,
+			group: None,
+			init: Some(init.clone()),
+			track_id: trak.tkhd.track_id,
+			timescale: trak.mdia.mdhd.timescale as u64,
+		})
+	}
+
+	/// The track ID used by the init segment and every fragment.
+	pub fn track_id(&self) -> u32 {
+		self.track_id
+	}
+
+	/// The media timescale of the track, in ticks per second.
+	pub fn timescale(&self) -> u64 {
+		self.timescale
+	}
+
+	/// Get the next byte chunk.
+	///
+	/// The first call returns the init segment; each later call returns one fragment.
+	/// Groups are read in sequence order and late groups are skipped. Returns `None`
+	/// when the track ends.
+	pub async fn next(&mut self) -> Result<Option<Bytes>, crate::Error> {
+		conducer::wait(|waiter| self.poll_next(waiter)).await
+	}
+
+	/// Poll-based variant of [`Self::next`].
+	pub fn poll_next(&mut self, waiter: &conducer::Waiter) -> Poll<Result<Option<Bytes>, crate::Error>> {
+		if let Some(init) = self.init.take() {
+			return Poll::Ready(Ok(Some(init)));
+		}
+
+		loop {
+			if let Some(group) = self.group.as_mut() {
+				match ready!(group.poll_read_frame_chunks(waiter))? {
+					Some(chunks) => return Poll::Ready(Ok(Some(concat(chunks)))),
+					None => self.group = None,
+				}
+			}
+
+			match ready!(self.track.poll_next_group(waiter))? {
+				Some(group) => self.group = Some(group),
+				None => return Poll::Ready(Ok(None)),
+			}
+		}
+	}
+
+	/// Return the underlying track consumer.
+	pub fn into_inner(self) -> moq_lite::TrackConsumer {
+		self.track
+	}
+}
+
+/// Join the chunks of a frame, avoiding a copy when there's only one.
+fn concat(mut chunks: Vec<Bytes>) -> Bytes {
+	if chunks.len() == 1 {
+		return chunks.pop().unwrap();
+	}
+
+	let mut buf = BytesMut::with_capacity(chunks.iter().map(Bytes::len).sum());
+	for chunk in chunks {
+		buf.extend_from_slice(&chunk);
+	}
+	buf.freeze()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use mp4_atom::{DecodeMaybe, Encode};
+
+	use crate::container::{Container as _, Frame, Timestamp};
+
+	fn init_segment() -> (Bytes, mp4_atom::Trak) {
+		let mut cursor = std::io::Cursor::new(include_bytes!("../import/test/bbb.mp4").as_slice());
+		let mut ftyp = None;
+		while let Some(atom) = mp4_atom::Any::decode_maybe(&mut cursor).unwrap() {
+			match atom {
+				mp4_atom::Any::Ftyp(atom) => ftyp = Some(atom),
+				mp4_atom::Any::Moov(mut moov) => {
+					moov.trak.truncate(1);
+					let trak = moov.trak[0].clone();
+					let mut buf = Vec::new();
+					ftyp.take().unwrap().encode(&mut buf).unwrap();
+					moov.encode(&mut buf).unwrap();
+					return (buf.into(), trak);
+				}
+				_ => {}
+			}
+		}
+		panic!("no moov");
+	}
+
+	#[test]
+	fn legacy_rejected() {
+		let track = moq_lite::Track::new("video").produce();
+		let res = CmafConsumer::new(track.consume(), &Container::Legacy);
+		assert!(matches!(res, Err(crate::Error::Cmaf(CmafError::NotCmaf))));
+	}
+
+	#[tokio::test]
+	async fn passthrough_chunked() {
+		let (init, trak) = init_segment();
+		let container = Container::Cmaf { init: init.clone() };
+		let cmaf = Cmaf::new(trak.clone());
+
+		let mut track = moq_lite::Track::new("video").produce();
+		let mut consumer = CmafConsumer::new(track.consume(), &container).unwrap();
+		assert_eq!(consumer.track_id(), trak.tkhd.track_id);
+		assert_eq!(consumer.timescale(), trak.mdia.mdhd.timescale as u64);
+
+		// Encode two fragments, then republish them with moof and mdat as separate chunks.
+		let mut scratch = moq_lite::Track::new("scratch").produce();
+		let mut scratch_group = scratch.append_group().unwrap();
+		let mut reader = scratch_group.consume();
+		let mut fragments = Vec::new();
+		for (i, keyframe) in [true, false].into_iter().enumerate() {
+			let frame = Frame {
+				timestamp: Timestamp::from_millis(i as u64 * 40).unwrap(),
+				payload: Bytes::from(vec![i as u8; 16]),
+				keyframe,
+				capture: None,
+			};
+			cmaf.write(&mut scratch_group, &[frame]).unwrap();
+			fragments.push(reader.read_frame().await.unwrap().unwrap());
+		}
+
+		let mut group = track.append_group().unwrap();
+		for fragment in &fragments {
+			let mdat_start = fragment.len() - (8 + 16);
+			let mut frame = group.create_frame(fragment.len().into()).unwrap();
+			frame.write(fragment.slice(..mdat_start)).unwrap();
+			frame.write(fragment.slice(mdat_start..)).unwrap();
+			frame.finish().unwrap();
+		}
+		group.finish().unwrap();
+		track.finish().unwrap();
+
+		assert_eq!(consumer.next().await.unwrap(), Some(init));
+		assert_eq!(consumer.next().await.unwrap().as_ref(), Some(&fragments[0]));
+		assert_eq!(consumer.next().await.unwrap().as_ref(), Some(&fragments[1]));
+		assert_eq!(consumer.next().await.unwrap(), None);
+	}
+}
@@ -72,7 +72,8 @@ impl Fmp4 {
 	/// Set the maximum buffering latency for each per-track [`Consumer`].
 	///
 	/// See [`Consumer::with_latency`] for the per-track skip behavior. Default is zero
-	/// (skip aggressively).
+	/// (skip aggressively). This only applies to live broadcasts: when the catalog's
+	/// [`Mode`](hang::catalog::Mode) is a recording, no group is skipped, see [`Consumer::with_mode`].
 	pub fn with_latency(mut self, latency: Duration) -> Self {
 		self.latency = latency;
 		self
@@ -176,7 +177,10 @@ impl Fmp4 {
 
 			let media: Hang = (*container).try_into()?;
 			let track = self.broadcast.subscribe_track(&moq_lite::Track::new(name.clone()))?;
-			let consumer = Consumer::new(track, media).with_latency(self.latency);
+			let consumer = match catalog.mode {
+				hang::catalog::Mode::Live => Consumer::new(track, media).with_latency(self.latency),
+				hang::catalog::Mode::Recording => Consumer::new(track, media).with_mode(catalog.mode),
+			};
 
 			let timescale = catalog_timescale(catalog, name).context("track not in catalog")?;
 
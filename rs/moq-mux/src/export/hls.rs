@@ -0,0 +1,795 @@
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::path::PathBuf;
+use std::task::{Poll, ready};
+
+use anyhow::Context;
+use bytes::{Bytes, BytesMut};
+use hang::catalog::{AudioConfig, Catalog, Container, VideoConfig};
+use m3u8_rs::{AlternativeMedia, AlternativeMediaType, Map, MasterPlaylist, MediaPlaylist, MediaPlaylistType};
+
+use crate::container::{Cmaf, Timestamp};
+
+/// The name of the master playlist written by [`Hls`].
+pub const HLS_MASTER: &str = "master.m3u8";
+
+/// Where [`Hls`] writes its playlists and segments.
+///
+/// Paths are relative and use `/` as the separator, e.g. `video/playlist.m3u8`.
+/// [`HlsDirectory`] writes to the local filesystem; implement this for an object store.
+pub trait HlsStorage {
+	/// Write (or overwrite) the file at `path`.
+	///
+	/// Playlists are rewritten in place, so readers should never observe a partial write.
+	fn write(&mut self, path: &str, data: Bytes) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+	/// Delete a segment that slid out of a [`HlsPlaylistType::Live`] playlist.
+	///
+	/// The default keeps every segment.
+	fn remove(&mut self, path: &str) -> impl Future<Output = anyhow::Result<()>> + Send {
+		let _ = path;
+		std::future::ready(Ok(()))
+	}
+}
+
+/// Write HLS output to a directory on disk.
+///
+/// Files are written to a temporary name and renamed into place, so a web server
+/// serving the directory never hands out a partial playlist.
+#[derive(Clone, Debug)]
+pub struct HlsDirectory {
+	root: PathBuf,
+}
+
+impl HlsDirectory {
+	/// Write into `root`, creating it and any rendition directories as needed.
+	pub fn new(root: impl Into<PathBuf>) -> Self {
+		Self { root: root.into() }
+	}
+}
+
+impl HlsStorage for HlsDirectory {
+	async fn write(&mut self, path: &str, data: Bytes) -> anyhow::Result<()> {
+		let path = self.root.join(path);
+		if let Some(parent) = path.parent() {
+			tokio::fs::create_dir_all(parent).await?;
+		}
+
+		let mut tmp = path.clone().into_os_string();
+		tmp.push(".tmp");
+
+		tokio::fs::write(&tmp, &data)
+			.await
+			.with_context(|| format!("failed to write {}", path.display()))?;
+		tokio::fs::rename(&tmp, &path).await?;
+
+		Ok(())
+	}
+
+	async fn remove(&mut self, path: &str) -> anyhow::Result<()> {
+		match tokio::fs::remove_file(self.root.join(path)).await {
+			Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// The `EXT-X-PLAYLIST-TYPE` of the media playlists written by [`Hls`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HlsPlaylistType {
+	/// A sliding window of the most recent segments; older segments are removed.
+	#[default]
+	Live,
+
+	/// Segments are only ever appended, so a player can seek back to the start.
+	Event,
+
+	/// Each media playlist is written once, after its track has ended.
+	Vod,
+}
+
+/// Subscribe to a hang broadcast and write it out as HLS.
+///
+/// The inverse of the HLS [import](crate::import::Hls): `Hls` follows the catalog and
+/// writes a media playlist for every CMAF rendition, plus a master playlist
+/// ([`HLS_MASTER`]) listing them. Each moq-lite group becomes one fMP4 segment, so the
+/// publisher's group boundaries are the segment boundaries, and the rendition's init
+/// segment from the catalog is referenced with `EXT-X-MAP`.
+///
+/// The output for a rendition lives in a directory named after it:
+///
+/// ```text
+/// master.m3u8
+/// video/init.mp4
+/// video/playlist.m3u8
+/// video/0.m4s
+/// video/1.m4s
+/// ```
+///
+/// Only [`Container::Cmaf`] renditions are exported; Legacy renditions are skipped with a
+/// warning. A segment is added to its playlist once the next group starts, since that's
+/// what determines its duration.
+pub struct Hls<S> {
+	broadcast: moq_lite::BroadcastConsumer,
+	catalog: Option<crate::catalog::Consumer>,
+	storage: S,
+
+	playlist_type: HlsPlaylistType,
+	window: usize,
+
+	/// Every rendition seen so far, including skipped ones, so none is subscribed twice.
+	seen: HashSet<String>,
+	renditions: Vec<HlsRendition>,
+}
+
+enum HlsEvent {
+	Catalog(Catalog),
+	Segment(usize, HlsSegmentBuf, f64),
+	Ended(usize),
+}
+
+impl<S: HlsStorage> Hls<S> {
+	/// Subscribe to the catalog of `broadcast`, writing into `storage`.
+	///
+	/// Rendition tracks are subscribed once the first catalog arrives.
+	pub fn new(broadcast: moq_lite::BroadcastConsumer, storage: S) -> Result<Self, crate::Error> {
+		let catalog_track = broadcast.subscribe_track(&hang::Catalog::default_track())?;
+		let catalog = crate::catalog::Consumer::new(catalog_track);
+
+		Ok(Self {
+			broadcast,
+			catalog: Some(catalog),
+			storage,
+			playlist_type: HlsPlaylistType::default(),
+			window: 6,
+			seen: HashSet::new(),
+			renditions: Vec::new(),
+		})
+	}
+
+	/// Set the playlist type, [`HlsPlaylistType::Live`] by default.
+	pub fn with_playlist_type(mut self, playlist_type: HlsPlaylistType) -> Self {
+		self.playlist_type = playlist_type;
+		self
+	}
+
+	/// The number of segments kept in a [`HlsPlaylistType::Live`] playlist, 6 by default.
+	pub fn with_window(mut self, segments: usize) -> Self {
+		self.window = segments.max(1);
+		self
+	}
+
+	/// Export until the catalog and every rendition have ended.
+	///
+	/// Returns the storage so the caller can inspect or finish it.
+	pub async fn run(mut self) -> anyhow::Result<S> {
+		while let Some(event) = conducer::wait(|waiter| self.poll_event(waiter)).await? {
+			match event {
+				HlsEvent::Catalog(catalog) => self.write_catalog(&catalog).await?,
+				HlsEvent::Segment(index, segment, duration) => self.write_segment(index, segment, duration).await?,
+				HlsEvent::Ended(index) => {
+					self.renditions[index].ended = true;
+					self.write_playlist(index).await?;
+				}
+			}
+		}
+
+		Ok(self.storage)
+	}
+
+	fn poll_event(&mut self, waiter: &conducer::Waiter) -> Poll<anyhow::Result<Option<HlsEvent>>> {
+		if let Some(catalog) = self.catalog.as_mut() {
+			match catalog.poll_next(waiter).map_err(crate::Error::from)? {
+				Poll::Ready(Some(snapshot)) => {
+					self.subscribe(&snapshot)?;
+					return Poll::Ready(Ok(Some(HlsEvent::Catalog(snapshot))));
+				}
+				Poll::Ready(None) => self.catalog = None,
+				Poll::Pending => {}
+			}
+		}
+
+		for (index, rendition) in self.renditions.iter_mut().enumerate() {
+			if rendition.ended {
+				continue;
+			}
+
+			match rendition.poll_segment(waiter) {
+				Poll::Ready(Ok(Some((segment, duration)))) => {
+					return Poll::Ready(Ok(Some(HlsEvent::Segment(index, segment, duration))));
+				}
+				Poll::Ready(Ok(None)) => return Poll::Ready(Ok(Some(HlsEvent::Ended(index)))),
+				Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+				Poll::Pending => {}
+			}
+		}
+
+		if self.catalog.is_none() && self.renditions.iter().all(|rendition| rendition.ended) {
+			return Poll::Ready(Ok(None));
+		}
+
+		Poll::Pending
+	}
+
+	// Subscribe to any CMAF rendition we haven't seen yet.
+	fn subscribe(&mut self, catalog: &Catalog) -> anyhow::Result<()> {
+		let video = catalog
+			.video
+			.renditions
+			.iter()
+			.map(|(name, config)| (name, &config.container));
+		let audio = catalog
+			.audio
+			.renditions
+			.iter()
+			.map(|(name, config)| (name, &config.container));
+
+		for (name, container) in video.chain(audio) {
+			if !self.seen.insert(name.clone()) {
+				continue;
+			}
+
+			let Container::Cmaf { init } = container else {
+				tracing::warn!(rendition = %name, "skipping rendition; only CMAF can be exported to HLS");
+				continue;
+			};
+
+			tracing::debug!(rendition = %name, "exporting rendition");
+
+			let timescale = Cmaf::from_init(init)?.trak().mdia.mdhd.timescale as u64;
+			let track = self.broadcast.subscribe_track(&moq_lite::Track::new(name.as_str()))?;
+
+			self.renditions.push(HlsRendition {
+				name: name.clone(),
+				dir: rendition_dir(name),
+				init: Some(init.clone()),
+				timescale,
+				track: Some(track),
+				group: None,
+				reading: None,
+				complete: None,
+				segments: VecDeque::new(),
+				media_sequence: 0,
+				target_duration: 1,
+				ended: false,
+			});
+		}
+
+		Ok(())
+	}
+
+	async fn write_catalog(&mut self, catalog: &Catalog) -> anyhow::Result<()> {
+		for rendition in &mut self.renditions {
+			if let Some(init) = rendition.init.take() {
+				self.storage.write(&format!("{}/init.mp4", rendition.dir), init).await?;
+			}
+		}
+
+		let master = self.master_playlist(catalog);
+		self.storage
+			.write(HLS_MASTER, encode_playlist(|w| master.write_to(w))?)
+			.await
+	}
+
+	async fn write_segment(&mut self, index: usize, segment: HlsSegmentBuf, duration: f64) -> anyhow::Result<()> {
+		let rendition = &mut self.renditions[index];
+
+		let uri = format!("{}.m4s", segment.sequence);
+		self.storage
+			.write(&format!("{}/{uri}", rendition.dir), segment.data.freeze())
+			.await?;
+
+		rendition.target_duration = rendition.target_duration.max(duration.ceil() as u64);
+		rendition.segments.push_back(HlsSegment { uri, duration });
+
+		if self.playlist_type == HlsPlaylistType::Live {
+			while rendition.segments.len() > self.window {
+				let old = rendition.segments.pop_front().expect("window is non-zero");
+				rendition.media_sequence += 1;
+				self.storage.remove(&format!("{}/{}", rendition.dir, old.uri)).await?;
+			}
+		}
+
+		if self.playlist_type == HlsPlaylistType::Vod {
+			// A VOD playlist must not change once written.
+			return Ok(());
+		}
+
+		self.write_playlist(index).await
+	}
+
+	async fn write_playlist(&mut self, index: usize) -> anyhow::Result<()> {
+		let rendition = &self.renditions[index];
+		let playlist = rendition.media_playlist(self.playlist_type);
+		let path = format!("{}/playlist.m3u8", rendition.dir);
+		self.storage
+			.write(&path, encode_playlist(|w| playlist.write_to(w))?)
+			.await
+	}
+
+	fn master_playlist(&self, catalog: &Catalog) -> MasterPlaylist {
+		let exported = |name: &String| self.renditions.iter().find(|rendition| &rendition.name == name);
+
+		let video: Vec<_> = catalog
+			.video
+			.renditions
+			.iter()
+			.filter_map(|(name, config)| Some((exported(name)?, config)))
+			.collect();
+		let audio: Vec<_> = catalog
+			.audio
+			.renditions
+			.iter()
+			.filter_map(|(name, config)| Some((exported(name)?, config)))
+			.collect();
+
+		let mut master = MasterPlaylist {
+			version: Some(7),
+			independent_segments: true,
+			..Default::default()
+		};
+
+		if video.is_empty() {
+			// Audio only, so each audio rendition is a variant of its own.
+			for (rendition, config) in audio {
+				master.variants.push(m3u8_rs::VariantStream {
+					uri: rendition.playlist_uri(),
+					bandwidth: config.bitrate.unwrap_or_default(),
+					codecs: Some(config.codec.to_string()),
+					..Default::default()
+				});
+			}
+
+			return master;
+		}
+
+		// Every video variant can be paired with any of the audio renditions.
+		let audio_bitrate = audio.iter().filter_map(|(_, config)| config.bitrate).max();
+		let audio_codec = audio.first().map(|(_, config)| config.codec.to_string());
+
+		for (i, (rendition, config)) in audio.iter().enumerate() {
+			master.alternatives.push(audio_media(rendition, config, i == 0));
+		}
+
+		for (rendition, config) in video {
+			master
+				.variants
+				.push(video_variant(rendition, config, audio_bitrate, audio_codec.as_deref()));
+		}
+
+		master
+	}
+}
+
+const AUDIO_GROUP: &str = "audio";
+
+fn video_variant(
+	rendition: &HlsRendition,
+	config: &VideoConfig,
+	audio_bitrate: Option<u64>,
+	audio_codec: Option<&str>,
+) -> m3u8_rs::VariantStream {
+	let codecs = match audio_codec {
+		Some(audio) => format!("{},{audio}", config.codec),
+		None => config.codec.to_string(),
+	};
+
+	let resolution = match (config.coded_width, config.coded_height) {
+		(Some(width), Some(height)) => Some(m3u8_rs::Resolution {
+			width: width as u64,
+			height: height as u64,
+		}),
+		_ => None,
+	};
+
+	m3u8_rs::VariantStream {
+		uri: rendition.playlist_uri(),
+		bandwidth: config.bitrate.unwrap_or_default() + audio_bitrate.unwrap_or_default(),
+		codecs: Some(codecs),
+		resolution,
+		frame_rate: config.framerate,
+		audio: audio_codec.map(|_| AUDIO_GROUP.to_string()),
+		..Default::default()
+	}
+}
+
+fn audio_media(rendition: &HlsRendition, config: &AudioConfig, default: bool) -> AlternativeMedia {
+	AlternativeMedia {
+		media_type: AlternativeMediaType::Audio,
+		uri: Some(rendition.playlist_uri()),
+		group_id: AUDIO_GROUP.to_string(),
+		language: config.lang.clone(),
+		name: rendition.name.clone(),
+		default,
+		autoselect: true,
+		channels: Some(config.channel_count.to_string()),
+		..Default::default()
+	}
+}
+
+struct HlsRendition {
+	name: String,
+	dir: String,
+
+	/// The init segment, until it has been written.
+	init: Option<Bytes>,
+	timescale: u64,
+
+	/// `None` once the track has ended.
+	track: Option<moq_lite::TrackConsumer>,
+	group: Option<moq_lite::GroupConsumer>,
+
+	/// The segment being read from `group`.
+	reading: Option<HlsSegmentBuf>,
+
+	/// A finished segment, waiting for the next one to start so its duration is known.
+	complete: Option<HlsSegmentBuf>,
+
+	/// The segments currently in the playlist.
+	segments: VecDeque<HlsSegment>,
+	media_sequence: u64,
+	target_duration: u64,
+	ended: bool,
+}
+
+struct HlsSegment {
+	uri: String,
+	duration: f64,
+}
+
+struct HlsSegmentBuf {
+	sequence: u64,
+	data: BytesMut,
+
+	/// The earliest and latest presentation timestamps of any sample.
+	start: Timestamp,
+	end: Timestamp,
+	samples: usize,
+}
+
+impl HlsSegmentBuf {
+	fn new(sequence: u64) -> Self {
+		Self {
+			sequence,
+			data: BytesMut::new(),
+			start: Timestamp::MAX,
+			end: Timestamp::ZERO,
+			samples: 0,
+		}
+	}
+
+	// Estimate the duration of the last segment, which has no next segment to measure against.
+	fn duration(&self) -> f64 {
+		let span = seconds(self.start, self.end);
+		match self.samples {
+			0 | 1 => span,
+			// The span covers one sample fewer than the segment.
+			samples => span * samples as f64 / (samples - 1) as f64,
+		}
+	}
+}
+
+impl HlsRendition {
+	fn playlist_uri(&self) -> String {
+		format!("{}/playlist.m3u8", self.dir)
+	}
+
+	// Return the next segment whose duration is known, or None once the track has ended.
+	fn poll_segment(&mut self, waiter: &conducer::Waiter) -> Poll<anyhow::Result<Option<(HlsSegmentBuf, f64)>>> {
+		loop {
+			if let Some(group) = self.group.as_mut() {
+				match ready!(group.poll_read_frame(waiter))? {
+					Some(fragment) => {
+						let frames = crate::container::cmaf::decode(fragment.clone(), self.timescale)?;
+
+						let reading = self.reading.get_or_insert_with(|| HlsSegmentBuf::new(group.sequence));
+						reading.data.extend_from_slice(&fragment);
+						for frame in &frames {
+							reading.start = reading.start.min(frame.timestamp);
+							reading.end = reading.end.max(frame.timestamp);
+						}
+						reading.samples += frames.len();
+
+						// The previous segment lasts until this one starts.
+						if reading.samples > 0
+							&& let Some(complete) = self.complete.take()
+						{
+							let duration = seconds(complete.start, reading.start);
+							return Poll::Ready(Ok(Some((complete, duration))));
+						}
+					}
+					None => {
+						self.group = None;
+						if let Some(reading) = self.reading.take().filter(|reading| reading.samples > 0) {
+							self.complete = Some(reading);
+						}
+					}
+				}
+
+				continue;
+			}
+
+			let Some(track) = self.track.as_mut() else {
+				return Poll::Ready(Ok(None));
+			};
+
+			match ready!(track.poll_next_group(waiter))? {
+				Some(group) => self.group = Some(group),
+				None => {
+					self.track = None;
+					if let Some(complete) = self.complete.take() {
+						let duration = complete.duration();
+						return Poll::Ready(Ok(Some((complete, duration))));
+					}
+				}
+			}
+		}
+	}
+
+	fn media_playlist(&self, playlist_type: HlsPlaylistType) -> MediaPlaylist {
+		let segments = self
+			.segments
+			.iter()
+			.enumerate()
+			.map(|(i, segment)| m3u8_rs::MediaSegment {
+				uri: segment.uri.clone(),
+				duration: segment.duration as f32,
+				map: (i == 0).then(|| Map {
+					uri: "init.mp4".to_string(),
+					..Default::default()
+				}),
+				..Default::default()
+			})
+			.collect();
+
+		MediaPlaylist {
+			version: Some(7),
+			target_duration: self.target_duration,
+			media_sequence: self.media_sequence,
+			segments,
+			end_list: self.ended,
+			playlist_type: match playlist_type {
+				HlsPlaylistType::Live => None,
+				HlsPlaylistType::Event => Some(MediaPlaylistType::Event),
+				HlsPlaylistType::Vod => Some(MediaPlaylistType::Vod),
+			},
+			independent_segments: true,
+			..Default::default()
+		}
+	}
+}
+
+fn seconds(start: Timestamp, end: Timestamp) -> f64 {
+	end.as_micros().saturating_sub(start.as_micros()) as f64 / 1_000_000.0
+}
+
+// Rendition names come from the publisher, so keep them to a single safe path component.
+fn rendition_dir(name: &str) -> String {
+	let dir: String = name
+		.chars()
+		.map(|c| match c {
+			'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+			_ => '_',
+		})
+		.collect();
+
+	if dir.is_empty() { "_".to_string() } else { dir }
+}
+
+fn encode_playlist(write: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> anyhow::Result<Bytes> {
+	let mut buf = Vec::new();
+	write(&mut buf)?;
+	Ok(buf.into())
+}
+
+#[cfg(test)]
+mod test {
+	use std::collections::HashMap;
+
+	use mp4_atom::{DecodeMaybe, Encode};
+
+	use super::*;
+	use crate::container::{Container as _, Frame};
+
+	#[derive(Default)]
+	struct Memory {
+		files: HashMap<String, Bytes>,
+		removed: Vec<String>,
+	}
+
+	impl HlsStorage for Memory {
+		async fn write(&mut self, path: &str, data: Bytes) -> anyhow::Result<()> {
+			self.files.insert(path.to_string(), data);
+			Ok(())
+		}
+
+		async fn remove(&mut self, path: &str) -> anyhow::Result<()> {
+			self.files.remove(path);
+			self.removed.push(path.to_string());
+			Ok(())
+		}
+	}
+
+	impl Memory {
+		fn text(&self, path: &str) -> &str {
+			std::str::from_utf8(self.files.get(path).unwrap_or_else(|| panic!("missing {path}"))).unwrap()
+		}
+	}
+
+	fn init_segment() -> (Bytes, mp4_atom::Trak) {
+		let mut cursor = std::io::Cursor::new(include_bytes!("../import/test/bbb.mp4").as_slice());
+		let mut ftyp = None;
+		while let Some(atom) = mp4_atom::Any::decode_maybe(&mut cursor).unwrap() {
+			match atom {
+				mp4_atom::Any::Ftyp(atom) => ftyp = Some(atom),
+				mp4_atom::Any::Moov(mut moov) => {
+					moov.trak.truncate(1);
+					let trak = moov.trak[0].clone();
+					let mut buf = Vec::new();
+					ftyp.take().unwrap().encode(&mut buf).unwrap();
+					moov.encode(&mut buf).unwrap();
+					return (buf.into(), trak);
+				}
+				_ => {}
+			}
+		}
+		panic!("no moov");
+	}
+
+	fn video(init: Bytes) -> VideoConfig {
+		VideoConfig {
+			codec: hang::catalog::H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: false,
+			}
+			.into(),
+			description: None,
+			coded_width: Some(1280),
+			coded_height: Some(720),
+			display_ratio_width: None,
+			display_ratio_height: None,
+			bitrate: Some(1_000_000),
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Cmaf { init },
+			jitter: None,
+			lang: None,
+		}
+	}
+
+	// Publish `groups` groups of four 100ms frames, so every segment lasts 400ms.
+	fn publish(
+		groups: u64,
+	) -> (
+		moq_lite::BroadcastProducer,
+		crate::catalog::Producer,
+		moq_lite::TrackProducer,
+		Bytes,
+	) {
+		let (init, trak) = init_segment();
+		let cmaf = Cmaf::new(trak);
+
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let mut track = broadcast.create_track(moq_lite::Track::new("video")).unwrap();
+		catalog
+			.lock()
+			.video
+			.renditions
+			.insert("video".to_string(), video(init.clone()));
+
+		for sequence in 0..groups {
+			let mut group = track.create_group(moq_lite::Group { sequence }).unwrap();
+			for i in 0..4 {
+				let frame = Frame {
+					timestamp: Timestamp::from_millis(sequence * 400 + i * 100).unwrap(),
+					payload: Bytes::from(vec![i as u8; 16]),
+					keyframe: i == 0,
+					capture: None,
+				};
+				cmaf.write(&mut group, &[frame]).unwrap();
+			}
+			group.finish().unwrap();
+		}
+		track.finish().unwrap();
+		catalog.finish().unwrap();
+
+		(broadcast, catalog, track, init)
+	}
+
+	#[tokio::test]
+	async fn event_playlist() {
+		let (broadcast, _catalog, _track, init) = publish(3);
+
+		let storage = Hls::new(broadcast.consume(), Memory::default())
+			.unwrap()
+			.with_playlist_type(HlsPlaylistType::Event)
+			.run()
+			.await
+			.unwrap();
+
+		assert_eq!(storage.files["video/init.mp4"], init);
+
+		let master = storage.text(HLS_MASTER);
+		assert!(master.contains("BANDWIDTH=1000000"), "{master}");
+		assert!(master.contains("CODECS=\"avc1.64001f\""), "{master}");
+		assert!(master.contains("RESOLUTION=1280x720"), "{master}");
+		assert!(master.contains("video/playlist.m3u8"), "{master}");
+
+		let playlist = storage.text("video/playlist.m3u8");
+		let MediaPlaylist {
+			segments,
+			playlist_type,
+			end_list,
+			..
+		} = m3u8_rs::parse_media_playlist_res(playlist.as_bytes()).unwrap();
+		assert_eq!(playlist_type, Some(MediaPlaylistType::Event));
+		assert!(end_list);
+
+		// One segment per group, each starting with the init segment map.
+		let uris: Vec<_> = segments.iter().map(|segment| segment.uri.as_str()).collect();
+		assert_eq!(uris, ["0.m4s", "1.m4s", "2.m4s"]);
+		assert_eq!(segments[0].map.as_ref().unwrap().uri, "init.mp4");
+		for segment in &segments {
+			assert!((segment.duration - 0.4).abs() < 0.001, "{}", segment.duration);
+		}
+
+		// Each segment is the group's four fragments back to back.
+		let data = &storage.files["video/1.m4s"];
+		assert_eq!(&data[4..8], b"moof");
+		assert_eq!(data.len() % 4, 0);
+	}
+
+	#[tokio::test]
+	async fn live_window() {
+		let (broadcast, _catalog, _track, _init) = publish(5);
+
+		let storage = Hls::new(broadcast.consume(), Memory::default())
+			.unwrap()
+			.with_window(2)
+			.run()
+			.await
+			.unwrap();
+
+		let playlist = storage.text("video/playlist.m3u8");
+		let playlist = m3u8_rs::parse_media_playlist_res(playlist.as_bytes()).unwrap();
+		assert_eq!(playlist.playlist_type, None);
+		assert_eq!(playlist.media_sequence, 3);
+		let uris: Vec<_> = playlist.segments.iter().map(|segment| segment.uri.as_str()).collect();
+		assert_eq!(uris, ["3.m4s", "4.m4s"]);
+
+		// The first segment in the window still carries the init map.
+		assert_eq!(playlist.segments[0].map.as_ref().unwrap().uri, "init.mp4");
+
+		assert_eq!(storage.removed, ["video/0.m4s", "video/1.m4s", "video/2.m4s"]);
+		assert!(!storage.files.contains_key("video/0.m4s"));
+	}
+
+	#[tokio::test]
+	async fn legacy_skipped() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let mut config = video(Bytes::new());
+		config.container = Container::Legacy;
+		catalog.lock().video.renditions.insert("legacy".to_string(), config);
+		catalog.finish().unwrap();
+
+		let storage = Hls::new(broadcast.consume(), Memory::default())
+			.unwrap()
+			.run()
+			.await
+			.unwrap();
+
+		assert!(!storage.text(HLS_MASTER).contains("legacy"));
+		assert_eq!(storage.files.len(), 1);
+	}
+
+	#[test]
+	fn dir_is_one_component() {
+		assert_eq!(rendition_dir("video"), "video");
+		assert_eq!(rendition_dir("../etc/passwd"), "___etc_passwd");
+		assert_eq!(rendition_dir(""), "_");
+	}
+}
@@ -4,7 +4,23 @@
 //! [`Consumer<Hang>`](crate::container::Consumer), and yields a single fMP4 / CMAF byte
 //! stream — the merged init segment followed by moof+mdat fragments in
 //! timestamp order across tracks.
+//!
+//! [`CmafConsumer`] reads a single CMAF track and yields its init segment followed by
+//! the published moof+mdat fragments unchanged, for players that accept CMAF directly.
+//!
+//! [`BroadcastPlayer`] follows the catalog, selects one audio and one video rendition,
+//! and yields their decoded frames, switching renditions when the catalog changes.
+//! [`subscribe_best`] makes the same choice once, among the renditions using a supported codec.
+//!
+//! [`Hls`] writes every CMAF rendition out as an HLS media playlist of fMP4 segments, one
+//! per group, plus a master playlist, for legacy players and CDNs.
 
+mod cmaf;
 mod fmp4;
+mod hls;
+mod player;
 
+pub use cmaf::*;
 pub use fmp4::*;
+pub use hls::*;
+pub use player::*;
@@ -0,0 +1,427 @@
+use std::task::Poll;
+use std::time::Duration;
+
+use hang::catalog::{Catalog, Container, RenditionSelector};
+
+use crate::container::{Consumer, Frame, Hang};
+
+/// A decoded frame from a [`BroadcastPlayer`], tagged with the kind of track it came from.
+#[derive(Clone, Debug)]
+pub enum PlayerFrame {
+	/// A frame from the selected video rendition.
+	Video(Frame),
+	/// A frame from the selected audio rendition.
+	Audio(Frame),
+}
+
+/// Play a hang broadcast: follow the catalog and decode one audio and one video rendition.
+///
+/// Built from a [`moq_lite::BroadcastConsumer`], `BroadcastPlayer` subscribes to the hang
+/// catalog and picks a rendition of each kind with a [`RenditionSelector`]: the top video
+/// rendition that fits within [`with_max_resolution`](Self::with_max_resolution), and the
+/// highest bitrate audio rendition. When a catalog update removes or changes the selected rendition, the
+/// player switches to the new selection and continues from the new track.
+///
+/// Each track is read through a [`Consumer<Hang>`]. Unless a latency is set explicitly
+/// with [`with_latency`](Self::with_latency), it's chosen from the catalog's
+/// [`Mode`](hang::catalog::Mode) via [`Consumer::with_mode`].
+pub struct BroadcastPlayer {
+	broadcast: moq_lite::BroadcastConsumer,
+	catalog: Option<crate::catalog::Consumer>,
+	latency: Option<Duration>,
+	selector: RenditionSelector,
+	video_priority: moq_lite::Priority,
+	audio_priority: moq_lite::Priority,
+
+	/// The most recent catalog snapshot.
+	current: Option<Catalog>,
+
+	video: Option<PlayerTrack>,
+	audio: Option<PlayerTrack>,
+}
+
+struct PlayerTrack {
+	name: String,
+	container: Container,
+	consumer: Consumer<Hang>,
+	finished: bool,
+}
+
+impl BroadcastPlayer {
+	/// Subscribe to the catalog of `broadcast`.
+	///
+	/// Rendition tracks are subscribed once the first catalog arrives.
+	pub fn new(broadcast: moq_lite::BroadcastConsumer) -> Result<Self, crate::Error> {
+		let catalog_track = broadcast.subscribe_track(&hang::Catalog::default_track())?;
+		let catalog = crate::catalog::Consumer::new(catalog_track);
+
+		Ok(Self {
+			broadcast,
+			catalog: Some(catalog),
+			latency: None,
+			selector: RenditionSelector::default(),
+			video_priority: MediaKind::Video.default_priority(),
+			audio_priority: MediaKind::Audio.default_priority(),
+			current: None,
+			video: None,
+			audio: None,
+		})
+	}
+
+	/// Set the maximum buffering latency for each track, overriding the catalog mode.
+	///
+	/// See [`Consumer::with_latency`] for the per-track skip behavior.
+	pub fn with_latency(mut self, latency: Duration) -> Self {
+		self.latency = Some(latency);
+		self
+	}
+
+	/// Only select video renditions whose coded size fits within `width` x `height`.
+	///
+	/// If no rendition fits, the smallest one is selected instead.
+	pub fn with_max_resolution(mut self, width: u32, height: u32) -> Self {
+		self.selector = self.selector.with_max_resolution(width, height);
+		self
+	}
+
+	/// Subscribe to renditions of `kind` with the given delivery priority.
+	///
+	/// Defaults to [`MediaKind::default_priority`], which favors audio over video. Raise the
+	/// video priority when the picture matters more, such as a screen share.
+	pub fn with_priority(mut self, kind: MediaKind, priority: moq_lite::Priority) -> Self {
+		match kind {
+			MediaKind::Video => self.video_priority = priority,
+			MediaKind::Audio => self.audio_priority = priority,
+		}
+		self
+	}
+
+	/// The most recent catalog, if one has been received.
+	pub fn catalog(&self) -> Option<&Catalog> {
+		self.current.as_ref()
+	}
+
+	/// The name of the selected video rendition, if any.
+	pub fn video_rendition(&self) -> Option<&str> {
+		self.video.as_ref().map(|track| track.name.as_str())
+	}
+
+	/// The name of the selected audio rendition, if any.
+	pub fn audio_rendition(&self) -> Option<&str> {
+		self.audio.as_ref().map(|track| track.name.as_str())
+	}
+
+	/// Get the next decoded frame from either track.
+	///
+	/// Audio is checked first, since it's the more latency sensitive of the two.
+	/// Returns `None` once the catalog and every selected track have ended.
+	pub async fn next(&mut self) -> Result<Option<PlayerFrame>, crate::Error> {
+		conducer::wait(|waiter| self.poll_next(waiter)).await
+	}
+
+	/// Poll-based variant of [`Self::next`].
+	pub fn poll_next(&mut self, waiter: &conducer::Waiter) -> Poll<Result<Option<PlayerFrame>, crate::Error>> {
+		// Drain catalog updates and switch renditions accordingly.
+		while let Some(catalog) = self.catalog.as_mut() {
+			match catalog.poll_next(waiter)? {
+				Poll::Ready(Some(snapshot)) => self.update_catalog(snapshot)?,
+				Poll::Ready(None) => {
+					self.catalog = None;
+					break;
+				}
+				Poll::Pending => break,
+			}
+		}
+
+		if let Some(frame) = poll_track(&mut self.audio, waiter)? {
+			return Poll::Ready(Ok(Some(PlayerFrame::Audio(frame))));
+		}
+
+		if let Some(frame) = poll_track(&mut self.video, waiter)? {
+			return Poll::Ready(Ok(Some(PlayerFrame::Video(frame))));
+		}
+
+		let finished = |track: &Option<PlayerTrack>| track.as_ref().is_none_or(|track| track.finished);
+		if self.catalog.is_none() && finished(&self.audio) && finished(&self.video) {
+			return Poll::Ready(Ok(None));
+		}
+
+		Poll::Pending
+	}
+
+	fn update_catalog(&mut self, catalog: Catalog) -> Result<(), crate::Error> {
+		let video = self.selector.best(&catalog.video).map(str::to_string);
+		let video = video.and_then(|name| catalog.video.renditions.get_key_value(&name));
+		let video = video.map(|(name, config)| (name, &config.container));
+
+		let audio = self.selector.select_audio(&catalog.audio);
+		let audio = audio.and_then(|name| catalog.audio.renditions.get_key_value(name));
+		let audio = audio.map(|(name, config)| (name, &config.container));
+
+		let existing = self.video.take();
+		self.video = self.subscribe(existing, video, self.video_priority, &catalog)?;
+
+		let existing = self.audio.take();
+		self.audio = self.subscribe(existing, audio, self.audio_priority, &catalog)?;
+
+		self.current = Some(catalog);
+
+		Ok(())
+	}
+
+	// Keep the existing track if the selection didn't change, otherwise subscribe to the new one.
+	fn subscribe(
+		&self,
+		existing: Option<PlayerTrack>,
+		selected: Option<(&String, &Container)>,
+		priority: moq_lite::Priority,
+		catalog: &Catalog,
+	) -> Result<Option<PlayerTrack>, crate::Error> {
+		let Some((name, container)) = selected else {
+			return Ok(None);
+		};
+
+		if let Some(existing) = existing
+			&& existing.name == *name
+			&& existing.container == *container
+		{
+			return Ok(Some(existing));
+		}
+
+		tracing::debug!(rendition = %name, "subscribing to rendition");
+
+		let track = self.broadcast.subscribe_track(&moq_lite::Track {
+			name: name.clone(),
+			priority,
+			clean_join: false,
+			datagrams: false,
+			ascending: false,
+		})?;
+
+		let consumer = Consumer::new(track, container.try_into()?);
+		let consumer = match self.latency {
+			Some(latency) => consumer.with_latency(latency),
+			None => consumer.with_mode(catalog.mode),
+		};
+
+		Ok(Some(PlayerTrack {
+			name: name.clone(),
+			container: container.clone(),
+			consumer,
+			finished: false,
+		}))
+	}
+}
+
+/// The kind of rendition to select with [`subscribe_best`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaKind {
+	Video,
+	Audio,
+}
+
+impl MediaKind {
+	/// The delivery priority used unless overridden: video is [`Priority::low`](moq_lite::Priority::low)
+	/// and audio is [`Priority::high`](moq_lite::Priority::high), since audio glitches are more noticeable.
+	pub const fn default_priority(self) -> moq_lite::Priority {
+		match self {
+			MediaKind::Video => moq_lite::Priority::low(),
+			MediaKind::Audio => moq_lite::Priority::high(),
+		}
+	}
+}
+
+/// Wait for the catalog of `broadcast`, then subscribe to the best rendition of `kind` whose
+/// codec is in `supported_codecs`.
+///
+/// Codecs are matched as in [`RenditionSelector::with_codecs`]. Among the matches, the
+/// choice is the same as [`BroadcastPlayer`]: the top video or the highest bitrate audio.
+///
+/// Returns the rendition name along with its consumer, or [`Error::NoRendition`](crate::Error::NoRendition)
+/// if nothing matches.
+pub async fn subscribe_best(
+	broadcast: &moq_lite::BroadcastConsumer,
+	kind: MediaKind,
+	supported_codecs: &[&str],
+) -> Result<(String, Consumer<Hang>), crate::Error> {
+	let catalog_track = broadcast.subscribe_track(&hang::Catalog::default_track())?;
+	let catalog = crate::catalog::Consumer::new(catalog_track)
+		.next()
+		.await?
+		.ok_or(moq_lite::Error::Closed)?;
+
+	let (name, container) = select_supported(&catalog, kind, supported_codecs).ok_or(crate::Error::NoRendition)?;
+
+	let priority = kind.default_priority();
+
+	tracing::debug!(rendition = %name, "subscribing to best rendition");
+
+	let track = broadcast.subscribe_track(&moq_lite::Track {
+		name: name.clone(),
+		priority,
+		clean_join: false,
+		datagrams: false,
+		ascending: false,
+	})?;
+
+	let consumer = Consumer::new(track, (&container).try_into()?).with_mode(catalog.mode);
+	Ok((name, consumer))
+}
+
+/// Select the best rendition of `kind` using one of the supported codecs.
+fn select_supported(catalog: &Catalog, kind: MediaKind, supported: &[&str]) -> Option<(String, Container)> {
+	let mut selector = RenditionSelector::default().with_codecs(supported.iter().copied());
+
+	match kind {
+		MediaKind::Video => {
+			let name = selector.best(&catalog.video)?;
+			let config = catalog.video.renditions.get(name)?;
+			Some((name.to_string(), config.container.clone()))
+		}
+		MediaKind::Audio => {
+			let name = selector.select_audio(&catalog.audio)?;
+			let config = catalog.audio.renditions.get(name)?;
+			Some((name.to_string(), config.container.clone()))
+		}
+	}
+}
+
+fn poll_track(track: &mut Option<PlayerTrack>, waiter: &conducer::Waiter) -> Result<Option<Frame>, crate::Error> {
+	let Some(track) = track.as_mut().filter(|track| !track.finished) else {
+		return Ok(None);
+	};
+
+	match track.consumer.poll_read(waiter) {
+		Poll::Ready(Ok(Some(frame))) => Ok(Some(frame)),
+		Poll::Ready(Ok(None)) => {
+			track.finished = true;
+			Ok(None)
+		}
+		Poll::Ready(Err(err)) => Err(err),
+		Poll::Pending => Ok(None),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::collections::HashMap;
+
+	use hang::catalog::{AudioCodec, AudioConfig, H264, VideoConfig};
+
+	fn video(width: u32, height: u32) -> VideoConfig {
+		VideoConfig {
+			codec: H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: false,
+			}
+			.into(),
+			description: None,
+			coded_width: Some(width),
+			coded_height: Some(height),
+			display_ratio_width: None,
+			display_ratio_height: None,
+			bitrate: None,
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Legacy,
+			jitter: None,
+			lang: None,
+		}
+	}
+
+	fn audio(bitrate: u64) -> AudioConfig {
+		AudioConfig {
+			codec: AudioCodec::Opus,
+			sample_rate: 48_000,
+			channel_count: 2,
+			bitrate: Some(bitrate),
+			description: None,
+			container: Container::Legacy,
+			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
+		}
+	}
+
+	#[test]
+	fn selects_supported_codec() {
+		let mut av1 = video(3840, 2160);
+		av1.codec = "av01.0.08M.08".parse().unwrap();
+
+		let mut catalog = Catalog::default();
+		catalog.video.renditions.insert("720p".to_string(), video(1280, 720));
+		catalog.video.renditions.insert("1080p".to_string(), video(1920, 1080));
+		catalog.video.renditions.insert("4k".to_string(), av1);
+		catalog.audio.renditions.insert("opus".to_string(), audio(128_000));
+
+		let select = |kind, supported: &[&str]| select_supported(&catalog, kind, supported).map(|(name, _)| name);
+
+		assert_eq!(select(MediaKind::Video, &["av01", "avc1"]).unwrap(), "4k");
+		assert_eq!(select(MediaKind::Video, &["avc1"]).unwrap(), "1080p");
+		assert_eq!(select(MediaKind::Video, &["avc1.64001f"]).unwrap(), "1080p");
+		assert_eq!(select(MediaKind::Audio, &["opus"]).unwrap(), "opus");
+
+		// A partial codec component doesn't match.
+		assert!(select(MediaKind::Video, &["avc"]).is_none());
+		assert!(select(MediaKind::Video, &["vp09"]).is_none());
+		assert!(select(MediaKind::Audio, &["mp4a"]).is_none());
+	}
+
+	#[tokio::test]
+	async fn subscribe_best_rendition() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let _tracks = [
+			broadcast.create_track(moq_lite::Track::new("720p")).unwrap(),
+			broadcast.create_track(moq_lite::Track::new("1080p")).unwrap(),
+		];
+		{
+			let mut catalog = catalog.lock();
+			catalog.video.renditions.insert("720p".to_string(), video(1280, 720));
+			catalog.video.renditions.insert("1080p".to_string(), video(1920, 1080));
+		}
+
+		let consumer = broadcast.consume();
+		let (name, _) = subscribe_best(&consumer, MediaKind::Video, &["avc1"]).await.unwrap();
+		assert_eq!(name, "1080p");
+
+		let res = subscribe_best(&consumer, MediaKind::Video, &["hev1", "vp09"]).await;
+		assert!(matches!(res, Err(crate::Error::NoRendition)));
+	}
+
+	#[tokio::test]
+	async fn subscribes_with_priority() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		{
+			let mut catalog = catalog.lock();
+			catalog.video.renditions.insert("screen".to_string(), video(1920, 1080));
+			catalog.audio.renditions.insert("mic".to_string(), audio(64_000));
+		}
+
+		// Rendition tracks are created on demand, taking the priority of the subscription.
+		let mut dynamic = broadcast.dynamic();
+		let mut player = BroadcastPlayer::new(broadcast.consume())
+			.unwrap()
+			.with_priority(MediaKind::Video, moq_lite::Priority::MAX);
+
+		// Nothing is published, so this only processes the catalog.
+		let _ = tokio::time::timeout(Duration::from_millis(10), player.next()).await;
+
+		let mut tracks = HashMap::new();
+		for _ in 0..2 {
+			let track = dynamic.requested_track().await.unwrap();
+			tracks.insert(track.name.clone(), track.priority);
+		}
+
+		// The screen share now outranks the audio, so its groups are transmitted first.
+		assert_eq!(tracks["screen"], moq_lite::Priority::MAX);
+		assert_eq!(tracks["mic"], MediaKind::Audio.default_priority());
+		assert!(tracks["screen"] > tracks["mic"]);
+	}
+}
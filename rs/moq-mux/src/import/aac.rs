@@ -1,5 +1,5 @@
 use anyhow::Context;
-use bytes::{Buf, BytesMut};
+use bytes::{Buf, Bytes, BytesMut};
 
 // Pack ~100ms of audio per group. AAC frames are typically ~21ms (1024 samples at 48kHz),
 // so 5 frames is a good fit. Any value works — this is just a knob for relay efficiency.
@@ -108,13 +108,26 @@ pub struct Aac {
 	track: crate::container::Producer<crate::container::Hang>,
 	zero: Option<tokio::time::Instant>,
 	frames: usize,
+
+	#[cfg(feature = "audio-resample")]
+	resample: Option<Box<super::resample::AacResampler>>,
 }
 
 impl Aac {
 	pub fn new(
+		broadcast: moq_lite::BroadcastProducer,
+		catalog: crate::catalog::Producer,
+		config: AacConfig,
+	) -> anyhow::Result<Self> {
+		Self::with_description(broadcast, catalog, config, None)
+	}
+
+	/// Like [`Self::new`], but advertises the given AudioSpecificConfig as the catalog description.
+	pub(crate) fn with_description(
 		mut broadcast: moq_lite::BroadcastProducer,
 		mut catalog: crate::catalog::Producer,
 		config: AacConfig,
+		description: Option<Bytes>,
 	) -> anyhow::Result<Self> {
 		let track = broadcast.unique_track(".aac")?;
 
@@ -126,9 +139,12 @@ impl Aac {
 			sample_rate: config.sample_rate,
 			channel_count: config.channel_count,
 			bitrate: None,
-			description: None,
+			description,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
 		};
 
 		tracing::debug!(name = ?track.name, config = ?audio_config, "starting track");
@@ -139,12 +155,57 @@ impl Aac {
 			track: crate::container::Producer::new(track, crate::container::Hang::Legacy),
 			zero: None,
 			frames: 0,
+			#[cfg(feature = "audio-resample")]
+			resample: None,
 		})
 	}
 
+	/// Resample to `sample_rate`, publishing 16-bit PCM instead of AAC.
+	///
+	/// AAC can't be resampled without re-encoding, so each frame is decoded and resampled,
+	/// and the catalog advertises `pcm-s16` at the new rate.
+	/// Output frames are 1024 samples, timed from the first input timestamp.
+	#[cfg(feature = "audio-resample")]
+	pub fn with_sample_rate(mut self, sample_rate: u32) -> anyhow::Result<Self> {
+		let mut catalog = self.catalog.lock();
+		let audio = catalog
+			.audio
+			.renditions
+			.get_mut(&self.track.name)
+			.context("missing rendition")?;
+
+		let hang::catalog::AudioCodec::AAC(aac) = &audio.codec else {
+			anyhow::bail!("already resampled");
+		};
+
+		let config = AacConfig {
+			profile: aac.profile,
+			sample_rate: audio.sample_rate,
+			channel_count: audio.channel_count,
+		};
+		let resample = super::resample::AacResampler::new(&config, audio.description.clone(), sample_rate)?;
+
+		audio.codec = hang::catalog::AudioCodec::PcmS16;
+		audio.sample_rate = sample_rate;
+		audio.description = None;
+		audio.bitrate = Some(sample_rate as u64 * audio.channel_count as u64 * 16);
+		drop(catalog);
+
+		self.resample = Some(Box::new(resample));
+		Ok(self)
+	}
+
 	/// Finish the track, flushing the current group.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
+		#[cfg(feature = "audio-resample")]
+		if let Some(resample) = &mut self.resample {
+			for (pts, payload) in resample.finish()? {
+				self.write(pts, payload)?;
+			}
+		}
+
 		self.track.finish()?;
+		self.catalog.finish()?;
 		Ok(())
 	}
 
@@ -160,11 +221,24 @@ impl Aac {
 			buf.advance(len);
 		}
 
+		#[cfg(feature = "audio-resample")]
+		if let Some(resample) = &mut self.resample {
+			for (pts, payload) in resample.decode(&payload, pts)? {
+				self.write(pts, payload)?;
+			}
+			return Ok(());
+		}
+
+		self.write(pts, payload.freeze())
+	}
+
+	fn write(&mut self, pts: hang::container::Timestamp, payload: Bytes) -> anyhow::Result<()> {
 		// Start a new group every GROUP_FRAMES frames.
 		let frame = crate::container::Frame {
 			timestamp: pts,
-			payload: payload.freeze(),
+			payload,
 			keyframe: self.frames % GROUP_FRAMES == 0,
+			capture: None,
 		};
 		self.frames += 1;
 
@@ -0,0 +1,295 @@
+use bytes::{Buf, Bytes};
+use hang::container::Timestamp;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{Aac, AacConfig};
+
+/// Each AAC frame decodes to 1024 samples.
+const FRAME_SAMPLES: u64 = 1024;
+
+/// The size of an ADTS header without and with the CRC.
+const HEADER_SIZE: usize = 7;
+const HEADER_SIZE_CRC: usize = 9;
+
+/// ADTS AAC importer.
+///
+/// Splits a stream of ADTS frames into raw AAC frames for the [`Aac`] importer. The track
+/// is created from the first ADTS header, which describes the AudioSpecificConfig that is
+/// published as the catalog description. Both 7-byte and CRC-protected 9-byte headers are
+/// supported; the headers and CRCs are stripped.
+///
+/// ADTS has no timestamps, so [`decode_stream`](Self::decode_stream) counts samples from zero.
+pub struct Adts {
+	broadcast: moq_lite::BroadcastProducer,
+	catalog: crate::catalog::Producer,
+	aac: Option<Aac>,
+	sample_rate: u64,
+
+	// The number of samples emitted by decode_stream, used for timestamps.
+	samples: u64,
+}
+
+impl Adts {
+	/// Create a new ADTS importer that will write to the given broadcast.
+	///
+	/// The track is created once the first ADTS header is decoded.
+	pub fn new(broadcast: moq_lite::BroadcastProducer, catalog: crate::catalog::Producer) -> Self {
+		Self {
+			broadcast,
+			catalog,
+			aac: None,
+			sample_rate: 0,
+			samples: 0,
+		}
+	}
+
+	/// Decode from an asynchronous reader.
+	pub async fn decode_from<T: AsyncRead + Unpin>(&mut self, reader: &mut T) -> anyhow::Result<()> {
+		let mut buffer = bytes::BytesMut::new();
+		while reader.read_buf(&mut buffer).await? > 0 {
+			self.decode_stream(&mut buffer)?;
+		}
+
+		self.finish()
+	}
+
+	/// Decode as many whole ADTS frames as possible from the given buffer.
+	///
+	/// If the buffer is not fully consumed, more data is needed.
+	pub fn decode_stream<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		while let Some(header) = Header::parse(buf.as_ref())? {
+			if buf.remaining() < header.frame_len {
+				break;
+			}
+
+			let frame = buf.copy_to_bytes(header.frame_len);
+			self.init(&header)?;
+
+			let pts = Timestamp::from_scale(self.samples, self.sample_rate)?;
+			self.samples += FRAME_SAMPLES;
+
+			self.decode_frame(&header, frame, pts)?;
+		}
+
+		Ok(())
+	}
+
+	/// Decode one or more whole ADTS frames that share the given timestamp.
+	///
+	/// Each frame after the first is offset by the duration of the frames before it, which
+	/// is how MPEG-TS packs several ADTS frames into a single PES packet.
+	/// The buffer will be fully consumed, or an error will be returned.
+	pub fn decode<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T, pts: Option<Timestamp>) -> anyhow::Result<()> {
+		let mut index = 0;
+
+		while buf.has_remaining() {
+			let header = Header::parse(buf.as_ref())?.ok_or_else(|| anyhow::anyhow!("truncated ADTS header"))?;
+			anyhow::ensure!(buf.remaining() >= header.frame_len, "truncated ADTS frame");
+
+			let frame = buf.copy_to_bytes(header.frame_len);
+			self.init(&header)?;
+
+			let pts = match pts {
+				Some(pts) => pts.checked_add(Timestamp::from_scale(index * FRAME_SAMPLES, self.sample_rate)?)?,
+				None => Timestamp::from_scale(self.samples, self.sample_rate)?,
+			};
+			self.samples += FRAME_SAMPLES;
+			index += 1;
+
+			self.decode_frame(&header, frame, pts)?;
+		}
+
+		Ok(())
+	}
+
+	/// Finish the track, flushing the current group.
+	pub fn finish(&mut self) -> anyhow::Result<()> {
+		if let Some(aac) = &mut self.aac {
+			aac.finish()?;
+		}
+
+		self.catalog.finish()?;
+
+		Ok(())
+	}
+
+	/// Returns true once the first ADTS header has been decoded.
+	pub fn is_initialized(&self) -> bool {
+		self.aac.is_some()
+	}
+
+	fn init(&mut self, header: &Header) -> anyhow::Result<()> {
+		if self.aac.is_some() {
+			return Ok(());
+		}
+
+		// Build the 2-byte AudioSpecificConfig described by the ADTS header.
+		let asc = Bytes::copy_from_slice(&[
+			(header.profile << 3) | (header.freq_index >> 1),
+			((header.freq_index & 0x01) << 7) | (header.channel_config << 3),
+		]);
+		let config = AacConfig::parse(&mut asc.clone())?;
+		self.sample_rate = config.sample_rate as u64;
+		self.aac = Some(Aac::with_description(
+			self.broadcast.clone(),
+			self.catalog.clone(),
+			config,
+			Some(asc),
+		)?);
+
+		Ok(())
+	}
+
+	fn decode_frame(&mut self, header: &Header, mut frame: Bytes, pts: Timestamp) -> anyhow::Result<()> {
+		let aac = self.aac.as_mut().expect("not initialized");
+		frame.advance(header.header_len);
+		aac.decode(&mut frame, Some(pts))
+	}
+}
+
+/// The fields of an ADTS header needed to import the frame.
+struct Header {
+	profile: u8,
+	freq_index: u8,
+	channel_config: u8,
+	header_len: usize,
+	frame_len: usize,
+}
+
+impl Header {
+	/// Parse the header at the start of the buffer, or return None if more data is needed.
+	fn parse(data: &[u8]) -> anyhow::Result<Option<Self>> {
+		if data.len() < HEADER_SIZE {
+			return Ok(None);
+		}
+
+		anyhow::ensure!(data[0] == 0xFF && data[1] & 0xF0 == 0xF0, "invalid ADTS sync word");
+
+		let protection_absent = data[1] & 0x01 != 0;
+		let header_len = if protection_absent {
+			HEADER_SIZE
+		} else {
+			HEADER_SIZE_CRC
+		};
+		let frame_len = (((data[3] & 0x03) as usize) << 11) | ((data[4] as usize) << 3) | ((data[5] as usize) >> 5);
+		anyhow::ensure!(frame_len >= header_len, "invalid ADTS frame length");
+
+		Ok(Some(Self {
+			profile: (data[2] >> 6) + 1,
+			freq_index: (data[2] >> 2) & 0x0F,
+			channel_config: ((data[2] & 0x01) << 2) | (data[3] >> 6),
+			header_len,
+			frame_len,
+		}))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use bytes::BytesMut;
+
+	use super::*;
+
+	/// An ADTS frame (AAC-LC, 44.1kHz, stereo) wrapping the given payload.
+	fn frame(payload: &[u8], crc: bool) -> Vec<u8> {
+		let header_len = if crc { HEADER_SIZE_CRC } else { HEADER_SIZE };
+		let len = header_len + payload.len();
+		let mut out = vec![
+			0xFF,
+			if crc { 0xF0 } else { 0xF1 },
+			(1 << 6) | (4 << 2),
+			(2 << 6) | ((len >> 11) & 0x03) as u8,
+			(len >> 3) as u8,
+			((len & 0x07) << 5) as u8 | 0x1F,
+			0xFC,
+		];
+		if crc {
+			out.extend_from_slice(&[0xAB, 0xCD]);
+		}
+		out.extend_from_slice(payload);
+		out
+	}
+
+	fn setup() -> (Adts, moq_lite::BroadcastConsumer, crate::catalog::Producer) {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let consumer = broadcast.consume();
+		(Adts::new(broadcast, catalog.clone()), consumer, catalog)
+	}
+
+	async fn read_frames(
+		broadcast: &moq_lite::BroadcastConsumer,
+		catalog: &crate::catalog::Producer,
+	) -> Vec<(u64, Bytes)> {
+		let snapshot = catalog.snapshot();
+		let name = snapshot.audio.renditions.keys().next().expect("no audio rendition");
+		let track = broadcast.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+		let mut consumer = crate::container::Consumer::new(track, crate::container::Hang::Legacy);
+
+		let mut out = Vec::new();
+		while let Some(frame) = consumer.read().await.unwrap() {
+			out.push((frame.timestamp.as_micros() as u64, frame.payload));
+		}
+		out
+	}
+
+	#[tokio::test]
+	async fn decode_stream_across_buffers() {
+		let (mut adts, broadcast, catalog) = setup();
+
+		let mut data = frame(&[1, 2, 3], false);
+		data.extend(frame(&[4, 5], true));
+		data.extend(frame(&[6], false));
+
+		// Split the input mid-header and mid-frame.
+		let mut buf = BytesMut::new();
+		for chunk in [&data[..4], &data[4..14], &data[14..]] {
+			buf.extend_from_slice(chunk);
+			adts.decode_stream(&mut buf).unwrap();
+		}
+		assert!(buf.is_empty());
+		assert!(adts.is_initialized());
+		adts.finish().unwrap();
+
+		// The same config the fMP4 importer produces for AAC-LC.
+		let snapshot = catalog.snapshot();
+		let config = snapshot.audio.renditions.values().next().unwrap();
+		assert_eq!(config.codec, hang::catalog::AAC { profile: 2 }.into());
+		assert_eq!(config.sample_rate, 44_100);
+		assert_eq!(config.channel_count, 2);
+		assert_eq!(config.description.as_deref(), Some(&[0x12, 0x10][..]));
+
+		let frames = read_frames(&broadcast, &catalog).await;
+		assert_eq!(
+			frames,
+			vec![
+				(0, Bytes::from_static(&[1, 2, 3])),
+				(23_219, Bytes::from_static(&[4, 5])),
+				(46_439, Bytes::from_static(&[6])),
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn decode_from_finishes_catalog() {
+		let (mut adts, broadcast, catalog) = setup();
+		let mut updates = catalog.consume().unwrap();
+
+		let data = frame(&[1, 2, 3], false);
+		adts.decode_from(&mut data.as_slice()).await.unwrap();
+
+		// The track and the catalog both end with the input.
+		let frames = read_frames(&broadcast, &catalog).await;
+		assert_eq!(frames, vec![(0, Bytes::from_static(&[1, 2, 3]))]);
+		while updates.next().await.unwrap().is_some() {}
+	}
+
+	#[test]
+	fn decode_rejects_truncated_frame() {
+		let (mut adts, _broadcast, _catalog) = setup();
+
+		let data = frame(&[1, 2, 3], false);
+		assert!(adts.decode(&mut Bytes::copy_from_slice(&data[..8]), None).is_err());
+		assert!(adts.decode(&mut Bytes::from_static(&[0x00; 8]), None).is_err());
+	}
+}
@@ -6,6 +6,9 @@ use bytes::{Buf, Bytes};
 use scuffle_av1::seq::SequenceHeaderObu;
 
 /// A decoder for AV1 with inline sequence headers.
+///
+/// Each temporal unit becomes one frame. It's marked as a keyframe when it shows a key frame,
+/// either directly or by showing a previously hidden one.
 pub struct Av01 {
 	// The broadcast being produced.
 	broadcast: moq_lite::BroadcastProducer,
@@ -27,6 +30,12 @@ pub struct Av01 {
 
 	// Tracks the minimum frame duration and updates the catalog `jitter` field.
 	jitter: MinFrameDuration,
+
+	// Every frame is a key frame without the usual frame header fields.
+	reduced_still_picture_header: bool,
+
+	// A key frame was decoded but not shown; showing it later is a random access point.
+	hidden_keyframe: bool,
 }
 
 #[derive(Default)]
@@ -46,10 +55,14 @@ impl Av01 {
 			current: Default::default(),
 			zero: None,
 			jitter: MinFrameDuration::new(),
+			reduced_still_picture_header: false,
+			hidden_keyframe: false,
 		}
 	}
 
 	fn init(&mut self, seq_header: &SequenceHeaderObu) -> anyhow::Result<()> {
+		self.reduced_still_picture_header = seq_header.reduced_still_picture_header;
+
 		let config = hang::catalog::VideoConfig {
 			coded_width: Some(seq_header.max_frame_width as u32),
 			coded_height: Some(seq_header.max_frame_height as u32),
@@ -89,6 +102,7 @@ impl Av01 {
 			optimize_for_latency: None,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			lang: None,
 		};
 
 		if let Some(old) = &self.config
@@ -144,6 +158,7 @@ impl Av01 {
 			optimize_for_latency: None,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			lang: None,
 		};
 
 		let track = self.broadcast.unique_track(".av01")?;
@@ -224,6 +239,7 @@ impl Av01 {
 			optimize_for_latency: None,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			lang: None,
 		};
 
 		if let Some(old) = &self.config
@@ -314,40 +330,12 @@ impl Av01 {
 						}
 					}
 				}
-
-				self.current.contains_keyframe = true;
 			}
 			ObuType::TemporalDelimiter => {
 				self.maybe_start_frame(pts)?;
 			}
 			ObuType::FrameHeader | ObuType::Frame => {
-				let is_keyframe = if obu_data.len() > payload_offset {
-					let data = &obu_data[payload_offset..];
-					if data.is_empty() {
-						false
-					} else {
-						let first_byte = data[0];
-
-						let show_existing_frame = (first_byte >> 7) & 1;
-
-						if show_existing_frame == 1 {
-							self.current.contains_keyframe
-						} else {
-							let frame_type = (first_byte >> 5) & 0b11;
-
-							frame_type == 0
-						}
-					}
-				} else {
-					tracing::warn!(
-						"Frame OBU too short: {} bytes (payload_offset={})",
-						obu_data.len(),
-						payload_offset
-					);
-					false
-				};
-
-				if is_keyframe || self.current.contains_keyframe {
+				if self.is_keyframe(&obu_data[payload_offset..]) {
 					self.current.contains_keyframe = true;
 				}
 
@@ -371,6 +359,40 @@ impl Av01 {
 		Ok(())
 	}
 
+	// Check the start of an uncompressed frame header for a random access point.
+	fn is_keyframe(&mut self, header: &[u8]) -> bool {
+		if self.reduced_still_picture_header {
+			return true;
+		}
+
+		let Some(&first) = header.first() else {
+			tracing::warn!("frame header is empty");
+			return false;
+		};
+
+		// show_existing_frame (1) | frame_type (2) | show_frame (1)
+		if first >> 7 == 1 {
+			// Assume the frame being shown is the last hidden key frame, if any.
+			return std::mem::take(&mut self.hidden_keyframe);
+		}
+
+		const KEY_FRAME: u8 = 0;
+		let frame_type = (first >> 5) & 0b11;
+		let show_frame = (first >> 4) & 1 == 1;
+
+		if frame_type != KEY_FRAME {
+			return false;
+		}
+
+		if !show_frame {
+			self.hidden_keyframe = true;
+			return false;
+		}
+
+		self.hidden_keyframe = false;
+		true
+	}
+
 	fn maybe_start_frame(&mut self, pts: Option<hang::container::Timestamp>) -> anyhow::Result<()> {
 		if !self.current.contains_frame {
 			return Ok(());
@@ -388,6 +410,7 @@ impl Av01 {
 			timestamp: pts,
 			payload,
 			keyframe: self.current.contains_keyframe,
+			capture: None,
 		};
 
 		track.write(frame)?;
@@ -404,10 +427,20 @@ impl Av01 {
 		Ok(())
 	}
 
+	/// Decode the rest of a finite stream, emitting the final frame.
+	///
+	/// [Self::decode_stream] holds back the last frame until the next temporal unit arrives, so
+	/// call this once the input ends with whatever it left unconsumed. Like [Self::decode_stream],
+	/// the wall clock is used for the timestamp.
+	pub fn finalize<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		self.decode_frame(buf, None)
+	}
+
 	/// Finish the track, flushing the current group.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		let track = self.track.as_mut().context("not initialized")?;
 		track.finish()?;
+		self.catalog.finish()?;
 		Ok(())
 	}
 
@@ -523,3 +556,133 @@ impl<'a, T: Buf + AsRef<[u8]> + 'a> Iterator for ObuIterator<'a, T> {
 		Some(Ok(obu))
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	// Pack a string of '0' and '1' into bytes, padding the last byte with zeros.
+	fn bits(s: &str) -> Vec<u8> {
+		let bits: Vec<u8> = s
+			.bytes()
+			.filter(|b| !b.is_ascii_whitespace())
+			.map(|b| b - b'0')
+			.collect();
+		bits.chunks(8)
+			.map(|chunk| chunk.iter().enumerate().fold(0, |acc, (i, bit)| acc | bit << (7 - i)))
+			.collect()
+	}
+
+	fn obu(obu_type: u8, payload: &[u8]) -> Vec<u8> {
+		assert!(payload.len() < 128, "single byte LEB128 only");
+		let mut obu = vec![(obu_type << 3) | 0b10, payload.len() as u8];
+		obu.extend_from_slice(payload);
+		obu
+	}
+
+	// A 1280x720 main profile, level 4.0, 8-bit 4:2:0 sequence header.
+	fn sequence_header() -> Vec<u8> {
+		obu(
+			1,
+			&bits(concat!(
+				"000 0 0",                 // seq_profile, still_picture, reduced_still_picture_header
+				"0 0 00000",               // timing_info, initial_display_delay, operating_points_cnt_minus_1
+				"000000000000 01000 0",    // idc, seq_level_idx, seq_tier
+				"1010 1010",               // frame_{width,height}_bits_minus_1
+				"10011111111 01011001111", // max_frame_{width,height}_minus_1
+				"0 000 00000",             // frame_id_numbers, superblock/intra tools, inter tools, order hint
+				"0 0",                     // seq_choose_screen_content_tools, seq_force_screen_content_tools
+				"000",                     // superres, cdef, restoration
+				"0 0 0 0",                 // high_bitdepth, mono_chrome, color_description, color_range
+				"00 0",                    // chroma_sample_position, separate_uv_delta_q
+				"0 1",                     // film_grain_params_present, trailing bit
+			)),
+		)
+	}
+
+	// A frame OBU whose header starts with show_existing_frame, frame_type and show_frame.
+	fn frame(first_byte: u8) -> Vec<u8> {
+		obu(6, &[first_byte, 0xAA, 0xBB])
+	}
+
+	const TEMPORAL_DELIMITER: [u8; 2] = [0x12, 0x00];
+	const KEY: u8 = 0b0001_0000;
+	const INTER: u8 = 0b0011_0000;
+	const HIDDEN_KEY: u8 = 0b0000_0000;
+	const SHOW_EXISTING: u8 = 0b1000_0000;
+
+	#[tokio::test]
+	async fn temporal_units() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let mut av01 = Av01::new(broadcast.clone(), catalog);
+
+		let consumer = broadcast.consume();
+		let mut catalog =
+			crate::catalog::Consumer::new(consumer.subscribe_track(&hang::Catalog::default_track()).unwrap());
+
+		let units = [
+			[&TEMPORAL_DELIMITER[..], &sequence_header(), &frame(KEY)].concat(),
+			[&TEMPORAL_DELIMITER[..], &frame(INTER)].concat(),
+			// A repeated sequence header doesn't make an inter frame a keyframe.
+			[&TEMPORAL_DELIMITER[..], &sequence_header(), &frame(INTER)].concat(),
+			[&TEMPORAL_DELIMITER[..], &frame(HIDDEN_KEY), &frame(INTER)].concat(),
+			[&TEMPORAL_DELIMITER[..], &frame(SHOW_EXISTING)].concat(),
+		];
+
+		for (i, unit) in units.iter().enumerate() {
+			let pts = hang::container::Timestamp::from_micros(i as u64 * 33_333).unwrap();
+			av01.decode_frame(&mut Bytes::copy_from_slice(unit), Some(pts)).unwrap();
+		}
+
+		let catalog = catalog.next().await.unwrap().unwrap();
+		let (name, config) = catalog.video.renditions.iter().next().expect("no video rendition");
+
+		assert_eq!(config.coded_width, Some(1280));
+		assert_eq!(config.coded_height, Some(720));
+		match &config.codec {
+			hang::catalog::VideoCodec::AV1(av1) => {
+				assert_eq!(av1.profile, 0);
+				assert_eq!(av1.level, 8);
+				assert_eq!(av1.tier, 'M');
+				assert_eq!(av1.bitdepth, 8);
+				assert!(av1.chroma_subsampling_x && av1.chroma_subsampling_y);
+			}
+			codec => panic!("unexpected codec: {codec:?}"),
+		}
+
+		let mut consumer = crate::container::Consumer::new(
+			broadcast
+				.consume()
+				.subscribe_track(&moq_lite::Track::new(name.as_str()))
+				.unwrap(),
+			crate::container::Hang::Legacy,
+		)
+		.with_latency(std::time::Duration::MAX);
+		av01.finish().unwrap();
+
+		let mut frames = Vec::new();
+		while let Some(frame) = consumer.read().await.unwrap() {
+			frames.push(frame);
+		}
+
+		let keyframes: Vec<bool> = frames.iter().map(|frame| frame.keyframe).collect();
+		assert_eq!(keyframes, [true, false, false, false, true]);
+
+		// Each temporal unit is one frame, OBUs included verbatim.
+		for (frame, unit) in frames.iter().zip(&units) {
+			assert_eq!(frame.payload, unit[..]);
+		}
+	}
+
+	#[test]
+	fn reduced_still_picture() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let mut av01 = Av01::new(broadcast, catalog);
+
+		// There's no frame_type in a reduced still picture frame header; every frame is a key frame.
+		av01.reduced_still_picture_header = true;
+		assert!(av01.is_keyframe(&[INTER]));
+	}
+}
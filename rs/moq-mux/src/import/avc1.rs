@@ -88,6 +88,7 @@ impl Avc1 {
 			optimize_for_latency: None,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			lang: None,
 		};
 
 		if let Some(old) = &self.config
@@ -134,6 +135,7 @@ impl Avc1 {
 			timestamp: pts,
 			payload: data.to_vec().into(),
 			keyframe,
+			capture: None,
 		})?;
 
 		if let Some(jitter) = self.jitter.observe(pts)
@@ -182,6 +184,7 @@ impl Avc1 {
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		let track = self.track.as_mut().context("not initialized")?;
 		track.finish()?;
+		self.catalog.finish()?;
 		Ok(())
 	}
 
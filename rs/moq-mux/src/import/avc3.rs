@@ -86,6 +86,7 @@ impl Avc3 {
 			optimize_for_latency: None,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			lang: None,
 		};
 
 		if let Some(old) = &self.config
@@ -126,7 +127,8 @@ impl Avc3 {
 			self.decode_stream(&mut buffer, None)?;
 		}
 
-		Ok(())
+		self.finalize(&mut buffer)?;
+		self.finish()
 	}
 
 	/// Decode as much data as possible from the given buffer.
@@ -277,6 +279,7 @@ impl Avc3 {
 			timestamp: pts,
 			payload,
 			keyframe: self.current.contains_idr,
+			capture: None,
 		};
 
 		self.track.write(frame)?;
@@ -295,9 +298,19 @@ impl Avc3 {
 		Ok(())
 	}
 
+	/// Decode the rest of a finite stream, emitting the final frame.
+	///
+	/// [Self::decode_stream] holds back the last frame until the next start code arrives, so
+	/// call this once the input ends with whatever it left unconsumed. Like [Self::decode_stream],
+	/// the wall clock is used for the timestamp.
+	pub fn finalize<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		self.decode_frame(buf, None)
+	}
+
 	/// Finish the track, flushing the current group.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		self.track.finish()?;
+		self.catalog.finish()?;
 		Ok(())
 	}
 
@@ -1,8 +1,8 @@
 use anyhow::Context;
 use bytes::{Buf, Bytes, BytesMut};
-use hang::catalog::{AAC, AV1, AudioCodec, AudioConfig, Container, H264, H265, VP9, VideoCodec, VideoConfig};
+use hang::catalog::{AAC, AV1, AudioCodec, AudioConfig, Container, H264, H265, Rotation, VP9, VideoCodec, VideoConfig};
 use hang::container::Timestamp;
-use mp4_atom::{Any, Atom, DecodeMaybe, Encode, Mdat, Moof, Moov, Trak};
+use mp4_atom::{Any, Atom, DecodeMaybe, Encode, Mdat, Moof, Moov, Sidx, Trak};
 use std::collections::HashMap;
 use tokio::io::{AsyncRead, AsyncReadExt};
 
@@ -39,6 +39,43 @@ pub struct Fmp4 {
 	// The latest moof header
 	moof: Option<Moof>,
 	moof_size: usize,
+
+	// Whether the input is live or a recording, advertised in the catalog.
+	mode: hang::catalog::Mode,
+
+	// Whether a moof without an mdat is an error, or discarded with a warning.
+	strict: bool,
+
+	// The number of bytes consumed so far, used to resolve sidx offsets.
+	offset: u64,
+
+	// The subsegments listed by any sidx atoms.
+	index: Vec<Subsegment>,
+}
+
+/// A subsegment listed in a `sidx` atom, used to seek without scanning the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subsegment {
+	/// The track the index was written for.
+	pub track_id: u32,
+
+	/// The byte offset of the subsegment from the start of the input.
+	pub offset: u64,
+
+	/// The size of the subsegment in bytes.
+	pub size: u32,
+
+	/// The earliest presentation time of the subsegment.
+	pub start: Timestamp,
+
+	/// The duration of the subsegment.
+	pub duration: Timestamp,
+
+	/// Whether the subsegment starts with a stream access point, such as a keyframe.
+	pub starts_with_sap: bool,
+
+	/// The type of the stream access point, if any (1-6; 1 and 2 are a plain keyframe).
+	pub sap_type: u8,
 }
 
 #[derive(PartialEq, Debug)]
@@ -61,6 +98,141 @@ struct Fmp4Track {
 
 	// The minimum duration between frames for this track.
 	min_duration: Option<Timestamp>,
+
+	// The presentation offset from the edit list, in media timescale units.
+	edit_offset: i64,
+
+	// The catalog config for each sample entry, indexed by sample_description_index - 1.
+	entries: SampleEntries,
+
+	// The sample entry currently advertised in the catalog.
+	sample_description_index: u32,
+
+	// Whether the first keyframe has been checked for parameter sets.
+	checked_parameter_sets: bool,
+}
+
+enum SampleEntries {
+	Video(Vec<VideoConfig>),
+	Audio(Vec<AudioConfig>),
+}
+
+impl Fmp4Track {
+	/// Advertise the config of a different sample entry, keeping the jitter measured so far.
+	fn select_entry(&mut self, catalog: &mut hang::Catalog, index: u32) -> anyhow::Result<()> {
+		let entry = (index as usize)
+			.checked_sub(1)
+			.context("invalid sample description index")?;
+		let name = &self.track.name;
+
+		match &self.entries {
+			SampleEntries::Video(configs) => {
+				let config = configs.get(entry).context("invalid sample description index")?;
+				let rendition = catalog.video.renditions.get_mut(name).context("missing video config")?;
+				*rendition = VideoConfig {
+					jitter: rendition.jitter,
+					..config.clone()
+				};
+			}
+			SampleEntries::Audio(configs) => {
+				let config = configs.get(entry).context("invalid sample description index")?;
+				let rendition = catalog.audio.renditions.get_mut(name).context("missing audio config")?;
+				*rendition = AudioConfig {
+					jitter: rendition.jitter,
+					..config.clone()
+				};
+			}
+		}
+
+		tracing::debug!(track = %name, index, "switched sample entry");
+		self.sample_description_index = index;
+
+		Ok(())
+	}
+
+	/// Check that an H.265 keyframe carries its parameter sets where the sample entry claims.
+	///
+	/// Some muxers label `hev1` (inline) as `hvc1` (description only) or vice versa, and the
+	/// browser decoder fails without a clear error. If the parameter sets are only found in the
+	/// other place, the catalog is corrected to match.
+	fn check_parameter_sets(&mut self, catalog: &mut crate::catalog::Producer, sample: &[u8]) -> anyhow::Result<()> {
+		self.checked_parameter_sets = true;
+
+		let SampleEntries::Video(configs) = &mut self.entries else {
+			return Ok(());
+		};
+		let entry = (self.sample_description_index as usize)
+			.checked_sub(1)
+			.context("invalid sample description index")?;
+		let config = configs.get_mut(entry).context("invalid sample description index")?;
+		let VideoCodec::H265(h265) = &mut config.codec else {
+			return Ok(());
+		};
+		let Some(description) = &config.description else {
+			return Ok(());
+		};
+
+		let hvcc = mp4_atom::Hvcc::decode_body(&mut description.as_ref())?;
+		let described = H265_PARAMETER_SETS.iter().all(|kind| {
+			hvcc.arrays
+				.iter()
+				.any(|array| array.nal_unit_type & 0x3f == *kind && !array.nalus.is_empty())
+		});
+
+		let length_size = hvcc.length_size_minus_one as usize + 1;
+		let inline = h265_nal_types(sample, length_size);
+		let inline = H265_PARAMETER_SETS.iter().all(|kind| inline.contains(kind));
+
+		let in_band = match (h265.in_band, described, inline) {
+			(false, false, true) => true,
+			(true, true, false) => false,
+			(_, false, false) => {
+				tracing::warn!(track = %self.track.name, "missing H.265 parameter sets");
+				return Ok(());
+			}
+			_ => return Ok(()),
+		};
+
+		tracing::warn!(
+			track = %self.track.name,
+			in_band,
+			"H.265 parameter sets don't match the sample entry, correcting the codec"
+		);
+		h265.in_band = in_band;
+
+		let codec = config.codec.clone();
+		let mut catalog = catalog.lock();
+		let rendition = catalog
+			.video
+			.renditions
+			.get_mut(&self.track.name)
+			.context("missing video config")?;
+		rendition.codec = codec;
+
+		Ok(())
+	}
+}
+
+/// The H.265 VPS, SPS and PPS NAL unit types, all needed to configure a decoder.
+const H265_PARAMETER_SETS: [u8; 3] = [32, 33, 34];
+
+/// The NAL unit types in a sample of length-prefixed H.265 NAL units, stopping at any malformed unit.
+fn h265_nal_types(mut sample: &[u8], length_size: usize) -> Vec<u8> {
+	let mut types = Vec::new();
+
+	while sample.len() >= length_size {
+		let (length, rest) = sample.split_at(length_size);
+		let length = length.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+		let Some(nal) = rest.get(..length) else {
+			break;
+		};
+		if let Some(header) = nal.first() {
+			types.push((header >> 1) & 0x3f);
+		}
+		sample = &rest[length..];
+	}
+
+	types
 }
 
 impl Fmp4 {
@@ -74,18 +246,40 @@ impl Fmp4 {
 			moov: None,
 			moof: None,
 			moof_size: 0,
+			mode: hang::catalog::Mode::Live,
+			strict: true,
+			offset: 0,
+			index: Vec::new(),
 			broadcast,
 		}
 	}
 
-	/// Decode from an asynchronous reader.
+	/// Advertise the broadcast as live or a recording in the catalog.
+	///
+	/// Defaults to [`Mode::Live`](hang::catalog::Mode::Live); file imports should use
+	/// [`Mode::Recording`](hang::catalog::Mode::Recording) so players start from the beginning.
+	pub fn with_mode(mut self, mode: hang::catalog::Mode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	/// Reject malformed streams instead of working around them.
+	///
+	/// Defaults to true, failing on a `moof` that follows another `moof` without an `mdat` in between.
+	/// When false, the stale `moof` is discarded with a warning, losing its samples but not the broadcast.
+	pub fn with_strict(mut self, strict: bool) -> Self {
+		self.strict = strict;
+		self
+	}
+
+	/// Decode from an asynchronous reader until EOF, then [`finish`](Self::finish).
 	pub async fn decode_from<T: AsyncRead + Unpin>(&mut self, reader: &mut T) -> anyhow::Result<()> {
 		let mut buffer = BytesMut::new();
 		while reader.read_buf(&mut buffer).await? > 0 {
 			self.decode(&mut buffer)?;
 		}
 
-		Ok(())
+		self.finish()
 	}
 
 	/// Decode a buffer of bytes.
@@ -106,16 +300,22 @@ impl Fmp4 {
 					self.init(moov)?;
 				}
 				Any::Moof(moof) => {
-					anyhow::ensure!(self.moof.is_none(), "duplicate moof box");
-					self.moof.replace(moof);
+					if let Some(stale) = self.moof.replace(moof) {
+						anyhow::ensure!(!self.strict, "duplicate moof box");
+						tracing::warn!(sequence = stale.mfhd.sequence_number, "discarding moof without mdat");
+					}
 					self.moof_size = size;
 				}
 				Any::Mdat(mdat) => {
 					self.extract(mdat, raw)?;
 				}
+				Any::Sidx(sidx) => {
+					// Offsets are relative to the first byte after the sidx.
+					let end = self.offset + cursor.position();
+					self.index_sidx(sidx, end)?;
+				}
 				_ => {
-					// Skip unknown atoms (e.g., sidx, which is optional and used for segment indexing)
-					// These are safe to ignore and don't affect playback
+					// Skip unknown atoms, which are safe to ignore and don't affect playback
 				}
 			}
 
@@ -124,6 +324,73 @@ impl Fmp4 {
 
 		// Advance the buffer by the amount of data that was processed.
 		cursor.into_inner().advance(position);
+		self.offset += position as u64;
+
+		Ok(())
+	}
+
+	/// Decode only up to and including the `moov` atom, populating the catalog without extracting frames.
+	///
+	/// Useful to index a file without reading its media. The buffer is advanced right past the `moov`,
+	/// so [`decode`](Self::decode) can pick up from there. Atoms before it are skipped.
+	/// Returns true once the `moov` has been decoded, or false if more data is needed.
+	pub fn decode_header_only<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<bool> {
+		let mut cursor = std::io::Cursor::new(buf);
+		let mut position = 0;
+
+		while !self.is_initialized() {
+			let Some(atom) = mp4_atom::Any::decode_maybe(&mut cursor)? else {
+				break;
+			};
+
+			if let Any::Moov(moov) = atom {
+				self.init(moov)?;
+			}
+
+			position = cursor.position() as usize;
+		}
+
+		cursor.into_inner().advance(position);
+		self.offset += position as u64;
+
+		Ok(self.is_initialized())
+	}
+
+	/// Decode only the header from an asynchronous reader, like [`decode_header_only`](Self::decode_header_only).
+	///
+	/// The reader is left positioned right after the `moov` atom. Atoms before it, such as an `mdat`
+	/// in a file that isn't fast-start, are skipped without being buffered.
+	pub async fn decode_header_only_from<T: AsyncRead + Unpin>(&mut self, reader: &mut T) -> anyhow::Result<()> {
+		while !self.is_initialized() {
+			// Read one atom at a time so we never read past the moov.
+			let mut header = BytesMut::zeroed(8);
+			reader.read_exact(&mut header).await.context("missing moov atom")?;
+
+			let size = match u32::from_be_bytes(header[..4].try_into().unwrap()) {
+				1 => {
+					let mut large = [0u8; 8];
+					reader.read_exact(&mut large).await?;
+					header.extend_from_slice(&large);
+					u64::from_be_bytes(large)
+				}
+				0 => anyhow::bail!("missing moov atom"),
+				size => size as u64,
+			};
+			let remaining = size.checked_sub(header.len() as u64).context("invalid atom size")?;
+
+			if &header[4..8] != b"moov" {
+				self.offset += header.len() as u64;
+				self.offset += tokio::io::copy(&mut (&mut *reader).take(remaining), &mut tokio::io::sink()).await?;
+				continue;
+			}
+
+			let mut atom = header;
+			atom.resize(size.try_into().context("moov atom too large")?, 0);
+			let offset = atom.len() - remaining as usize;
+			reader.read_exact(&mut atom[offset..]).await?;
+
+			anyhow::ensure!(self.decode_header_only(&mut atom)?, "invalid moov atom");
+		}
 
 		Ok(())
 	}
@@ -132,10 +399,49 @@ impl Fmp4 {
 		self.moov.is_some()
 	}
 
+	/// The subsegments listed by any `sidx` atoms decoded so far, in file order.
+	///
+	/// Only present in indexed files, typically VOD. Each subsegment's offset points at its
+	/// first `moof`, so a reader can seek there and resume with [`decode`](Self::decode).
+	pub fn segment_index(&self) -> &[Subsegment] {
+		&self.index
+	}
+
+	fn index_sidx(&mut self, sidx: Sidx, end: u64) -> anyhow::Result<()> {
+		anyhow::ensure!(sidx.timescale > 0, "invalid sidx timescale");
+
+		let mut offset = end + sidx.first_offset;
+		let mut time = sidx.earliest_presentation_time;
+		let timescale = sidx.timescale as u64;
+
+		for reference in sidx.references {
+			let duration = reference.subsegment_duration as u64;
+
+			// Hierarchical indexes reference another sidx instead of media, which is indexed once decoded.
+			if !reference.reference_type {
+				self.index.push(Subsegment {
+					track_id: sidx.reference_id,
+					offset,
+					size: reference.reference_size,
+					start: Timestamp::from_scale(time, timescale)?,
+					duration: Timestamp::from_scale(duration, timescale)?,
+					starts_with_sap: reference.starts_with_sap,
+					sap_type: reference.sap_type,
+				});
+			}
+
+			offset += reference.reference_size as u64;
+			time += duration;
+		}
+
+		Ok(())
+	}
+
 	fn init(&mut self, moov: Moov) -> anyhow::Result<()> {
 		// Clone the catalog to avoid the borrow checker.
 		let mut catalog = self.catalog.clone();
 		let mut catalog = catalog.lock();
+		catalog.mode = self.mode;
 
 		for trak in &moov.trak {
 			let track_id = trak.tkhd.track_id;
@@ -144,21 +450,41 @@ impl Fmp4 {
 
 			let track = self.broadcast.unique_track(suffix)?;
 
-			let kind = match handler.as_ref() {
+			let lang = language(&trak.mdia.mdhd.language);
+			let sample_description_index = default_sample_description_index(&moov, track_id);
+			let entry = sample_description_index as usize - 1;
+
+			let (kind, entries) = match handler.as_ref() {
 				b"vide" => {
-					let config = self.init_video(trak, &moov)?;
-					catalog.video.renditions.insert(track.name.clone(), config);
-					TrackKind::Video
+					let mut configs = self.init_video(trak, &moov)?;
+					for config in &mut configs {
+						config.lang = lang.clone();
+					}
+
+					let config = configs.get(entry).context("invalid sample description index")?;
+					catalog.video.renditions.insert(track.name.clone(), config.clone());
+					// Identity is the default, so only advertise an actual rotation.
+					if let Some(rotation) = rotation(&trak.tkhd.matrix).filter(|r| *r != Rotation::Deg0) {
+						catalog.video.rotation = Some(rotation);
+					}
+					(TrackKind::Video, SampleEntries::Video(configs))
 				}
 				b"soun" => {
-					let config = self.init_audio(trak, &moov)?;
-					catalog.audio.renditions.insert(track.name.clone(), config);
-					TrackKind::Audio
+					let mut configs = self.init_audio(trak, &moov)?;
+					for config in &mut configs {
+						config.lang = lang.clone();
+					}
+
+					let config = configs.get(entry).context("invalid sample description index")?;
+					catalog.audio.renditions.insert(track.name.clone(), config.clone());
+					(TrackKind::Audio, SampleEntries::Audio(configs))
 				}
 				b"sbtl" => anyhow::bail!("subtitle tracks are not supported"),
 				handler => anyhow::bail!("unknown track type: {:?}", handler),
 			};
 
+			let edit_offset = edit_offset(trak, &moov)?;
+
 			self.tracks.insert(
 				track_id,
 				Fmp4Track {
@@ -168,6 +494,10 @@ impl Fmp4 {
 					jitter: None,
 					last_timestamp: None,
 					min_duration: None,
+					edit_offset,
+					entries,
+					sample_description_index,
+					checked_parameter_sets: false,
 				},
 			);
 		}
@@ -201,9 +531,15 @@ impl Fmp4 {
 					..Default::default()
 				});
 
+			// The edit list is applied to the fragment timestamps, so drop it to avoid applying it twice.
+			let trak = Trak {
+				edts: None,
+				..trak.clone()
+			};
+
 			let single_moov = Moov {
 				mvhd: moov.mvhd.clone(),
-				trak: vec![trak.clone()],
+				trak: vec![trak],
 				mvex: Some(mp4_atom::Mvex {
 					mehd: None,
 					trex: vec![trex],
@@ -220,16 +556,19 @@ impl Fmp4 {
 		}
 	}
 
-	fn init_video(&mut self, trak: &Trak, moov: &Moov) -> anyhow::Result<VideoConfig> {
+	// Build a config for each sample entry, in sample_description_index order.
+	fn init_video(&mut self, trak: &Trak, moov: &Moov) -> anyhow::Result<Vec<VideoConfig>> {
 		let container = self.container(trak, moov)?;
 		let stsd = &trak.mdia.minf.stbl.stsd;
+		anyhow::ensure!(!stsd.codecs.is_empty(), "missing codec");
 
-		let codec = match stsd.codecs.len() {
-			0 => anyhow::bail!("missing codec"),
-			1 => &stsd.codecs[0],
-			_ => anyhow::bail!("multiple codecs"),
-		};
+		stsd.codecs
+			.iter()
+			.map(|codec| self.video_config(codec, container.clone()))
+			.collect()
+	}
 
+	fn video_config(&mut self, codec: &mp4_atom::Codec, container: Container) -> anyhow::Result<VideoConfig> {
 		let config = match codec {
 			mp4_atom::Codec::Avc1(avc1) => {
 				let avcc = &avc1.avcc;
@@ -256,6 +595,7 @@ impl Fmp4 {
 					optimize_for_latency: None,
 					container,
 					jitter: None,
+					lang: None,
 				}
 			}
 			mp4_atom::Codec::Hev1(hev1) => self.init_h265(true, &hev1.hvcc, &hev1.visual, container)?,
@@ -273,6 +613,7 @@ impl Fmp4 {
 				optimize_for_latency: None,
 				container,
 				jitter: None,
+				lang: None,
 			},
 			mp4_atom::Codec::Vp09(vp09) => {
 				// https://github.com/gpac/mp4box.js/blob/325741b592d910297bf609bc7c400fc76101077b/src/box-codecs.js#L238
@@ -301,6 +642,7 @@ impl Fmp4 {
 					framerate: None,
 					container,
 					jitter: None,
+					lang: None,
 				}
 			}
 			mp4_atom::Codec::Av01(av01) => {
@@ -335,6 +677,7 @@ impl Fmp4 {
 					framerate: None,
 					container,
 					jitter: None,
+					lang: None,
 				}
 			}
 			mp4_atom::Codec::Unknown(unknown) => anyhow::bail!("unknown codec: {:?}", unknown),
@@ -376,19 +719,23 @@ impl Fmp4 {
 			optimize_for_latency: None,
 			container,
 			jitter: None,
+			lang: None,
 		})
 	}
 
-	fn init_audio(&mut self, trak: &Trak, moov: &Moov) -> anyhow::Result<AudioConfig> {
+	// Build a config for each sample entry, in sample_description_index order.
+	fn init_audio(&mut self, trak: &Trak, moov: &Moov) -> anyhow::Result<Vec<AudioConfig>> {
 		let container = self.container(trak, moov)?;
 		let stsd = &trak.mdia.minf.stbl.stsd;
+		anyhow::ensure!(!stsd.codecs.is_empty(), "missing codec");
 
-		let codec = match stsd.codecs.len() {
-			0 => anyhow::bail!("missing codec"),
-			1 => &stsd.codecs[0],
-			_ => anyhow::bail!("multiple codecs"),
-		};
+		stsd.codecs
+			.iter()
+			.map(|codec| self.audio_config(codec, container.clone()))
+			.collect()
+	}
 
+	fn audio_config(&mut self, codec: &mp4_atom::Codec, container: Container) -> anyhow::Result<AudioConfig> {
 		let config = match codec {
 			mp4_atom::Codec::Mp4a(mp4a) => {
 				let desc = &mp4a.esds.es_desc.dec_config;
@@ -415,6 +762,9 @@ impl Fmp4 {
 					description: Some(description),
 					container,
 					jitter: None,
+					target_loudness: None,
+					true_peak: None,
+					lang: None,
 				}
 			}
 			mp4_atom::Codec::Opus(opus) => {
@@ -426,6 +776,9 @@ impl Fmp4 {
 					description: None, // TODO?
 					container,
 					jitter: None,
+					target_loudness: None,
+					true_peak: None,
+					lang: None,
 				}
 			}
 			mp4_atom::Codec::Unknown(unknown) => anyhow::bail!("unknown codec: {:?}", unknown),
@@ -463,6 +816,15 @@ impl Fmp4 {
 			let default_sample_size = trex.map(|trex| trex.default_sample_size).unwrap_or_default();
 			let default_sample_flags = trex.map(|trex| trex.default_sample_flags).unwrap_or_default();
 
+			// Each fragment can reference a different sample entry, so keep the catalog in sync.
+			let sample_description_index = traf
+				.tfhd
+				.sample_description_index
+				.unwrap_or_else(|| default_sample_description_index(moov, track_id));
+			if sample_description_index != track.sample_description_index {
+				track.select_entry(&mut self.catalog.lock(), sample_description_index)?;
+			}
+
 			let tfdt = traf.tfdt.as_ref().context("missing tfdt box")?;
 			let mut dts = tfdt.base_media_decode_time;
 			let timescale = trak.mdia.mdhd.timescale as u64;
@@ -512,7 +874,8 @@ impl Fmp4 {
 						.size
 						.unwrap_or(tfhd.default_sample_size.unwrap_or(default_sample_size)) as usize;
 
-					let pts = (dts as i64 + entry.cts.unwrap_or_default() as i64) as u64;
+					// Version 1 trun boxes carry signed composition offsets; clamp at zero instead of wrapping.
+					let pts = dts.saturating_add_signed(entry.cts.unwrap_or_default() as i64 + track.edit_offset);
 					let timestamp = hang::container::Timestamp::from_scale(pts, timescale)?;
 
 					if offset + size > mdat.data.len() {
@@ -530,6 +893,10 @@ impl Fmp4 {
 
 					contains_keyframe |= keyframe;
 
+					if keyframe && track.kind == TrackKind::Video && !track.checked_parameter_sets {
+						track.check_parameter_sets(&mut self.catalog, &mdat.data[offset..offset + size])?;
+					}
+
 					if timestamp >= max_timestamp.unwrap_or(Timestamp::ZERO) {
 						max_timestamp = Some(timestamp);
 					}
@@ -583,6 +950,25 @@ impl Fmp4 {
 			// and ensuring trun.data_offset is Some(...) reserves 4 bytes per trun.
 			for traf_mut in &mut adjusted_moof.traf {
 				traf_mut.tfhd.base_data_offset = None;
+
+				// Shift the decode time by the edit list offset. A negative result can't be
+				// represented in tfdt, so the remainder moves into the composition offsets.
+				if track.edit_offset != 0
+					&& let Some(tfdt) = traf_mut.tfdt.as_mut()
+				{
+					let shifted = tfdt.base_media_decode_time as i64 + track.edit_offset;
+					tfdt.base_media_decode_time = shifted.max(0) as u64;
+
+					if shifted < 0 {
+						let remainder: i32 = shifted.try_into().context("edit list offset too large")?;
+						for trun_mut in &mut traf_mut.trun {
+							for entry in &mut trun_mut.entries {
+								entry.cts = Some(entry.cts.unwrap_or_default().saturating_add(remainder));
+							}
+						}
+					}
+				}
+
 				for trun_mut in &mut traf_mut.trun {
 					// Reserve the data_offset field; the real value is filled in below.
 					trun_mut.data_offset = Some(0);
@@ -674,7 +1060,10 @@ impl Fmp4 {
 }
 
 impl Fmp4 {
-	/// Finish all tracks, flushing current groups.
+	/// Finish all tracks and the catalog, flushing current groups.
+	///
+	/// Call this at the end of the input so consumers observe a clean end of broadcast.
+	/// The catalog is finished too, so don't call this while another importer shares it.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		for track in self.tracks.values_mut() {
 			if let Some(mut g) = track.group.take() {
@@ -682,6 +1071,9 @@ impl Fmp4 {
 			}
 			track.track.finish()?;
 		}
+
+		self.catalog.finish()?;
+
 		Ok(())
 	}
 }
@@ -703,6 +1095,116 @@ impl Drop for Fmp4 {
 	}
 }
 
+/// The sample entry used by fragments that don't specify one in `tfhd`.
+fn default_sample_description_index(moov: &Moov, track_id: u32) -> u32 {
+	moov.mvex
+		.as_ref()
+		.and_then(|mvex| mvex.trex.iter().find(|trex| trex.track_id == track_id))
+		.map(|trex| trex.default_sample_description_index)
+		// The index is 1-based; treat a zero as the first entry.
+		.filter(|&index| index > 0)
+		.unwrap_or(1)
+}
+
+/// Convert the ISO-639-2 code in `mdhd` to a BCP-47 tag.
+///
+/// BCP-47 uses the two-letter ISO-639-1 code when one exists, so common languages are mapped
+/// from either their bibliographic or terminology code. Anything else is kept as-is, and
+/// `und` (undetermined) or a malformed code is omitted.
+fn language(code: &str) -> Option<String> {
+	if code.len() != 3 || !code.bytes().all(|b| b.is_ascii_lowercase()) || code == "und" {
+		return None;
+	}
+
+	let short = match code {
+		"ara" => "ar",
+		"ben" => "bn",
+		"chi" | "zho" => "zh",
+		"cze" | "ces" => "cs",
+		"dan" => "da",
+		"dut" | "nld" => "nl",
+		"eng" => "en",
+		"fin" => "fi",
+		"fre" | "fra" => "fr",
+		"ger" | "deu" => "de",
+		"gre" | "ell" => "el",
+		"heb" => "he",
+		"hin" => "hi",
+		"hun" => "hu",
+		"ind" => "id",
+		"ita" => "it",
+		"jpn" => "ja",
+		"kor" => "ko",
+		"nor" => "no",
+		"per" | "fas" => "fa",
+		"pol" => "pl",
+		"por" => "pt",
+		"rum" | "ron" => "ro",
+		"rus" => "ru",
+		"spa" => "es",
+		"swe" => "sv",
+		"tha" => "th",
+		"tur" => "tr",
+		"ukr" => "uk",
+		"vie" => "vi",
+		code => code,
+	};
+
+	Some(short.to_string())
+}
+
+/// Extract the clockwise rotation encoded in a `tkhd` transformation matrix.
+///
+/// Returns `None` for anything other than a quarter turn, such as a flip or a scale.
+fn rotation(matrix: &mp4_atom::Matrix) -> Option<Rotation> {
+	// The 2x2 part is 16.16 fixed point; only the signs matter for a quarter turn.
+	match (
+		matrix.a.signum(),
+		matrix.b.signum(),
+		matrix.c.signum(),
+		matrix.d.signum(),
+	) {
+		(1, 0, 0, 1) => Some(Rotation::Deg0),
+		(0, 1, -1, 0) => Some(Rotation::Deg90),
+		(-1, 0, 0, -1) => Some(Rotation::Deg180),
+		(0, -1, 1, 0) => Some(Rotation::Deg270),
+		_ => None,
+	}
+}
+
+/// Compute the presentation offset implied by a track's edit list, in media timescale units.
+///
+/// Leading empty edits (media_time = -1) delay the start of the track, and the first
+/// non-empty edit skips `media_time` ticks of media, such as AAC priming samples.
+/// Any later edits are ignored.
+fn edit_offset(trak: &Trak, moov: &Moov) -> anyhow::Result<i64> {
+	let Some(elst) = trak.edts.as_ref().and_then(|edts| edts.elst.as_ref()) else {
+		return Ok(0);
+	};
+
+	let movie_timescale = moov.mvhd.timescale as u128;
+	let media_timescale = trak.mdia.mdhd.timescale as u128;
+	anyhow::ensure!(movie_timescale > 0, "invalid movie timescale");
+
+	let mut offset: i64 = 0;
+
+	for entry in &elst.entries {
+		// Version 0 boxes encode -1 as a 32-bit value.
+		if entry.media_time == u64::MAX || entry.media_time == u32::MAX as u64 {
+			// The segment duration is in the movie timescale.
+			let delay = entry.segment_duration as u128 * media_timescale / movie_timescale;
+			let delay: i64 = delay.try_into().context("empty edit too long")?;
+			offset = offset.checked_add(delay).context("edit list overflow")?;
+		} else {
+			let media_time: i64 = entry.media_time.try_into().context("edit media time too large")?;
+			offset = offset.checked_sub(media_time).context("edit list overflow")?;
+			break;
+		}
+	}
+
+	Ok(offset)
+}
+
 /// Reconstruct the AudioSpecificConfig from parsed fields.
 ///
 /// Layout (ISO 14496-3):
@@ -713,7 +1215,7 @@ impl Drop for Fmp4 {
 ///
 /// For standard sample rates this produces exactly 2 bytes (e.g. 0x12 0x10
 /// for AAC-LC / 44100 Hz / stereo).
-fn build_aac_audio_specific_config(profile: u8, sample_rate: u32, channels: u32) -> Bytes {
+pub(super) fn build_aac_audio_specific_config(profile: u8, sample_rate: u32, channels: u32) -> Bytes {
 	// audioObjectType is a 5-bit field; mask to prevent shift overflow.
 	let profile = profile & 0x1F;
 
@@ -23,6 +23,12 @@ pub enum FramedFormat {
 	Aac,
 	/// Raw Opus frames (not Ogg).
 	Opus,
+	/// MPEG transport stream, in whole 188-byte packets.
+	Mpegts,
+	/// Ogg-encapsulated Opus, in whole pages.
+	Ogg,
+	/// AAC with ADTS headers, in whole frames.
+	Adts,
 }
 
 impl FromStr for FramedFormat {
@@ -41,6 +47,9 @@ impl FromStr for FramedFormat {
 			"av01" | "av1" | "av1C" => Ok(FramedFormat::Av01),
 			"aac" => Ok(FramedFormat::Aac),
 			"opus" => Ok(FramedFormat::Opus),
+			"ts" | "mpegts" => Ok(FramedFormat::Mpegts),
+			"ogg" => Ok(FramedFormat::Ogg),
+			"adts" => Ok(FramedFormat::Adts),
 			_ => Err(Error::UnknownFormat(s.to_string())),
 		}
 	}
@@ -56,6 +65,9 @@ impl fmt::Display for FramedFormat {
 			FramedFormat::Av01 => write!(f, "av01"),
 			FramedFormat::Aac => write!(f, "aac"),
 			FramedFormat::Opus => write!(f, "opus"),
+			FramedFormat::Mpegts => write!(f, "mpegts"),
+			FramedFormat::Ogg => write!(f, "ogg"),
+			FramedFormat::Adts => write!(f, "adts"),
 		}
 	}
 }
@@ -67,6 +79,9 @@ impl From<StreamFormat> for FramedFormat {
 			StreamFormat::Fmp4 => FramedFormat::Fmp4,
 			StreamFormat::Hev1 => FramedFormat::Hev1,
 			StreamFormat::Av01 => FramedFormat::Av01,
+			StreamFormat::Mpegts => FramedFormat::Mpegts,
+			StreamFormat::Ogg => FramedFormat::Ogg,
+			StreamFormat::Adts => FramedFormat::Adts,
 		}
 	}
 }
@@ -84,6 +99,9 @@ enum FramedKind {
 	Av01(super::Av01),
 	Aac(super::Aac),
 	Opus(super::Opus),
+	Mpegts(super::Mpegts),
+	Ogg(super::Ogg),
+	Adts(super::Adts),
 }
 
 /// An importer for formats with known frame boundaries.
@@ -137,6 +155,21 @@ impl Framed {
 				let config = super::OpusConfig::parse(buf)?;
 				super::Opus::new(broadcast, catalog, config)?.into()
 			}
+			FramedFormat::Mpegts => {
+				let mut decoder = super::Mpegts::new(broadcast, catalog);
+				decoder.decode_stream(buf)?;
+				decoder.into()
+			}
+			FramedFormat::Ogg => {
+				let mut decoder = super::Ogg::new(broadcast, catalog);
+				decoder.decode_stream(buf)?;
+				decoder.into()
+			}
+			FramedFormat::Adts => {
+				let mut decoder = super::Adts::new(broadcast, catalog);
+				decoder.decode(buf, None)?;
+				decoder.into()
+			}
 		};
 
 		anyhow::ensure!(!buf.has_remaining(), "buffer was not fully consumed");
@@ -144,10 +177,22 @@ impl Framed {
 		Ok(Self { decoder })
 	}
 
+	/// Emit any frame still buffered by the decoder, without finishing the tracks.
+	///
+	/// Frames passed to [Self::decode_frame] are emitted immediately, except for MPEG-TS,
+	/// which holds the last PES packet until the next one starts.
+	pub fn finalize(&mut self) -> anyhow::Result<()> {
+		match self.decoder {
+			FramedKind::Mpegts(ref mut decoder) => decoder.finalize(&mut bytes::Bytes::new()),
+			_ => Ok(()),
+		}
+	}
+
 	/// Finish the decoder, flushing any buffered data.
 	///
 	/// This should be called when the input stream ends to ensure the last
-	/// group is properly finalized.
+	/// group is properly finalized. Every format also finishes the catalog,
+	/// signaling the end of the broadcast.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		match self.decoder {
 			FramedKind::Avc1(ref mut decoder) => decoder.finish(),
@@ -157,6 +202,9 @@ impl Framed {
 			FramedKind::Av01(ref mut decoder) => decoder.finish(),
 			FramedKind::Aac(ref mut decoder) => decoder.finish(),
 			FramedKind::Opus(ref mut decoder) => decoder.finish(),
+			FramedKind::Mpegts(ref mut decoder) => decoder.finish(),
+			FramedKind::Ogg(ref mut decoder) => decoder.finish(),
+			FramedKind::Adts(ref mut decoder) => decoder.finish(),
 		}
 	}
 
@@ -182,6 +230,9 @@ impl Framed {
 			FramedKind::Av01(ref mut decoder) => decoder.decode_frame(buf, pts)?,
 			FramedKind::Aac(ref mut decoder) => decoder.decode(buf, pts)?,
 			FramedKind::Opus(ref mut decoder) => decoder.decode(buf, pts)?,
+			FramedKind::Mpegts(ref mut decoder) => decoder.decode_stream(buf)?,
+			FramedKind::Ogg(ref mut decoder) => decoder.decode_stream(buf)?,
+			FramedKind::Adts(ref mut decoder) => decoder.decode(buf, pts)?,
 		}
 
 		anyhow::ensure!(!buf.has_remaining(), "buffer was not fully consumed");
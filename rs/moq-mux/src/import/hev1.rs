@@ -77,6 +77,7 @@ impl Hev1 {
 			optimize_for_latency: None,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			lang: None,
 		};
 
 		if let Some(old) = &self.config
@@ -294,6 +295,7 @@ impl Hev1 {
 			timestamp: pts,
 			payload,
 			keyframe: self.current.contains_idr,
+			capture: None,
 		};
 
 		track.write(frame)?;
@@ -313,10 +315,20 @@ impl Hev1 {
 		Ok(())
 	}
 
+	/// Decode the rest of a finite stream, emitting the final frame.
+	///
+	/// [Self::decode_stream] holds back the last frame until the next start code arrives, so
+	/// call this once the input ends with whatever it left unconsumed. Like [Self::decode_stream],
+	/// the wall clock is used for the timestamp.
+	pub fn finalize<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		self.decode_frame(buf, None)
+	}
+
 	/// Finish the track, flushing the current group.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		let track = self.track.as_mut().context("not initialized")?;
 		track.finish()?;
+		self.catalog.finish()?;
 		Ok(())
 	}
 
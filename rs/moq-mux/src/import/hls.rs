@@ -1,7 +1,7 @@
-//! HLS (HTTP Live Streaming) ingest built on top of fMP4.
+//! HLS (HTTP Live Streaming) ingest built on top of fMP4 and MPEG-TS.
 //!
 //! This module provides reusable logic to ingest HLS master/media playlists and
-//! feed their fMP4 segments into a `hang` broadcast. It is designed to be
+//! feed their fMP4 or MPEG-TS segments into a `hang` broadcast. It is designed to be
 //! independent of any particular HTTP client; callers provide an implementation
 //! of [`Fetcher`] to perform the actual network I/O.
 
@@ -11,7 +11,7 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Context;
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 use m3u8_rs::{
 	AlternativeMedia, AlternativeMediaType, Map, MasterPlaylist, MediaPlaylist, MediaSegment, Resolution, VariantStream,
 };
@@ -19,7 +19,7 @@ use reqwest::Client;
 use tracing::{debug, info, warn};
 use url::Url;
 
-use super::Fmp4;
+use super::{Fmp4, Mpegts};
 
 /// Configuration for the single-rendition HLS ingest loop.
 #[derive(Clone)]
@@ -63,23 +63,26 @@ struct StepOutcome {
 	pub target_duration: Option<u64>,
 }
 
-/// HLS ingest that pulls an HLS media playlist and feeds the bytes into the fMP4 ingest.
+/// HLS ingest that pulls an HLS media playlist and feeds the bytes into the fMP4 or MPEG-TS ingest.
+///
+/// Playlists with an `EXT-X-MAP` init segment are treated as fMP4; otherwise the
+/// segments are assumed to be MPEG-TS.
 ///
 /// Provides `init()` to prime the ingest with initial segments, and `service()`
 /// to run the continuous ingest loop.
 pub struct Hls {
-	/// Broadcast that all CMAF importers write into.
+	/// Broadcast that all importers write into.
 	broadcast: moq_lite::BroadcastProducer,
 
 	/// The catalog being produced.
 	catalog: crate::catalog::Producer,
 
-	/// fMP4 importers for each discovered video rendition.
+	/// Importers for each discovered video rendition.
 	/// Each importer feeds a separate MoQ track but shares the same catalog.
-	video_importers: Vec<Fmp4>,
+	video_importers: Vec<Importer>,
 
-	/// fMP4 importer for the selected audio rendition, if any.
-	audio_importer: Option<Fmp4>,
+	/// Importer for the selected audio rendition, if any.
+	audio_importer: Option<Importer>,
 
 	client: Client,
 	/// Parsed base URL for the playlist (file:// or http(s)://).
@@ -96,10 +99,48 @@ enum TrackKind {
 	Audio,
 }
 
+/// The segment container used by a media playlist.
+#[derive(Debug, Clone, Copy)]
+enum SegmentFormat {
+	Fmp4,
+	Mpegts,
+}
+
+// Boxed because they're large structs and clippy complains about the size.
+enum Importer {
+	Fmp4(Box<Fmp4>),
+	Mpegts(Box<Mpegts>),
+}
+
+impl Importer {
+	fn new(broadcast: moq_lite::BroadcastProducer, catalog: crate::catalog::Producer, format: SegmentFormat) -> Self {
+		match format {
+			SegmentFormat::Fmp4 => Self::Fmp4(Box::new(Fmp4::new(broadcast, catalog))),
+			SegmentFormat::Mpegts => Self::Mpegts(Box::new(Mpegts::new(broadcast, catalog))),
+		}
+	}
+
+	fn decode(&mut self, bytes: &mut Bytes) -> anyhow::Result<()> {
+		match self {
+			Self::Fmp4(importer) => importer.decode(bytes),
+			Self::Mpegts(importer) => importer.decode_stream(bytes),
+		}
+	}
+
+	fn is_initialized(&self) -> bool {
+		match self {
+			Self::Fmp4(importer) => importer.is_initialized(),
+			// The PAT/PMT are carried in-band at the start of every segment.
+			Self::Mpegts(_) => true,
+		}
+	}
+}
+
 struct TrackState {
 	playlist: Url,
 	next_sequence: Option<u64>,
 	init_ready: bool,
+	format: SegmentFormat,
 }
 
 impl TrackState {
@@ -108,6 +149,7 @@ impl TrackState {
 			playlist,
 			next_sequence: None,
 			init_ready: false,
+			format: SegmentFormat::Fmp4,
 		}
 	}
 }
@@ -390,13 +432,19 @@ impl Hls {
 			return Ok(());
 		}
 
-		let map = self.find_map(playlist).context("playlist missing EXT-X-MAP")?;
+		// Without an init segment, the segments are self-describing MPEG-TS.
+		let Some(map) = self.find_map(playlist) else {
+			track.format = SegmentFormat::Mpegts;
+			track.init_ready = true;
+			info!(?kind, "no EXT-X-MAP, treating HLS segments as MPEG-TS");
+			return Ok(());
+		};
 
 		let url = resolve_uri(&track.playlist, &map.uri)?;
 		let mut bytes = self.fetch_bytes(url).await?;
 		let importer = match kind {
-			TrackKind::Video(index) => self.ensure_video_importer_for(index),
-			TrackKind::Audio => self.ensure_audio_importer(),
+			TrackKind::Video(index) => self.ensure_video_importer_for(index, track.format),
+			TrackKind::Audio => self.ensure_audio_importer(track.format),
 		};
 
 		importer.decode(&mut bytes).context("init segment parse error")?;
@@ -434,8 +482,8 @@ impl Hls {
 
 		// Get importer after ensuring init segment
 		let importer = match kind {
-			TrackKind::Video(index) => self.ensure_video_importer_for(index),
-			TrackKind::Audio => self.ensure_audio_importer(),
+			TrackKind::Video(index) => self.ensure_video_importer_for(index, track.format),
+			TrackKind::Audio => self.ensure_audio_importer(track.format),
 		};
 
 		// Final check after ensuring init segment
@@ -447,6 +495,13 @@ impl Hls {
 		}
 
 		importer.decode(&mut bytes).context("failed to parse media segment")?;
+		if bytes.has_remaining() {
+			warn!(
+				?kind,
+				remaining = bytes.remaining(),
+				"media segment was not fully consumed"
+			);
+		}
 		track.next_sequence = Some(sequence + 1);
 
 		Ok(())
@@ -469,23 +524,23 @@ impl Hls {
 		}
 	}
 
-	/// Create or retrieve the fMP4 importer for a specific video rendition.
+	/// Create or retrieve the importer for a specific video rendition.
 	///
 	/// Each video variant gets its own importer so that their tracks remain
 	/// independent while still contributing to the same shared catalog.
-	fn ensure_video_importer_for(&mut self, index: usize) -> &mut Fmp4 {
+	fn ensure_video_importer_for(&mut self, index: usize, format: SegmentFormat) -> &mut Importer {
 		while self.video_importers.len() <= index {
-			let importer = Fmp4::new(self.broadcast.clone(), self.catalog.clone());
+			let importer = Importer::new(self.broadcast.clone(), self.catalog.clone(), format);
 			self.video_importers.push(importer);
 		}
 
 		self.video_importers.get_mut(index).unwrap()
 	}
 
-	/// Create or retrieve the fMP4 importer for the audio rendition.
-	fn ensure_audio_importer(&mut self) -> &mut Fmp4 {
+	/// Create or retrieve the importer for the audio rendition.
+	fn ensure_audio_importer(&mut self, format: SegmentFormat) -> &mut Importer {
 		self.audio_importer
-			.get_or_insert_with(|| Fmp4::new(self.broadcast.clone(), self.catalog.clone()))
+			.get_or_insert_with(|| Importer::new(self.broadcast.clone(), self.catalog.clone(), format))
 	}
 
 	#[cfg(test)]
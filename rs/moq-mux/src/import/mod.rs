@@ -6,9 +6,14 @@
 //! ## Choosing an entry point
 //!
 //! - If you know the codec/container in advance, use the dedicated producer
-//!   ([`Aac`], [`Avc1`], [`Avc3`], [`Av01`], [`Hev1`], [`Opus`], [`Fmp4`], [`Hls`]).
+//!   ([`Aac`], [`Adts`], [`Avc1`], [`Avc3`], [`Av01`], [`Hev1`], [`Opus`], [`Ogg`], [`Fmp4`],
+//!   [`Mpegts`], [`Hls`]).
 //! - If you only know the wrapping container, use [`Framed`] (frame boundaries known —
 //!   e.g. fMP4) or [`Stream`] (raw byte stream, no framing — e.g. piped Annex B H.264).
+//! - If you don't have any media at all, [`SyntheticSource`] generates a test pattern.
+//!
+//! When ingesting many sources at once, share a [`DecodePool`] between the jobs to bound how
+//! many decode concurrently.
 //!
 //! Codec producers publish through [`catalog::Producer`](crate::catalog::Producer), which
 //! manages the hang and MSF catalog tracks; per-track encoding goes through
@@ -16,6 +21,7 @@
 //! [`Container`](crate::container::Container) implementation.
 
 mod aac;
+mod adts;
 mod annexb;
 mod av01;
 mod avc1;
@@ -25,10 +31,17 @@ mod framed;
 mod hev1;
 mod hls;
 mod jitter;
+mod mpegts;
+mod ogg;
 mod opus;
+mod pool;
+#[cfg(feature = "audio-resample")]
+mod resample;
 mod stream;
+mod synthetic;
 
 pub use aac::*;
+pub use adts::*;
 pub use av01::*;
 pub use avc1::*;
 pub use avc3::*;
@@ -36,8 +49,12 @@ pub use fmp4::*;
 pub use framed::*;
 pub use hev1::*;
 pub use hls::*;
+pub use mpegts::*;
+pub use ogg::*;
 pub use opus::*;
+pub use pool::*;
 pub use stream::*;
+pub use synthetic::*;
 
 #[cfg(test)]
 mod test;
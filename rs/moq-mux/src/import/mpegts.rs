@@ -0,0 +1,788 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use bytes::{Buf, Bytes, BytesMut};
+use hang::container::Timestamp;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{Adts, Avc3};
+
+/// The size of a single transport stream packet.
+const PACKET_SIZE: usize = 188;
+
+/// Every packet starts with this sync byte.
+const SYNC_BYTE: u8 = 0x47;
+
+/// The PID carrying the Program Association Table.
+const PAT_PID: u16 = 0x0000;
+
+/// PES timestamps and PCR bases are 33-bit counters at 90kHz.
+const CLOCK_RATE: u64 = 90_000;
+const CLOCK_WRAP: i64 = 1 << 33;
+
+/// PMT stream types we know how to import.
+const STREAM_TYPE_AAC: u8 = 0x0F;
+const STREAM_TYPE_H264: u8 = 0x1B;
+
+/// Demuxes an MPEG transport stream (as used by most HLS segments) into MoQ tracks.
+///
+/// The PAT/PMT are parsed to discover elementary streams, PES packets are reassembled
+/// per PID, and the payloads are fed into the existing [`Avc3`] and [`Adts`] importers
+/// using the PES presentation timestamps.
+///
+/// ## Supported Codecs
+///
+/// **Video:**
+/// - H.264 (stream type `0x1B`)
+///
+/// **Audio:**
+/// - AAC in ADTS framing (stream type `0x0F`)
+///
+/// Other stream types are ignored.
+pub struct Mpegts {
+	broadcast: moq_lite::BroadcastProducer,
+	catalog: crate::catalog::Producer,
+
+	// The PID of the PMT, learned from the PAT.
+	pmt: Option<u16>,
+
+	// The PID carrying the program clock, learned from the PMT.
+	pcr: Option<u16>,
+
+	// The elementary streams, keyed by PID, learned from the PMT.
+	streams: HashMap<u16, PesStream>,
+
+	// Maps the 33-bit PES/PCR clock onto a monotonic timeline shared by all streams.
+	timeline: Timeline,
+}
+
+impl Mpegts {
+	/// Create a new MPEG-TS importer that will write to the given broadcast.
+	///
+	/// Tracks are created as elementary streams are discovered in the PMT.
+	pub fn new(broadcast: moq_lite::BroadcastProducer, catalog: crate::catalog::Producer) -> Self {
+		Self {
+			broadcast,
+			catalog,
+			pmt: None,
+			pcr: None,
+			streams: HashMap::new(),
+			timeline: Timeline::default(),
+		}
+	}
+
+	/// Decode from an asynchronous reader.
+	pub async fn decode_from<T: AsyncRead + Unpin>(&mut self, reader: &mut T) -> anyhow::Result<()> {
+		let mut buffer = BytesMut::new();
+		while reader.read_buf(&mut buffer).await? > 0 {
+			self.decode_stream(&mut buffer)?;
+		}
+
+		self.finish()
+	}
+
+	/// Decode as many whole packets as possible from the given buffer.
+	///
+	/// If the buffer is not fully consumed, more data is needed.
+	pub fn decode_stream<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		loop {
+			let data = buf.as_ref();
+
+			// Resynchronize on the next sync byte if we lost alignment.
+			let Some(start) = data.iter().position(|b| *b == SYNC_BYTE) else {
+				let len = data.len();
+				buf.advance(len);
+				return Ok(());
+			};
+
+			if start > 0 {
+				tracing::warn!(skipped = start, "lost MPEG-TS sync");
+				buf.advance(start);
+				continue;
+			}
+
+			if data.len() < PACKET_SIZE {
+				return Ok(());
+			}
+
+			let packet = Bytes::copy_from_slice(&data[..PACKET_SIZE]);
+			buf.advance(PACKET_SIZE);
+
+			self.decode_packet(packet)?;
+		}
+	}
+
+	/// Decode the rest of a finite stream and flush any buffered PES packets.
+	///
+	/// Video PES packets usually have an unbounded length and end at the next unit start,
+	/// so the last one is held back until this is called. Unlike [Self::finish], the tracks
+	/// and catalog stay open.
+	pub fn finalize<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		self.decode_stream(buf)?;
+
+		for stream in self.streams.values_mut() {
+			stream.flush(&mut self.timeline)?;
+		}
+
+		Ok(())
+	}
+
+	/// Flush any buffered PES packets and finish all tracks and the catalog.
+	pub fn finish(&mut self) -> anyhow::Result<()> {
+		for stream in self.streams.values_mut() {
+			stream.flush(&mut self.timeline)?;
+			stream.decoder.finish()?;
+		}
+
+		self.catalog.finish()?;
+
+		Ok(())
+	}
+
+	/// Returns true once the PMT has been parsed and every elementary stream is initialized.
+	pub fn is_initialized(&self) -> bool {
+		!self.streams.is_empty() && self.streams.values().all(|stream| stream.decoder.is_initialized())
+	}
+
+	fn decode_packet(&mut self, packet: Bytes) -> anyhow::Result<()> {
+		let header = PacketHeader::parse(&packet)?;
+		if header.transport_error {
+			tracing::warn!(pid = header.pid, "dropping packet with transport error");
+			return Ok(());
+		}
+
+		let mut payload = packet.slice(4..);
+
+		if header.adaptation {
+			let len = *payload.first().context("missing adaptation field length")? as usize;
+			anyhow::ensure!(payload.len() > len, "adaptation field too long");
+
+			if len > 0 {
+				let flags = payload[1];
+
+				// Only a discontinuity on the PCR PID means the time base jumps; on any other PID
+				// it just resets the continuity counter.
+				if flags & 0x80 != 0 && Some(header.pid) == self.pcr {
+					tracing::debug!(pid = header.pid, "MPEG-TS discontinuity");
+
+					// Buffered PES packets still use the old time base.
+					for stream in self.streams.values_mut() {
+						stream.flush(&mut self.timeline)?;
+					}
+					self.timeline.discontinuity();
+				}
+
+				// The PCR is a reference point for wraparound detection.
+				if flags & 0x10 != 0 {
+					anyhow::ensure!(len >= 7, "adaptation field too short for PCR");
+					let pcr = payload.slice(2..8);
+					let base = ((pcr[0] as u64) << 25)
+						| ((pcr[1] as u64) << 17)
+						| ((pcr[2] as u64) << 9)
+						| ((pcr[3] as u64) << 1)
+						| ((pcr[4] as u64) >> 7);
+					self.timeline.reference(base);
+				}
+			}
+
+			payload.advance(len + 1);
+		}
+
+		if !header.payload {
+			return Ok(());
+		}
+
+		if header.pid == PAT_PID {
+			return self.decode_pat(header, payload);
+		}
+
+		if Some(header.pid) == self.pmt {
+			return self.decode_pmt(header, payload);
+		}
+
+		if let Some(stream) = self.streams.get_mut(&header.pid) {
+			if header.unit_start {
+				stream.flush(&mut self.timeline)?;
+			} else if stream.buffer.is_empty() {
+				// We joined in the middle of a PES packet; wait for the next one.
+				return Ok(());
+			}
+
+			stream.buffer.extend_from_slice(&payload);
+
+			// Flush bounded PES packets as soon as they're complete to avoid a packet of latency.
+			if stream.is_complete() {
+				stream.flush(&mut self.timeline)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn decode_pat(&mut self, header: PacketHeader, payload: Bytes) -> anyhow::Result<()> {
+		let Some(mut section) = psi_section(header, payload, 0x00)? else {
+			return Ok(());
+		};
+
+		while section.remaining() >= 4 {
+			let program = section.get_u16();
+			let pid = section.get_u16() & 0x1FFF;
+
+			// Program 0 is the network information table.
+			if program == 0 {
+				continue;
+			}
+
+			if self.pmt.is_some_and(|pmt| pmt != pid) {
+				tracing::warn!(pid, "ignoring additional MPEG-TS program");
+				continue;
+			}
+
+			self.pmt = Some(pid);
+		}
+
+		Ok(())
+	}
+
+	fn decode_pmt(&mut self, header: PacketHeader, payload: Bytes) -> anyhow::Result<()> {
+		let Some(mut section) = psi_section(header, payload, 0x02)? else {
+			return Ok(());
+		};
+
+		anyhow::ensure!(section.remaining() >= 4, "PMT too short");
+		self.pcr = Some(section.get_u16() & 0x1FFF);
+		let info_len = (section.get_u16() & 0x0FFF) as usize;
+		anyhow::ensure!(section.remaining() >= info_len, "PMT program info too long");
+		section.advance(info_len);
+
+		while section.remaining() >= 5 {
+			let stream_type = section.get_u8();
+			let pid = section.get_u16() & 0x1FFF;
+			let info_len = (section.get_u16() & 0x0FFF) as usize;
+			anyhow::ensure!(section.remaining() >= info_len, "PMT stream info too long");
+			section.advance(info_len);
+
+			// The PMT is repeated periodically; only create each stream once.
+			if self.streams.contains_key(&pid) {
+				continue;
+			}
+
+			let decoder = match stream_type {
+				STREAM_TYPE_H264 => Decoder::Avc3(Avc3::new(self.broadcast.clone(), self.catalog.clone())),
+				STREAM_TYPE_AAC => Decoder::Aac(Adts::new(self.broadcast.clone(), self.catalog.clone())),
+				_ => {
+					tracing::debug!(pid, stream_type, "ignoring unsupported MPEG-TS stream");
+					continue;
+				}
+			};
+
+			tracing::debug!(pid, stream_type, "discovered MPEG-TS stream");
+			self.streams.insert(pid, PesStream::new(decoder));
+		}
+
+		Ok(())
+	}
+}
+
+struct PacketHeader {
+	transport_error: bool,
+	unit_start: bool,
+	pid: u16,
+	adaptation: bool,
+	payload: bool,
+}
+
+impl PacketHeader {
+	fn parse(packet: &[u8]) -> anyhow::Result<Self> {
+		anyhow::ensure!(packet.len() == PACKET_SIZE, "invalid packet size");
+		anyhow::ensure!(packet[0] == SYNC_BYTE, "missing sync byte");
+
+		Ok(Self {
+			transport_error: packet[1] & 0x80 != 0,
+			unit_start: packet[1] & 0x40 != 0,
+			pid: u16::from_be_bytes([packet[1] & 0x1F, packet[2]]),
+			adaptation: packet[3] & 0x20 != 0,
+			payload: packet[3] & 0x10 != 0,
+		})
+	}
+}
+
+/// Returns the body of a PSI section (after the common header, before the CRC).
+///
+/// Sections are assumed to fit within a single packet, which is always the case for
+/// the PAT and PMT of the single-program streams used by HLS.
+fn psi_section(header: PacketHeader, mut payload: Bytes, table_id: u8) -> anyhow::Result<Option<Bytes>> {
+	if !header.unit_start {
+		return Ok(None);
+	}
+
+	let pointer = *payload.first().context("missing pointer field")? as usize;
+	anyhow::ensure!(payload.len() > pointer + 1, "pointer field too long");
+	payload.advance(pointer + 1);
+
+	anyhow::ensure!(payload.len() >= 3, "PSI section too short");
+	if payload[0] != table_id {
+		return Ok(None);
+	}
+
+	let length = (u16::from_be_bytes([payload[1], payload[2]]) & 0x0FFF) as usize;
+	anyhow::ensure!(payload.len() >= 3 + length, "PSI section spans multiple packets");
+	anyhow::ensure!(length >= 9, "PSI section too short");
+
+	// Skip table id (1), length (2), id (2), version (1), section numbers (2); drop the CRC (4).
+	Ok(Some(payload.slice(8..3 + length - 4)))
+}
+
+/// An elementary stream carried in PES packets.
+struct PesStream {
+	decoder: Decoder,
+
+	// The PES packet being reassembled.
+	buffer: BytesMut,
+
+	// The previous and largest unwrapped PTS, and the smallest positive step between them.
+	// Used to continue one frame after the last one when the time base jumps.
+	prev: Option<i64>,
+	max: Option<i64>,
+	step: Option<i64>,
+}
+
+impl PesStream {
+	fn new(decoder: Decoder) -> Self {
+		Self {
+			decoder,
+			buffer: BytesMut::new(),
+			prev: None,
+			max: None,
+			step: None,
+		}
+	}
+
+	/// The PTS one frame after the latest one, if the frame duration is known.
+	///
+	/// The largest PTS is used so B-frames don't overlap the following frames.
+	fn next(&self) -> Option<i64> {
+		Some(self.max? + self.step?)
+	}
+
+	fn observe(&mut self, pts: i64) {
+		if let Some(prev) = self.prev {
+			let step = pts - prev;
+			if step > 0 && self.step.is_none_or(|min| step < min) {
+				self.step = Some(step);
+			}
+		}
+
+		self.prev = Some(pts);
+		self.max = Some(self.max.map_or(pts, |max| max.max(pts)));
+	}
+
+	/// Returns true if the buffered PES packet has a known length and is fully received.
+	fn is_complete(&self) -> bool {
+		if self.buffer.len() < 6 {
+			return false;
+		}
+
+		// A length of zero means unbounded (common for video); it ends at the next unit start.
+		let length = u16::from_be_bytes([self.buffer[4], self.buffer[5]]) as usize;
+		length > 0 && self.buffer.len() >= 6 + length
+	}
+
+	/// Decode the buffered PES packet, if any.
+	fn flush(&mut self, timeline: &mut Timeline) -> anyhow::Result<()> {
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+
+		let pes = self.buffer.split().freeze();
+		let (pts, mut payload) = parse_pes(pes)?;
+		let pts = match pts {
+			Some(pts) => {
+				let pts = timeline.unwrap_pts(pts, self.next());
+				self.observe(pts);
+				Some(Timeline::timestamp(pts)?)
+			}
+			None => None,
+		};
+
+		match &mut self.decoder {
+			Decoder::Avc3(decoder) => decoder.decode_frame(&mut payload, pts),
+			Decoder::Aac(decoder) => decoder.decode(&mut payload, pts),
+		}
+	}
+}
+
+/// Parses a PES packet, returning the raw 33-bit PTS (if any) and the payload.
+fn parse_pes(mut pes: Bytes) -> anyhow::Result<(Option<u64>, Bytes)> {
+	anyhow::ensure!(pes.len() >= 9, "PES packet too short");
+	anyhow::ensure!(pes[..3] == [0x00, 0x00, 0x01], "invalid PES start code");
+
+	let length = u16::from_be_bytes([pes[4], pes[5]]) as usize;
+	if length > 0 {
+		anyhow::ensure!(pes.len() >= 6 + length, "truncated PES packet");
+		pes.truncate(6 + length);
+	}
+
+	let flags = pes[7];
+	let header_len = pes[8] as usize;
+	anyhow::ensure!(pes.len() >= 9 + header_len, "PES header too long");
+
+	// The DTS (flags 0b11) follows the PTS; the importers only need the PTS.
+	let pts = match flags >> 6 {
+		0b10 | 0b11 => {
+			anyhow::ensure!(header_len >= 5, "PES header too short for PTS");
+			Some(parse_timestamp(&pes[9..14]))
+		}
+		_ => None,
+	};
+
+	Ok((pts, pes.slice(9 + header_len..)))
+}
+
+/// Decodes a 33-bit timestamp spread across 5 bytes with marker bits.
+fn parse_timestamp(b: &[u8]) -> u64 {
+	(((b[0] as u64) >> 1) & 0x07) << 30
+		| (b[1] as u64) << 22
+		| ((b[2] as u64) >> 1) << 15
+		| (b[3] as u64) << 7
+		| (b[4] as u64) >> 1
+}
+
+enum Decoder {
+	Avc3(Avc3),
+	Aac(Adts),
+}
+
+impl Decoder {
+	fn finish(&mut self) -> anyhow::Result<()> {
+		match self {
+			Self::Avc3(decoder) => decoder.finish(),
+			Self::Aac(decoder) => decoder.finish(),
+		}
+	}
+
+	fn is_initialized(&self) -> bool {
+		match self {
+			Self::Avc3(decoder) => decoder.is_initialized(),
+			Self::Aac(decoder) => decoder.is_initialized(),
+		}
+	}
+}
+
+/// Unwraps the 33-bit MPEG-TS clock into a monotonic 64-bit timeline.
+///
+/// Timestamps are shifted by whole clock periods to stay near the previous value,
+/// which undoes wraparound at 2^33 while tolerating small reordering (B-frames,
+/// audio/video interleaving). After a discontinuity the timeline is rebased on the
+/// next PES timestamp, so it continues one frame after the previous frame instead of jumping.
+#[derive(Default)]
+struct Timeline {
+	// Added to the raw clock to produce the unwrapped value.
+	offset: i64,
+
+	// The last unwrapped value, used as the reference point.
+	last: Option<i64>,
+
+	// Set when the PCR PID signals a discontinuity, until the next PES timestamp.
+	discontinuity: bool,
+}
+
+impl Timeline {
+	fn discontinuity(&mut self) {
+		self.discontinuity = true;
+	}
+
+	/// Use a PCR as the reference point for wraparound detection.
+	///
+	/// Ignored after a discontinuity until a PES timestamp rebases the timeline.
+	fn reference(&mut self, raw: u64) {
+		if !self.discontinuity {
+			self.unwrap(raw);
+		}
+	}
+
+	/// Unwrap a PES timestamp, where `next` is the value expected after a discontinuity.
+	///
+	/// Without an expected value, the timeline resumes one tick after the last value.
+	fn unwrap_pts(&mut self, raw: u64, next: Option<i64>) -> i64 {
+		if std::mem::take(&mut self.discontinuity)
+			&& let Some(last) = self.last
+		{
+			self.offset = next.unwrap_or(last + 1) - raw as i64;
+		}
+
+		self.unwrap(raw)
+	}
+
+	fn unwrap(&mut self, raw: u64) -> i64 {
+		let raw = raw as i64;
+
+		let mut value = raw + self.offset;
+		if let Some(last) = self.last {
+			while value - last > CLOCK_WRAP / 2 {
+				value -= CLOCK_WRAP;
+				self.offset -= CLOCK_WRAP;
+			}
+			while last - value > CLOCK_WRAP / 2 {
+				value += CLOCK_WRAP;
+				self.offset += CLOCK_WRAP;
+			}
+		}
+
+		self.last = Some(value);
+		value
+	}
+
+	fn timestamp(value: i64) -> anyhow::Result<Timestamp> {
+		// Clamp at zero rather than wrapping if a stream starts just before a wrap.
+		Ok(Timestamp::from_scale(value.max(0) as u64, CLOCK_RATE)?)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Build a single 188-byte packet, padding with an adaptation field.
+	fn packet(pid: u16, unit_start: bool, cc: u8, af_flags: Option<u8>, pcr: Option<u64>, payload: &[u8]) -> Vec<u8> {
+		let mut af = Vec::new();
+		if let Some(flags) = af_flags {
+			af.push(flags | if pcr.is_some() { 0x10 } else { 0 });
+			if let Some(pcr) = pcr {
+				af.extend_from_slice(&[
+					(pcr >> 25) as u8,
+					(pcr >> 17) as u8,
+					(pcr >> 9) as u8,
+					(pcr >> 1) as u8,
+					((pcr & 1) << 7) as u8 | 0x7E,
+					0,
+				]);
+			}
+		}
+
+		let available = PACKET_SIZE - 4 - payload.len();
+		let adaptation = af_flags.is_some() || available > 0;
+
+		let mut out = vec![
+			SYNC_BYTE,
+			(if unit_start { 0x40 } else { 0 }) | (pid >> 8) as u8,
+			pid as u8,
+			(if adaptation { 0x30 } else { 0x10 }) | (cc & 0x0F),
+		];
+
+		if adaptation {
+			let len = available - 1;
+			out.push(len as u8);
+			if len > 0 {
+				if af.is_empty() {
+					af.push(0);
+				}
+				af.resize(len, 0xFF);
+				out.extend_from_slice(&af);
+			}
+		}
+
+		out.extend_from_slice(payload);
+		assert_eq!(out.len(), PACKET_SIZE);
+		out
+	}
+
+	fn psi(table_id: u8, body: &[u8]) -> Vec<u8> {
+		let length = 5 + body.len() + 4;
+		let mut out = vec![0, table_id, 0xB0 | (length >> 8) as u8, length as u8, 0, 1, 0xC1, 0, 0];
+		out.extend_from_slice(body);
+		out.extend_from_slice(&[0, 0, 0, 0]); // CRC (not verified)
+		out
+	}
+
+	fn pat() -> Vec<u8> {
+		packet(PAT_PID, true, 0, None, None, &psi(0x00, &[0x00, 0x01, 0xF0, 0x00]))
+	}
+
+	fn pmt() -> Vec<u8> {
+		let body = [
+			0xE1,
+			0x00, // PCR PID 0x100
+			0xF0,
+			0x00, // no program info
+			STREAM_TYPE_AAC,
+			0xE1,
+			0x01,
+			0xF0,
+			0x00, // AAC on PID 0x101
+		];
+		packet(0x1000, true, 0, None, None, &psi(0x02, &body))
+	}
+
+	fn encode_timestamp(ts: u64) -> [u8; 5] {
+		[
+			0x21 | ((ts >> 29) & 0x0E) as u8,
+			(ts >> 22) as u8,
+			0x01 | ((ts >> 14) & 0xFE) as u8,
+			(ts >> 7) as u8,
+			0x01 | ((ts << 1) & 0xFE) as u8,
+		]
+	}
+
+	/// An ADTS frame (AAC-LC, 48kHz, stereo) with a tiny payload.
+	fn adts(payload: &[u8]) -> Vec<u8> {
+		let len = 7 + payload.len();
+		let mut out = vec![
+			0xFF,
+			0xF1,
+			(1 << 6) | (3 << 2),
+			(2 << 6) | ((len >> 11) & 0x03) as u8,
+			(len >> 3) as u8,
+			((len & 0x07) << 5) as u8 | 0x1F,
+			0xFC,
+		];
+		out.extend_from_slice(payload);
+		out
+	}
+
+	fn pes(pts: u64, payload: &[u8]) -> Vec<u8> {
+		let length = 3 + 5 + payload.len();
+		let mut out = vec![0, 0, 1, 0xC0, (length >> 8) as u8, length as u8, 0x80, 0x80, 5];
+		out.extend_from_slice(&encode_timestamp(pts));
+		out.extend_from_slice(payload);
+		out
+	}
+
+	fn setup() -> (Mpegts, moq_lite::BroadcastConsumer, crate::catalog::Producer) {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let consumer = broadcast.consume();
+		(Mpegts::new(broadcast, catalog.clone()), consumer, catalog)
+	}
+
+	async fn read_timestamps(broadcast: &moq_lite::BroadcastConsumer, catalog: &crate::catalog::Producer) -> Vec<u64> {
+		let snapshot = catalog.snapshot();
+		let name = snapshot.audio.renditions.keys().next().expect("no audio rendition");
+		let track = broadcast.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+		let mut consumer = crate::container::Consumer::new(track, crate::container::Hang::Legacy);
+
+		let mut out = Vec::new();
+		while let Some(frame) = consumer.read().await.unwrap() {
+			out.push(frame.timestamp.as_micros() as u64);
+		}
+		out
+	}
+
+	#[test]
+	fn timestamp_parse_round_trip() {
+		for ts in [0, 1, 90_000, (1 << 32) + 12345, (1 << 33) - 1] {
+			assert_eq!(parse_timestamp(&encode_timestamp(ts)), ts);
+		}
+	}
+
+	#[test]
+	fn timeline_unwraps_at_33_bits() {
+		let mut timeline = Timeline::default();
+		let near = (1 << 33) - 9000;
+		assert_eq!(timeline.unwrap(near), near as i64);
+		// The clock wraps to a small value; the timeline keeps counting up.
+		assert_eq!(timeline.unwrap(9000), (1 << 33) + 9000);
+		// Slight reordering on the other side of the wrap stays in the past.
+		assert_eq!(timeline.unwrap((1 << 33) - 4500), (1 << 33) - 4500);
+	}
+
+	#[test]
+	fn timeline_rebases_on_discontinuity() {
+		let mut timeline = Timeline::default();
+		assert_eq!(timeline.unwrap_pts(900_000, None), 900_000);
+
+		// The PCR on the new time base doesn't move the timeline.
+		timeline.discontinuity();
+		timeline.reference(5_000_000_000);
+
+		// An arbitrary jump flagged as a discontinuity continues from the expected value.
+		assert_eq!(timeline.unwrap_pts(5_000_000_000, Some(903_000)), 903_000);
+		assert_eq!(timeline.unwrap_pts(5_000_003_000, Some(0)), 906_000);
+
+		// Without an expected value, it resumes just after the last one.
+		timeline.discontinuity();
+		assert_eq!(timeline.unwrap_pts(42, None), 906_001);
+	}
+
+	#[tokio::test]
+	async fn decode_aac_with_pcr_wraparound() {
+		let (mut ts, broadcast, catalog) = setup();
+
+		let mut data = Vec::new();
+		data.extend(pat());
+		data.extend(pmt());
+
+		// The first PES is just before the wrap, the second just after it.
+		let before = (1u64 << 33) - 1920;
+		data.extend(packet(0x100, false, 0, Some(0), Some(before), &[]));
+		data.extend(packet(0x101, true, 0, None, None, &pes(before, &adts(&[1, 2, 3]))));
+		data.extend(packet(0x101, true, 1, None, None, &pes(0, &adts(&[4, 5, 6]))));
+
+		let mut buf = BytesMut::from(&data[..]);
+		ts.decode_stream(&mut buf).unwrap();
+		assert!(buf.is_empty());
+		assert!(ts.is_initialized());
+		ts.finish().unwrap();
+
+		let audio = catalog.snapshot();
+		let config = audio.audio.renditions.values().next().unwrap();
+		assert_eq!(config.sample_rate, 48000);
+		assert_eq!(config.channel_count, 2);
+
+		let timestamps = read_timestamps(&broadcast, &catalog).await;
+		let start = before * 1_000_000 / CLOCK_RATE;
+		assert_eq!(timestamps, vec![start, start + 21_333]);
+	}
+
+	#[tokio::test]
+	async fn decode_aac_across_discontinuity() {
+		let (mut ts, broadcast, catalog) = setup();
+
+		let mut data = Vec::new();
+		data.extend(pat());
+		data.extend(pmt());
+		data.extend(packet(0x101, true, 0, None, None, &pes(90_000, &adts(&[1]))));
+		data.extend(packet(0x101, true, 1, None, None, &pes(91_920, &adts(&[2]))));
+
+		// A discontinuity on a PID other than the PCR PID doesn't touch the time base.
+		data.extend(packet(0x101, true, 2, Some(0x80), None, &pes(93_840, &adts(&[3]))));
+
+		// The encoder restarts with a new time base, flagged as a discontinuity.
+		data.extend(packet(0x100, false, 0, Some(0x80), Some(7_000_000), &[]));
+		data.extend(packet(0x101, true, 3, None, None, &pes(7_000_000, &adts(&[4]))));
+		data.extend(packet(0x101, true, 4, None, None, &pes(7_001_920, &adts(&[5]))));
+
+		// Split the input mid-packet to exercise buffering.
+		let (first, second) = data.split_at(300);
+		let mut buf = BytesMut::from(first);
+		ts.decode_stream(&mut buf).unwrap();
+		assert_eq!(buf.len(), 300 - PACKET_SIZE);
+		buf.extend_from_slice(second);
+		ts.decode_stream(&mut buf).unwrap();
+		assert!(buf.is_empty());
+		ts.finish().unwrap();
+
+		// Each frame lasts 1920 ticks; the new time base continues one frame after the last.
+		let timestamps = read_timestamps(&broadcast, &catalog).await;
+		assert_eq!(timestamps, vec![1_000_000, 1_021_333, 1_042_666, 1_064_000, 1_085_333]);
+	}
+
+	#[tokio::test]
+	async fn multiple_adts_frames_per_pes() {
+		let (mut ts, broadcast, catalog) = setup();
+
+		let mut frames = adts(&[1]);
+		frames.extend(adts(&[2]));
+
+		let mut data = Vec::new();
+		data.extend(pat());
+		data.extend(pmt());
+		data.extend(packet(0x101, true, 0, None, None, &pes(0, &frames)));
+
+		let mut buf = BytesMut::from(&data[..]);
+		ts.decode_stream(&mut buf).unwrap();
+		ts.finish().unwrap();
+
+		assert_eq!(read_timestamps(&broadcast, &catalog).await, vec![0, 21_333]);
+	}
+}
@@ -0,0 +1,404 @@
+use anyhow::Context;
+use bytes::{Buf, Bytes, BytesMut};
+use hang::container::Timestamp;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{Opus, OpusConfig};
+
+/// Every page starts with this capture pattern.
+const CAPTURE: &[u8; 4] = b"OggS";
+
+/// The fixed part of the page header, before the segment table.
+const HEADER_SIZE: usize = 27;
+
+/// Header type flags.
+const FLAG_CONTINUED: u8 = 0x01;
+const FLAG_BOS: u8 = 0x02;
+
+/// Granule positions for Ogg Opus always count samples at 48kHz, regardless of the input rate.
+const GRANULE_RATE: u64 = 48_000;
+
+/// Demuxes an Ogg Opus stream (`.opus` or `.ogg` files) into a MoQ track.
+///
+/// Ogg pages are parsed and reassembled into packets, including packets continued across pages.
+/// The OpusHead header creates the track, and is advertised as the catalog description so the
+/// decoder knows the pre-skip. The OpusTags header is skipped, and every audio packet is fed into
+/// the [`Opus`] importer as its own frame.
+///
+/// Timestamps are computed from the granule position of the first audio page and the duration of
+/// each packet, minus the pre-skip, so the first audible sample is at zero. Packets that are
+/// entirely pre-skip are clamped to zero.
+///
+/// Only the first Opus logical stream is imported; any other multiplexed streams are ignored.
+pub struct Ogg {
+	broadcast: moq_lite::BroadcastProducer,
+	catalog: crate::catalog::Producer,
+
+	// The serial number of the Opus logical stream, learned from its first page.
+	serial: Option<u32>,
+
+	// A packet continued from the previous page.
+	partial: BytesMut,
+
+	// Created from the OpusHead header.
+	opus: Option<Opus>,
+
+	// Set once the OpusTags header has been skipped.
+	tags: bool,
+
+	// The number of samples to discard from the start of the stream.
+	pre_skip: u64,
+
+	// The granule position of the next packet, learned from the first audio page.
+	position: Option<u64>,
+}
+
+impl Ogg {
+	/// Create a new Ogg importer that will write to the given broadcast.
+	///
+	/// The track is created once the OpusHead header is decoded.
+	pub fn new(broadcast: moq_lite::BroadcastProducer, catalog: crate::catalog::Producer) -> Self {
+		Self {
+			broadcast,
+			catalog,
+			serial: None,
+			partial: BytesMut::new(),
+			opus: None,
+			tags: false,
+			pre_skip: 0,
+			position: None,
+		}
+	}
+
+	/// Decode from an asynchronous reader.
+	pub async fn decode_from<T: AsyncRead + Unpin>(&mut self, reader: &mut T) -> anyhow::Result<()> {
+		let mut buffer = BytesMut::new();
+		while reader.read_buf(&mut buffer).await? > 0 {
+			self.decode_stream(&mut buffer)?;
+		}
+
+		self.finish()
+	}
+
+	/// Decode as many whole pages as possible from the given buffer.
+	///
+	/// If the buffer is not fully consumed, more data is needed.
+	pub fn decode_stream<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		loop {
+			let data = buf.as_ref();
+			if data.len() < HEADER_SIZE {
+				return Ok(());
+			}
+
+			anyhow::ensure!(data.starts_with(CAPTURE), "missing Ogg capture pattern");
+
+			let segments = data[26] as usize;
+			if data.len() < HEADER_SIZE + segments {
+				return Ok(());
+			}
+
+			let lacing = &data[HEADER_SIZE..HEADER_SIZE + segments];
+			let size = HEADER_SIZE + segments + lacing.iter().map(|&len| len as usize).sum::<usize>();
+			if data.len() < size {
+				return Ok(());
+			}
+
+			let page = Bytes::copy_from_slice(&data[..size]);
+			buf.advance(size);
+
+			self.decode_page(page)?;
+		}
+	}
+
+	/// Finish the track and the catalog.
+	pub fn finish(&mut self) -> anyhow::Result<()> {
+		if let Some(opus) = self.opus.as_mut() {
+			opus.finish()?;
+		}
+
+		self.catalog.finish()?;
+
+		Ok(())
+	}
+
+	/// Returns true once the OpusHead header has been decoded.
+	pub fn is_initialized(&self) -> bool {
+		self.opus.is_some()
+	}
+
+	fn decode_page(&mut self, page: Bytes) -> anyhow::Result<()> {
+		anyhow::ensure!(page[4] == 0, "unsupported Ogg version: {}", page[4]);
+
+		let flags = page[5];
+		let granule = u64::from_le_bytes(page[6..14].try_into().unwrap());
+		let serial = u32::from_le_bytes(page[14..18].try_into().unwrap());
+		let segments = page[26] as usize;
+
+		let lacing = &page[HEADER_SIZE..HEADER_SIZE + segments];
+		let mut body = page.slice(HEADER_SIZE + segments..);
+
+		match self.serial {
+			Some(expected) if expected == serial => {}
+			Some(_) => return Ok(()),
+			None => {
+				// Adopt the first logical stream that starts with an OpusHead.
+				if flags & FLAG_BOS == 0 || !body.starts_with(b"OpusHead") {
+					tracing::debug!(serial, "ignoring non-Opus Ogg stream");
+					return Ok(());
+				}
+				self.serial = Some(serial);
+			}
+		}
+
+		if flags & FLAG_CONTINUED == 0 && !self.partial.is_empty() {
+			tracing::warn!(size = self.partial.len(), "dropping unfinished Ogg packet");
+			self.partial.clear();
+		}
+
+		let mut packets = Vec::new();
+		for &len in lacing {
+			self.partial.extend_from_slice(&body.split_to(len as usize));
+
+			// A lacing value under 255 ends the packet; otherwise it continues.
+			if len < 255 {
+				packets.push(self.partial.split().freeze());
+			}
+		}
+
+		// The OpusHead and OpusTags headers come first, followed by the audio packets.
+		let mut audio = Vec::new();
+		for packet in packets {
+			if self.opus.is_none() {
+				self.decode_head(packet)?;
+			} else if !self.tags {
+				anyhow::ensure!(packet.starts_with(b"OpusTags"), "expected OpusTags header");
+				self.tags = true;
+			} else {
+				audio.push(packet);
+			}
+		}
+
+		if audio.is_empty() {
+			return Ok(());
+		}
+
+		// The granule position counts the samples up to the end of the last packet on this page.
+		let position = match self.position {
+			Some(position) => position,
+			None => {
+				let duration: u64 = audio.iter().map(|packet| packet_samples(packet)).sum();
+				// -1 means no packet ends on this page, which can't happen here.
+				anyhow::ensure!(granule != u64::MAX, "missing granule position");
+				granule.saturating_sub(duration)
+			}
+		};
+
+		let opus = self.opus.as_mut().context("missing OpusHead")?;
+
+		let mut position = position;
+		for mut packet in audio {
+			let timestamp = Timestamp::from_scale(position.saturating_sub(self.pre_skip), GRANULE_RATE)?;
+			position += packet_samples(&packet);
+			opus.decode(&mut packet, Some(timestamp))?;
+		}
+
+		self.position = Some(position);
+
+		Ok(())
+	}
+
+	fn decode_head(&mut self, head: Bytes) -> anyhow::Result<()> {
+		// Parse OpusHead (https://datatracker.ietf.org/doc/html/rfc7845#section-5.1)
+		let config = OpusConfig::parse(&mut head.clone())?;
+		self.pre_skip = u16::from_le_bytes([head[10], head[11]]) as u64;
+
+		self.opus = Some(Opus::with_description(
+			self.broadcast.clone(),
+			self.catalog.clone(),
+			config,
+			Some(head),
+		)?);
+
+		Ok(())
+	}
+}
+
+/// The number of 48kHz samples in an Opus packet, from its TOC byte.
+///
+/// See https://datatracker.ietf.org/doc/html/rfc6716#section-3.1
+fn packet_samples(packet: &[u8]) -> u64 {
+	let Some(&toc) = packet.first() else {
+		return 0;
+	};
+
+	let config = toc >> 3;
+	let frame = match config {
+		// SILK: 10, 20, 40, 60 ms
+		0..=11 => [480, 960, 1920, 2880][config as usize % 4],
+		// Hybrid: 10, 20 ms
+		12..=15 => [480, 960][config as usize % 2],
+		// CELT: 2.5, 5, 10, 20 ms
+		_ => [120, 240, 480, 960][config as usize % 4],
+	};
+
+	let frames = match toc & 0x03 {
+		0 => 1,
+		1 | 2 => 2,
+		_ => packet.get(1).map(|count| count & 0x3F).unwrap_or(0) as u64,
+	};
+
+	frame * frames
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A 20ms CELT packet (config 31, one frame).
+	const PACKET_20MS: u8 = 31 << 3;
+
+	fn head(pre_skip: u16) -> Vec<u8> {
+		let mut out = b"OpusHead".to_vec();
+		out.push(1); // version
+		out.push(2); // channels
+		out.extend_from_slice(&pre_skip.to_le_bytes());
+		out.extend_from_slice(&44_100u32.to_le_bytes());
+		out.extend_from_slice(&[0, 0, 0]); // gain, mapping family
+		out
+	}
+
+	fn tags() -> Vec<u8> {
+		let mut out = b"OpusTags".to_vec();
+		out.extend_from_slice(&4u32.to_le_bytes());
+		out.extend_from_slice(b"test");
+		out.extend_from_slice(&0u32.to_le_bytes());
+		out
+	}
+
+	/// Build a page from the given segments of packet data.
+	///
+	/// Each entry is `(data, complete)`; an incomplete entry must be a multiple of 255 bytes.
+	fn page(flags: u8, granule: u64, serial: u32, segments: &[(&[u8], bool)]) -> Vec<u8> {
+		let mut lacing = Vec::new();
+		let mut body = Vec::new();
+		for (data, complete) in segments {
+			lacing.extend(std::iter::repeat_n(255u8, data.len() / 255));
+			if *complete {
+				lacing.push((data.len() % 255) as u8);
+			} else {
+				assert_eq!(data.len() % 255, 0);
+			}
+			body.extend_from_slice(data);
+		}
+
+		let mut out = CAPTURE.to_vec();
+		out.push(0); // version
+		out.push(flags);
+		out.extend_from_slice(&granule.to_le_bytes());
+		out.extend_from_slice(&serial.to_le_bytes());
+		out.extend_from_slice(&0u32.to_le_bytes()); // sequence
+		out.extend_from_slice(&0u32.to_le_bytes()); // CRC (not verified)
+		out.push(lacing.len() as u8);
+		out.extend_from_slice(&lacing);
+		out.extend_from_slice(&body);
+		out
+	}
+
+	fn setup() -> (Ogg, moq_lite::BroadcastConsumer, crate::catalog::Producer) {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let consumer = broadcast.consume();
+		(Ogg::new(broadcast, catalog.clone()), consumer, catalog)
+	}
+
+	async fn read_frames(
+		broadcast: &moq_lite::BroadcastConsumer,
+		catalog: &crate::catalog::Producer,
+	) -> Vec<(u64, Bytes)> {
+		let snapshot = catalog.snapshot();
+		let name = snapshot.audio.renditions.keys().next().expect("no audio rendition");
+		let track = broadcast.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+		let mut consumer = crate::container::Consumer::new(track, crate::container::Hang::Legacy);
+
+		let mut out = Vec::new();
+		while let Some(frame) = consumer.read().await.unwrap() {
+			out.push((frame.timestamp.as_micros() as u64, frame.payload));
+		}
+		out
+	}
+
+	#[test]
+	fn packet_durations() {
+		assert_eq!(packet_samples(&[PACKET_20MS]), 960);
+		assert_eq!(packet_samples(&[(16 << 3) | 1]), 240); // 2x 2.5ms CELT
+		assert_eq!(packet_samples(&[(3 << 3) | 3, 3]), 8640); // 3x 60ms SILK
+		assert_eq!(packet_samples(&[]), 0);
+	}
+
+	#[tokio::test]
+	async fn decode_with_pre_skip() {
+		let (mut ogg, broadcast, catalog) = setup();
+
+		let a = [PACKET_20MS, 1];
+		let b = [PACKET_20MS, 2];
+		let c = [PACKET_20MS, 3];
+
+		let mut data = Vec::new();
+		data.extend(page(FLAG_BOS, 0, 7, &[(&head(312), true)]));
+		data.extend(page(0, 0, 7, &[(&tags(), true)]));
+		data.extend(page(0, 1920, 7, &[(&a, true), (&b, true)]));
+		data.extend(page(0x04, 2880, 7, &[(&c, true)]));
+
+		// Split the input mid-page to exercise buffering.
+		let (first, second) = data.split_at(40);
+		let mut buf = BytesMut::from(first);
+		ogg.decode_stream(&mut buf).unwrap();
+		assert!(!ogg.is_initialized());
+		buf.extend_from_slice(second);
+		ogg.decode_stream(&mut buf).unwrap();
+		assert!(buf.is_empty());
+		assert!(ogg.is_initialized());
+		ogg.finish().unwrap();
+
+		let snapshot = catalog.snapshot();
+		let config = snapshot.audio.renditions.values().next().unwrap();
+		assert_eq!(config.sample_rate, 44_100);
+		assert_eq!(config.channel_count, 2);
+		assert_eq!(config.description.as_deref(), Some(&head(312)[..]));
+
+		// The first packet is mostly pre-skip, so the first audible sample lands at zero.
+		let frames = read_frames(&broadcast, &catalog).await;
+		let timestamps: Vec<_> = frames.iter().map(|(ts, _)| *ts).collect();
+		assert_eq!(timestamps, vec![0, 13_500, 33_500]);
+		assert_eq!(frames[1].1, Bytes::copy_from_slice(&b));
+	}
+
+	#[tokio::test]
+	async fn decode_continued_packet() {
+		let (mut ogg, broadcast, catalog) = setup();
+
+		// A packet spanning two pages, followed by a short one.
+		let mut large = vec![PACKET_20MS];
+		large.resize(600, 0xAB);
+		let short = [PACKET_20MS, 9];
+
+		let mut data = Vec::new();
+		data.extend(page(FLAG_BOS, 0, 1, &[(&head(0), true)]));
+		data.extend(page(0, 0, 1, &[(&tags(), true)]));
+		data.extend(page(0, u64::MAX, 1, &[(&large[..510], false)]));
+		data.extend(page(FLAG_CONTINUED, 1920, 1, &[(&large[510..], true), (&short, true)]));
+
+		// Pages from another logical stream are ignored.
+		data.extend(page(FLAG_BOS, 0, 2, &[(b"\x80theora", true)]));
+
+		let mut buf = BytesMut::from(&data[..]);
+		ogg.decode_stream(&mut buf).unwrap();
+		assert!(buf.is_empty());
+		ogg.finish().unwrap();
+
+		let frames = read_frames(&broadcast, &catalog).await;
+		assert_eq!(frames.len(), 2);
+		assert_eq!(frames[0], (0, Bytes::from(large)));
+		assert_eq!(frames[1], (20_000, Bytes::copy_from_slice(&short)));
+	}
+}
@@ -1,4 +1,4 @@
-use bytes::{Buf, BytesMut};
+use bytes::{Buf, Bytes, BytesMut};
 
 // Make a new audio group every 100ms.
 // NOTE: We could do this per-frame, but there's not much benefit to it.
@@ -46,7 +46,7 @@ impl OpusConfig {
 ///
 /// Initialized from an OpusHead packet. Each input buffer passed to [`decode`](Self::decode)
 /// is published as one hang frame. Group boundaries are managed automatically every ~100 ms.
-/// Ogg framing is not supported — feed raw Opus packets.
+/// Ogg framing is not supported — feed raw Opus packets, or use [`Ogg`](super::Ogg) instead.
 pub struct Opus {
 	catalog: crate::catalog::Producer,
 	track: crate::container::Producer<crate::container::Hang>,
@@ -56,9 +56,19 @@ pub struct Opus {
 
 impl Opus {
 	pub fn new(
+		broadcast: moq_lite::BroadcastProducer,
+		catalog: crate::catalog::Producer,
+		config: OpusConfig,
+	) -> anyhow::Result<Self> {
+		Self::with_description(broadcast, catalog, config, None)
+	}
+
+	/// Like [`Self::new`], but advertises the given OpusHead as the catalog description.
+	pub(crate) fn with_description(
 		mut broadcast: moq_lite::BroadcastProducer,
 		mut catalog: crate::catalog::Producer,
 		config: OpusConfig,
+		description: Option<Bytes>,
 	) -> anyhow::Result<Self> {
 		let track = broadcast.unique_track(".opus")?;
 
@@ -67,9 +77,12 @@ impl Opus {
 			sample_rate: config.sample_rate,
 			channel_count: config.channel_count,
 			bitrate: None,
-			description: None,
+			description,
 			container: hang::catalog::Container::Legacy,
 			jitter: None,
+			target_loudness: None,
+			true_peak: None,
+			lang: None,
 		};
 
 		tracing::debug!(name = ?track.name, config = ?audio_config, "starting track");
@@ -83,6 +96,21 @@ impl Opus {
 		})
 	}
 
+	/// Advertise `sample_rate` in the catalog, for consistency with resampled AAC.
+	///
+	/// Opus always decodes at 48kHz whatever the input rate, so packets pass through untouched
+	/// and only 48kHz is accepted.
+	#[cfg(feature = "audio-resample")]
+	pub fn with_sample_rate(mut self, sample_rate: u32) -> anyhow::Result<Self> {
+		anyhow::ensure!(sample_rate == 48_000, "Opus can only be resampled to 48kHz");
+
+		if let Some(audio) = self.catalog.lock().audio.renditions.get_mut(&self.track.name) {
+			audio.sample_rate = sample_rate;
+		}
+
+		Ok(self)
+	}
+
 	/// Returns a reference to the underlying track producer, e.g. for
 	/// monitoring subscriber state via `used()`/`unused()`.
 	pub fn track(&self) -> &moq_lite::TrackProducer {
@@ -92,6 +120,7 @@ impl Opus {
 	/// Finish the track, flushing the current group.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		self.track.finish()?;
+		self.catalog.finish()?;
 		Ok(())
 	}
 
@@ -112,6 +141,7 @@ impl Opus {
 			timestamp: pts,
 			payload: payload.freeze(),
 			keyframe: self.frames % GROUP_FRAMES == 0,
+			capture: None,
 		};
 		self.frames += 1;
 
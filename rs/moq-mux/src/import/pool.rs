@@ -0,0 +1,133 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Bounds how many importers decode at the same time.
+///
+/// A batch importer can ingest many files or streams at once. Each job acquires a
+/// [`DecodePermit`] before feeding its [`Framed`](super::Framed) or [`Stream`](super::Stream)
+/// decoder and holds it until it's done; once `limit` permits are out, the rest queue in
+/// FIFO order.
+///
+/// Cloning is cheap and every clone shares the same limit.
+#[derive(Clone, Debug)]
+pub struct DecodePool {
+	semaphore: Arc<Semaphore>,
+	limit: usize,
+}
+
+impl DecodePool {
+	/// Allow at most `limit` concurrent decode jobs.
+	///
+	/// # Panics
+	///
+	/// Panics if `limit` is zero, since no job could ever run.
+	pub fn new(limit: usize) -> Self {
+		assert!(limit > 0, "decode pool limit must be non-zero");
+
+		Self {
+			semaphore: Arc::new(Semaphore::new(limit)),
+			limit,
+		}
+	}
+
+	/// The maximum number of concurrent decode jobs.
+	pub fn limit(&self) -> usize {
+		self.limit
+	}
+
+	/// The number of decode jobs currently holding a permit.
+	pub fn active(&self) -> usize {
+		self.limit - self.semaphore.available_permits()
+	}
+
+	/// Wait for a free slot; the slot is released when the permit is dropped.
+	pub async fn acquire(&self) -> DecodePermit {
+		let permit = self
+			.semaphore
+			.clone()
+			.acquire_owned()
+			.await
+			.expect("decode pool semaphore is never closed");
+
+		DecodePermit { _permit: permit }
+	}
+
+	/// Acquire a slot if one is free right now.
+	pub fn try_acquire(&self) -> Option<DecodePermit> {
+		let permit = self.semaphore.clone().try_acquire_owned().ok()?;
+		Some(DecodePermit { _permit: permit })
+	}
+
+	/// Run `job` once a slot is free, holding the slot until it completes.
+	pub async fn run<F: Future>(&self, job: F) -> F::Output {
+		let _permit = self.acquire().await;
+		job.await
+	}
+}
+
+/// A slot in a [`DecodePool`], released on drop.
+#[derive(Debug)]
+pub struct DecodePermit {
+	_permit: OwnedSemaphorePermit,
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::time::Duration;
+
+	use super::*;
+
+	#[tokio::test(start_paused = true)]
+	async fn bounded_concurrency() {
+		let pool = DecodePool::new(3);
+		let active = Arc::new(AtomicUsize::new(0));
+		let peak = Arc::new(AtomicUsize::new(0));
+		let completed = Arc::new(AtomicUsize::new(0));
+
+		let mut jobs = Vec::new();
+		for i in 0..10 {
+			let pool = pool.clone();
+			let active = active.clone();
+			let peak = peak.clone();
+			let completed = completed.clone();
+
+			jobs.push(tokio::spawn(async move {
+				pool.run(async {
+					let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+					peak.fetch_max(now, Ordering::SeqCst);
+					assert!(pool.active() <= pool.limit());
+
+					tokio::time::sleep(Duration::from_millis(10 + i)).await;
+
+					active.fetch_sub(1, Ordering::SeqCst);
+					completed.fetch_add(1, Ordering::SeqCst);
+				})
+				.await
+			}));
+		}
+
+		for job in jobs {
+			job.await.unwrap();
+		}
+
+		assert_eq!(completed.load(Ordering::SeqCst), 10);
+		assert_eq!(peak.load(Ordering::SeqCst), 3);
+		assert_eq!(pool.active(), 0);
+	}
+
+	#[tokio::test]
+	async fn try_acquire() {
+		let pool = DecodePool::new(1);
+
+		let permit = pool.try_acquire().expect("slot is free");
+		assert_eq!(pool.active(), 1);
+		assert!(pool.try_acquire().is_none());
+
+		drop(permit);
+		assert_eq!(pool.active(), 0);
+		assert!(pool.try_acquire().is_some());
+	}
+}
@@ -0,0 +1,274 @@
+use anyhow::Context;
+use bytes::{BufMut, Bytes, BytesMut};
+use rubato::Resampler as _;
+use symphonia_core::{
+	audio::{AudioBuffer, Signal},
+	codecs::{CODEC_TYPE_AAC, CodecParameters, Decoder, DecoderOptions},
+	formats::Packet,
+};
+
+/// The number of samples per channel in each output frame.
+const FRAME_SAMPLES: usize = 1024;
+
+/// The number of input samples fed to the resampler at a time, matching an AAC frame.
+const CHUNK_SAMPLES: usize = 1024;
+
+/// Decodes AAC and resamples it to a fixed rate, re-framed as interleaved 16-bit PCM.
+///
+/// AAC can't be resampled without re-encoding, and this crate has no AAC encoder,
+/// so the output is published as `pcm-s16` which WebCodecs decodes directly.
+/// Output timestamps count samples at the target rate from the first input timestamp,
+/// so each frame lasts exactly [FRAME_SAMPLES] samples.
+pub(crate) struct AacResampler {
+	decoder: symphonia_codec_aac::AacDecoder,
+	resampler: rubato::FftFixedIn<f32>,
+	decoded: Option<AudioBuffer<f32>>,
+
+	channels: usize,
+	sample_rate: u32,
+	source_rate: u32,
+
+	// Planar samples waiting to be resampled, and resampled samples waiting to be framed.
+	input: Vec<Vec<f32>>,
+	output: Vec<Vec<f32>>,
+
+	// Resampled samples still to discard, so the output isn't shifted by the filter delay.
+	delay: usize,
+
+	start: Option<hang::container::Timestamp>,
+	consumed: u64,
+	emitted: u64,
+}
+
+impl AacResampler {
+	pub fn new(config: &super::AacConfig, description: Option<Bytes>, sample_rate: u32) -> anyhow::Result<Self> {
+		let description = description.unwrap_or_else(|| {
+			super::fmp4::build_aac_audio_specific_config(config.profile, config.sample_rate, config.channel_count)
+		});
+
+		let mut params = CodecParameters::new();
+		params
+			.for_codec(CODEC_TYPE_AAC)
+			.with_extra_data(description.to_vec().into_boxed_slice());
+
+		let decoder = <symphonia_codec_aac::AacDecoder as Decoder>::try_new(&params, &DecoderOptions::default())
+			.context("unsupported AAC configuration")?;
+
+		let channels = config.channel_count as usize;
+		let resampler = rubato::FftFixedIn::new(
+			config.sample_rate as usize,
+			sample_rate as usize,
+			CHUNK_SAMPLES,
+			1,
+			channels,
+		)
+		.context("unsupported resample rate")?;
+
+		Ok(Self {
+			delay: resampler.output_delay(),
+			decoder,
+			resampler,
+			decoded: None,
+			channels,
+			sample_rate,
+			source_rate: config.sample_rate,
+			input: vec![Vec::new(); channels],
+			output: vec![Vec::new(); channels],
+			start: None,
+			consumed: 0,
+			emitted: 0,
+		})
+	}
+
+	/// Decode an AAC frame, returning any complete PCM frames.
+	pub fn decode(
+		&mut self,
+		payload: &[u8],
+		pts: hang::container::Timestamp,
+	) -> anyhow::Result<Vec<(hang::container::Timestamp, Bytes)>> {
+		self.start.get_or_insert(pts);
+
+		let packet = Packet::new_from_slice(0, 0, 0, payload);
+		let decoded = self.decoder.decode(&packet).context("failed to decode AAC")?;
+
+		let decoded_buf = self
+			.decoded
+			.get_or_insert_with(|| AudioBuffer::new(decoded.capacity() as u64, *decoded.spec()));
+		decoded.convert(decoded_buf);
+
+		anyhow::ensure!(
+			decoded_buf.spec().channels.count() == self.channels,
+			"channel count changed"
+		);
+		for (channel, input) in self.input.iter_mut().enumerate() {
+			input.extend_from_slice(decoded_buf.chan(channel));
+		}
+		self.consumed += decoded_buf.frames() as u64;
+
+		while self.input[0].len() >= self.resampler.input_frames_next() {
+			let needed = self.resampler.input_frames_next();
+			let resampled = self.resampler.process(&self.input, None)?;
+			for input in &mut self.input {
+				input.drain(..needed);
+			}
+			self.push(resampled);
+		}
+
+		self.frames(false)
+	}
+
+	/// Flush the samples still buffered in the resampler, returning the final PCM frames.
+	pub fn finish(&mut self) -> anyhow::Result<Vec<(hang::container::Timestamp, Bytes)>> {
+		if self.start.is_none() {
+			return Ok(Vec::new());
+		}
+
+		let input = std::mem::replace(&mut self.input, vec![Vec::new(); self.channels]);
+		if !input[0].is_empty() {
+			let resampled = self.resampler.process_partial(Some(&input), None)?;
+			self.push(resampled);
+		}
+
+		// Push the filter delay out with silence.
+		while self.emitted + (self.output[0].len() as u64) < self.expected() {
+			let resampled = self.resampler.process_partial::<Vec<f32>>(None, None)?;
+			self.push(resampled);
+		}
+
+		self.frames(true)
+	}
+
+	/// The number of output samples matching the input consumed so far.
+	fn expected(&self) -> u64 {
+		self.consumed * self.sample_rate as u64 / self.source_rate as u64
+	}
+
+	fn push(&mut self, resampled: Vec<Vec<f32>>) {
+		let skip = self.delay.min(resampled[0].len());
+		self.delay -= skip;
+
+		for (output, resampled) in self.output.iter_mut().zip(resampled) {
+			output.extend_from_slice(&resampled[skip..]);
+		}
+	}
+
+	fn frames(&mut self, finish: bool) -> anyhow::Result<Vec<(hang::container::Timestamp, Bytes)>> {
+		let mut frames = Vec::new();
+
+		if finish {
+			// Trim the silence used to flush, so the output lasts as long as the input.
+			let remaining = self.expected().saturating_sub(self.emitted) as usize;
+			for output in &mut self.output {
+				output.truncate(remaining);
+			}
+		}
+
+		while self.output[0].len() >= FRAME_SAMPLES || (finish && !self.output[0].is_empty()) {
+			let samples = self.output[0].len().min(FRAME_SAMPLES);
+
+			let mut payload = BytesMut::with_capacity(samples * self.channels * 2);
+			for i in 0..samples {
+				for output in &self.output {
+					payload.put_i16_le((output[i].clamp(-1.0, 1.0) * i16::MAX as f32) as i16);
+				}
+			}
+
+			for output in &mut self.output {
+				output.drain(..samples);
+			}
+
+			let offset = hang::container::Timestamp::from_scale(self.emitted, self.sample_rate as u64)?;
+			let timestamp = self.start.context("no input")?.checked_add(offset)?;
+			self.emitted += samples as u64;
+
+			frames.push((timestamp, payload.freeze()));
+		}
+
+		Ok(frames)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use bytes::Bytes;
+
+	use crate::import::{Aac, AacConfig};
+
+	/// A silent AAC-LC frame for a stereo channel pair, with no spectral data.
+	fn silent_frame() -> Bytes {
+		let mut bits = Vec::new();
+		let mut push = |value: u32, len: usize| bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+
+		// ID_CPE, element_instance_tag, common_window
+		push(0b001, 3);
+		push(0, 4);
+		push(0, 1);
+
+		for _ in 0..2 {
+			// global_gain, then ics_info with max_sfb = 0
+			push(100, 8);
+			push(0, 11);
+			// pulse, tns and gain control data absent
+			push(0, 3);
+		}
+
+		// ID_END
+		push(0b111, 3);
+
+		let bytes: Vec<u8> = bits
+			.chunks(8)
+			.map(|byte| {
+				byte.iter()
+					.enumerate()
+					.fold(0, |acc, (i, &bit)| acc | (bit as u8) << (7 - i))
+			})
+			.collect();
+		bytes.into()
+	}
+
+	#[tokio::test]
+	async fn resample_44100_to_48000() {
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let consumer = broadcast.consume();
+
+		let config = AacConfig {
+			profile: 2,
+			sample_rate: 44_100,
+			channel_count: 2,
+		};
+		let mut aac = Aac::new(broadcast.clone(), catalog.clone(), config)
+			.unwrap()
+			.with_sample_rate(48_000)
+			.unwrap();
+
+		// About a second of audio, 1024 samples per frame at 44.1kHz.
+		const FRAMES: u64 = 43;
+		for i in 0..FRAMES {
+			let pts = hang::container::Timestamp::from_scale(i * 1024, 44_100).unwrap();
+			aac.decode(&mut silent_frame(), Some(pts)).unwrap();
+		}
+		aac.finish().unwrap();
+
+		let snapshot = catalog.snapshot();
+		let (name, audio) = snapshot.audio.renditions.iter().next().unwrap();
+		assert_eq!(audio.codec, hang::catalog::AudioCodec::PcmS16);
+		assert_eq!(audio.sample_rate, 48_000);
+		assert_eq!(audio.channel_count, 2);
+		assert_eq!(audio.description, None);
+
+		let track = consumer.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+		let mut track = crate::container::Consumer::new(track, crate::container::Hang::Legacy);
+
+		let mut samples = 0;
+		while let Some(frame) = track.read().await.unwrap() {
+			// Each frame starts where the previous one ended, counted at 48kHz.
+			let expected = hang::container::Timestamp::from_scale(samples, 48_000).unwrap();
+			assert_eq!(frame.timestamp, expected);
+			samples += frame.payload.len() as u64 / 4;
+		}
+
+		// The output lasts as long as the input.
+		assert_eq!(samples, FRAMES * 1024 * 48_000 / 44_100);
+	}
+}
@@ -15,6 +15,12 @@ pub enum StreamFormat {
 	Hev1,
 	/// AV1 with inline sequence headers
 	Av01,
+	/// MPEG transport stream (H.264 and AAC).
+	Mpegts,
+	/// Ogg-encapsulated Opus.
+	Ogg,
+	/// AAC with ADTS headers.
+	Adts,
 }
 
 impl FromStr for StreamFormat {
@@ -30,11 +36,61 @@ impl FromStr for StreamFormat {
 			"hev1" => Ok(StreamFormat::Hev1),
 			"fmp4" | "cmaf" => Ok(StreamFormat::Fmp4),
 			"av01" | "av1" | "av1C" => Ok(StreamFormat::Av01),
+			"ts" | "mpegts" => Ok(StreamFormat::Mpegts),
+			"ogg" => Ok(StreamFormat::Ogg),
+			"adts" => Ok(StreamFormat::Adts),
 			_ => Err(Error::UnknownFormat(s.to_string())),
 		}
 	}
 }
 
+impl StreamFormat {
+	/// Guess the format from the first bytes of a stream.
+	///
+	/// Only a short prefix is needed: a box header for fMP4, a page or packet header for Ogg,
+	/// ADTS and MPEG-TS, or the first NAL unit header for Annex B H.264/H.265.
+	/// MPEG-TS needs a second sync byte, 188 bytes in, to tell it apart from noise.
+	/// Returns `None` if the prefix is too short or doesn't match a supported format.
+	/// AV1 has no magic bytes, so it's never detected.
+	pub fn probe(buf: &[u8]) -> Option<Self> {
+		if buf.len() >= 8 && matches!(&buf[4..8], b"ftyp" | b"styp") {
+			return Some(Self::Fmp4);
+		}
+
+		if buf.starts_with(b"OggS") {
+			return Some(Self::Ogg);
+		}
+
+		// A sync byte every 188 bytes.
+		if buf.len() > 188 && buf[0] == 0x47 && buf[188] == 0x47 {
+			return Some(Self::Mpegts);
+		}
+
+		// The 12-bit syncword, followed by the MPEG version and a layer that's always 0.
+		if buf.len() >= 2 && buf[0] == 0xFF && buf[1] & 0xF6 == 0xF0 {
+			return Some(Self::Adts);
+		}
+
+		let start = super::annexb::after_start_code(buf).ok()??;
+		let header = buf.get(start..start + 2)?;
+
+		// H.265 streams start with a VPS, SPS, PPS, AUD or prefix SEI, in layer 0.
+		// Checked first because the H.265 VPS header is a valid H.264 SEI header.
+		let h265 = (header[0] >> 1) & 0x3F;
+		if header[0] & 0x81 == 0 && header[1] & 0xF8 == 0 && header[1] & 0x07 != 0 && matches!(h265, 32..=35 | 39) {
+			return Some(Self::Hev1);
+		}
+
+		// H.264 streams start with an IDR slice, SEI, SPS, PPS or AUD.
+		let h264 = header[0] & 0x1F;
+		if header[0] & 0x80 == 0 && matches!(h264, 5..=9) {
+			return Some(Self::Avc3);
+		}
+
+		None
+	}
+}
+
 impl fmt::Display for StreamFormat {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match *self {
@@ -42,6 +98,9 @@ impl fmt::Display for StreamFormat {
 			StreamFormat::Fmp4 => write!(f, "fmp4"),
 			StreamFormat::Hev1 => write!(f, "hev1"),
 			StreamFormat::Av01 => write!(f, "av01"),
+			StreamFormat::Mpegts => write!(f, "mpegts"),
+			StreamFormat::Ogg => write!(f, "ogg"),
+			StreamFormat::Adts => write!(f, "adts"),
 		}
 	}
 }
@@ -55,11 +114,14 @@ enum StreamKind {
 	/// aka H265 with inline SPS/PPS
 	Hev1(super::Hev1),
 	Av01(super::Av01),
+	Mpegts(super::Mpegts),
+	Ogg(super::Ogg),
+	Adts(super::Adts),
 }
 
 /// An importer for formats that support stream decoding (unknown frame boundaries).
 ///
-/// This includes formats like H.264 (AVC3), H.265 (HEV1), and fMP4/CMAF.
+/// This includes formats like H.264 (AVC3), H.265 (HEV1), fMP4/CMAF, MPEG-TS, Ogg Opus, and ADTS AAC.
 /// Use this when the caller does not know the frame boundaries.
 pub struct Stream {
 	decoder: StreamKind,
@@ -77,6 +139,9 @@ impl Stream {
 			StreamFormat::Fmp4 => Box::new(super::Fmp4::new(broadcast, catalog)).into(),
 			StreamFormat::Hev1 => super::Hev1::new(broadcast, catalog).into(),
 			StreamFormat::Av01 => super::Av01::new(broadcast, catalog).into(),
+			StreamFormat::Mpegts => super::Mpegts::new(broadcast, catalog).into(),
+			StreamFormat::Ogg => super::Ogg::new(broadcast, catalog).into(),
+			StreamFormat::Adts => super::Adts::new(broadcast, catalog).into(),
 		};
 
 		Self { decoder }
@@ -84,7 +149,7 @@ impl Stream {
 
 	/// Initialize the decoder with the given buffer and populate the broadcast.
 	///
-	/// This is not required for self-describing formats like fMP4 or AVC3.
+	/// This is not required for self-describing formats like fMP4, AVC3, MPEG-TS, Ogg, or ADTS.
 	///
 	/// The buffer will be fully consumed, or an error will be returned.
 	pub fn initialize<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
@@ -93,6 +158,9 @@ impl Stream {
 			StreamKind::Fmp4(ref mut decoder) => decoder.decode(buf)?,
 			StreamKind::Hev1(ref mut decoder) => decoder.initialize(buf)?,
 			StreamKind::Av01(ref mut decoder) => decoder.initialize(buf)?,
+			StreamKind::Mpegts(ref mut decoder) => decoder.decode_stream(buf)?,
+			StreamKind::Ogg(ref mut decoder) => decoder.decode_stream(buf)?,
+			StreamKind::Adts(ref mut decoder) => decoder.decode_stream(buf)?,
 		}
 
 		anyhow::ensure!(!buf.has_remaining(), "buffer was not fully consumed");
@@ -115,19 +183,43 @@ impl Stream {
 			StreamKind::Fmp4(ref mut decoder) => decoder.decode(buf),
 			StreamKind::Hev1(ref mut decoder) => decoder.decode_stream(buf, None),
 			StreamKind::Av01(ref mut decoder) => decoder.decode_stream(buf, None),
+			StreamKind::Mpegts(ref mut decoder) => decoder.decode_stream(buf),
+			StreamKind::Ogg(ref mut decoder) => decoder.decode_stream(buf),
+			StreamKind::Adts(ref mut decoder) => decoder.decode_stream(buf),
+		}
+	}
+
+	/// Decode the rest of a finite stream, emitting any frame held back by [Self::decode_stream].
+	///
+	/// Formats that find frame boundaries by the start of the next frame (AVC3, HEV1, AV1 and
+	/// MPEG-TS) can't emit the last frame until the input ends. When reading a file, call this
+	/// after the last [Self::decode_stream] with whatever it left unconsumed, then [Self::finish].
+	pub fn finalize<T: Buf + AsRef<[u8]>>(&mut self, buf: &mut T) -> anyhow::Result<()> {
+		match self.decoder {
+			StreamKind::Avc3(ref mut decoder) => decoder.finalize(buf),
+			StreamKind::Fmp4(ref mut decoder) => decoder.decode(buf),
+			StreamKind::Hev1(ref mut decoder) => decoder.finalize(buf),
+			StreamKind::Av01(ref mut decoder) => decoder.finalize(buf),
+			StreamKind::Mpegts(ref mut decoder) => decoder.finalize(buf),
+			StreamKind::Ogg(ref mut decoder) => decoder.decode_stream(buf),
+			StreamKind::Adts(ref mut decoder) => decoder.decode_stream(buf),
 		}
 	}
 
 	/// Finish the decoder, flushing any buffered data.
 	///
 	/// This should be called when the input stream ends to ensure the last
-	/// group is properly finalized.
+	/// group is properly finalized. Every format also finishes the catalog,
+	/// signaling the end of the broadcast.
 	pub fn finish(&mut self) -> anyhow::Result<()> {
 		match self.decoder {
 			StreamKind::Avc3(ref mut decoder) => decoder.finish(),
 			StreamKind::Fmp4(ref mut decoder) => decoder.finish(),
 			StreamKind::Hev1(ref mut decoder) => decoder.finish(),
 			StreamKind::Av01(ref mut decoder) => decoder.finish(),
+			StreamKind::Mpegts(ref mut decoder) => decoder.finish(),
+			StreamKind::Ogg(ref mut decoder) => decoder.finish(),
+			StreamKind::Adts(ref mut decoder) => decoder.finish(),
 		}
 	}
 
@@ -138,6 +230,91 @@ impl Stream {
 			StreamKind::Fmp4(ref decoder) => decoder.is_initialized(),
 			StreamKind::Hev1(ref decoder) => decoder.is_initialized(),
 			StreamKind::Av01(ref decoder) => decoder.is_initialized(),
+			StreamKind::Mpegts(ref decoder) => decoder.is_initialized(),
+			StreamKind::Ogg(ref decoder) => decoder.is_initialized(),
+			StreamKind::Adts(ref decoder) => decoder.is_initialized(),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn probe_fmp4() {
+		let buf = [0, 0, 0, 0x18, b'f', b't', b'y', b'p', b'i', b's', b'o', b'6'];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Fmp4));
+
+		let buf = [0, 0, 0, 0x18, b's', b't', b'y', b'p'];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Fmp4));
+	}
+
+	#[test]
+	fn probe_ogg() {
+		assert_eq!(StreamFormat::probe(b"OggS\0\x02"), Some(StreamFormat::Ogg));
+	}
+
+	#[test]
+	fn probe_mpegts() {
+		let mut buf = vec![0u8; 189];
+		buf[0] = 0x47;
+		buf[188] = 0x47;
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Mpegts));
+
+		// A single packet isn't enough to be sure.
+		assert_eq!(StreamFormat::probe(&buf[..188]), None);
+	}
+
+	#[test]
+	fn probe_adts() {
+		// MPEG-4, no CRC.
+		let buf = [0xFF, 0xF1, 0x50, 0x80, 0x02, 0x1F, 0xFC];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Adts));
+
+		// MP3 has the same syncword but a non-zero layer.
+		assert_eq!(StreamFormat::probe(&[0xFF, 0xFB, 0x90, 0x64]), None);
+	}
+
+	#[test]
+	fn probe_avc3() {
+		// SPS with a 4-byte start code.
+		let buf = [0, 0, 0, 1, 0x67, 0x64, 0x00, 0x1F];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Avc3));
+
+		// AUD with a 3-byte start code.
+		let buf = [0, 0, 1, 0x09, 0xF0];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Avc3));
+	}
+
+	#[test]
+	fn probe_hev1() {
+		// VPS, which would be an SEI in H.264.
+		let buf = [0, 0, 0, 1, 0x40, 0x01, 0x0C];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Hev1));
+
+		// AUD.
+		let buf = [0, 0, 0, 1, 0x46, 0x01, 0x50];
+		assert_eq!(StreamFormat::probe(&buf), Some(StreamFormat::Hev1));
+	}
+
+	#[test]
+	fn probe_insufficient() {
+		assert_eq!(StreamFormat::probe(&[]), None);
+		assert_eq!(StreamFormat::probe(b"Ogg"), None);
+		assert_eq!(StreamFormat::probe(&[0, 0, 0, 0x18, b'f', b't']), None);
+
+		// A start code without the NAL header.
+		assert_eq!(StreamFormat::probe(&[0, 0, 0, 1]), None);
+		assert_eq!(StreamFormat::probe(&[0, 0, 0, 1, 0x40]), None);
+	}
+
+	#[test]
+	fn probe_unknown() {
+		assert_eq!(StreamFormat::probe(b"RIFF\0\0\0\0WAVE"), None);
+		assert_eq!(StreamFormat::probe(&[0x1A, 0x45, 0xDF, 0xA3]), None);
+
+		// A start code followed by a slice that can't begin a stream.
+		assert_eq!(StreamFormat::probe(&[0, 0, 1, 0x41, 0x9A]), None);
+	}
+}
@@ -0,0 +1,516 @@
+use std::time::Duration;
+
+use bytes::BytesMut;
+
+use super::Avc3;
+use super::annexb::START_CODE;
+
+/// The codecs a [`SyntheticSource`] can produce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SyntheticCodec {
+	/// Constrained Baseline H.264 with inline SPS/PPS.
+	H264,
+}
+
+/// Generate a synthetic broadcast with a color bar test pattern, without any media file.
+///
+/// This is meant for demos, reproducing issues and load testing relays: the output is
+/// deterministic and every frame is valid media that a real decoder can play.
+///
+/// H.264 is produced without an encoder library: keyframes store every macroblock
+/// uncompressed (`I_PCM`) and the frames in between skip every macroblock, repeating the
+/// keyframe. The bitrate is high (roughly `width * height * 1.5` bytes per keyframe), so
+/// use small dimensions or a long keyframe interval for load tests. A block along the
+/// bottom moves with every keyframe so it's obvious the stream is live.
+///
+/// There is no audio track yet, since a tone would require an Opus or AAC encoder.
+pub struct SyntheticSource {
+	broadcast: moq_lite::BroadcastProducer,
+	catalog: crate::catalog::Producer,
+	video: Avc3,
+
+	pattern: Pattern,
+	fps: u32,
+	keyframe_interval: u32,
+
+	// The number of frames written so far.
+	frame: u64,
+	// The number of keyframes written so far, used for the IDR picture ID and the moving block.
+	keyframes: u64,
+}
+
+impl SyntheticSource {
+	/// Create a broadcast with a single video rendition of the given size and frame rate.
+	///
+	/// The width and height must be even. Keyframes are written once a second by default.
+	pub fn new(width: u32, height: u32, fps: u32, codec: SyntheticCodec) -> anyhow::Result<Self> {
+		anyhow::ensure!(width > 0 && height > 0, "dimensions must be non-zero");
+		anyhow::ensure!(width % 2 == 0 && height % 2 == 0, "dimensions must be even");
+		anyhow::ensure!(fps > 0, "frame rate must be non-zero");
+
+		match codec {
+			SyntheticCodec::H264 => {}
+		}
+
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast)?;
+		let video = Avc3::new(broadcast.clone(), catalog.clone());
+
+		Ok(Self {
+			broadcast,
+			catalog,
+			video,
+			pattern: Pattern::new(width, height)?,
+			fps,
+			keyframe_interval: fps,
+			frame: 0,
+			keyframes: 0,
+		})
+	}
+
+	/// Write a keyframe (starting a new group) every `frames` frames.
+	pub fn with_keyframe_interval(mut self, frames: u32) -> Self {
+		self.keyframe_interval = frames.max(1);
+		self
+	}
+
+	/// Returns the broadcast being produced, e.g. to publish it to an origin.
+	pub fn broadcast(&self) -> &moq_lite::BroadcastProducer {
+		&self.broadcast
+	}
+
+	/// Write the next frame immediately.
+	///
+	/// Timestamps are derived from the frame count, so the output doesn't depend on wall clock time.
+	pub fn write_frame(&mut self) -> anyhow::Result<()> {
+		let timestamp = hang::container::Timestamp::from_micros(self.frame * 1_000_000 / self.fps as u64)?;
+		let keyframe = self.frame % self.keyframe_interval as u64 == 0;
+
+		let mut buf = BytesMut::new();
+		if keyframe {
+			for nal in [self.pattern.sps(), self.pattern.pps(), self.pattern.idr(self.keyframes)] {
+				buf.extend_from_slice(&START_CODE);
+				buf.extend_from_slice(&nal);
+			}
+			self.keyframes += 1;
+		} else {
+			let frame_num = self.frame % self.keyframe_interval as u64;
+			buf.extend_from_slice(&START_CODE);
+			buf.extend_from_slice(&self.pattern.skip(frame_num));
+		}
+
+		self.video.decode_frame(&mut buf, Some(timestamp))?;
+		self.frame += 1;
+
+		Ok(())
+	}
+
+	/// Write frames in real time at the configured frame rate, until an error occurs.
+	pub async fn run(&mut self) -> anyhow::Result<()> {
+		let mut interval = tokio::time::interval(Duration::from_secs(1) / self.fps);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+		loop {
+			interval.tick().await;
+			self.write_frame()?;
+		}
+	}
+
+	/// Finish the video track and the catalog.
+	pub fn finish(&mut self) -> anyhow::Result<()> {
+		self.video.finish()?;
+		self.catalog.finish()?;
+		Ok(())
+	}
+}
+
+// Limited range BT.601 YUV for 75% color bars, from left to right.
+const BARS: [[u8; 3]; 7] = [
+	[180, 128, 128], // gray
+	[162, 44, 142],  // yellow
+	[131, 156, 44],  // cyan
+	[112, 72, 58],   // green
+	[84, 184, 198],  // magenta
+	[65, 100, 212],  // red
+	[35, 212, 114],  // blue
+];
+
+const BLACK: [u8; 3] = [16, 128, 128];
+const WHITE: [u8; 3] = [235, 128, 128];
+
+// The number of positions the moving block cycles through.
+const BLOCK_POSITIONS: u64 = 16;
+
+// log2(MaxFrameNum) in the SPS; frame_num is written with this many bits.
+const LOG2_MAX_FRAME_NUM: u32 = 8;
+
+/// Generates the H.264 NAL units for the test pattern.
+struct Pattern {
+	width: u32,
+	height: u32,
+	width_mbs: u32,
+	height_mbs: u32,
+}
+
+impl Pattern {
+	fn new(width: u32, height: u32) -> anyhow::Result<Self> {
+		let width_mbs = width.div_ceil(16);
+		let height_mbs = height.div_ceil(16);
+		anyhow::ensure!(width_mbs * height_mbs <= 139_264, "dimensions are too large for H.264");
+
+		Ok(Self {
+			width,
+			height,
+			width_mbs,
+			height_mbs,
+		})
+	}
+
+	fn sps(&self) -> Vec<u8> {
+		let mut w = BitWriter::default();
+		w.put_bits(66, 8); // profile_idc: Baseline
+		w.put_bits(0b1100_0000, 8); // constraint_set0 and constraint_set1: Constrained Baseline
+		w.put_bits(
+			if self.width_mbs * self.height_mbs <= 8192 {
+				40
+			} else {
+				51
+			},
+			8,
+		); // level_idc
+		w.put_ue(0); // seq_parameter_set_id
+		w.put_ue(LOG2_MAX_FRAME_NUM - 4); // log2_max_frame_num_minus4
+		w.put_ue(2); // pic_order_cnt_type: derived from frame_num
+		w.put_ue(1); // max_num_ref_frames
+		w.put_bit(false); // gaps_in_frame_num_value_allowed_flag
+		w.put_ue(self.width_mbs - 1); // pic_width_in_mbs_minus1
+		w.put_ue(self.height_mbs - 1); // pic_height_in_map_units_minus1
+		w.put_bit(true); // frame_mbs_only_flag
+		w.put_bit(true); // direct_8x8_inference_flag
+
+		// Crop the padding macroblocks, in units of two samples for 4:2:0.
+		let crop_right = (self.width_mbs * 16 - self.width) / 2;
+		let crop_bottom = (self.height_mbs * 16 - self.height) / 2;
+		let crop = crop_right > 0 || crop_bottom > 0;
+		w.put_bit(crop); // frame_cropping_flag
+		if crop {
+			w.put_ue(0);
+			w.put_ue(crop_right);
+			w.put_ue(0);
+			w.put_ue(crop_bottom);
+		}
+
+		w.put_bit(false); // vui_parameters_present_flag
+		w.finish(0x67)
+	}
+
+	fn pps(&self) -> Vec<u8> {
+		let mut w = BitWriter::default();
+		w.put_ue(0); // pic_parameter_set_id
+		w.put_ue(0); // seq_parameter_set_id
+		w.put_bit(false); // entropy_coding_mode_flag: CAVLC
+		w.put_bit(false); // bottom_field_pic_order_in_frame_present_flag
+		w.put_ue(0); // num_slice_groups_minus1
+		w.put_ue(0); // num_ref_idx_l0_default_active_minus1
+		w.put_ue(0); // num_ref_idx_l1_default_active_minus1
+		w.put_bit(false); // weighted_pred_flag
+		w.put_bits(0, 2); // weighted_bipred_idc
+		w.put_se(0); // pic_init_qp_minus26
+		w.put_se(0); // pic_init_qs_minus26
+		w.put_se(0); // chroma_qp_index_offset
+		w.put_bit(false); // deblocking_filter_control_present_flag
+		w.put_bit(false); // constrained_intra_pred_flag
+		w.put_bit(false); // redundant_pic_cnt_present_flag
+		w.finish(0x68)
+	}
+
+	// A keyframe with every macroblock stored as raw samples.
+	fn idr(&self, index: u64) -> Vec<u8> {
+		let mut w = BitWriter::default();
+		w.put_ue(0); // first_mb_in_slice
+		w.put_ue(7); // slice_type: I (all slices)
+		w.put_ue(0); // pic_parameter_set_id
+		w.put_bits(0, LOG2_MAX_FRAME_NUM); // frame_num
+		w.put_ue((index % 65_536) as u32); // idr_pic_id: must differ between consecutive IDRs
+		w.put_bit(false); // no_output_of_prior_pics_flag
+		w.put_bit(false); // long_term_reference_flag
+		w.put_se(0); // slice_qp_delta
+
+		let block = index % BLOCK_POSITIONS;
+		for mb_y in 0..self.height_mbs {
+			for mb_x in 0..self.width_mbs {
+				w.put_ue(25); // mb_type: I_PCM
+				w.align();
+
+				for y in 0..16 {
+					for x in 0..16 {
+						w.put_byte(self.color(mb_x * 16 + x, mb_y * 16 + y, block)[0]);
+					}
+				}
+
+				for plane in 1..3 {
+					for y in 0..8 {
+						for x in 0..8 {
+							w.put_byte(self.color(mb_x * 16 + x * 2, mb_y * 16 + y * 2, block)[plane]);
+						}
+					}
+				}
+			}
+		}
+
+		w.finish(0x65)
+	}
+
+	// A frame that skips every macroblock, repeating the previous frame.
+	fn skip(&self, frame_num: u64) -> Vec<u8> {
+		let mut w = BitWriter::default();
+		w.put_ue(0); // first_mb_in_slice
+		w.put_ue(5); // slice_type: P (all slices)
+		w.put_ue(0); // pic_parameter_set_id
+		w.put_bits((frame_num % (1 << LOG2_MAX_FRAME_NUM)) as u32, LOG2_MAX_FRAME_NUM); // frame_num
+		w.put_bit(false); // num_ref_idx_active_override_flag
+		w.put_bit(false); // ref_pic_list_modification_flag_l0
+		w.put_bit(false); // adaptive_ref_pic_marking_mode_flag
+		w.put_se(0); // slice_qp_delta
+		w.put_ue(self.width_mbs * self.height_mbs); // mb_skip_run
+		w.finish(0x41)
+	}
+
+	// The YUV color of the pattern at the given luma sample.
+	fn color(&self, x: u32, y: u32, block: u64) -> [u8; 3] {
+		// Bars on top, and a black strip with a moving white block on the bottom third.
+		if y < self.height * 2 / 3 {
+			let bar = (x as usize * BARS.len() / self.width as usize).min(BARS.len() - 1);
+			return BARS[bar];
+		}
+
+		let position = x as u64 * BLOCK_POSITIONS / self.width as u64;
+		if position == block { WHITE } else { BLACK }
+	}
+}
+
+/// Writes an H.264 RBSP, MSB first.
+#[derive(Default)]
+struct BitWriter {
+	buf: Vec<u8>,
+	// The number of bits used in the last byte, or 0 if it's full.
+	bits: u32,
+}
+
+impl BitWriter {
+	fn put_bit(&mut self, bit: bool) {
+		if self.bits == 0 {
+			self.buf.push(0);
+		}
+
+		if bit {
+			*self.buf.last_mut().unwrap() |= 0x80 >> self.bits;
+		}
+
+		self.bits = (self.bits + 1) % 8;
+	}
+
+	fn put_bits(&mut self, value: u32, count: u32) {
+		for i in (0..count).rev() {
+			self.put_bit((value >> i) & 1 == 1);
+		}
+	}
+
+	// Write a byte when aligned.
+	fn put_byte(&mut self, byte: u8) {
+		debug_assert_eq!(self.bits, 0);
+		self.buf.push(byte);
+	}
+
+	// Unsigned Exp-Golomb.
+	fn put_ue(&mut self, value: u32) {
+		let value = value as u64 + 1;
+		let len = 64 - value.leading_zeros();
+		self.put_bits(0, len - 1);
+		for i in (0..len).rev() {
+			self.put_bit((value >> i) & 1 == 1);
+		}
+	}
+
+	// Signed Exp-Golomb.
+	fn put_se(&mut self, value: i32) {
+		let mapped = if value > 0 {
+			value as u32 * 2 - 1
+		} else {
+			value.unsigned_abs() * 2
+		};
+		self.put_ue(mapped);
+	}
+
+	// Pad with zero bits to a byte boundary.
+	fn align(&mut self) {
+		while self.bits != 0 {
+			self.put_bit(false);
+		}
+	}
+
+	// Add the RBSP trailing bits and return the NAL unit with emulation prevention.
+	fn finish(mut self, header: u8) -> Vec<u8> {
+		self.put_bit(true);
+		self.align();
+
+		let mut nal = Vec::with_capacity(self.buf.len() + self.buf.len() / 64 + 1);
+		nal.push(header);
+
+		let mut zeros = 0;
+		for byte in self.buf {
+			if zeros >= 2 && byte <= 3 {
+				nal.push(3);
+				zeros = 0;
+			}
+
+			zeros = if byte == 0 { zeros + 1 } else { 0 };
+			nal.push(byte);
+		}
+
+		nal
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn exp_golomb() {
+		let mut w = BitWriter::default();
+		w.put_ue(0); // 1
+		w.put_ue(1); // 010
+		w.put_ue(4); // 00101
+		w.put_se(-1); // 011
+		w.put_se(1); // 010
+		assert_eq!(w.buf, [0b1010_0010, 0b1011_0100]);
+	}
+
+	#[test]
+	fn emulation_prevention() {
+		let mut w = BitWriter::default();
+		for byte in [0, 0, 1, 0, 0, 0] {
+			w.put_byte(byte);
+		}
+		assert_eq!(w.finish(0x65), [0x65, 0, 0, 3, 1, 0, 0, 3, 0, 0x80]);
+	}
+
+	#[test]
+	fn parse_headers() {
+		use h264_parser::{NalUnitType, nal::ebsp_to_rbsp, slice::SliceHeader, slice::SliceType};
+
+		// Not a multiple of 16, so the SPS crops the padding.
+		let pattern = Pattern::new(100, 60).unwrap();
+
+		let sps = h264_parser::Sps::parse(&ebsp_to_rbsp(&pattern.sps()[1..])).unwrap();
+		assert_eq!((sps.width, sps.height), (100, 60));
+		let pps = h264_parser::Pps::parse(&ebsp_to_rbsp(&pattern.pps()[1..])).unwrap();
+
+		let idr = pattern.idr(3);
+		let header = SliceHeader::parse(&ebsp_to_rbsp(&idr[1..]), NalUnitType::IdrSlice, &sps, &pps).unwrap();
+		assert_eq!(header.slice_type, SliceType::I);
+		assert_eq!(header.idr_pic_id, 3);
+		// 7x4 macroblocks of raw 4:2:0 samples.
+		assert!(idr.len() > 7 * 4 * 384);
+
+		let skip = pattern.skip(5);
+		let header = SliceHeader::parse(&ebsp_to_rbsp(&skip[1..]), NalUnitType::NonIdrSlice, &sps, &pps).unwrap();
+		assert_eq!(header.slice_type, SliceType::P);
+		assert_eq!(header.frame_num, 5);
+	}
+
+	#[tokio::test]
+	async fn stream_finalize_emits_last_frame() {
+		const FRAMES: u64 = 12;
+
+		// An annex-b file that ends exactly at a frame boundary, with no trailing start code.
+		let pattern = Pattern::new(64, 48).unwrap();
+		let mut file = BytesMut::new();
+		for frame in 0..FRAMES {
+			let nals = match frame % 4 {
+				0 => vec![pattern.sps(), pattern.pps(), pattern.idr(frame / 4)],
+				n => vec![pattern.skip(n)],
+			};
+			for nal in nals {
+				file.extend_from_slice(&START_CODE);
+				file.extend_from_slice(&nal);
+			}
+		}
+
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let mut stream = crate::import::Stream::new(broadcast.clone(), catalog, crate::import::StreamFormat::Avc3);
+		let broadcast = broadcast.consume();
+		let mut catalog =
+			crate::catalog::Consumer::new(broadcast.subscribe_track(&hang::Catalog::default_track()).unwrap());
+
+		// Without a following start code, the last NAL stays in the buffer.
+		stream.decode_stream(&mut file).unwrap();
+		assert!(!file.is_empty());
+
+		let catalog = catalog.next().await.unwrap().unwrap();
+		let name = catalog.video.renditions.keys().next().expect("no video rendition");
+		let track = broadcast.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+		let mut consumer =
+			crate::container::Consumer::new(track, crate::container::Hang::Legacy).with_latency(Duration::MAX);
+
+		stream.finalize(&mut file).unwrap();
+		assert!(file.is_empty());
+		stream.finish().unwrap();
+
+		let mut count = 0;
+		while consumer.read().await.unwrap().is_some() {
+			count += 1;
+		}
+		assert_eq!(count, FRAMES);
+	}
+
+	#[tokio::test]
+	async fn one_second_of_video() {
+		let mut source = SyntheticSource::new(320, 180, 30, SyntheticCodec::H264)
+			.unwrap()
+			.with_keyframe_interval(10);
+
+		let broadcast = source.broadcast().consume();
+		let mut catalog =
+			crate::catalog::Consumer::new(broadcast.subscribe_track(&hang::Catalog::default_track()).unwrap());
+
+		for _ in 0..30 {
+			source.write_frame().unwrap();
+		}
+
+		let catalog = catalog.next().await.unwrap().unwrap();
+		let (name, config) = catalog.video.renditions.iter().next().expect("no video rendition");
+		assert_eq!(config.coded_width, Some(320));
+		assert_eq!(config.coded_height, Some(180));
+
+		let track = moq_lite::Track::new(name.as_str());
+		let mut groups = broadcast.subscribe_track(&track).unwrap();
+		let mut consumer = crate::container::Consumer::new(
+			broadcast.subscribe_track(&track).unwrap(),
+			crate::container::Hang::Legacy,
+		)
+		.with_latency(Duration::MAX);
+		source.finish().unwrap();
+
+		// One group per keyframe, each starting with the keyframe.
+		let mut frames = Vec::new();
+		while let Some(frame) = consumer.read().await.unwrap() {
+			frames.push(frame);
+		}
+
+		assert_eq!(frames.len(), 30);
+		for (i, frame) in frames.iter().enumerate() {
+			assert_eq!(frame.keyframe, i % 10 == 0, "frame {i}");
+			assert_eq!(frame.timestamp.as_micros(), i as u128 * 1_000_000 / 30);
+		}
+
+		let mut count = 0;
+		while groups.recv_group().await.unwrap().is_some() {
+			count += 1;
+		}
+		assert_eq!(count, 3);
+	}
+}
@@ -5,7 +5,7 @@ fn run_fmp4(data: &[u8]) -> hang::Catalog {
 	let mut broadcast = moq_lite::Broadcast::new().produce();
 	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
 
-	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone());
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone()).with_mode(hang::catalog::Mode::Recording);
 
 	let mut buf = bytes::BytesMut::from(data);
 	// Ignore errors from incomplete/malformed trailing fragments in test files.
@@ -26,6 +26,7 @@ fn test_bbb_catalog() {
 	let data = include_bytes!("bbb.mp4");
 	let catalog = run_fmp4(data);
 
+	assert_eq!(catalog.mode, hang::catalog::Mode::Recording);
 	assert_eq!(catalog.video.renditions.len(), 1);
 	assert_eq!(catalog.audio.renditions.len(), 1);
 
@@ -127,3 +128,666 @@ fn test_vp9_catalog() {
 	assert_eq!(mvex.trex.len(), 1);
 	assert_eq!(mvex.trex[0].track_id, moov.trak[0].tkhd.track_id);
 }
+
+#[test]
+fn test_vp8_catalog() {
+	// Rewrite the VP9 fixture's sample entry as VP8, keeping its dimensions and vpcC.
+	let (ftyp, mut moov) = decode_init(include_bytes!("vp9.mp4"));
+	let stsd = &mut moov.trak[0].mdia.minf.stbl.stsd;
+	let mp4_atom::Codec::Vp09(vp09) = stsd.codecs[0].clone() else {
+		panic!("expected vp09");
+	};
+	stsd.codecs[0] = mp4_atom::Vp08 {
+		visual: vp09.visual.clone(),
+		vpcc: vp09.vpcc.clone(),
+		..Default::default()
+	}
+	.into();
+
+	let mut init = Vec::new();
+	ftyp.encode(&mut init).unwrap();
+	moov.encode(&mut init).unwrap();
+
+	let catalog = run_fmp4(&init);
+	let video = catalog.video.renditions.values().next().unwrap();
+	assert_eq!(video.codec, hang::catalog::VideoCodec::VP8);
+	assert_eq!(video.coded_width, Some(vp09.visual.width as u32));
+	assert_eq!(video.coded_height, Some(vp09.visual.height as u32));
+	assert!(!video.codec.requires_description());
+
+	// WebCodecs takes VP8 as a bare "vp8", with no profile or color parameters.
+	let json: serde_json::Value = serde_json::from_slice(&catalog.to_vec().unwrap()).unwrap();
+	let rendition = json["video"]["renditions"]
+		.as_object()
+		.unwrap()
+		.values()
+		.next()
+		.unwrap();
+	assert_eq!(rendition["codec"], "vp8");
+}
+
+/// Re-encode the ftyp and moov from a fixture so fragments can be appended to it.
+fn init_segment(data: &[u8]) -> Vec<u8> {
+	let (ftyp, moov) = decode_init(data);
+	let mut buf = Vec::new();
+	ftyp.encode(&mut buf).unwrap();
+	moov.encode(&mut buf).unwrap();
+	buf
+}
+
+#[test]
+fn test_rotation() {
+	let (ftyp, mut moov) = decode_init(include_bytes!("bbb.mp4"));
+	assert_eq!(run_fmp4(&init_segment(include_bytes!("bbb.mp4"))).video.rotation, None);
+
+	// The matrix an iPhone writes for a portrait recording: rotate 90 degrees clockwise.
+	let video = moov
+		.trak
+		.iter_mut()
+		.find(|trak| trak.mdia.hdlr.handler == b"vide".into())
+		.unwrap();
+	video.tkhd.matrix.a = 0;
+	video.tkhd.matrix.b = 0x10000;
+	video.tkhd.matrix.c = -0x10000;
+	video.tkhd.matrix.d = 0;
+
+	let mut init = Vec::new();
+	ftyp.encode(&mut init).unwrap();
+	moov.encode(&mut init).unwrap();
+
+	let catalog = run_fmp4(&init);
+	assert_eq!(catalog.video.rotation, Some(hang::catalog::Rotation::Deg90));
+
+	let json: serde_json::Value = serde_json::from_slice(&catalog.to_vec().unwrap()).unwrap();
+	assert_eq!(json["video"]["rotation"], 90);
+}
+
+#[test]
+fn test_language() {
+	let (ftyp, mut moov) = decode_init(include_bytes!("bbb.mp4"));
+	for trak in &mut moov.trak {
+		trak.mdia.mdhd.language = match trak.mdia.hdlr.handler == b"soun".into() {
+			true => "fre".to_string(),
+			false => "und".to_string(),
+		};
+	}
+
+	let mut init = Vec::new();
+	ftyp.encode(&mut init).unwrap();
+	moov.encode(&mut init).unwrap();
+
+	let catalog = run_fmp4(&init);
+	let audio = catalog.audio.renditions.values().next().unwrap();
+	assert_eq!(audio.lang.as_deref(), Some("fr"));
+	let video = catalog.video.renditions.values().next().unwrap();
+	assert_eq!(video.lang, None);
+
+	let json: serde_json::Value = serde_json::from_slice(&catalog.to_vec().unwrap()).unwrap();
+	let audio = json["audio"]["renditions"]
+		.as_object()
+		.unwrap()
+		.values()
+		.next()
+		.unwrap();
+	assert_eq!(audio["lang"], "fr");
+}
+
+#[test]
+fn test_multiple_sample_entries() {
+	let (ftyp, mut moov) = decode_init(include_bytes!("bbb.mp4"));
+	moov.trak.retain(|trak| trak.mdia.hdlr.handler == b"vide".into());
+	let track_id = moov.trak[0].tkhd.track_id;
+
+	// Add a second AVC sample entry for a 1080p, level 4.0 encoding.
+	let stsd = &mut moov.trak[0].mdia.minf.stbl.stsd;
+	let mp4_atom::Codec::Avc1(mut avc1) = stsd.codecs[0].clone() else {
+		panic!("expected avc1");
+	};
+	avc1.visual.width = 1920;
+	avc1.visual.height = 1080;
+	avc1.avcc.avc_level_indication = 0x28;
+	stsd.codecs.push(avc1.into());
+
+	let mut buf = Vec::new();
+	ftyp.encode(&mut buf).unwrap();
+	moov.encode(&mut buf).unwrap();
+
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone());
+
+	let video = |catalog: &crate::catalog::Producer| catalog.snapshot().video.renditions.into_values().next().unwrap();
+
+	// The first sample entry is advertised until a fragment references another.
+	fmp4.decode(&mut bytes::BytesMut::from(buf.as_slice())).unwrap();
+	assert_eq!(video(&catalog).codec.to_string(), "avc1.64001f");
+
+	let mut buf = bytes::BytesMut::from(fragment_with_entry(track_id, Some(2), &[(1000, 0)]).as_slice());
+	fmp4.decode(&mut buf).unwrap();
+	let config = video(&catalog);
+	assert_eq!(config.codec.to_string(), "avc1.640028");
+	assert_eq!(config.coded_width, Some(1920));
+
+	// Without an index in tfhd, the trex default (the first entry) applies again.
+	let mut buf = bytes::BytesMut::from(fragment(track_id, &[(1000, 0)]).as_slice());
+	fmp4.decode(&mut buf).unwrap();
+	assert_eq!(video(&catalog).codec.to_string(), "avc1.64001f");
+
+	// An out-of-range index is an error.
+	let mut buf = bytes::BytesMut::from(fragment_with_entry(track_id, Some(3), &[(1000, 0)]).as_slice());
+	assert!(fmp4.decode(&mut buf).is_err());
+}
+
+/// Build a moof+mdat for a single track with the given (duration, cts) samples.
+///
+/// Only the first sample is marked as a sync sample. Each sample carries a 4-byte payload.
+fn fragment(track_id: u32, samples: &[(u32, i32)]) -> Vec<u8> {
+	fragment_with_entry(track_id, None, samples)
+}
+
+/// Like [`fragment`], but referencing the given sample entry in `tfhd`.
+fn fragment_with_entry(track_id: u32, sample_description_index: Option<u32>, samples: &[(u32, i32)]) -> Vec<u8> {
+	let entries = samples
+		.iter()
+		.enumerate()
+		.map(|(i, &(duration, cts))| mp4_atom::TrunEntry {
+			duration: Some(duration),
+			size: Some(4),
+			flags: Some(if i == 0 { 0x0200_0000 } else { 0x0101_0000 }),
+			cts: Some(cts),
+		})
+		.collect();
+	let data = (0..samples.len() * 4).map(|i| i as u8).collect();
+
+	fragment_with_data(track_id, sample_description_index, entries, data)
+}
+
+/// Like [`fragment`], but with a single sync sample carrying the given payload.
+fn keyframe(track_id: u32, payload: &[u8]) -> Vec<u8> {
+	let entry = mp4_atom::TrunEntry {
+		duration: Some(1000),
+		size: Some(payload.len() as u32),
+		flags: Some(0x0200_0000),
+		cts: Some(0),
+	};
+
+	fragment_with_data(track_id, None, vec![entry], payload.to_vec())
+}
+
+fn fragment_with_data(
+	track_id: u32,
+	sample_description_index: Option<u32>,
+	entries: Vec<mp4_atom::TrunEntry>,
+	data: Vec<u8>,
+) -> Vec<u8> {
+	let mut moof = mp4_atom::Moof {
+		mfhd: mp4_atom::Mfhd { sequence_number: 1 },
+		traf: vec![mp4_atom::Traf {
+			tfhd: mp4_atom::Tfhd {
+				track_id,
+				sample_description_index,
+				..Default::default()
+			},
+			tfdt: Some(mp4_atom::Tfdt {
+				base_media_decode_time: 0,
+			}),
+			trun: vec![mp4_atom::Trun {
+				data_offset: Some(0),
+				entries,
+			}],
+			..Default::default()
+		}],
+	};
+
+	// The data offset is relative to the start of the moof and skips the mdat header.
+	let mut buf = Vec::new();
+	moof.encode(&mut buf).unwrap();
+	moof.traf[0].trun[0].data_offset = Some(buf.len() as i32 + 8);
+
+	let mut buf = Vec::new();
+	moof.encode(&mut buf).unwrap();
+	mp4_atom::Mdat { data }.encode(&mut buf).unwrap();
+	buf
+}
+
+/// Convert ticks in the bbb.mp4 video timescale to microseconds.
+fn micros(ticks: u64) -> u128 {
+	hang::container::Timestamp::from_scale(ticks, 24_000)
+		.unwrap()
+		.as_micros()
+}
+
+/// Import an init segment plus a fragment for one track, returning the presentation timestamps in decode order.
+///
+/// The first video rendition is read, falling back to the first audio rendition.
+async fn timestamps(init: &[u8], track_id: u32, samples: &[(u32, i32)]) -> Vec<u128> {
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let consumer = broadcast.consume();
+
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone());
+
+	let mut buf = bytes::BytesMut::from(init);
+	buf.extend_from_slice(&fragment(track_id, samples));
+	fmp4.decode(&mut buf).unwrap();
+	fmp4.finish().unwrap();
+
+	let snapshot = catalog.snapshot();
+	let (name, container) = snapshot
+		.video
+		.renditions
+		.iter()
+		.map(|(name, video)| (name, &video.container))
+		.chain(
+			snapshot
+				.audio
+				.renditions
+				.iter()
+				.map(|(name, audio)| (name, &audio.container)),
+		)
+		.next()
+		.unwrap();
+	let track = consumer.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+	let format = crate::container::Hang::try_from(container).unwrap();
+	let mut track = crate::container::Consumer::new(track, format);
+
+	let mut timestamps = Vec::new();
+	while let Some(frame) = track.read().await.unwrap() {
+		timestamps.push(frame.timestamp.as_micros());
+	}
+	timestamps
+}
+
+/// Import the fixture's init segment plus a video fragment, returning the presentation timestamps in decode order.
+async fn video_timestamps(samples: &[(u32, i32)]) -> Vec<u128> {
+	timestamps(&init_segment(include_bytes!("bbb.mp4")), 1, samples).await
+}
+
+/// Import only the fixture's audio track with the given edit list, returning the presentation timestamps.
+async fn audio_timestamps(edits: Vec<mp4_atom::ElstEntry>, samples: &[(u32, i32)]) -> Vec<u128> {
+	let (ftyp, mut moov) = decode_init(include_bytes!("bbb.mp4"));
+	moov.trak.retain(|trak| trak.tkhd.track_id == 2);
+	moov.trak[0].edts = Some(mp4_atom::Edts {
+		elst: Some(mp4_atom::Elst { entries: edits }),
+	});
+
+	let mut init = Vec::new();
+	ftyp.encode(&mut init).unwrap();
+	moov.encode(&mut init).unwrap();
+
+	timestamps(&init, 2, samples).await
+}
+
+/// Convert ticks in the bbb.mp4 audio timescale to microseconds.
+fn audio_micros(ticks: u64) -> u128 {
+	hang::container::Timestamp::from_scale(ticks, 44_100)
+		.unwrap()
+		.as_micros()
+}
+
+#[tokio::test]
+async fn test_negative_cts_reordered_frames() {
+	// I P B B in decode order (24000 timescale, 1000 ticks per frame), using
+	// version-1 style signed offsets so the B-frames present before the P-frame.
+	let samples = [(1000, 0), (1000, 2000), (1000, -1000), (1000, -1000)];
+	let timestamps = video_timestamps(&samples).await;
+
+	assert_eq!(timestamps, vec![micros(0), micros(3000), micros(1000), micros(2000)]);
+
+	// Presentation order is monotonic once sorted, one frame apart.
+	let mut sorted = timestamps.clone();
+	sorted.sort();
+	assert_eq!(sorted, vec![micros(0), micros(1000), micros(2000), micros(3000)]);
+}
+
+#[tokio::test]
+async fn test_negative_pts_clamped_to_zero() {
+	// A negative offset on the first sample would produce a negative PTS.
+	let samples = [(1000, -500), (1000, 0)];
+	let timestamps = video_timestamps(&samples).await;
+
+	assert_eq!(timestamps, vec![0, micros(1000)]);
+}
+
+#[tokio::test]
+async fn test_edit_list_skips_aac_priming() {
+	// A single edit skipping 1024 ticks of encoder priming. The priming frame is clamped
+	// at zero and the first real frame presents at zero.
+	let edits = vec![mp4_atom::ElstEntry {
+		segment_duration: 0,
+		media_time: 1024,
+		media_rate: 1,
+		media_rate_fraction: 0,
+	}];
+	let samples = [(1024, 0), (1024, 0), (1024, 0)];
+	let timestamps = audio_timestamps(edits, &samples).await;
+
+	assert_eq!(timestamps, vec![0, 0, audio_micros(1024)]);
+}
+
+#[tokio::test]
+async fn test_edit_list_empty_edit_delays_start() {
+	// An empty edit of half a second (movie timescale) delays the track.
+	let (_, moov) = decode_init(include_bytes!("bbb.mp4"));
+	let edits = vec![
+		mp4_atom::ElstEntry {
+			segment_duration: moov.mvhd.timescale as u64 / 2,
+			media_time: u32::MAX as u64,
+			media_rate: 1,
+			media_rate_fraction: 0,
+		},
+		mp4_atom::ElstEntry {
+			segment_duration: 0,
+			media_time: 0,
+			media_rate: 1,
+			media_rate_fraction: 0,
+		},
+	];
+	let samples = [(1024, 0), (1024, 0)];
+	let timestamps = audio_timestamps(edits, &samples).await;
+
+	assert_eq!(timestamps, vec![audio_micros(22_050), audio_micros(22_050 + 1024)]);
+}
+
+#[tokio::test]
+async fn test_finish_closes_broadcast() {
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let consumer = broadcast.consume();
+	let mut catalog_consumer = catalog.consume().unwrap();
+
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone());
+
+	let mut buf = bytes::BytesMut::from(&init_segment(include_bytes!("bbb.mp4"))[..]);
+	buf.extend_from_slice(&fragment(1, &[(1000, 0), (1000, 0)]));
+	fmp4.decode(&mut buf).unwrap();
+
+	let snapshot = catalog.snapshot();
+	let name = snapshot.video.renditions.keys().next().unwrap().clone();
+	let mut track = consumer.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+
+	fmp4.finish().unwrap();
+
+	// The track ends cleanly after the flushed group.
+	let mut group = track.next_group().await.unwrap().expect("missing group");
+	assert!(group.read_frame().await.unwrap().is_some());
+	assert!(group.read_frame().await.unwrap().is_none());
+	assert!(track.next_group().await.unwrap().is_none());
+
+	// The catalog ends cleanly after the last update.
+	let catalog = catalog_consumer.next().await.unwrap().expect("missing catalog");
+	assert!(catalog.video.renditions.contains_key(&name));
+	assert!(catalog_consumer.next().await.unwrap().is_none());
+}
+
+/// Import the fixture's init segment, a moof without its mdat, then a complete fragment.
+fn decode_duplicate_moof(strict: bool) -> anyhow::Result<moq_lite::TrackConsumer> {
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let consumer = broadcast.consume();
+
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone()).with_strict(strict);
+
+	let stale = fragment(1, &[(1000, 0), (1000, 0), (1000, 0)]);
+	let moof_size = u32::from_be_bytes(stale[..4].try_into().unwrap()) as usize;
+
+	let mut buf = bytes::BytesMut::from(&init_segment(include_bytes!("bbb.mp4"))[..]);
+	buf.extend_from_slice(&stale[..moof_size]);
+	buf.extend_from_slice(&fragment(1, &[(1000, 0), (1000, 0)]));
+	fmp4.decode(&mut buf)?;
+	fmp4.finish()?;
+
+	let name = catalog.snapshot().video.renditions.keys().next().unwrap().clone();
+	Ok(consumer.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap())
+}
+
+#[tokio::test]
+async fn test_duplicate_moof() {
+	assert!(decode_duplicate_moof(true).is_err());
+
+	// The stale moof is dropped and the following fragment is imported as usual.
+	let mut track = decode_duplicate_moof(false).unwrap();
+	let mut group = track.next_group().await.unwrap().expect("missing group");
+	let frame = group.read_frame().await.unwrap().expect("missing frame");
+	assert!(frame.ends_with(&[0, 1, 2, 3, 4, 5, 6, 7]), "wrong mdat: {frame:?}");
+	assert!(group.read_frame().await.unwrap().is_none());
+	assert!(track.next_group().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_decode_header_only() {
+	let data = include_bytes!("bbb.mp4");
+
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let consumer = broadcast.consume();
+
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone()).with_mode(hang::catalog::Mode::Recording);
+
+	let mut buf = bytes::BytesMut::from(&data[..]);
+	assert!(fmp4.decode_header_only(&mut buf).unwrap());
+	assert!(fmp4.is_initialized());
+
+	// The same renditions as decoding the whole file, minus anything measured from frames.
+	let snapshot = catalog.snapshot();
+	let full = run_fmp4(data);
+	assert_eq!(snapshot.video.renditions.len(), 1);
+	assert_eq!(snapshot.audio.renditions.len(), 1);
+	for (name, config) in &snapshot.video.renditions {
+		assert_eq!(config.codec, full.video.renditions[name].codec);
+	}
+	for (name, config) in &snapshot.audio.renditions {
+		assert_eq!(config.codec, full.audio.renditions[name].codec);
+	}
+
+	// The buffer is left right after the moov, at the next top-level atom.
+	assert!(buf.len() < data.len());
+	assert!(data.ends_with(&buf));
+	assert!(!matches!(
+		mp4_atom::Any::decode(&mut std::io::Cursor::new(&buf[..])).unwrap(),
+		mp4_atom::Any::Ftyp(_) | mp4_atom::Any::Moov(_)
+	));
+
+	// No frames were written.
+	fmp4.finish().unwrap();
+	let name = snapshot.video.renditions.keys().next().unwrap();
+	let mut track = consumer.subscribe_track(&moq_lite::Track::new(name.as_str())).unwrap();
+	assert!(track.next_group().await.unwrap().is_none());
+
+	// The async reader stops at the same position.
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone()).with_mode(hang::catalog::Mode::Recording);
+
+	let mut reader = &data[..];
+	fmp4.decode_header_only_from(&mut reader).await.unwrap();
+	assert_eq!(reader.len(), buf.len());
+	assert_eq!(catalog.snapshot(), snapshot);
+}
+
+#[test]
+fn test_segment_index() {
+	let data = include_bytes!("bbb.mp4");
+
+	// The init segment as written in the file.
+	let mut cursor = std::io::Cursor::new(&data[..]);
+	mp4_atom::Ftyp::decode(&mut cursor).unwrap();
+	mp4_atom::Moov::decode(&mut cursor).unwrap();
+	let (init, data) = data.split_at(cursor.position() as usize);
+
+	// Index every moof+mdat fragment that follows it.
+	let mut fragments = Vec::new();
+	let mut cursor = std::io::Cursor::new(data);
+	let mut moof = false;
+	while let Ok(atom) = mp4_atom::Any::decode(&mut cursor) {
+		match atom {
+			// The fixture ends with a malformed fragment with two moofs, so stop there.
+			mp4_atom::Any::Moof(_) if moof => break,
+			mp4_atom::Any::Moof(_) => moof = true,
+			mp4_atom::Any::Mdat(_) => {
+				fragments.push(cursor.position() as usize);
+				moof = false;
+			}
+			_ => {}
+		}
+	}
+	let mut start = 0;
+	let sizes: Vec<u32> = fragments
+		.into_iter()
+		.map(|end| {
+			let size = (end - start) as u32;
+			start = end;
+			size
+		})
+		.collect();
+	assert!(sizes.len() > 1);
+
+	let sidx = mp4_atom::Sidx {
+		reference_id: 1,
+		timescale: 1000,
+		earliest_presentation_time: 500,
+		first_offset: 0,
+		references: sizes
+			.iter()
+			.enumerate()
+			.map(|(i, &size)| mp4_atom::SegmentReference {
+				reference_type: false,
+				reference_size: size,
+				subsegment_duration: 2000,
+				starts_with_sap: i == 0,
+				sap_type: if i == 0 { 1 } else { 0 },
+				sap_delta_time: 0,
+			})
+			.collect(),
+	};
+
+	let mut file = init.to_vec();
+	sidx.encode(&mut file).unwrap();
+	let media = file.len();
+	file.extend_from_slice(&data[..sizes.iter().sum::<u32>() as usize]);
+
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog);
+	assert!(fmp4.segment_index().is_empty());
+
+	let mut buf = bytes::BytesMut::from(&file[..]);
+	fmp4.decode(&mut buf).unwrap();
+	assert!(buf.is_empty());
+
+	let index = fmp4.segment_index();
+	assert_eq!(index.len(), sizes.len());
+
+	let mut offset = media as u64;
+	for (i, segment) in index.iter().enumerate() {
+		assert_eq!(segment.track_id, 1);
+		assert_eq!(segment.offset, offset);
+		assert_eq!(segment.size, sizes[i]);
+		assert_eq!(segment.start.as_millis(), 500 + 2000 * i as u128);
+		assert_eq!(segment.duration.as_millis(), 2000);
+		assert_eq!(segment.starts_with_sap, i == 0);
+
+		// Each offset lands on a moof.
+		let atom = mp4_atom::Any::decode(&mut std::io::Cursor::new(&file[offset as usize..])).unwrap();
+		assert!(matches!(atom, mp4_atom::Any::Moof(_)));
+
+		offset += segment.size as u64;
+	}
+	assert_eq!(offset, file.len() as u64);
+}
+
+/// An init segment whose video track is H.265, labeled `hvc1` or `hev1`, with the given `hvcC` arrays.
+fn h265_init(in_band: bool, arrays: Vec<mp4_atom::HvcCArray>) -> Vec<u8> {
+	let (ftyp, mut moov) = decode_init(include_bytes!("bbb.mp4"));
+	moov.trak.retain(|trak| trak.mdia.hdlr.handler == b"vide".into());
+
+	let stsd = &mut moov.trak[0].mdia.minf.stbl.stsd;
+	let mp4_atom::Codec::Avc1(avc1) = stsd.codecs[0].clone() else {
+		panic!("expected avc1");
+	};
+
+	let hvcc = mp4_atom::Hvcc {
+		general_profile_idc: 1,
+		general_level_idc: 93,
+		length_size_minus_one: 3,
+		arrays,
+		..mp4_atom::Hvcc::new()
+	};
+	stsd.codecs[0] = match in_band {
+		true => mp4_atom::Hev1 {
+			visual: avc1.visual,
+			hvcc,
+			..Default::default()
+		}
+		.into(),
+		false => mp4_atom::Hvc1 {
+			visual: avc1.visual,
+			hvcc,
+			..Default::default()
+		}
+		.into(),
+	};
+
+	let mut buf = Vec::new();
+	ftyp.encode(&mut buf).unwrap();
+	moov.encode(&mut buf).unwrap();
+	buf
+}
+
+/// A dummy H.265 NAL unit of the given type.
+fn h265_nal(kind: u8) -> Vec<u8> {
+	vec![kind << 1, 0x01, 0xaa, 0xbb]
+}
+
+/// Length-prefix each NAL unit with 4 bytes, as in an MP4 sample.
+fn h265_sample(nals: &[Vec<u8>]) -> Vec<u8> {
+	let mut sample = Vec::new();
+	for nal in nals {
+		sample.extend_from_slice(&(nal.len() as u32).to_be_bytes());
+		sample.extend_from_slice(nal);
+	}
+	sample
+}
+
+/// Import an H.265 init segment and keyframe, returning the advertised sample entry type.
+fn h265_codec(init: &[u8], sample: &[u8]) -> String {
+	let mut broadcast = moq_lite::Broadcast::new().produce();
+	let catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+	let mut fmp4 = super::Fmp4::new(broadcast, catalog.clone());
+
+	let mut buf = bytes::BytesMut::from(init);
+	buf.extend_from_slice(&keyframe(1, sample));
+	fmp4.decode(&mut buf).unwrap();
+
+	let video = catalog.snapshot().video.renditions.into_values().next().unwrap();
+	let codec = video.codec.to_string();
+	codec.split('.').next().unwrap().to_string()
+}
+
+#[test]
+fn test_h265_parameter_sets() {
+	let parameter_sets = [32, 33, 34].map(h265_nal);
+	let inline = h265_sample(&[parameter_sets.to_vec(), vec![h265_nal(19)]].concat());
+	let idr = h265_sample(&[h265_nal(19)]);
+	let described = parameter_sets
+		.iter()
+		.map(|nal| mp4_atom::HvcCArray {
+			completeness: true,
+			nal_unit_type: nal[0] >> 1,
+			nalus: vec![nal.clone()],
+		})
+		.collect::<Vec<_>>();
+
+	// Correctly labeled tracks are left alone.
+	assert_eq!(h265_codec(&h265_init(false, described.clone()), &idr), "hvc1");
+	assert_eq!(h265_codec(&h265_init(true, Vec::new()), &inline), "hev1");
+
+	// Labeled hvc1 but the parameter sets are only inline, so it's really hev1.
+	assert_eq!(h265_codec(&h265_init(false, Vec::new()), &inline), "hev1");
+
+	// Labeled hev1 but the parameter sets are only in the description, so it's really hvc1.
+	assert_eq!(h265_codec(&h265_init(true, described), &idr), "hvc1");
+
+	// Nothing to correct to if the parameter sets are missing entirely.
+	assert_eq!(h265_codec(&h265_init(false, Vec::new()), &idr), "hvc1");
+}
@@ -1,7 +1,7 @@
 //! Media muxers and demuxers for MoQ.
 //!
 //! `moq-mux` sits between [`moq_lite`] (the generic pub/sub protocol) and [`hang`]
-//! (the media catalog/container format). It exposes four submodules:
+//! (the media catalog/container format). It exposes five submodules:
 //!
 //! - [`container`]: the wire-level container abstraction and per-track wrappers —
 //!   the [`Container`](container::Container) trait, the [`Hang`](container::Hang) enum
@@ -16,12 +16,18 @@
 //!   [`catalog::Producer`].
 //! - [`export`]: subscribe to a moq broadcast and produce media bytes —
 //!   [`Fmp4`](export::Fmp4) yields a single fMP4 / CMAF byte stream (init segment +
-//!   moof+mdat fragments) in timestamp order across tracks.
+//!   moof+mdat fragments) in timestamp order across tracks, and
+//!   [`CmafConsumer`](export::CmafConsumer) passes a single CMAF track through as-is, and
+//!   [`BroadcastPlayer`](export::BroadcastPlayer) yields decoded audio and video frames.
+//! - [`record`]: save a broadcast to disk group by group with
+//!   [`Recorder`](record::Recorder), and re-publish it with its original timing with
+//!   [`Replayer`](record::Replayer).
 
 pub mod catalog;
 pub mod container;
 mod error;
 pub mod export;
 pub mod import;
+pub mod record;
 
 pub use error::*;
@@ -0,0 +1,274 @@
+//! Record a hang broadcast to disk and replay it later with its original timing.
+//!
+//! Unlike the [`export`](crate::export) module, which decodes media for playback, a
+//! recording preserves the moq-lite structure: every group and frame of every rendition,
+//! plus each catalog snapshot, stamped with the time it arrived.
+//!
+//! [`Recorder`] follows the catalog and writes everything to an [`AsyncWrite`](tokio::io::AsyncWrite).
+//! [`Replayer`] reads a recording back and re-publishes it into a broadcast, sleeping
+//! between records so groups are produced with their original spacing.
+//!
+//! ## Format
+//!
+//! A recording is the [`MAGIC`] header followed by a sequence of records. Each record
+//! starts with a little-endian `u64` timestamp in microseconds since the recording
+//! started and a `u8` kind:
+//!
+//! - `0` catalog: a `u32` length and the hang catalog JSON.
+//! - `1` group start: the track name (`u16` length + UTF-8) and a `u64` sequence.
+//! - `2` frame: the track name, the `u64` group sequence, a `u32` length and the payload.
+//! - `3` group end: the track name and the `u64` group sequence.
+
+mod recorder;
+mod replayer;
+
+pub use recorder::*;
+pub use replayer::*;
+
+use std::time::Duration;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// The header at the start of every recording, including the format version.
+pub const MAGIC: &[u8; 8] = b"MOQREC01";
+
+const KIND_CATALOG: u8 = 0;
+const KIND_GROUP: u8 = 1;
+const KIND_FRAME: u8 = 2;
+const KIND_END: u8 = 3;
+
+/// A single entry in a recording.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Record {
+	Catalog(Bytes),
+	Group {
+		track: String,
+		sequence: u64,
+	},
+	Frame {
+		track: String,
+		sequence: u64,
+		payload: Bytes,
+	},
+	End {
+		track: String,
+		sequence: u64,
+	},
+}
+
+impl Record {
+	fn encode(&self, timestamp: Duration, buf: &mut BytesMut) -> anyhow::Result<()> {
+		buf.put_u64_le(u64::try_from(timestamp.as_micros())?);
+
+		match self {
+			Self::Catalog(json) => {
+				buf.put_u8(KIND_CATALOG);
+				put_bytes(buf, json)?;
+			}
+			Self::Group { track, sequence } => {
+				buf.put_u8(KIND_GROUP);
+				put_track(buf, track)?;
+				buf.put_u64_le(*sequence);
+			}
+			Self::Frame {
+				track,
+				sequence,
+				payload,
+			} => {
+				buf.put_u8(KIND_FRAME);
+				put_track(buf, track)?;
+				buf.put_u64_le(*sequence);
+				put_bytes(buf, payload)?;
+			}
+			Self::End { track, sequence } => {
+				buf.put_u8(KIND_END);
+				put_track(buf, track)?;
+				buf.put_u64_le(*sequence);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Read the next record, or `None` at the end of the recording.
+	async fn decode<R: AsyncRead + Unpin>(r: &mut R) -> anyhow::Result<Option<(Duration, Self)>> {
+		let timestamp = match r.read_u64_le().await {
+			Ok(timestamp) => Duration::from_micros(timestamp),
+			Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+			Err(err) => return Err(err.into()),
+		};
+
+		let record = match r.read_u8().await? {
+			KIND_CATALOG => Self::Catalog(read_bytes(r).await?),
+			KIND_GROUP => Self::Group {
+				track: read_track(r).await?,
+				sequence: r.read_u64_le().await?,
+			},
+			KIND_FRAME => Self::Frame {
+				track: read_track(r).await?,
+				sequence: r.read_u64_le().await?,
+				payload: read_bytes(r).await?,
+			},
+			KIND_END => Self::End {
+				track: read_track(r).await?,
+				sequence: r.read_u64_le().await?,
+			},
+			kind => anyhow::bail!("unknown record kind: {kind}"),
+		};
+
+		Ok(Some((timestamp, record)))
+	}
+}
+
+fn put_track(buf: &mut BytesMut, track: &str) -> anyhow::Result<()> {
+	buf.put_u16_le(u16::try_from(track.len())?);
+	buf.put_slice(track.as_bytes());
+	Ok(())
+}
+
+fn put_bytes(buf: &mut BytesMut, bytes: &[u8]) -> anyhow::Result<()> {
+	buf.put_u32_le(u32::try_from(bytes.len())?);
+	buf.put_slice(bytes);
+	Ok(())
+}
+
+async fn read_track<R: AsyncRead + Unpin>(r: &mut R) -> anyhow::Result<String> {
+	let mut buf = vec![0; r.read_u16_le().await? as usize];
+	r.read_exact(&mut buf).await?;
+	Ok(String::from_utf8(buf)?)
+}
+
+async fn read_bytes<R: AsyncRead + Unpin>(r: &mut R) -> anyhow::Result<Bytes> {
+	let mut buf = vec![0; r.read_u32_le().await? as usize];
+	r.read_exact(&mut buf).await?;
+	Ok(buf.into())
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+
+	use hang::catalog::{Container, H264, VideoConfig};
+	use tokio::time::Instant;
+
+	use super::*;
+
+	fn video() -> VideoConfig {
+		VideoConfig {
+			codec: H264 {
+				profile: 0x64,
+				constraints: 0x00,
+				level: 0x1f,
+				inline: true,
+			}
+			.into(),
+			description: None,
+			coded_width: Some(1280),
+			coded_height: Some(720),
+			display_ratio_width: None,
+			display_ratio_height: None,
+			bitrate: None,
+			framerate: None,
+			optimize_for_latency: None,
+			container: Container::Legacy,
+			jitter: None,
+			lang: None,
+		}
+	}
+
+	#[test]
+	fn record_encode_decode() {
+		let records = [
+			Record::Catalog(Bytes::from_static(b"{}")),
+			Record::Group {
+				track: "video".to_string(),
+				sequence: 7,
+			},
+			Record::Frame {
+				track: "video".to_string(),
+				sequence: 7,
+				payload: Bytes::from_static(b"keyframe"),
+			},
+			Record::End {
+				track: "video".to_string(),
+				sequence: 7,
+			},
+		];
+
+		let mut buf = BytesMut::new();
+		for (i, record) in records.iter().enumerate() {
+			record.encode(Duration::from_millis(i as u64), &mut buf).unwrap();
+		}
+
+		let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+		let mut r = &buf[..];
+		for (i, record) in records.iter().enumerate() {
+			let (timestamp, decoded) = rt.block_on(Record::decode(&mut r)).unwrap().unwrap();
+			assert_eq!(timestamp, Duration::from_millis(i as u64));
+			assert_eq!(&decoded, record);
+		}
+		assert!(rt.block_on(Record::decode(&mut r)).unwrap().is_none());
+	}
+
+	#[tokio::test]
+	async fn record_and_replay() {
+		tokio::time::pause();
+
+		// Publish a catalog and a video track with a group every 100ms.
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut catalog = crate::catalog::Producer::new(&mut broadcast).unwrap();
+		let mut track = broadcast.create_track(moq_lite::Track::new("video")).unwrap();
+		catalog.lock().video.renditions.insert("video".to_string(), video());
+
+		let recorder = Recorder::new(broadcast.consume(), Vec::new()).unwrap();
+		let recording = tokio::spawn(recorder.run());
+
+		for sequence in 0..3u64 {
+			tokio::time::sleep(Duration::from_millis(100)).await;
+			let mut group = track.create_group(moq_lite::Group { sequence }).unwrap();
+			group.write_frame(Bytes::from(format!("frame {sequence}"))).unwrap();
+			group.finish().unwrap();
+		}
+		track.finish().unwrap();
+		catalog.finish().unwrap();
+
+		let recording = recording.await.unwrap().unwrap();
+		assert_eq!(&recording[..MAGIC.len()], MAGIC);
+
+		// Replay it into a new broadcast and check the structure and timing survived.
+		let replayed = moq_lite::Broadcast::new().produce();
+		let consumer = replayed.consume();
+		let replayer = Replayer::new(replayed, std::io::Cursor::new(recording)).unwrap();
+		let replaying = tokio::spawn(replayer.run());
+
+		let catalog_track = consumer.subscribe_track(&hang::Catalog::default_track()).unwrap();
+		let snapshot = crate::catalog::Consumer::new(catalog_track)
+			.next()
+			.await
+			.unwrap()
+			.unwrap();
+		assert!(snapshot.video.renditions.contains_key("video"));
+
+		let mut track = consumer.subscribe_track(&moq_lite::Track::new("video")).unwrap();
+		let mut arrivals = Vec::new();
+		while let Some(mut group) = track.recv_group().await.unwrap() {
+			let frame = group.read_frame().await.unwrap().unwrap();
+			assert_eq!(frame, format!("frame {}", group.sequence));
+			arrivals.push((group.sequence, Instant::now()));
+		}
+
+		let sequences: Vec<_> = arrivals.iter().map(|(sequence, _)| *sequence).collect();
+		assert_eq!(sequences, [0, 1, 2]);
+		// Tokio timers have millisecond resolution, so allow a little rounding.
+		for pair in arrivals.windows(2) {
+			let gap = pair[1].1 - pair[0].1;
+			assert!(
+				gap.abs_diff(Duration::from_millis(100)) <= Duration::from_millis(1),
+				"{gap:?}"
+			);
+		}
+
+		replaying.await.unwrap().unwrap();
+	}
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::task::Poll;
+
+use bytes::BytesMut;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::{MAGIC, Record};
+
+/// Record a hang broadcast, preserving its group and frame structure.
+///
+/// Built from a [`moq_lite::BroadcastConsumer`], `Recorder` subscribes to the hang catalog
+/// and to every audio and video rendition it lists, including renditions added by later
+/// catalog updates. Each catalog snapshot, group start, frame and group end is written
+/// as it arrives, stamped with the time since recording started.
+///
+/// See the [module docs](super) for the on-disk format and [`Replayer`](super::Replayer)
+/// to publish a recording again.
+pub struct Recorder<W> {
+	broadcast: moq_lite::BroadcastConsumer,
+	catalog: Option<crate::catalog::Consumer>,
+	writer: W,
+
+	/// Every rendition subscribed so far, so an ended track isn't subscribed again.
+	subscribed: HashSet<String>,
+	tracks: Vec<RecordTrack>,
+}
+
+struct RecordTrack {
+	name: String,
+	/// `None` once the track has ended; its groups may still be in progress.
+	track: Option<moq_lite::TrackConsumer>,
+	groups: Vec<moq_lite::GroupConsumer>,
+}
+
+impl<W: AsyncWrite + Unpin> Recorder<W> {
+	/// Subscribe to the catalog of `broadcast`, recording into `writer`.
+	///
+	/// Rendition tracks are subscribed once the first catalog arrives.
+	pub fn new(broadcast: moq_lite::BroadcastConsumer, writer: W) -> Result<Self, crate::Error> {
+		let catalog_track = broadcast.subscribe_track(&hang::Catalog::default_track())?;
+		let catalog = crate::catalog::Consumer::new(catalog_track);
+
+		Ok(Self {
+			broadcast,
+			catalog: Some(catalog),
+			writer,
+			subscribed: HashSet::new(),
+			tracks: Vec::new(),
+		})
+	}
+
+	/// Record until the catalog and every subscribed track have ended.
+	///
+	/// Returns the writer, flushed, so the caller can finish it (e.g. sync a file).
+	pub async fn run(mut self) -> anyhow::Result<W> {
+		let start = tokio::time::Instant::now();
+		self.writer.write_all(MAGIC).await?;
+
+		let mut buf = BytesMut::new();
+		while let Some(record) = conducer::wait(|waiter| self.poll_record(waiter)).await? {
+			record.encode(start.elapsed(), &mut buf)?;
+			self.writer.write_all(&buf).await?;
+			buf.clear();
+		}
+
+		self.writer.flush().await?;
+		Ok(self.writer)
+	}
+
+	fn poll_record(&mut self, waiter: &conducer::Waiter) -> Poll<Result<Option<Record>, crate::Error>> {
+		if let Some(catalog) = self.catalog.as_mut() {
+			match catalog.poll_next(waiter)? {
+				Poll::Ready(Some(snapshot)) => {
+					self.subscribe(&snapshot)?;
+					return Poll::Ready(Ok(Some(Record::Catalog(snapshot.to_vec()?.into()))));
+				}
+				Poll::Ready(None) => self.catalog = None,
+				Poll::Pending => {}
+			}
+		}
+
+		let mut index = 0;
+		while index < self.tracks.len() {
+			if let Some(record) = poll_track(&mut self.tracks[index], waiter) {
+				return Poll::Ready(Ok(Some(record)));
+			}
+
+			let track = &self.tracks[index];
+			if track.track.is_none() && track.groups.is_empty() {
+				self.tracks.swap_remove(index);
+			} else {
+				index += 1;
+			}
+		}
+
+		if self.catalog.is_none() && self.tracks.is_empty() {
+			return Poll::Ready(Ok(None));
+		}
+
+		Poll::Pending
+	}
+
+	// Subscribe to any rendition we haven't seen yet.
+	fn subscribe(&mut self, catalog: &hang::Catalog) -> Result<(), crate::Error> {
+		let names = catalog.video.renditions.keys().chain(catalog.audio.renditions.keys());
+
+		for name in names {
+			if !self.subscribed.insert(name.clone()) {
+				continue;
+			}
+
+			tracing::debug!(rendition = %name, "recording rendition");
+
+			let track = self.broadcast.subscribe_track(&moq_lite::Track::new(name.as_str()))?;
+			self.tracks.push(RecordTrack {
+				name: name.clone(),
+				track: Some(track),
+				groups: Vec::new(),
+			});
+		}
+
+		Ok(())
+	}
+}
+
+// Return the next record for a track: a frame or end from an active group, or a new group.
+fn poll_track(track: &mut RecordTrack, waiter: &conducer::Waiter) -> Option<Record> {
+	let mut index = 0;
+	while index < track.groups.len() {
+		let group = &mut track.groups[index];
+		let sequence = group.sequence;
+
+		let ended = match group.poll_read_frame(waiter) {
+			Poll::Ready(Ok(Some(payload))) => {
+				return Some(Record::Frame {
+					track: track.name.clone(),
+					sequence,
+					payload,
+				});
+			}
+			Poll::Ready(Ok(None)) => true,
+			Poll::Ready(Err(err)) => {
+				tracing::warn!(track = %track.name, sequence, %err, "recorded group failed");
+				true
+			}
+			Poll::Pending => false,
+		};
+
+		if ended {
+			track.groups.swap_remove(index);
+			return Some(Record::End {
+				track: track.name.clone(),
+				sequence,
+			});
+		}
+
+		index += 1;
+	}
+
+	let consumer = track.track.as_mut()?;
+	match consumer.poll_recv_group(waiter) {
+		Poll::Ready(Ok(Some(group))) => {
+			let sequence = group.sequence;
+			track.groups.push(group);
+			Some(Record::Group {
+				track: track.name.clone(),
+				sequence,
+			})
+		}
+		Poll::Ready(Ok(None)) => {
+			track.track = None;
+			None
+		}
+		Poll::Ready(Err(err)) => {
+			tracing::warn!(track = %track.name, %err, "recorded track failed");
+			track.track = None;
+			None
+		}
+		Poll::Pending => None,
+	}
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{MAGIC, Record};
+
+/// Re-publish a recording made by [`Recorder`](super::Recorder) into a broadcast.
+///
+/// Records are replayed at their original offsets from the start of the recording, so
+/// groups are created with the same sequence numbers and the same spacing as the live
+/// broadcast. Catalog snapshots are published through a
+/// [`catalog::Producer`](crate::catalog::Producer), which also keeps the MSF catalog in sync.
+pub struct Replayer<R> {
+	reader: R,
+	broadcast: moq_lite::BroadcastProducer,
+	catalog: crate::catalog::Producer,
+
+	tracks: HashMap<String, moq_lite::TrackProducer>,
+	groups: HashMap<(String, u64), moq_lite::GroupProducer>,
+}
+
+impl<R: AsyncRead + Unpin> Replayer<R> {
+	/// Replay the recording in `reader` into `broadcast`.
+	pub fn new(mut broadcast: moq_lite::BroadcastProducer, reader: R) -> Result<Self, crate::Error> {
+		let catalog = crate::catalog::Producer::new(&mut broadcast)?;
+
+		Ok(Self {
+			reader,
+			broadcast,
+			catalog,
+			tracks: HashMap::new(),
+			groups: HashMap::new(),
+		})
+	}
+
+	/// Replay until the end of the recording, then finish every track and the catalog.
+	pub async fn run(mut self) -> anyhow::Result<()> {
+		let mut magic = [0; MAGIC.len()];
+		self.reader.read_exact(&mut magic).await.context("missing header")?;
+		anyhow::ensure!(&magic == MAGIC, "not a recording");
+
+		let start = tokio::time::Instant::now();
+
+		while let Some((timestamp, record)) = Record::decode(&mut self.reader).await? {
+			tokio::time::sleep_until(start + timestamp).await;
+			self.replay(record)?;
+		}
+
+		for group in self.groups.values_mut() {
+			group.finish()?;
+		}
+
+		for track in self.tracks.values_mut() {
+			track.finish()?;
+		}
+
+		self.catalog.finish()?;
+
+		Ok(())
+	}
+
+	fn replay(&mut self, record: Record) -> anyhow::Result<()> {
+		match record {
+			Record::Catalog(json) => {
+				let catalog = hang::Catalog::from_slice(&json)?;
+
+				// Create the renditions first so they can be subscribed as soon as the catalog is seen.
+				let names = catalog.video.renditions.keys().chain(catalog.audio.renditions.keys());
+				for name in names {
+					self.track(name)?;
+				}
+
+				*self.catalog.lock() = catalog;
+			}
+			Record::Group { track, sequence } => {
+				let group = self.track(&track)?.create_group(moq_lite::Group { sequence })?;
+				self.groups.insert((track, sequence), group);
+			}
+			Record::Frame {
+				track,
+				sequence,
+				payload,
+			} => match self.groups.get_mut(&(track, sequence)) {
+				Some(group) => group.write_frame(payload)?,
+				None => tracing::warn!(sequence, "frame for unknown group; skipping"),
+			},
+			Record::End { track, sequence } => {
+				if let Some(mut group) = self.groups.remove(&(track, sequence)) {
+					group.finish()?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	// Return the track with the given name, creating it on first use.
+	fn track(&mut self, name: &str) -> Result<&mut moq_lite::TrackProducer, moq_lite::Error> {
+		if !self.tracks.contains_key(name) {
+			let track = self.broadcast.create_track(moq_lite::Track::new(name))?;
+			self.tracks.insert(name.to_string(), track);
+		}
+
+		Ok(self.tracks.get_mut(name).expect("track was just inserted"))
+	}
+}
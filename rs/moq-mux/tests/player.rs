@@ -0,0 +1,161 @@
+//! Integration test: play a hang broadcast over a loopback connection.
+//!
+//! The server publishes a catalog with one video and one audio rendition, plus a
+//! couple of frames on each track. The client connects, constructs a
+//! `BroadcastPlayer` for the announced broadcast, and verifies that it yields
+//! the decoded frames of both tracks.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use bytes::Bytes;
+use hang::catalog::{AudioCodec, AudioConfig, Container, H264, VideoConfig};
+use moq_mux::container::{Frame, Hang, Producer, Timestamp};
+use moq_mux::export::{BroadcastPlayer, PlayerFrame};
+use moq_native::moq_lite::{self, Origin};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn frame(micros: u64, keyframe: bool) -> Frame {
+	Frame {
+		timestamp: Timestamp::from_micros(micros).unwrap(),
+		payload: Bytes::from_static(b"payload"),
+		keyframe,
+		capture: None,
+	}
+}
+
+#[tokio::test]
+async fn player_yields_audio_and_video() {
+	// ── publisher (server) ──────────────────────────────────────────
+	let pub_origin = Origin::random().produce();
+	let mut broadcast = pub_origin.create_broadcast("test").expect("failed to create broadcast");
+	let mut catalog = moq_mux::catalog::Producer::new(&mut broadcast).expect("failed to create catalog");
+
+	let video_track = broadcast.create_track(moq_lite::Track::new("video")).unwrap();
+	let audio_track = broadcast.create_track(moq_lite::Track::new("audio")).unwrap();
+
+	{
+		let mut catalog = catalog.lock();
+		catalog.video.renditions = BTreeMap::from([(
+			"video".to_string(),
+			VideoConfig {
+				codec: H264 {
+					profile: 0x64,
+					constraints: 0x00,
+					level: 0x1f,
+					inline: false,
+				}
+				.into(),
+				description: None,
+				coded_width: Some(1280),
+				coded_height: Some(720),
+				display_ratio_width: None,
+				display_ratio_height: None,
+				bitrate: None,
+				framerate: None,
+				optimize_for_latency: None,
+				container: Container::Legacy,
+				jitter: None,
+				lang: None,
+			},
+		)]);
+		catalog.audio.renditions = BTreeMap::from([(
+			"audio".to_string(),
+			AudioConfig {
+				codec: AudioCodec::Opus,
+				sample_rate: 48_000,
+				channel_count: 2,
+				bitrate: None,
+				description: None,
+				container: Container::Legacy,
+				jitter: None,
+				target_loudness: None,
+				true_peak: None,
+				lang: None,
+			},
+		)]);
+	}
+
+	let mut video = Producer::new(video_track, Hang::Legacy);
+	video.write(frame(0, true)).unwrap();
+	video.write(frame(33_000, false)).unwrap();
+	video.finish().unwrap();
+
+	let mut audio = Producer::new(audio_track, Hang::Legacy);
+	audio.write(frame(0, true)).unwrap();
+	audio.write(frame(20_000, false)).unwrap();
+	audio.finish().unwrap();
+
+	catalog.finish().unwrap();
+
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+
+	let mut server = server_config.init().expect("failed to init server");
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	let server_handle = tokio::spawn(async move {
+		let request = server.accept().await.expect("no incoming connection");
+		let session = request.with_publish(pub_origin.consume()).ok().await?;
+
+		// Keep producers alive so the subscriber can read data.
+		let _broadcast = broadcast;
+
+		// Block until the client disconnects.
+		let _ = session.closed().await;
+		Ok::<_, anyhow::Error>(())
+	});
+
+	// ── subscriber (client) ─────────────────────────────────────────
+	let sub_origin = Origin::random().produce();
+	let mut announcements = sub_origin.consume();
+
+	let mut client_config = moq_native::ClientConfig::default();
+	client_config.tls.disable_verify = Some(true);
+
+	let client = client_config
+		.init()
+		.expect("failed to init client")
+		.with_consume(sub_origin);
+	let url: url::Url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+	let session = tokio::time::timeout(TIMEOUT, client.connect(url))
+		.await
+		.expect("client connect timed out")
+		.expect("client connect failed");
+
+	let (_, consumer) = tokio::time::timeout(TIMEOUT, announcements.announced())
+		.await
+		.expect("announce timed out")
+		.expect("origin closed");
+	let consumer = consumer.expect("expected announce, got unannounce");
+
+	let mut player = BroadcastPlayer::new(consumer).expect("failed to create player");
+
+	let mut video_frames = Vec::new();
+	let mut audio_frames = Vec::new();
+	while video_frames.len() < 2 || audio_frames.len() < 2 {
+		let frame = tokio::time::timeout(TIMEOUT, player.next())
+			.await
+			.expect("player timed out")
+			.expect("player failed")
+			.expect("player ended early");
+
+		match frame {
+			PlayerFrame::Video(frame) => video_frames.push(frame.timestamp.as_micros()),
+			PlayerFrame::Audio(frame) => audio_frames.push(frame.timestamp.as_micros()),
+		}
+	}
+
+	assert_eq!(player.video_rendition(), Some("video"));
+	assert_eq!(player.audio_rendition(), Some("audio"));
+	assert_eq!(video_frames, vec![0, 33_000]);
+	assert_eq!(audio_frames, vec![0, 20_000]);
+
+	drop(session);
+	server_handle
+		.await
+		.expect("server task panicked")
+		.expect("server task failed");
+}
@@ -1,7 +1,8 @@
 use crate::crypto;
-use crate::{Backoff, QuicBackend, Reconnect};
+use crate::{Backoff, CongestionControl, ConnectError, DEFAULT_CONNECT_TIMEOUT, QuicBackend, Reconnect};
 use anyhow::Context;
 use std::path::PathBuf;
+use std::time::Duration;
 use std::{net, sync::Arc};
 use url::Url;
 
@@ -81,6 +82,94 @@ pub struct ClientConfig {
 	)]
 	pub max_streams: Option<u64>,
 
+	/// The congestion control algorithm, defaulting to cubic.
+	/// Only supported by the quinn backend.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[arg(id = "client-congestion", long = "client-congestion", env = "MOQ_CLIENT_CONGESTION")]
+	pub congestion: Option<CongestionControl>,
+
+	/// Enable path MTU discovery, allowing packets larger than the conservative default.
+	///
+	/// Disabled by default. When enabled, probes search for a path MTU of up to 1452 bytes
+	/// of UDP payload and are repeated every 10 minutes. Probes are occasional padded packets,
+	/// so the overhead is small, and like any other packet they count as activity for the
+	/// idle timeout. Only supported by the quinn backend.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[arg(
+		id = "client-mtu-discovery",
+		long = "client-mtu-discovery",
+		env = "MOQ_CLIENT_MTU_DISCOVERY",
+		default_missing_value = "true",
+		num_args = 0..=1,
+		require_equals = true,
+		value_parser = clap::value_parser!(bool),
+	)]
+	pub mtu_discovery: Option<bool>,
+
+	/// Send the session handshake as 0-RTT data when reconnecting to a server, saving a round trip.
+	///
+	/// Disabled by default. Session tickets are cached per server name for the lifetime of the
+	/// client. 0-RTT data can be replayed by an attacker, so only enable this when repeating the
+	/// session setup is harmless, such as a subscriber whose setup is idempotent.
+	/// A server may still reject the 0-RTT data, in which case the connection attempt is repeated
+	/// or fails and is retried. Only supported by the quinn backend.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[arg(
+		id = "client-0rtt",
+		long = "client-0rtt",
+		env = "MOQ_CLIENT_0RTT",
+		default_missing_value = "true",
+		num_args = 0..=1,
+		require_equals = true,
+		value_parser = clap::value_parser!(bool),
+	)]
+	pub enable_0rtt: Option<bool>,
+
+	/// Close the connection after this long without any network activity.
+	/// Defaults to 30s.
+	#[arg(
+		id = "client-idle-timeout",
+		long = "client-idle-timeout",
+		env = "MOQ_CLIENT_IDLE_TIMEOUT",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub idle_timeout: Option<Duration>,
+
+	/// Send a keep-alive after this long without sending anything.
+	/// Defaults to 5s and must be less than half the idle timeout. Not supported by the quiche backend.
+	#[arg(
+		id = "client-keep-alive",
+		long = "client-keep-alive",
+		env = "MOQ_CLIENT_KEEP_ALIVE",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub keep_alive: Option<Duration>,
+
+	/// Give up on a connection attempt that hasn't finished the handshake after this long.
+	/// Covers the whole attempt, including the WebSocket fallback. Defaults to 10s.
+	#[arg(
+		id = "client-connect-timeout",
+		long = "client-connect-timeout",
+		env = "MOQ_CLIENT_CONNECT_TIMEOUT",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub connect_timeout: Option<Duration>,
+
+	/// Keep remote broadcasts announced for this long after the session closes, so
+	/// [`Client::reconnect`] can resubscribe their active tracks on the next session.
+	/// Disabled by default, in which case subscriptions end with the session.
+	#[arg(
+		id = "client-resubscribe",
+		long = "client-resubscribe",
+		env = "MOQ_CLIENT_RESUBSCRIBE",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub resubscribe: Option<Duration>,
+
 	/// Restrict the client to specific MoQ protocol version(s).
 	///
 	/// By default, the client offers all supported versions and lets the server choose.
@@ -194,6 +283,13 @@ impl Default for ClientConfig {
 			bind: "[::]:0".parse().unwrap(),
 			backend: None,
 			max_streams: None,
+			congestion: None,
+			mtu_discovery: None,
+			enable_0rtt: None,
+			idle_timeout: None,
+			keep_alive: None,
+			connect_timeout: None,
+			resubscribe: None,
 			version: Vec::new(),
 			tls: ClientTls::default(),
 			backoff: Backoff::default(),
@@ -203,14 +299,41 @@ impl Default for ClientConfig {
 	}
 }
 
+/// Returned by [`Client::connect`] when the handshake doesn't finish within the connect timeout.
+///
+/// It's wrapped in an [`anyhow::Error`], so use `downcast_ref` to tell it apart from other failures.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("connection timed out after {}", humantime::format_duration(*.0))]
+pub struct ConnectTimeout(pub Duration);
+
+/// The result of a successful [`Client::probe`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ProbeInfo {
+	/// The negotiated protocol version.
+	pub version: moq_lite::Version,
+
+	/// The transport's smoothed round-trip time, if the backend measures it.
+	pub rtt: Option<Duration>,
+
+	/// The maximum datagram payload the server accepts, if it supports datagrams.
+	pub max_datagram_size: Option<usize>,
+
+	/// How long the connection and MoQ handshake took.
+	pub elapsed: Duration,
+}
+
 /// Client for establishing MoQ connections over QUIC, WebTransport, or WebSocket.
 ///
 /// Create via [`ClientConfig::init`] or [`Client::new`].
 #[derive(Clone)]
 pub struct Client {
 	moq: moq_lite::Client,
+	consume: Option<moq_lite::OriginProducer>,
 	versions: moq_lite::Versions,
 	backoff: Backoff,
+	resubscribe: Option<Duration>,
+	connect_timeout: Duration,
 	#[cfg(feature = "websocket")]
 	websocket: super::ClientWebSocket,
 	tls: rustls::ClientConfig,
@@ -278,8 +401,11 @@ impl Client {
 		let versions = config.versions();
 		Ok(Self {
 			moq: moq_lite::Client::new().with_versions(versions.clone()),
+			consume: None,
 			versions,
 			backoff: config.backoff,
+			resubscribe: config.resubscribe,
+			connect_timeout: config.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT),
 			#[cfg(feature = "websocket")]
 			websocket: config.websocket,
 			tls,
@@ -318,18 +444,98 @@ impl Client {
 	}
 
 	pub fn with_consume(mut self, consume: impl Into<Option<moq_lite::OriginProducer>>) -> Self {
-		self.moq = self.moq.with_consume(consume);
+		self.consume = consume.into();
+		self.moq = self.moq.with_consume(self.consume.clone());
 		self
 	}
 
 	/// Start a background reconnect loop that connects to the given URL,
 	/// waits for the session to close, then reconnects with exponential backoff.
 	///
+	/// With [`ClientConfig::resubscribe`] set, remote broadcasts stay announced across a
+	/// reconnect, and tracks subscribed through them resume from the new session.
+	///
 	/// Returns a [`Reconnect`] handle. Drop or call [`Reconnect::close`] to stop.
 	pub fn reconnect(&self, url: Url) -> Reconnect {
 		Reconnect::new(self.clone(), url, self.backoff.clone())
 	}
 
+	/// Mirror remote broadcasts across reconnects, if configured with an origin to consume into.
+	pub(crate) fn resubscribe(&self) -> Option<crate::resubscribe::Resubscribe> {
+		let grace = self.resubscribe?;
+		let origin = self.consume.clone()?;
+		Some(crate::resubscribe::Resubscribe::new(origin, grace))
+	}
+
+	/// Connect to the given URL, retrying transport-level failures with the configured [`Backoff`].
+	///
+	/// Returns the first session that succeeds. Gives up immediately on a [`ConnectError::Fatal`],
+	/// such as an unsupported URL scheme or a certificate mismatch, or once the backoff runs out.
+	pub async fn connect_retry(&self, url: Url) -> Result<moq_lite::Session, ConnectError> {
+		Self::check_scheme(&url).map_err(ConnectError::Fatal)?;
+
+		let backoff = &self.backoff;
+		let mut delay = backoff.initial;
+		let start = tokio::time::Instant::now();
+		let mut attempt = 0;
+
+		loop {
+			attempt += 1;
+
+			let err = match self.connect(url.clone()).await {
+				Ok(session) => return Ok(session),
+				Err(err) => ConnectError::classify(err),
+			};
+
+			if !err.is_retryable() || backoff.exhausted(attempt, start) {
+				return Err(err);
+			}
+
+			let sleep = backoff.jitter(delay);
+			tracing::warn!(%url, %err, attempt, ?sleep, "connection failed, retrying");
+			tokio::time::sleep(sleep).await;
+			delay = backoff.next(delay);
+		}
+	}
+
+	/// Check that a server is up by completing a connection and the MoQ handshake, then closing.
+	///
+	/// Nothing is published or subscribed, even if this client was configured with origins,
+	/// so it's cheap enough for health checks. The attempt is bounded by the connect timeout.
+	pub async fn probe(&self, url: Url) -> anyhow::Result<ProbeInfo> {
+		let start = tokio::time::Instant::now();
+
+		let client = Self {
+			moq: moq_lite::Client::new().with_versions(self.versions.clone()),
+			consume: None,
+			..self.clone()
+		};
+		let mut session = client.connect(url).await?;
+
+		let info = ProbeInfo {
+			version: session.version(),
+			rtt: session.rtt(),
+			max_datagram_size: session.max_datagram_size(),
+			elapsed: start.elapsed(),
+		};
+
+		session.close(moq_lite::Error::Cancel);
+
+		Ok(info)
+	}
+
+	/// Reject URL schemes that no compiled backend can handle.
+	fn check_scheme(url: &Url) -> anyhow::Result<()> {
+		match url.scheme() {
+			"http" | "https" | "moqt" | "moql" | "moq" => Ok(()),
+			#[cfg(feature = "websocket")]
+			"ws" | "wss" => Ok(()),
+			#[cfg(feature = "iroh")]
+			"iroh" => Ok(()),
+			scheme => anyhow::bail!("unsupported URL scheme: {scheme}"),
+		}
+	}
+
 	#[cfg(not(any(
 		feature = "noq",
 		feature = "quinn",
@@ -349,7 +555,9 @@ impl Client {
 		feature = "websocket"
 	))]
 	pub async fn connect(&self, url: Url) -> anyhow::Result<moq_lite::Session> {
-		let session = self.connect_inner(url).await?;
+		let session = tokio::time::timeout(self.connect_timeout, self.connect_inner(url))
+			.await
+			.map_err(|_| ConnectTimeout(self.connect_timeout))??;
 		tracing::info!(version = %session.version(), "connected");
 		Ok(session)
 	}
@@ -586,6 +794,99 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_toml_mtu_discovery() {
+		let mut config: ClientConfig = toml::from_str("mtu_discovery = true").unwrap();
+		assert_eq!(config.mtu_discovery, Some(true));
+
+		config.update_from(["test"]);
+		assert_eq!(config.mtu_discovery, Some(true));
+	}
+
+	#[test]
+	fn test_cli_mtu_discovery() {
+		let config = ClientConfig::parse_from(["test", "--client-mtu-discovery"]);
+		assert_eq!(config.mtu_discovery, Some(true));
+
+		let config = ClientConfig::parse_from(["test", "--client-mtu-discovery=false"]);
+		assert_eq!(config.mtu_discovery, Some(false));
+
+		let config = ClientConfig::parse_from(["test"]);
+		assert_eq!(config.mtu_discovery, None);
+	}
+
+	#[test]
+	fn test_toml_idle_timeout() {
+		let config: ClientConfig = toml::from_str("idle_timeout = \"1m\"\nkeep_alive = \"10s\"").unwrap();
+		assert_eq!(config.idle_timeout, Some(Duration::from_secs(60)));
+		assert_eq!(config.keep_alive, Some(Duration::from_secs(10)));
+	}
+
+	#[test]
+	fn test_cli_idle_timeout() {
+		let config = ClientConfig::parse_from(["test", "--client-idle-timeout", "1m", "--client-keep-alive", "10s"]);
+		assert_eq!(config.idle_timeout, Some(Duration::from_secs(60)));
+		assert_eq!(config.keep_alive, Some(Duration::from_secs(10)));
+
+		let config = ClientConfig::parse_from(["test"]);
+		assert_eq!(config.idle_timeout, None);
+		assert_eq!(config.keep_alive, None);
+	}
+
+	#[test]
+	fn test_connect_timeout() {
+		let config: ClientConfig = toml::from_str("connect_timeout = \"3s\"").unwrap();
+		assert_eq!(config.connect_timeout, Some(Duration::from_secs(3)));
+
+		let config = ClientConfig::parse_from(["test", "--client-connect-timeout", "500ms"]);
+		assert_eq!(config.connect_timeout, Some(Duration::from_millis(500)));
+
+		let config = ClientConfig::parse_from(["test"]);
+		assert_eq!(config.connect_timeout, None);
+	}
+
+	#[tokio::test]
+	async fn test_connect_times_out() {
+		// A UDP socket that never answers, so the QUIC handshake stalls.
+		let blackhole = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+		let addr = blackhole.local_addr().unwrap();
+
+		let config = ClientConfig {
+			connect_timeout: Some(Duration::from_millis(200)),
+			..Default::default()
+		};
+		let client = config.init().unwrap();
+
+		let url = Url::parse(&format!("moql://{addr}")).unwrap();
+		let Err(err) = client.connect(url).await else {
+			panic!("expected the connection to time out");
+		};
+		let timeout = err.downcast_ref::<ConnectTimeout>().expect("expected a timeout");
+		assert_eq!(timeout.0, Duration::from_millis(200));
+	}
+
+	#[test]
+	fn test_idle_settings() {
+		let defaults = crate::idle_settings(None, None).unwrap();
+		assert_eq!(defaults, (crate::DEFAULT_IDLE_TIMEOUT, crate::DEFAULT_KEEP_ALIVE));
+
+		let custom = crate::idle_settings(Some(Duration::from_secs(60)), Some(Duration::from_secs(20))).unwrap();
+		assert_eq!(custom, (Duration::from_secs(60), Duration::from_secs(20)));
+
+		// The keep-alive must be strictly less than half the idle timeout.
+		assert!(crate::idle_settings(Some(Duration::from_secs(10)), None).is_err());
+		assert!(crate::idle_settings(None, Some(Duration::from_secs(15))).is_err());
+	}
+
+	#[tokio::test]
+	async fn test_connect_retry_fatal_scheme() {
+		let client = ClientConfig::default().init().unwrap();
+		let url = Url::parse("ftp://localhost").unwrap();
+
+		let res = client.connect_retry(url).await;
+		assert!(matches!(res, Err(ConnectError::Fatal(_))));
+	}
+
 	#[test]
 	fn test_cli_no_version_defaults_to_all() {
 		let config = ClientConfig::parse_from(["test"]);
@@ -152,10 +152,12 @@ impl IrohRequest {
 	}
 
 	/// Reject the session.
-	pub async fn close(self, status: http::StatusCode) -> Result<(), web_transport_iroh::ServerError> {
+	///
+	/// The reason is sent in the QUIC close frame; a WebTransport rejection only carries the status.
+	pub async fn close(self, status: http::StatusCode, reason: &str) -> Result<(), web_transport_iroh::ServerError> {
 		match self {
 			IrohRequest::Quic { request } => {
-				request.close(status);
+				request.conn().close(status.as_u16().into(), reason.as_bytes());
 				Ok(())
 			}
 			IrohRequest::WebTransport { request, .. } => request.reject(status).await,
@@ -201,7 +203,7 @@ pub(crate) async fn connect(
 	let session = match alpn.as_str() {
 		web_transport_iroh::ALPN_H3 => {
 			let conn = connecting.await?;
-			let url = url_set_scheme(url, "https")?;
+			let url = crate::util::url_set_scheme(url, "https")?;
 
 			let mut request = ConnectRequest::new(url);
 			for alpn in moq_lite::ALPNS {
@@ -219,19 +221,3 @@ pub(crate) async fn connect(
 
 	Ok(session)
 }
-
-/// Returns a new URL with a changed scheme.
-///
-/// [`Url::set_scheme`] returns an error if the scheme change is not valid according to
-/// [the URL specification's section on legal scheme state overrides](https://url.spec.whatwg.org/#scheme-state).
-///
-/// This function allows all scheme changes, as long as the resulting URL is valid.
-fn url_set_scheme(url: Url, scheme: &str) -> anyhow::Result<Url> {
-	let url = format!(
-		"{}:{}",
-		scheme,
-		url.to_string().split_once(":").context("invalid URL")?.1
-	)
-	.parse()?;
-	Ok(url)
-}
@@ -8,9 +8,44 @@
 //!
 //! See [`Client`] for connecting to relays and [`Server`] for accepting connections.
 
+use std::time::Duration;
+
 /// Default maximum number of concurrent QUIC streams (bidi and uni) per connection.
 pub(crate) const DEFAULT_MAX_STREAMS: u64 = 1024;
 
+/// Default QUIC idle timeout.
+pub(crate) const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default QUIC keep-alive interval.
+pub(crate) const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(5);
+
+/// Default limit on how long a server drain waits for open sessions, see [Server::drain].
+pub(crate) const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default limit on how long a client connection attempt may take.
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Resolve the idle timeout and keep-alive interval, applying the defaults.
+///
+/// The keep-alive must be less than half the idle timeout, so a single lost keep-alive
+/// doesn't close an otherwise healthy connection.
+pub(crate) fn idle_settings(
+	idle_timeout: Option<Duration>,
+	keep_alive: Option<Duration>,
+) -> anyhow::Result<(Duration, Duration)> {
+	let idle_timeout = idle_timeout.unwrap_or(DEFAULT_IDLE_TIMEOUT);
+	let keep_alive = keep_alive.unwrap_or(DEFAULT_KEEP_ALIVE);
+
+	anyhow::ensure!(
+		keep_alive < idle_timeout / 2,
+		"keep-alive ({}) must be less than half the idle timeout ({})",
+		humantime::format_duration(keep_alive),
+		humantime::format_duration(idle_timeout),
+	);
+
+	Ok((idle_timeout, keep_alive))
+}
+
 mod client;
 mod crypto;
 #[cfg(feature = "jemalloc")]
@@ -21,6 +56,7 @@ mod noq;
 #[cfg(feature = "quinn")]
 mod quinn;
 mod reconnect;
+mod resubscribe;
 mod server;
 #[cfg(any(feature = "noq", feature = "quinn"))]
 mod tls;
@@ -71,3 +107,21 @@ pub enum QuicBackend {
 	#[cfg(feature = "noq")]
 	Noq,
 }
+
+/// The congestion control algorithm used by the QUIC backend.
+///
+/// Currently only honored by the quinn backend.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum CongestionControl {
+	/// CUBIC, the loss-based default.
+	#[default]
+	Cubic,
+
+	/// BBR, model-based and better suited to paths with a high bandwidth-delay product.
+	Bbr,
+
+	/// NewReno, the classic loss-based algorithm.
+	NewReno,
+}
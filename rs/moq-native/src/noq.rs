@@ -2,9 +2,9 @@ use crate::client::ClientConfig;
 use crate::server::{ServerConfig, ServerId, ServerTlsInfo};
 use crate::tls::{FingerprintVerifier, ServeCerts};
 use anyhow::Context;
+use std::net;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use std::{net, time};
 use url::Url;
 
 use web_transport_noq::noq;
@@ -23,8 +23,9 @@ impl NoqClient {
 		let socket = std::net::UdpSocket::bind(config.bind).context("failed to bind UDP socket")?;
 
 		let mut transport = noq::TransportConfig::default();
-		transport.max_idle_timeout(Some(time::Duration::from_secs(30).try_into().unwrap()));
-		transport.keep_alive_interval(Some(time::Duration::from_secs(5)));
+		let (idle_timeout, keep_alive) = crate::idle_settings(config.idle_timeout, config.keep_alive)?;
+		transport.max_idle_timeout(Some(idle_timeout.try_into().context("idle timeout too large")?));
+		transport.keep_alive_interval(Some(keep_alive));
 		transport.mtu_discovery_config(None); // Disable MTU discovery
 
 		let max_streams = config.max_streams.unwrap_or(crate::DEFAULT_MAX_STREAMS);
@@ -82,10 +83,10 @@ impl NoqClient {
 				.error_for_status()
 				.context("fingerprint request failed")?;
 
-			let fingerprint = resp.text().await.context("failed to read fingerprint")?;
-			let fingerprint = hex::decode(fingerprint.trim()).context("invalid fingerprint")?;
+			let fingerprints = resp.text().await.context("failed to read fingerprint")?;
+			let fingerprints = FingerprintVerifier::parse(&fingerprints)?;
 
-			let verifier = FingerprintVerifier::new(config.crypto_provider().clone(), fingerprint);
+			let verifier = FingerprintVerifier::new(config.crypto_provider().clone(), fingerprints);
 			config.dangerous().set_certificate_verifier(Arc::new(verifier));
 
 			url.set_scheme("https").expect("failed to set scheme");
@@ -99,7 +100,15 @@ impl NoqClient {
 				.iter()
 				.map(|alpn| alpn.as_bytes().to_vec())
 				.collect(),
-			_ => anyhow::bail!("url scheme must be 'https', 'moqt', or 'moql'"),
+			// Offer both and let the server pick. H3 is last because it requires WebTransport framing.
+			"moq" => self
+				.versions
+				.alpns()
+				.iter()
+				.map(|alpn| alpn.as_bytes().to_vec())
+				.chain(std::iter::once(web_transport_noq::ALPN.as_bytes().to_vec()))
+				.collect(),
+			_ => anyhow::bail!("url scheme must be 'https', 'moqt', 'moql', or 'moq'"),
 		};
 
 		config.alpn_protocols = alpns;
@@ -121,7 +130,7 @@ impl NoqClient {
 
 		let session = match url.scheme() {
 			"https" => web_transport_noq::Session::connect(connection, request).await?,
-			"moqt" | "moql" => {
+			"moqt" | "moql" | "moq" => {
 				let handshake = connection
 					.handshake_data()
 					.context("missing handshake data")?
@@ -131,6 +140,12 @@ impl NoqClient {
 				let alpn = handshake.protocol.context("missing ALPN")?;
 				let alpn = String::from_utf8(alpn).context("failed to decode ALPN")?;
 
+				// The server picked HTTP/3, so continue with a WebTransport handshake.
+				if alpn == web_transport_noq::ALPN {
+					request.url = crate::util::url_set_scheme(url, "https")?;
+					return Ok(web_transport_noq::Session::connect(connection, request).await?);
+				}
+
 				let response = web_transport_noq::proto::ConnectResponse::OK.with_protocol(alpn);
 				web_transport_noq::Session::raw(connection, request, response)
 			}
@@ -151,8 +166,9 @@ pub(crate) struct NoqServer {
 impl NoqServer {
 	pub fn new(config: ServerConfig) -> anyhow::Result<Self> {
 		let mut transport = noq::TransportConfig::default();
-		transport.max_idle_timeout(Some(Duration::from_secs(30).try_into().unwrap()));
-		transport.keep_alive_interval(Some(Duration::from_secs(5)));
+		let (idle_timeout, keep_alive) = crate::idle_settings(config.idle_timeout, config.keep_alive)?;
+		transport.max_idle_timeout(Some(idle_timeout.try_into().context("idle timeout too large")?));
+		transport.keep_alive_interval(Some(keep_alive));
 		transport.mtu_discovery_config(None); // Disable MTU discovery
 
 		let max_streams = config.max_streams.unwrap_or(crate::DEFAULT_MAX_STREAMS);
@@ -255,6 +271,7 @@ pub(crate) enum NoqRequest {
 	WebTransport {
 		request: web_transport_noq::Request,
 		alpns: Vec<&'static str>,
+		remote: net::SocketAddr,
 	},
 }
 
@@ -284,10 +301,11 @@ impl NoqRequest {
 		match alpn.as_str() {
 			web_transport_noq::ALPN => {
 				// Wait for the CONNECT request.
+				let remote = conn.remote_address();
 				let request = web_transport_noq::Request::accept(conn)
 					.await
 					.context("failed to receive WebTransport request")?;
-				Ok(Self::WebTransport { request, alpns })
+				Ok(Self::WebTransport { request, alpns, remote })
 			}
 			alpn if moq_lite::ALPNS.contains(&alpn) => {
 				anyhow::ensure!(!host.is_empty(), "missing server name for raw QUIC connection");
@@ -319,7 +337,7 @@ impl NoqRequest {
 				request,
 				response,
 			} => Ok(web_transport_noq::Session::raw(connection, request, response)),
-			NoqRequest::WebTransport { request, alpns } => {
+			NoqRequest::WebTransport { request, alpns, .. } => {
 				let mut response = web_transport_noq::proto::ConnectResponse::OK;
 				if let Some(protocol) = request.protocols.iter().find(|p| alpns.contains(&p.as_str())) {
 					response = response.with_protocol(protocol);
@@ -337,14 +355,23 @@ impl NoqRequest {
 		}
 	}
 
-	/// Reject the session with a status code.
+	/// Returns the address of the client.
+	pub fn remote_addr(&self) -> net::SocketAddr {
+		match self {
+			NoqRequest::Raw { connection, .. } => connection.remote_address(),
+			NoqRequest::WebTransport { remote, .. } => *remote,
+		}
+	}
+
+	/// Reject the session with a status code, sending the reason in the QUIC close frame of a raw session.
 	pub async fn close(
 		self,
 		status: web_transport_noq::http::StatusCode,
+		reason: &str,
 	) -> Result<(), web_transport_noq::ServerError> {
 		match self {
 			NoqRequest::Raw { connection, .. } => {
-				connection.close(status.as_u16().into(), status.as_str().as_bytes());
+				connection.close(status.as_u16().into(), reason.as_bytes());
 				Ok(())
 			}
 			NoqRequest::WebTransport { request, alpns: _, .. } => request.reject(status).await,
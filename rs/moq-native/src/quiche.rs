@@ -1,16 +1,30 @@
 use crate::client::ClientConfig;
 use crate::crypto;
-use crate::server::{ServerConfig, ServerTlsInfo};
+use crate::server::{CertAlgorithm, ServerConfig, ServerTlsInfo};
 use anyhow::Context;
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use std::fs;
-use std::io::{self, Cursor, Read};
+use std::io::Cursor;
 use std::net;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use url::Url;
 use web_transport_quiche::proto::ConnectRequest;
 
+/// Validate the configured idle timeout, returning it only if it overrides quiche's default.
+///
+/// quiche doesn't send keep-alives, so a configured interval is ignored.
+fn idle_timeout(idle_timeout: Option<Duration>, keep_alive: Option<Duration>) -> anyhow::Result<Option<Duration>> {
+	crate::idle_settings(idle_timeout, keep_alive)?;
+
+	if keep_alive.is_some() {
+		tracing::warn!("keep-alive is not supported with the quiche backend; ignoring");
+	}
+
+	Ok(idle_timeout)
+}
+
 // ── Client ──────────────────────────────────────────────────────────
 
 #[derive(Clone)]
@@ -18,6 +32,7 @@ pub(crate) struct QuicheClient {
 	pub bind: net::SocketAddr,
 	pub disable_verify: bool,
 	pub max_streams: u64,
+	pub idle_timeout: Option<Duration>,
 	pub versions: moq_lite::Versions,
 }
 
@@ -31,6 +46,7 @@ impl QuicheClient {
 			bind: config.bind,
 			disable_verify: config.tls.disable_verify.unwrap_or_default(),
 			max_streams: config.max_streams.unwrap_or(crate::DEFAULT_MAX_STREAMS),
+			idle_timeout: idle_timeout(config.idle_timeout, config.keep_alive)?,
 			versions: config.versions(),
 		})
 	}
@@ -51,7 +67,15 @@ impl QuicheClient {
 				.iter()
 				.map(|alpn| alpn.as_bytes().to_vec())
 				.collect(),
-			_ => anyhow::bail!("url scheme must be 'https', 'moqt', or 'moql'"),
+			// Offer both and let the server pick. H3 is last because it requires WebTransport framing.
+			"moq" => self
+				.versions
+				.alpns()
+				.iter()
+				.map(|alpn| alpn.as_bytes().to_vec())
+				.chain(std::iter::once(web_transport_quiche::ALPN.as_bytes().to_vec()))
+				.collect(),
+			_ => anyhow::bail!("url scheme must be 'https', 'moqt', 'moql', or 'moq'"),
 		};
 
 		let mut settings = web_transport_quiche::Settings::default();
@@ -59,6 +83,9 @@ impl QuicheClient {
 		settings.alpn = alpns;
 		settings.initial_max_streams_bidi = self.max_streams;
 		settings.initial_max_streams_uni = self.max_streams;
+		if let Some(idle_timeout) = self.idle_timeout {
+			settings.max_idle_timeout = Some(idle_timeout);
+		}
 
 		let builder = web_transport_quiche::ez::ClientBuilder::default()
 			.with_settings(settings)
@@ -83,7 +110,7 @@ impl QuicheClient {
 					.context("failed to connect to quiche server")?;
 				Ok(session)
 			}
-			"moqt" | "moql" => {
+			"moqt" | "moql" | "moq" => {
 				// Raw QUIC mode
 				let conn = builder
 					.connect(&host, port)
@@ -93,6 +120,15 @@ impl QuicheClient {
 				let alpn = conn.alpn().context("missing ALPN")?;
 				let alpn = std::str::from_utf8(&alpn).context("failed to decode ALPN")?;
 
+				// The server picked HTTP/3, so continue with a WebTransport handshake.
+				if alpn == web_transport_quiche::ALPN {
+					request.url = crate::util::url_set_scheme(url, "https")?;
+					let session = web_transport_quiche::Connection::connect(conn, request)
+						.await
+						.context("failed to connect to quiche server")?;
+					return Ok(session);
+				}
+
 				let response = web_transport_quiche::proto::ConnectResponse::OK.with_protocol(alpn);
 				Ok(web_transport_quiche::Connection::raw(conn, request, response))
 			}
@@ -118,11 +154,13 @@ impl QuicheServer {
 			.context("failed to resolve bind address")?;
 
 		let (chain, key) = if !config.tls.generate.is_empty() {
-			generate_quiche_cert(&config.tls.generate)?
+			generate_quiche_cert(&config.tls.generate, config.tls.generate_alg.unwrap_or_default())?
+		} else if let Some((cert, key)) = config.tls.pem()? {
+			load_quiche_pem(cert, key)?
 		} else {
 			anyhow::ensure!(
 				!config.tls.cert.is_empty() && !config.tls.key.is_empty(),
-				"--tls-cert and --tls-key are required with the quiche backend"
+				"--tls-cert and --tls-key (or their -pem variants) are required with the quiche backend"
 			);
 			anyhow::ensure!(
 				config.tls.cert.len() == config.tls.key.len(),
@@ -161,6 +199,9 @@ impl QuicheServer {
 		settings.alpn = alpns;
 		settings.initial_max_streams_bidi = max_streams;
 		settings.initial_max_streams_uni = max_streams;
+		if let Some(idle_timeout) = idle_timeout(config.idle_timeout, config.keep_alive)? {
+			settings.max_idle_timeout = Some(idle_timeout);
+		}
 
 		let server = web_transport_quiche::ez::ServerBuilder::default()
 			.with_settings(settings)
@@ -198,21 +239,22 @@ impl QuicheServer {
 fn load_quiche_cert(
 	cert_path: &PathBuf,
 	key_path: &PathBuf,
-) -> anyhow::Result<(Vec<CertificateDer<'static>>, rustls::pki_types::PrivateKeyDer<'static>)> {
-	let chain_file = fs::File::open(cert_path).context("failed to open cert file")?;
-	let mut chain_reader = io::BufReader::new(chain_file);
+) -> anyhow::Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+	let chain = fs::read(cert_path).context("failed to open cert file")?;
+	let key = fs::read(key_path).context("failed to open key file")?;
 
-	let chain: Vec<CertificateDer> = rustls_pemfile::certs(&mut chain_reader)
+	load_quiche_pem(&chain, &key)
+}
+
+/// Parse a PEM certificate chain and private key.
+fn load_quiche_pem(chain: &[u8], key: &[u8]) -> anyhow::Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+	let chain: Vec<CertificateDer> = rustls_pemfile::certs(&mut Cursor::new(chain))
 		.collect::<Result<_, _>>()
 		.context("failed to read certs")?;
 
 	anyhow::ensure!(!chain.is_empty(), "could not find certificate");
 
-	let mut key_buf = Vec::new();
-	let mut key_file = fs::File::open(key_path).context("failed to open key file")?;
-	key_file.read_to_end(&mut key_buf)?;
-
-	let key = rustls_pemfile::private_key(&mut Cursor::new(&key_buf))?.context("missing private key")?;
+	let key = rustls_pemfile::private_key(&mut Cursor::new(key))?.context("missing private key")?;
 
 	Ok((chain, key))
 }
@@ -220,8 +262,9 @@ fn load_quiche_cert(
 #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
 fn generate_quiche_cert(
 	hostnames: &[String],
+	alg: CertAlgorithm,
 ) -> anyhow::Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
-	let key_pair = rcgen::KeyPair::generate()?;
+	let key_pair = alg.generate()?;
 
 	let mut params = rcgen::CertificateParams::new(hostnames)?;
 
@@ -240,7 +283,8 @@ fn generate_quiche_cert(
 
 #[cfg(not(any(feature = "aws-lc-rs", feature = "ring")))]
 fn generate_quiche_cert(
-	hostnames: &[String],
+	_hostnames: &[String],
+	_alg: CertAlgorithm,
 ) -> anyhow::Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
 	anyhow::bail!("no crypto provider available; enable aws-lc-rs or ring feature");
 }
@@ -257,6 +301,7 @@ pub(crate) enum QuicheRequest {
 	WebTransport {
 		request: web_transport_quiche::h3::Request,
 		alpns: Vec<&'static str>,
+		remote: net::SocketAddr,
 	},
 }
 
@@ -278,10 +323,11 @@ impl QuicheRequest {
 		match alpn {
 			web_transport_quiche::ALPN => {
 				// WebTransport over HTTP/3
+				let remote = conn.peer_addr();
 				let request = web_transport_quiche::h3::Request::accept(conn)
 					.await
 					.context("failed to accept WebTransport request")?;
-				Ok(Self::WebTransport { request, alpns })
+				Ok(Self::WebTransport { request, alpns, remote })
 			}
 			alpn if moq_lite::ALPNS.contains(&alpn) => Ok(Self::Raw {
 				connection: conn,
@@ -301,7 +347,7 @@ impl QuicheRequest {
 				request,
 				response,
 			} => Ok(web_transport_quiche::Connection::raw(connection, request, response)),
-			QuicheRequest::WebTransport { request, alpns } => {
+			QuicheRequest::WebTransport { request, alpns, .. } => {
 				let mut response = web_transport_quiche::proto::ConnectResponse::OK;
 				// Pick the first sub-protocol that we actually support.
 				// This is the WebTransport equivalent of ALPN negotiation.
@@ -321,14 +367,23 @@ impl QuicheRequest {
 		}
 	}
 
-	/// Reject the session with a status code.
+	/// Returns the address of the client.
+	pub fn remote_addr(&self) -> net::SocketAddr {
+		match self {
+			QuicheRequest::Raw { connection, .. } => connection.peer_addr(),
+			QuicheRequest::WebTransport { remote, .. } => *remote,
+		}
+	}
+
+	/// Reject the session with a status code, sending the reason in the QUIC close frame of a raw session.
 	pub async fn reject(
 		self,
 		status: web_transport_quiche::http::StatusCode,
+		reason: &str,
 	) -> Result<(), web_transport_quiche::ServerError> {
 		match self {
 			QuicheRequest::Raw { connection, .. } => {
-				let _: () = connection.close(status.as_u16().into(), status.as_str());
+				let _: () = connection.close(status.as_u16().into(), reason);
 				Ok(())
 			}
 			QuicheRequest::WebTransport { request, alpns: _, .. } => request.reject(status).await,
@@ -1,13 +1,28 @@
+use crate::CongestionControl;
 use crate::client::ClientConfig;
 use crate::server::{PeerIdentity, ServerConfig, ServerId, ServerTlsInfo};
 use crate::tls::{FingerprintVerifier, ServeCerts};
 use anyhow::Context;
 use rustls::pki_types::CertificateDer;
+use std::net;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use std::{net, time};
 use url::Url;
 
+/// Build the controller factory for the configured congestion control algorithm.
+fn congestion_controller(congestion: CongestionControl) -> Arc<dyn quinn::congestion::ControllerFactory + Send + Sync> {
+	match congestion {
+		CongestionControl::Cubic => Arc::new(quinn::congestion::CubicConfig::default()),
+		CongestionControl::Bbr => Arc::new(quinn::congestion::BbrConfig::default()),
+		CongestionControl::NewReno => Arc::new(quinn::congestion::NewRenoConfig::default()),
+	}
+}
+
+/// Path MTU discovery is disabled unless explicitly enabled; quinn's defaults cap probes at 1452 bytes.
+fn mtu_discovery(enabled: bool) -> Option<quinn::MtuDiscoveryConfig> {
+	enabled.then(quinn::MtuDiscoveryConfig::default)
+}
+
 // ── Client ──────────────────────────────────────────────────────────
 
 #[derive(Clone)]
@@ -15,22 +30,26 @@ pub(crate) struct QuinnClient {
 	pub quic: quinn::Endpoint,
 	pub transport: Arc<quinn::TransportConfig>,
 	pub versions: moq_lite::Versions,
+
+	/// Session tickets keyed by server name, set when 0-RTT is enabled.
+	pub sessions: Option<Arc<rustls::client::ClientSessionMemoryCache>>,
 }
 
 impl QuinnClient {
 	pub fn new(config: &ClientConfig) -> anyhow::Result<Self> {
 		let socket = std::net::UdpSocket::bind(config.bind).context("failed to bind UDP socket")?;
 
-		// TODO Validate the BBR implementation before enabling it
 		let mut transport = quinn::TransportConfig::default();
-		transport.max_idle_timeout(Some(time::Duration::from_secs(30).try_into().unwrap()));
-		transport.keep_alive_interval(Some(time::Duration::from_secs(5)));
-		transport.mtu_discovery_config(None); // Disable MTU discovery
+		let (idle_timeout, keep_alive) = crate::idle_settings(config.idle_timeout, config.keep_alive)?;
+		transport.max_idle_timeout(Some(idle_timeout.try_into().context("idle timeout too large")?));
+		transport.keep_alive_interval(Some(keep_alive));
+		transport.mtu_discovery_config(mtu_discovery(config.mtu_discovery.unwrap_or_default()));
 
 		let max_streams = config.max_streams.unwrap_or(crate::DEFAULT_MAX_STREAMS);
 		let max_streams = quinn::VarInt::from_u64(max_streams).unwrap_or(quinn::VarInt::MAX);
 		transport.max_concurrent_bidi_streams(max_streams);
 		transport.max_concurrent_uni_streams(max_streams);
+		transport.congestion_controller_factory(congestion_controller(config.congestion.unwrap_or_default()));
 
 		let transport = Arc::new(transport);
 
@@ -42,10 +61,16 @@ impl QuinnClient {
 		let quic =
 			quinn::Endpoint::new(endpoint_config, None, socket, runtime).context("failed to create QUIC endpoint")?;
 
+		let sessions = config
+			.enable_0rtt
+			.unwrap_or_default()
+			.then(|| Arc::new(rustls::client::ClientSessionMemoryCache::new(256)));
+
 		Ok(Self {
 			quic,
 			transport,
 			versions: config.versions(),
+			sessions,
 		})
 	}
 
@@ -82,10 +107,10 @@ impl QuinnClient {
 				.error_for_status()
 				.context("fingerprint request failed")?;
 
-			let fingerprint = resp.text().await.context("failed to read fingerprint")?;
-			let fingerprint = hex::decode(fingerprint.trim()).context("invalid fingerprint")?;
+			let fingerprints = resp.text().await.context("failed to read fingerprint")?;
+			let fingerprints = FingerprintVerifier::parse(&fingerprints)?;
 
-			let verifier = FingerprintVerifier::new(config.crypto_provider().clone(), fingerprint);
+			let verifier = FingerprintVerifier::new(config.crypto_provider().clone(), fingerprints);
 			config.dangerous().set_certificate_verifier(Arc::new(verifier));
 
 			url.set_scheme("https").expect("failed to set scheme");
@@ -99,19 +124,24 @@ impl QuinnClient {
 				.iter()
 				.map(|alpn| alpn.as_bytes().to_vec())
 				.collect(),
-			_ => anyhow::bail!("url scheme must be 'https', 'moqt', or 'moql'"),
+			// Offer both and let the server pick. H3 is last because it requires WebTransport framing.
+			"moq" => self
+				.versions
+				.alpns()
+				.iter()
+				.map(|alpn| alpn.as_bytes().to_vec())
+				.chain(std::iter::once(web_transport_quinn::ALPN.as_bytes().to_vec()))
+				.collect(),
+			_ => anyhow::bail!("url scheme must be 'https', 'moqt', 'moql', or 'moq'"),
 		};
 
-		config.alpn_protocols = alpns;
-		config.key_log = Arc::new(rustls::KeyLogFile::new());
-
-		let config: quinn::crypto::rustls::QuicClientConfig = config.try_into()?;
-		let mut config = quinn::ClientConfig::new(Arc::new(config));
-		config.transport_config(self.transport.clone());
+		config.alpn_protocols = alpns.clone();
 
 		tracing::debug!(%url, %ip, "connecting");
 
-		let connection = self.quic.connect_with(config, ip, &host)?.await?;
+		let (connection, zero_rtt) = self
+			.handshake(self.quic_config(config.clone(), true)?, ip, &host)
+			.await?;
 		tracing::Span::current().record("id", connection.stable_id());
 
 		let mut request = web_transport_quinn::proto::ConnectRequest::new(url.clone());
@@ -120,17 +150,40 @@ impl QuinnClient {
 		}
 
 		let session = match url.scheme() {
-			"https" => web_transport_quinn::Session::connect(connection, request).await?,
-			"moqt" | "moql" => {
-				let handshake = connection
-					.handshake_data()
-					.context("missing handshake data")?
-					.downcast::<quinn::crypto::rustls::HandshakeData>()
-					.unwrap();
-
-				let alpn = handshake.protocol.context("missing ALPN")?;
+			"https" => match zero_rtt {
+				Some(accepted) => {
+					let connect = web_transport_quinn::Session::connect(connection, request.clone());
+					tokio::select! {
+						res = connect => res?,
+						// The CONNECT was sent as 0-RTT data and lost, so start over with a full handshake.
+						false = accepted => {
+							tracing::debug!(%url, "0-RTT rejected, reconnecting");
+							let (connection, _) = self.handshake(self.quic_config(config, false)?, ip, &host).await?;
+							web_transport_quinn::Session::connect(connection, request).await?
+						}
+					}
+				}
+				None => web_transport_quinn::Session::connect(connection, request).await?,
+			},
+			"moqt" | "moql" | "moq" => {
+				let alpn = match zero_rtt {
+					// The resumed session can only use the ALPN we offered.
+					Some(_) if alpns.len() == 1 => alpns[0].clone(),
+					// Otherwise the choice isn't known until the handshake completes.
+					Some(accepted) => {
+						accepted.await;
+						Self::alpn(&connection)?
+					}
+					None => Self::alpn(&connection)?,
+				};
 				let alpn = String::from_utf8(alpn).context("failed to decode ALPN")?;
 
+				// The server picked HTTP/3, so continue with a WebTransport handshake.
+				if alpn == web_transport_quinn::ALPN {
+					request.url = crate::util::url_set_scheme(url, "https")?;
+					return Ok(web_transport_quinn::Session::connect(connection, request).await?);
+				}
+
 				let response = web_transport_quinn::proto::ConnectResponse::OK.with_protocol(alpn);
 				web_transport_quinn::Session::raw(connection, request, response)
 			}
@@ -139,6 +192,51 @@ impl QuinnClient {
 
 		Ok(session)
 	}
+
+	fn alpn(connection: &quinn::Connection) -> anyhow::Result<Vec<u8>> {
+		let handshake = connection
+			.handshake_data()
+			.context("missing handshake data")?
+			.downcast::<quinn::crypto::rustls::HandshakeData>()
+			.unwrap();
+
+		handshake.protocol.context("missing ALPN")
+	}
+
+	fn quic_config(&self, mut tls: rustls::ClientConfig, early_data: bool) -> anyhow::Result<quinn::ClientConfig> {
+		tls.key_log = Arc::new(rustls::KeyLogFile::new());
+
+		if let Some(sessions) = &self.sessions {
+			tls.resumption = rustls::client::Resumption::store(sessions.clone());
+			tls.enable_early_data = early_data;
+		}
+
+		let tls: quinn::crypto::rustls::QuicClientConfig = tls.try_into()?;
+		let mut config = quinn::ClientConfig::new(Arc::new(tls));
+		config.transport_config(self.transport.clone());
+		Ok(config)
+	}
+
+	/// Establish the QUIC connection, returning early with 0-RTT if a cached session allows it.
+	///
+	/// The returned future resolves once the handshake completes, to whether the server accepted
+	/// the 0-RTT data. If it didn't, anything sent beforehand is lost and the session will fail.
+	async fn handshake(
+		&self,
+		config: quinn::ClientConfig,
+		ip: net::SocketAddr,
+		host: &str,
+	) -> anyhow::Result<(quinn::Connection, Option<quinn::ZeroRttAccepted>)> {
+		let connecting = self.quic.connect_with(config, ip, host)?;
+
+		match connecting.into_0rtt() {
+			Ok((connection, accepted)) => {
+				tracing::debug!(%ip, "sending 0-RTT data");
+				Ok((connection, Some(accepted)))
+			}
+			Err(connecting) => Ok((connecting.await?, None)),
+		}
+	}
 }
 
 // ── Peer identity extraction ───────────────────────────────────────
@@ -164,17 +262,17 @@ pub(crate) struct QuinnServer {
 
 impl QuinnServer {
 	pub fn new(config: ServerConfig) -> anyhow::Result<Self> {
-		// Enable BBR congestion control
-		// TODO Validate the BBR implementation before enabling it
 		let mut transport = quinn::TransportConfig::default();
-		transport.max_idle_timeout(Some(Duration::from_secs(30).try_into().unwrap()));
-		transport.keep_alive_interval(Some(Duration::from_secs(5)));
-		transport.mtu_discovery_config(None); // Disable MTU discovery
+		let (idle_timeout, keep_alive) = crate::idle_settings(config.idle_timeout, config.keep_alive)?;
+		transport.max_idle_timeout(Some(idle_timeout.try_into().context("idle timeout too large")?));
+		transport.keep_alive_interval(Some(keep_alive));
+		transport.mtu_discovery_config(mtu_discovery(config.mtu_discovery.unwrap_or_default()));
 
 		let max_streams = config.max_streams.unwrap_or(crate::DEFAULT_MAX_STREAMS);
 		let max_streams = quinn::VarInt::from_u64(max_streams).unwrap_or(quinn::VarInt::MAX);
 		transport.max_concurrent_bidi_streams(max_streams);
 		transport.max_concurrent_uni_streams(max_streams);
+		transport.congestion_controller_factory(congestion_controller(config.congestion.unwrap_or_default()));
 
 		let transport = Arc::new(transport);
 
@@ -212,6 +310,9 @@ impl QuinnServer {
 		tls.alpn_protocols = alpns;
 		tls.key_log = Arc::new(rustls::KeyLogFile::new());
 
+		// Accept 0-RTT from clients that opt in; quinn requires this to be 0 or u32::MAX.
+		tls.max_early_data_size = u32::MAX;
+
 		let tls: quinn::crypto::rustls::QuicServerConfig = tls.try_into()?;
 		let mut tls = quinn::ServerConfig::with_crypto(Arc::new(tls));
 		tls.transport_config(transport);
@@ -369,6 +470,14 @@ impl QuinnRequest {
 		}
 	}
 
+	/// Returns the address of the client.
+	pub fn remote_addr(&self) -> net::SocketAddr {
+		match self {
+			QuinnRequest::Raw { connection, .. } => connection.remote_address(),
+			QuinnRequest::WebTransport { request, .. } => request.conn().remote_address(),
+		}
+	}
+
 	/// Returns the peer's validated client certificate identity, if any.
 	pub fn peer_identity(&self) -> anyhow::Result<Option<PeerIdentity>> {
 		let conn = match self {
@@ -378,14 +487,15 @@ impl QuinnRequest {
 		extract_peer_identity(conn)
 	}
 
-	/// Reject the session with a status code.
+	/// Reject the session with a status code, sending the reason in the QUIC close frame of a raw session.
 	pub async fn close(
 		self,
 		status: web_transport_quinn::http::StatusCode,
+		reason: &str,
 	) -> Result<(), web_transport_quinn::ServerError> {
 		match self {
 			QuinnRequest::Raw { connection, .. } => {
-				connection.close(status.as_u16().into(), status.as_str().as_bytes());
+				connection.close(status.as_u16().into(), reason.as_bytes());
 				Ok(())
 			}
 			QuinnRequest::WebTransport { request, alpns: _, .. } => request.reject(status).await,
@@ -426,3 +536,61 @@ impl quinn::ConnectionIdGenerator for ServerIdGenerator {
 		None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn zero_rtt_reuses_ticket() {
+		let mut server_config = ServerConfig {
+			bind: Some("[::]:0".to_string()),
+			..Default::default()
+		};
+		server_config.tls.generate = vec!["localhost".into()];
+
+		let server = QuinnServer::new(server_config).unwrap();
+		let addr: net::SocketAddr = ([127, 0, 0, 1], server.local_addr().unwrap().port()).into();
+
+		tokio::spawn(async move {
+			while let Some(incoming) = server.accept().await {
+				tokio::spawn(async move {
+					let connection = incoming.await?;
+					connection.closed().await;
+					anyhow::Ok(())
+				});
+			}
+		});
+
+		let mut client_config = ClientConfig {
+			enable_0rtt: Some(true),
+			..Default::default()
+		};
+		client_config.tls.disable_verify = Some(true);
+
+		let client = QuinnClient::new(&client_config).unwrap();
+		let mut tls = client_config.tls.build().unwrap();
+		tls.alpn_protocols = vec![moq_lite::ALPNS[0].as_bytes().to_vec()];
+
+		// The first connection has no ticket, so it does a full handshake.
+		let config = client.quic_config(tls.clone(), true).unwrap();
+		let (_first, zero_rtt) = client.handshake(config, addr, "localhost").await.unwrap();
+		assert!(zero_rtt.is_none());
+
+		// The ticket arrives shortly after the handshake, then reconnects resume with 0-RTT.
+		let (_resumed, accepted) = tokio::time::timeout(Duration::from_secs(10), async {
+			loop {
+				let config = client.quic_config(tls.clone(), true).unwrap();
+				if let (connection, Some(accepted)) = client.handshake(config, addr, "localhost").await.unwrap() {
+					return (connection, accepted);
+				}
+				tokio::time::sleep(Duration::from_millis(10)).await;
+			}
+		})
+		.await
+		.expect("no 0-RTT connection");
+
+		// The server accepts it, since it issued the ticket.
+		assert!(accepted.await);
+	}
+}
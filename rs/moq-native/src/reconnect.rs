@@ -34,6 +34,17 @@ pub struct Backoff {
 	#[serde(with = "humantime_serde")]
 	pub max: Duration,
 
+	/// Randomize each delay by up to this fraction in either direction, clamped to `0.0..=1.0`.
+	///
+	/// Avoids many clients reconnecting in lockstep after a relay restart.
+	#[arg(id = "backoff-jitter", long, default_value_t = 0.1, env = "MOQ_BACKOFF_JITTER")]
+	pub jitter: f64,
+
+	/// Give up after this many failed attempts in a row. Omit for unlimited attempts.
+	#[arg(id = "backoff-attempts", long, env = "MOQ_BACKOFF_ATTEMPTS")]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub attempts: Option<u32>,
+
 	/// Maximum time to spend retrying before giving up.
 	/// Resets after each successful connection. Omit for unlimited retries.
 	#[arg(
@@ -52,11 +63,111 @@ impl Default for Backoff {
 			initial: Duration::from_secs(1),
 			multiplier: 2,
 			max: Duration::from_secs(30),
+			jitter: 0.1,
+			attempts: None,
 			timeout: None,
 		}
 	}
 }
 
+impl Backoff {
+	/// Apply jitter to the given delay.
+	pub(crate) fn jitter(&self, delay: Duration) -> Duration {
+		let jitter = self.jitter.clamp(0.0, 1.0);
+		if jitter == 0.0 {
+			return delay;
+		}
+
+		delay.mul_f64(rand::random_range(1.0 - jitter..=1.0 + jitter))
+	}
+
+	/// The delay to use after `delay` fails too.
+	pub(crate) fn next(&self, delay: Duration) -> Duration {
+		std::cmp::min(delay * self.multiplier, self.max)
+	}
+
+	/// Returns true once `failures` attempts in a row have failed, or `start` is too long ago.
+	pub(crate) fn exhausted(&self, failures: u32, start: tokio::time::Instant) -> bool {
+		self.attempts.is_some_and(|max| failures >= max)
+			|| self.timeout.is_some_and(|timeout| start.elapsed() > timeout)
+	}
+}
+
+/// A failed connection attempt, classified by whether trying again could help.
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectError {
+	/// A transport-level failure, such as a timeout or an unreachable host.
+	#[error(transparent)]
+	Retryable(anyhow::Error),
+
+	/// A failure that will repeat on every attempt, such as an invalid URL or a rejected certificate.
+	#[error(transparent)]
+	Fatal(anyhow::Error),
+}
+
+impl ConnectError {
+	/// Classify an error returned by [`Client::connect`].
+	pub fn classify(err: anyhow::Error) -> Self {
+		if err.chain().any(Self::is_fatal_cause) {
+			Self::Fatal(err)
+		} else {
+			Self::Retryable(err)
+		}
+	}
+
+	fn is_fatal_cause(cause: &(dyn std::error::Error + 'static)) -> bool {
+		if cause.is::<url::ParseError>() || cause.is::<rustls::Error>() {
+			return true;
+		}
+
+		if let Some(err) = cause.downcast_ref::<moq_lite::Error>() {
+			return matches!(
+				err,
+				moq_lite::Error::Version | moq_lite::Error::Unauthorized | moq_lite::Error::UnknownAlpn(_)
+			);
+		}
+
+		#[cfg(feature = "quinn")]
+		{
+			if cause.is::<quinn::ConnectError>() {
+				return true;
+			}
+
+			// TLS alerts, including a certificate or fingerprint mismatch, use the crypto error range.
+			if let Some(quinn::ConnectionError::TransportError(err)) = cause.downcast_ref::<quinn::ConnectionError>() {
+				return (0x100..0x200).contains(&u64::from(err.code));
+			}
+		}
+
+		#[cfg(feature = "noq")]
+		{
+			use web_transport_noq::noq;
+
+			if cause.is::<noq::ConnectError>() {
+				return true;
+			}
+
+			if let Some(noq::ConnectionError::TransportError(err)) = cause.downcast_ref::<noq::ConnectionError>() {
+				return (0x100..0x200).contains(&u64::from(err.code));
+			}
+		}
+
+		false
+	}
+
+	/// Returns true if trying again could succeed.
+	pub fn is_retryable(&self) -> bool {
+		matches!(self, Self::Retryable(_))
+	}
+
+	/// Return the underlying error.
+	pub fn into_inner(self) -> anyhow::Error {
+		match self {
+			Self::Retryable(err) | Self::Fatal(err) => err,
+		}
+	}
+}
+
 /// Handle to a background reconnect loop.
 ///
 /// Spawns a tokio task that connects, waits for session close, then reconnects
@@ -84,28 +195,45 @@ impl Reconnect {
 	async fn run(client: Client, url: Url, backoff: Backoff) -> anyhow::Result<()> {
 		let mut delay = backoff.initial;
 		let mut retry_start = tokio::time::Instant::now();
+		let mut failures = 0;
+
+		let resubscribe = client.resubscribe();
 
 		loop {
-			if let Some(timeout) = backoff.timeout {
-				if retry_start.elapsed() > timeout {
-					anyhow::bail!("reconnect timed out after {timeout:?}");
-				}
+			if backoff.exhausted(failures, retry_start) {
+				anyhow::bail!("gave up reconnecting after {failures} attempts");
 			}
 
 			tracing::info!(%url, "connecting");
 
-			match client.connect(url.clone()).await {
+			// Consume into a per-session origin, mirrored into the caller's, when resubscribing.
+			let (attempt, announced) = match &resubscribe {
+				Some(_) => {
+					let origin = moq_lite::Origin::random().produce();
+					let announced = origin.consume();
+					(client.clone().with_consume(origin), Some(announced))
+				}
+				None => (client.clone(), None),
+			};
+
+			match attempt.connect(url.clone()).await {
 				Ok(session) => {
 					tracing::info!(%url, "connected");
 					delay = backoff.initial;
+					failures = 0;
+					if let (Some(resubscribe), Some(announced)) = (&resubscribe, announced) {
+						resubscribe.run(&session, announced).await;
+					}
 					let _ = session.closed().await;
 					tracing::warn!(%url, "session closed, reconnecting");
 					retry_start = tokio::time::Instant::now();
 				}
 				Err(err) => {
-					tracing::warn!(%url, %err, ?delay, "connection failed, retrying");
-					tokio::time::sleep(delay).await;
-					delay = std::cmp::min(delay * backoff.multiplier, backoff.max);
+					failures += 1;
+					let sleep = backoff.jitter(delay);
+					tracing::warn!(%url, %err, ?sleep, "connection failed, retrying");
+					tokio::time::sleep(sleep).await;
+					delay = backoff.next(delay);
 				}
 			}
 		}
@@ -114,11 +242,11 @@ impl Reconnect {
 	/// Wait until the reconnect loop stops.
 	///
 	/// Returns `Ok(())` if closed via [`close`](Self::close) or drop.
-	/// Returns `Err` if the reconnect timeout was exceeded.
+	/// Returns `Err` if the backoff ran out of attempts or time.
 	pub async fn closed(&self) -> anyhow::Result<()> {
 		let mut rx = self.closed_rx.clone();
 		match rx.wait_for(|&v| v).await {
-			Ok(_) => anyhow::bail!("reconnect timed out"),
+			Ok(_) => anyhow::bail!("reconnect gave up"),
 			Err(_) => Ok(()),
 		}
 	}
@@ -139,12 +267,54 @@ impl Drop for Reconnect {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_backoff_jitter() {
+		let backoff = Backoff {
+			jitter: 0.5,
+			..Default::default()
+		};
+		for _ in 0..100 {
+			let delay = backoff.jitter(Duration::from_secs(2));
+			assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(3));
+		}
+
+		let backoff = Backoff {
+			jitter: 0.0,
+			..Default::default()
+		};
+		assert_eq!(backoff.jitter(Duration::from_secs(2)), Duration::from_secs(2));
+	}
+
+	#[test]
+	fn test_backoff_next() {
+		let backoff = Backoff::default();
+		assert_eq!(backoff.next(Duration::from_secs(1)), Duration::from_secs(2));
+		assert_eq!(backoff.next(Duration::from_secs(20)), Duration::from_secs(30));
+	}
+
+	#[test]
+	fn test_classify() {
+		let err = anyhow::Error::new(moq_lite::Error::Version).context("failed to connect");
+		assert!(!ConnectError::classify(err).is_retryable());
+
+		let err = anyhow::Error::new(url::Url::parse("not a url").unwrap_err());
+		assert!(!ConnectError::classify(err).is_retryable());
+
+		let err = anyhow::Error::new(rustls::Error::General("fingerprint mismatch".into()));
+		assert!(!ConnectError::classify(err).is_retryable());
+
+		let err = anyhow::anyhow!("connection timed out");
+		assert!(ConnectError::classify(err).is_retryable());
+	}
+
 	#[test]
 	fn test_backoff_default() {
 		let backoff = Backoff::default();
 		assert_eq!(backoff.initial, Duration::from_secs(1));
 		assert_eq!(backoff.multiplier, 2);
 		assert_eq!(backoff.max, Duration::from_secs(30));
+		assert_eq!(backoff.jitter, 0.1);
+		assert_eq!(backoff.attempts, None);
 		assert_eq!(backoff.timeout, None);
 	}
 }
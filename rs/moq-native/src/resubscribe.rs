@@ -0,0 +1,330 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
+use moq_lite::{
+	Broadcast, BroadcastConsumer, DuplicatePolicy, Error, GroupConsumer, GroupProducer, OriginConsumer, OriginProducer,
+	PathOwned, TrackConsumer, TrackProducer,
+};
+use tokio::sync::watch;
+
+/// Mirrors the broadcasts announced by successive sessions into one origin, so
+/// subscriptions survive a reconnect.
+///
+/// Each session consumes into its own origin. Every broadcast it announces is republished
+/// under the same path in the caller's origin, where subscribed tracks are forwarded from
+/// whichever session currently announces the broadcast. When the session closes, the
+/// broadcast stays announced for the grace period; if a new session announces it again,
+/// active tracks resubscribe and carry on, otherwise the broadcast is closed.
+#[derive(Clone)]
+pub(crate) struct Resubscribe {
+	origin: OriginProducer,
+	grace: Duration,
+
+	// The current upstream of each mirrored broadcast, or None while waiting for one.
+	broadcasts: Arc<Mutex<HashMap<PathOwned, watch::Sender<Option<BroadcastConsumer>>>>>,
+}
+
+impl Resubscribe {
+	pub fn new(origin: OriginProducer, grace: Duration) -> Self {
+		Self {
+			origin,
+			grace,
+			broadcasts: Default::default(),
+		}
+	}
+
+	/// Mirror the broadcasts announced to `announced` until the session closes.
+	pub async fn run(&self, session: &moq_lite::Session, mut announced: OriginConsumer) {
+		let mut paths = HashSet::new();
+
+		loop {
+			tokio::select! {
+				next = announced.announced() => match next {
+					Some((path, Some(broadcast))) => {
+						self.attach(path.clone(), broadcast);
+						paths.insert(path);
+					}
+					Some((path, None)) => {
+						self.detach(&path);
+						paths.remove(&path);
+					}
+					None => break,
+				},
+				_ = session.closed() => break,
+			}
+		}
+
+		for path in paths {
+			self.detach(&path);
+		}
+	}
+
+	fn attach(&self, path: PathOwned, upstream: BroadcastConsumer) {
+		let mut broadcasts = self.broadcasts.lock().unwrap();
+
+		if let Some(current) = broadcasts.get(&path) {
+			tracing::debug!(broadcast = %path, "resubscribing");
+			current.send_replace(Some(upstream));
+			return;
+		}
+
+		let mut broadcast = Broadcast {
+			hops: upstream.hops.clone(),
+		}
+		.produce();
+		let dynamic = broadcast.dynamic();
+
+		if !self.origin.publish_broadcast(path.clone(), broadcast.consume()) {
+			tracing::warn!(broadcast = %path, "not allowed to mirror broadcast");
+			broadcast.abort(Error::Unauthorized).ok();
+			return;
+		}
+
+		let (tx, rx) = watch::channel(Some(upstream));
+		broadcasts.insert(path.clone(), tx);
+
+		let this = self.clone();
+		tokio::spawn(async move {
+			this.run_broadcast(&path, broadcast, dynamic, rx).await;
+		});
+	}
+
+	fn detach(&self, path: &PathOwned) {
+		if let Some(current) = self.broadcasts.lock().unwrap().get(path) {
+			current.send_replace(None);
+		}
+	}
+
+	async fn run_broadcast(
+		&self,
+		path: &PathOwned,
+		mut broadcast: moq_lite::BroadcastProducer,
+		mut dynamic: moq_lite::BroadcastDynamic,
+		mut upstream: watch::Receiver<Option<BroadcastConsumer>>,
+	) {
+		let mut expired = None;
+
+		loop {
+			tokio::select! {
+				track = dynamic.requested_track() => match track {
+					Ok(track) => {
+						tokio::spawn(Self::run_track(track, upstream.clone()));
+					}
+					Err(_) => break,
+				},
+				res = upstream.changed() => {
+					if res.is_err() {
+						break;
+					}
+
+					// Close the broadcast unless another session announces it in time.
+					expired = match *upstream.borrow_and_update() {
+						Some(_) => None,
+						None => Some(tokio::time::Instant::now() + self.grace),
+					};
+				}
+				_ = async { tokio::time::sleep_until(expired.unwrap()).await }, if expired.is_some() => {
+					// Check under the lock, so a session announcing it right now isn't lost.
+					let mut broadcasts = self.broadcasts.lock().unwrap();
+					if upstream.borrow().is_some() {
+						expired = None;
+						continue;
+					}
+
+					tracing::debug!(broadcast = %path, "not announced again, closing");
+					broadcasts.remove(path);
+					break;
+				}
+			}
+		}
+
+		broadcast.abort(Error::Cancel).ok();
+	}
+
+	async fn run_track(mut track: TrackProducer, mut upstream: watch::Receiver<Option<BroadcastConsumer>>) {
+		// A group cut off by the old session arrives again from the new one, replacing it.
+		track = track.with_duplicate_policy(DuplicatePolicy::Replace);
+
+		// The highest group forwarded in full, so a resubscription doesn't repeat it.
+		let mut done = None;
+
+		// Stop once nobody is subscribed.
+		let unused = track.clone();
+
+		loop {
+			// Wait for a session to announce the broadcast, skipping one that's already gone.
+			let live = |broadcast: &Option<BroadcastConsumer>| broadcast.as_ref().is_some_and(|b| !b.is_closed());
+			let broadcast = tokio::select! {
+				res = upstream.wait_for(live) => match res {
+					Ok(broadcast) => broadcast.clone().unwrap(),
+					Err(_) => {
+						track.abort(Error::Cancel).ok();
+						return;
+					}
+				},
+				_ = unused.unused() => return,
+			};
+
+			let err = match broadcast.subscribe_track(&track) {
+				Ok(mut consumer) => {
+					tokio::select! {
+						res = Self::forward(&mut consumer, &mut track, &mut done) => match res {
+							Ok(()) => {
+								track.finish().ok();
+								return;
+							}
+							Err(err) => err,
+						},
+						_ = unused.unused() => {
+							return;
+						}
+					}
+				}
+				Err(err) => err,
+			};
+
+			// The session went away, taking the broadcast with it; wait for the next one.
+			// A transport error may be noticed before the broadcast closes.
+			if matches!(err, Error::Transport(_)) {
+				broadcast.closed().await;
+			}
+			if !broadcast.is_closed() {
+				track.abort(err).ok();
+				return;
+			}
+
+			tracing::debug!(track = %track.name, %err, "upstream lost, waiting to resubscribe");
+		}
+	}
+
+	// Copy every group of the upstream track until it finishes.
+	async fn forward(
+		upstream: &mut TrackConsumer,
+		track: &mut TrackProducer,
+		done: &mut Option<u64>,
+	) -> Result<(), Error> {
+		let mut groups = FuturesUnordered::new();
+
+		loop {
+			tokio::select! {
+				group = upstream.recv_group() => {
+					let Some(group) = group? else { break };
+					if done.is_some_and(|done| group.sequence <= done) {
+						continue;
+					}
+
+					let producer = track.create_group(moq_lite::Group { sequence: group.sequence })?;
+					groups.push(Self::forward_group(group, producer));
+				}
+				Some(res) = groups.next() => {
+					if let Ok(sequence) = res {
+						*done = (*done).max(Some(sequence));
+					}
+				}
+			}
+		}
+
+		while let Some(res) = groups.next().await {
+			if let Ok(sequence) = res {
+				*done = (*done).max(Some(sequence));
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn forward_group(mut upstream: GroupConsumer, mut group: GroupProducer) -> Result<u64, Error> {
+		let res = async {
+			while let Some((info, data)) = upstream.read_frame_info().await? {
+				let mut frame = group.create_frame(info)?;
+				frame.write(data)?;
+				frame.finish()?;
+			}
+			group.finish()
+		}
+		.await;
+
+		match res {
+			Ok(()) => Ok(group.sequence),
+			Err(err) => {
+				group.abort(err.clone()).ok();
+				Err(err)
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use moq_lite::{Group, Track};
+
+	fn path() -> PathOwned {
+		PathOwned::from("demo".to_string())
+	}
+
+	#[tokio::test]
+	async fn test_resubscribe_after_reconnect() {
+		let origin = moq_lite::Origin::random().produce();
+		let consumer = origin.consume();
+		let resubscribe = Resubscribe::new(origin, Duration::from_secs(10));
+
+		// The first session announces the broadcast.
+		let mut first = Broadcast::new().produce();
+		let mut dynamic = first.dynamic();
+		resubscribe.attach(path(), first.consume());
+
+		let broadcast = consumer.announced_broadcast("demo").await.unwrap();
+		let mut track = broadcast.subscribe_track(&Track::new("video")).unwrap();
+
+		let mut upstream = dynamic.requested_track().await.unwrap();
+		upstream.write_frame(bytes::Bytes::from_static(b"one")).unwrap();
+		let mut group = track.next_group().await.unwrap().unwrap();
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "one");
+
+		// The session drops, taking the broadcast and its track with it.
+		upstream.abort(Error::Transport("closed".into())).unwrap();
+		first.abort(Error::Transport("closed".into())).unwrap();
+		resubscribe.detach(&path());
+
+		// The next session announces it again, and the same subscription carries on.
+		let second = Broadcast::new().produce();
+		let mut dynamic = second.dynamic();
+		resubscribe.attach(path(), second.consume());
+
+		let mut upstream = dynamic.requested_track().await.unwrap();
+		assert_eq!(upstream.name, "video");
+		let mut next = upstream.create_group(Group { sequence: 1 }).unwrap();
+		next.write_frame(bytes::Bytes::from_static(b"two")).unwrap();
+		next.finish().unwrap();
+
+		let mut group = track.next_group().await.unwrap().unwrap();
+		assert_eq!(group.sequence, 1);
+		assert_eq!(group.read_frame().await.unwrap().unwrap(), "two");
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_resubscribe_grace_expires() {
+		let origin = moq_lite::Origin::random().produce();
+		let mut consumer = origin.consume();
+		let resubscribe = Resubscribe::new(origin, Duration::from_secs(10));
+
+		let mut first = Broadcast::new().produce();
+		resubscribe.attach(path(), first.consume());
+		let (_, active) = consumer.announced().await.unwrap();
+		assert!(active.is_some());
+
+		let start = tokio::time::Instant::now();
+		first.abort(Error::Transport("closed".into())).unwrap();
+		resubscribe.detach(&path());
+
+		// Nobody announces it again, so it's unannounced once the grace period is up.
+		let (_, active) = consumer.announced().await.unwrap();
+		assert!(active.is_none());
+		assert_eq!(start.elapsed(), Duration::from_secs(10));
+		assert!(resubscribe.broadcasts.lock().unwrap().is_empty());
+	}
+}
@@ -1,7 +1,8 @@
 use std::net;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::QuicBackend;
+use crate::{CongestionControl, QuicBackend};
 use moq_lite::Session;
 use std::sync::{Arc, RwLock};
 use url::Url;
@@ -17,12 +18,13 @@ use futures::stream::StreamExt;
 
 /// TLS configuration for the server.
 ///
-/// Certificate and keys must currently be files on disk.
+/// Certificate and keys are loaded from files on disk, or from PEM strings (e.g. secrets
+/// passed as environment variables) so they never touch the disk.
 /// Alternatively, you can generate a self-signed certificate given a list of hostnames.
 ///
 /// In config files, each list field accepts either a single string or a TOML array.
 #[serde_with::serde_as]
-#[derive(clap::Args, Clone, Default, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(clap::Args, Clone, Default, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 #[non_exhaustive]
 pub struct ServerTlsConfig {
@@ -38,6 +40,21 @@ pub struct ServerTlsConfig {
 	#[serde_as(as = "serde_with::OneOrMany<_>")]
 	pub key: Vec<PathBuf>,
 
+	/// Or load a PEM certificate chain directly, instead of from a file.
+	#[arg(long = "tls-cert-pem", id = "tls-cert-pem", env = "MOQ_SERVER_TLS_CERT_PEM")]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cert_pem: Option<String>,
+
+	/// The PEM private key for `cert_pem`. Never serialized or logged.
+	#[arg(
+		long = "tls-key-pem",
+		id = "tls-key-pem",
+		env = "MOQ_SERVER_TLS_KEY_PEM",
+		hide_env_values = true
+	)]
+	#[serde(default, skip_serializing)]
+	pub key_pem: Option<String>,
+
 	/// Or generate a new certificate and key with the given hostnames.
 	/// This won't be valid unless the client uses the fingerprint or disables verification.
 	#[arg(
@@ -50,6 +67,17 @@ pub struct ServerTlsConfig {
 	#[serde_as(as = "serde_with::OneOrMany<_>")]
 	pub generate: Vec<String>,
 
+	/// The key algorithm for generated certificates, defaulting to ECDSA P-256.
+	///
+	/// Browsers only accept P-256 when verifying a certificate by its fingerprint.
+	#[arg(
+		long = "tls-generate-alg",
+		id = "tls-generate-alg",
+		env = "MOQ_SERVER_TLS_GENERATE_ALG"
+	)]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub generate_alg: Option<CertAlgorithm>,
+
 	/// PEM file(s) of root CAs for validating optional client certificates (mTLS).
 	///
 	/// When set, clients *may* present a certificate during the TLS handshake.
@@ -69,7 +97,64 @@ pub struct ServerTlsConfig {
 	pub root: Vec<PathBuf>,
 }
 
+/// The key algorithm used for a generated self-signed certificate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum CertAlgorithm {
+	/// ECDSA with the P-256 curve and SHA-256.
+	#[default]
+	P256,
+
+	/// ECDSA with the P-384 curve and SHA-384.
+	P384,
+
+	/// Ed25519, for smaller keys and signatures.
+	Ed25519,
+}
+
+impl CertAlgorithm {
+	/// Generate a new key pair using this algorithm.
+	#[cfg(all(
+		any(feature = "quinn", feature = "noq", feature = "quiche"),
+		any(feature = "aws-lc-rs", feature = "ring")
+	))]
+	pub(crate) fn generate(self) -> anyhow::Result<rcgen::KeyPair> {
+		let alg = match self {
+			Self::P256 => &rcgen::PKCS_ECDSA_P256_SHA256,
+			Self::P384 => &rcgen::PKCS_ECDSA_P384_SHA384,
+			Self::Ed25519 => &rcgen::PKCS_ED25519,
+		};
+
+		Ok(rcgen::KeyPair::generate_for(alg)?)
+	}
+}
+
+// The PEM key is a secret, so keep it out of logs.
+impl std::fmt::Debug for ServerTlsConfig {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ServerTlsConfig")
+			.field("cert", &self.cert)
+			.field("key", &self.key)
+			.field("cert_pem", &self.cert_pem)
+			.field("key_pem", &self.key_pem.as_ref().map(|_| "<redacted>"))
+			.field("generate", &self.generate)
+			.field("generate_alg", &self.generate_alg)
+			.field("root", &self.root)
+			.finish()
+	}
+}
+
 impl ServerTlsConfig {
+	/// The PEM certificate chain and key, if both were provided.
+	pub(crate) fn pem(&self) -> anyhow::Result<Option<(&[u8], &[u8])>> {
+		match (&self.cert_pem, &self.key_pem) {
+			(Some(cert), Some(key)) => Ok(Some((cert.as_bytes(), key.as_bytes()))),
+			(None, None) => Ok(None),
+			_ => anyhow::bail!("must provide both cert_pem and key_pem"),
+		}
+	}
+
 	/// Load all configured root CAs into a [`rustls::RootCertStore`].
 	pub fn load_roots(&self) -> anyhow::Result<rustls::RootCertStore> {
 		use rustls::pki_types::CertificateDer;
@@ -137,6 +222,63 @@ pub struct ServerConfig {
 	)]
 	pub max_streams: Option<u64>,
 
+	/// The congestion control algorithm, defaulting to cubic.
+	/// Only supported by the quinn backend.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[arg(id = "server-congestion", long = "server-congestion", env = "MOQ_SERVER_CONGESTION")]
+	pub congestion: Option<CongestionControl>,
+
+	/// Enable path MTU discovery, allowing packets larger than the conservative default.
+	///
+	/// Disabled by default. When enabled, probes search for a path MTU of up to 1452 bytes
+	/// of UDP payload and are repeated every 10 minutes. Probes are occasional padded packets,
+	/// so the overhead is small, and like any other packet they count as activity for the
+	/// idle timeout. Only supported by the quinn backend.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[arg(
+		id = "server-mtu-discovery",
+		long = "server-mtu-discovery",
+		env = "MOQ_SERVER_MTU_DISCOVERY",
+		default_missing_value = "true",
+		num_args = 0..=1,
+		require_equals = true,
+		value_parser = clap::value_parser!(bool),
+	)]
+	pub mtu_discovery: Option<bool>,
+
+	/// Close the connection after this long without any network activity.
+	/// Defaults to 30s.
+	#[arg(
+		id = "server-idle-timeout",
+		long = "server-idle-timeout",
+		env = "MOQ_SERVER_IDLE_TIMEOUT",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub idle_timeout: Option<Duration>,
+
+	/// Send a keep-alive after this long without sending anything.
+	/// Defaults to 5s and must be less than half the idle timeout. Not supported by the quiche backend.
+	#[arg(
+		id = "server-keep-alive",
+		long = "server-keep-alive",
+		env = "MOQ_SERVER_KEEP_ALIVE",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub keep_alive: Option<Duration>,
+
+	/// How long a graceful shutdown waits for open sessions before closing them.
+	/// Defaults to 10s.
+	#[arg(
+		id = "server-drain-timeout",
+		long = "server-drain-timeout",
+		env = "MOQ_SERVER_DRAIN_TIMEOUT",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub drain_timeout: Option<Duration>,
+
 	/// Restrict the server to specific MoQ protocol version(s).
 	///
 	/// By default, the server accepts all supported versions.
@@ -166,6 +308,11 @@ impl ServerConfig {
 			moq_lite::Versions::from(self.version.clone())
 		}
 	}
+
+	/// Returns the configured drain timeout, applying the default.
+	pub fn drain_timeout(&self) -> Duration {
+		self.drain_timeout.unwrap_or(crate::DEFAULT_DRAIN_TIMEOUT)
+	}
 }
 
 /// Default bind address used when [`ServerConfig::bind`] is not set.
@@ -178,6 +325,9 @@ pub struct Server {
 	moq: moq_lite::Server,
 	versions: moq_lite::Versions,
 	accept: FuturesUnordered<BoxFuture<'static, anyhow::Result<Request>>>,
+	accepting: bool,
+	sessions: Sessions,
+	drain_timeout: Duration,
 	#[cfg(feature = "iroh")]
 	iroh: Option<iroh::Endpoint>,
 	#[cfg(feature = "noq")]
@@ -210,6 +360,7 @@ impl Server {
 		});
 
 		let versions = config.versions();
+		let drain_timeout = config.drain_timeout();
 
 		if !config.tls.root.is_empty() {
 			#[cfg(feature = "quinn")]
@@ -241,6 +392,9 @@ impl Server {
 
 		Ok(Server {
 			accept: Default::default(),
+			accepting: true,
+			sessions: Default::default(),
+			drain_timeout,
 			moq: moq_lite::Server::new().with_versions(versions.clone()),
 			versions,
 			#[cfg(feature = "iroh")]
@@ -312,9 +466,16 @@ impl Server {
 	///
 	/// The [Request] is either a WebTransport or a raw QUIC request.
 	/// Call [Request::ok] or [Request::close] to complete the handshake.
+	///
+	/// Returns `None` once the server has been closed or drained.
+	/// On Ctrl-C, the server is drained with the configured [`ServerConfig::drain_timeout`] first.
 	#[cfg(any(feature = "noq", feature = "quinn", feature = "quiche", feature = "iroh"))]
 	pub async fn accept(&mut self) -> Option<Request> {
 		loop {
+			if !self.accepting {
+				return None;
+			}
+
 			// tokio::select! does not support cfg directives on arms, so we need to create the futures here.
 			#[cfg(feature = "noq")]
 			let noq_accept = async {
@@ -374,6 +535,7 @@ impl Server {
 
 			let server = self.moq.clone();
 			let versions = self.versions.clone();
+			let sessions = self.sessions.clone();
 
 			tokio::select! {
 				Some(_conn) = noq_accept => {
@@ -384,7 +546,8 @@ impl Server {
 							let noq = super::noq::NoqRequest::accept(_conn, alpns).await?;
 							Ok(Request {
 								server,
-								kind: RequestKind::Noq(noq),
+								sessions,
+								kind: RequestKind::Noq(Box::new(noq)),
 							})
 						}.boxed());
 					}
@@ -397,6 +560,7 @@ impl Server {
 							let quinn = super::quinn::QuinnRequest::accept(_conn, alpns).await?;
 							Ok(Request {
 								server,
+								sessions,
 								kind: RequestKind::Quinn(Box::new(quinn)),
 							})
 						}.boxed());
@@ -410,6 +574,7 @@ impl Server {
 							let quiche = super::quiche::QuicheRequest::accept(_conn, alpns).await?;
 							Ok(Request {
 								server,
+								sessions,
 								kind: RequestKind::Quiche(quiche),
 							})
 						}.boxed());
@@ -421,6 +586,7 @@ impl Server {
 						let iroh = super::iroh::IrohRequest::accept(_conn).await?;
 						Ok(Request {
 							server,
+							sessions,
 							kind: RequestKind::Iroh(iroh),
 						})
 					}.boxed());
@@ -431,6 +597,7 @@ impl Server {
 						Ok(session) => {
 							return Some(Request {
 								server,
+								sessions,
 								kind: RequestKind::WebSocket(session),
 							});
 						}
@@ -444,7 +611,7 @@ impl Server {
 					}
 				}
 				_ = tokio::signal::ctrl_c() => {
-					self.close().await;
+					self.drain(self.drain_timeout).await;
 					return None;
 				}
 			}
@@ -477,7 +644,110 @@ impl Server {
 		self.websocket.as_ref().and_then(|ws| ws.local_addr().ok())
 	}
 
+	/// The configured [`ServerConfig::drain_timeout`], for passing to [Self::drain].
+	pub fn drain_timeout(&self) -> Duration {
+		self.drain_timeout
+	}
+
+	/// Stop accepting new sessions and wait up to `timeout` for the existing ones to finish.
+	///
+	/// Handshakes still in progress are abandoned and new connections are refused at the endpoint.
+	/// Any session still open after the deadline is closed along with the server, see [Self::close].
+	/// [Self::accept] returns `None` afterwards.
+	pub async fn drain(&mut self, timeout: Duration) {
+		self.accepting = false;
+		self.accept.clear();
+
+		// Stop listening on the TCP port right away; the QUIC endpoints still carry the open sessions.
+		#[cfg(feature = "websocket")]
+		{
+			let _ = self.websocket.take();
+		}
+
+		let sessions = self.sessions.clone();
+		tokio::select! {
+			res = tokio::time::timeout(timeout, sessions.idle()) => if res.is_err() {
+				tracing::warn!(open = sessions.count(), "drain deadline reached, closing sessions");
+			},
+			_ = self.refuse() => {},
+		}
+
+		self.close().await;
+	}
+
+	// Refuse every new connection at the QUIC endpoints, so clients fail fast and try elsewhere.
+	async fn refuse(&mut self) {
+		loop {
+			// tokio::select! does not support cfg directives on arms, so we need to create the futures here.
+			#[cfg(feature = "noq")]
+			let noq_accept = async {
+				if let Some(noq) = self.noq.as_mut() {
+					return noq.accept().await;
+				}
+				None
+			};
+			#[cfg(not(feature = "noq"))]
+			let noq_accept = async { None::<()> };
+
+			#[cfg(feature = "iroh")]
+			let iroh_accept = async {
+				if let Some(endpoint) = self.iroh.as_mut() {
+					return endpoint.accept().await;
+				}
+				None
+			};
+			#[cfg(not(feature = "iroh"))]
+			let iroh_accept = async { None::<()> };
+
+			#[cfg(feature = "quinn")]
+			let quinn_accept = async {
+				if let Some(quinn) = self.quinn.as_mut() {
+					return quinn.accept().await;
+				}
+				None
+			};
+			#[cfg(not(feature = "quinn"))]
+			let quinn_accept = async { None::<()> };
+
+			#[cfg(feature = "quiche")]
+			let quiche_accept = async {
+				if let Some(quiche) = self.quiche.as_mut() {
+					return quiche.accept().await;
+				}
+				None
+			};
+			#[cfg(not(feature = "quiche"))]
+			let quiche_accept = async { None::<()> };
+
+			tokio::select! {
+				Some(_conn) = noq_accept => {
+					#[cfg(feature = "noq")]
+					_conn.refuse();
+				}
+				Some(_conn) = quinn_accept => {
+					#[cfg(feature = "quinn")]
+					_conn.refuse();
+				}
+				Some(_conn) = quiche_accept => {
+					#[cfg(feature = "quiche")]
+					_conn.reject(0, "server draining");
+				}
+				Some(_conn) = iroh_accept => {
+					#[cfg(feature = "iroh")]
+					_conn.refuse();
+				}
+				else => return std::future::pending().await,
+			}
+		}
+	}
+
+	/// Close the server immediately, along with any open sessions.
+	///
+	/// [Self::accept] returns `None` afterwards.
 	pub async fn close(&mut self) {
+		self.accepting = false;
+		self.accept.clear();
+
 		#[cfg(feature = "noq")]
 		if let Some(noq) = self.noq.as_mut() {
 			noq.close();
@@ -501,8 +771,31 @@ impl Server {
 		{
 			let _ = self.websocket.take();
 		}
-		#[cfg(not(any(feature = "noq", feature = "quinn", feature = "quiche", feature = "iroh")))]
-		unreachable!("no QUIC backend compiled");
+	}
+}
+
+/// Counts the sessions accepted by a [Server] that are still open, so it can drain them.
+#[derive(Clone, Default)]
+struct Sessions(Arc<tokio::sync::watch::Sender<usize>>);
+
+impl Sessions {
+	fn track(&self, session: &Session) {
+		self.0.send_modify(|count| *count += 1);
+
+		let session = session.clone();
+		let this = self.clone();
+		tokio::spawn(async move {
+			let _ = session.closed().await;
+			this.0.send_modify(|count| *count -= 1);
+		});
+	}
+
+	fn count(&self) -> usize {
+		*self.0.borrow()
+	}
+
+	async fn idle(&self) {
+		let _ = self.0.subscribe().wait_for(|count| *count == 0).await;
 	}
 }
 
@@ -515,7 +808,7 @@ pub struct PeerIdentity {}
 /// An incoming connection that can be accepted or rejected.
 pub(crate) enum RequestKind {
 	#[cfg(feature = "noq")]
-	Noq(crate::noq::NoqRequest),
+	Noq(Box<crate::noq::NoqRequest>),
 	#[cfg(feature = "quinn")]
 	Quinn(Box<crate::quinn::QuinnRequest>),
 	#[cfg(feature = "quiche")]
@@ -532,30 +825,49 @@ pub(crate) enum RequestKind {
 /// Otherwise, the Server's configuration is used by default.
 pub struct Request {
 	server: moq_lite::Server,
+	sessions: Sessions,
 	kind: RequestKind,
 }
 
 impl Request {
 	/// Reject the session, returning your favorite HTTP status code.
-	pub async fn close(self, _code: u16) -> anyhow::Result<()> {
+	///
+	/// Raw QUIC sessions are closed with the status's canonical reason; see [Self::close_with].
+	pub async fn close(self, code: u16) -> anyhow::Result<()> {
+		self.reject(code, None).await
+	}
+
+	/// Reject the session with an HTTP status code and a machine-readable reason, like `token_expired`.
+	///
+	/// Raw QUIC sessions carry the reason in the connection close frame, where the client sees it.
+	/// WebTransport rejections are an HTTP/3 response, which has no reason phrase, so only the
+	/// status code reaches the client.
+	pub async fn close_with(self, code: u16, reason: &str) -> anyhow::Result<()> {
+		self.reject(code, Some(reason)).await
+	}
+
+	async fn reject(self, _code: u16, _reason: Option<&str>) -> anyhow::Result<()> {
 		match self.kind {
 			#[cfg(feature = "noq")]
 			RequestKind::Noq(request) => {
 				let status = web_transport_noq::http::StatusCode::from_u16(_code).context("invalid status code")?;
-				request.close(status).await?;
+				let reason = _reason.or(status.canonical_reason()).unwrap_or(status.as_str());
+				request.close(status, reason).await?;
 				Ok(())
 			}
 			#[cfg(feature = "quinn")]
 			RequestKind::Quinn(request) => {
 				let status = web_transport_quinn::http::StatusCode::from_u16(_code).context("invalid status code")?;
-				request.close(status).await?;
+				let reason = _reason.or(status.canonical_reason()).unwrap_or(status.as_str());
+				request.close(status, reason).await?;
 				Ok(())
 			}
 			#[cfg(feature = "quiche")]
 			RequestKind::Quiche(request) => {
 				let status = web_transport_quiche::http::StatusCode::from_u16(_code).context("invalid status code")?;
+				let reason = _reason.or(status.canonical_reason()).unwrap_or(status.as_str());
 				request
-					.reject(status)
+					.reject(status, reason)
 					.await
 					.map_err(|e| anyhow::anyhow!("failed to close quiche WebTransport request: {e}"))?;
 				Ok(())
@@ -563,7 +875,8 @@ impl Request {
 			#[cfg(feature = "iroh")]
 			RequestKind::Iroh(request) => {
 				let status = web_transport_iroh::http::StatusCode::from_u16(_code).context("invalid status code")?;
-				request.close(status).await?;
+				let reason = _reason.or(status.canonical_reason()).unwrap_or(status.as_str());
+				request.close(status, reason).await?;
 				Ok(())
 			}
 			#[cfg(feature = "websocket")]
@@ -586,26 +899,35 @@ impl Request {
 		self
 	}
 
+	/// Count the subscriptions and groups served to the session.
+	pub fn with_stats(mut self, stats: moq_lite::PublishStats) -> Self {
+		self.server = self.server.with_stats(stats);
+		self
+	}
+
 	/// Accept the session, performing rest of the MoQ handshake.
 	pub async fn ok(self) -> anyhow::Result<Session> {
-		match self.kind {
+		let session = match self.kind {
 			#[cfg(feature = "noq")]
-			RequestKind::Noq(request) => Ok(self.server.accept(request.ok().await?).await?),
+			RequestKind::Noq(request) => self.server.accept(request.ok().await?).await?,
 			#[cfg(feature = "quinn")]
-			RequestKind::Quinn(request) => Ok(self.server.accept(request.ok().await?).await?),
+			RequestKind::Quinn(request) => self.server.accept(request.ok().await?).await?,
 			#[cfg(feature = "quiche")]
 			RequestKind::Quiche(request) => {
 				let conn = request
 					.ok()
 					.await
 					.map_err(|e| anyhow::anyhow!("failed to accept quiche WebTransport: {e}"))?;
-				Ok(self.server.accept(conn).await?)
+				self.server.accept(conn).await?
 			}
 			#[cfg(feature = "iroh")]
-			RequestKind::Iroh(request) => Ok(self.server.accept(request.ok().await?).await?),
+			RequestKind::Iroh(request) => self.server.accept(request.ok().await?).await?,
 			#[cfg(feature = "websocket")]
-			RequestKind::WebSocket(session) => Ok(self.server.accept(session).await?),
-		}
+			RequestKind::WebSocket(session) => self.server.accept(session).await?,
+		};
+
+		self.sessions.track(&session);
+		Ok(session)
 	}
 
 	/// Returns the transport type as a string (e.g. "quic", "iroh").
@@ -643,6 +965,33 @@ impl Request {
 		}
 	}
 
+	/// Returns the address of the client.
+	///
+	/// Returns `None` for transports without an IP address, such as iroh, or WebSocket
+	/// where the address is only known to the HTTP server.
+	pub fn remote_addr(&self) -> Option<net::SocketAddr> {
+		match self.kind {
+			#[cfg(feature = "noq")]
+			RequestKind::Noq(ref request) => Some(request.remote_addr()),
+			#[cfg(feature = "quinn")]
+			RequestKind::Quinn(ref request) => Some(request.remote_addr()),
+			#[cfg(feature = "quiche")]
+			RequestKind::Quiche(ref request) => Some(request.remote_addr()),
+			#[cfg(feature = "iroh")]
+			RequestKind::Iroh(_) => None,
+			#[cfg(feature = "websocket")]
+			RequestKind::WebSocket(_) => None,
+			#[cfg(not(any(
+				feature = "noq",
+				feature = "quinn",
+				feature = "quiche",
+				feature = "iroh",
+				feature = "websocket"
+			)))]
+			_ => None,
+		}
+	}
+
 	/// Returns the peer's TLS-validated identity, if it presented a client
 	/// certificate during the handshake that chained to a configured
 	/// [`ServerTlsConfig::root`].
@@ -709,6 +1058,7 @@ impl std::str::FromStr for ServerId {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use clap::Parser;
 
 	#[test]
 	fn test_tls_string_or_array() {
@@ -734,4 +1084,131 @@ mod tests {
 		assert_eq!(config.generate, vec!["localhost".to_string()]);
 		assert_eq!(config.root, vec![PathBuf::from("ca.pem")]);
 	}
+
+	#[tokio::test]
+	async fn test_tls_pem() {
+		let key_pair = rcgen::KeyPair::generate().unwrap();
+		let cert = rcgen::CertificateParams::new(vec!["localhost".to_string()])
+			.unwrap()
+			.self_signed(&key_pair)
+			.unwrap();
+
+		let mut config = ServerConfig {
+			bind: Some("[::]:0".to_string()),
+			..Default::default()
+		};
+		config.tls.cert_pem = Some(cert.pem());
+		config.tls.key_pem = Some(key_pair.serialize_pem());
+
+		// The key never shows up in logs or serialized configs.
+		let secret = key_pair.serialize_pem();
+		assert!(!format!("{:?}", config.tls).contains(&secret));
+		assert!(!toml::to_string(&config.tls).unwrap().contains(&secret));
+
+		let server = config.clone().init().unwrap();
+		assert_eq!(server.tls_info().read().unwrap().fingerprints.len(), 1);
+
+		// A key that doesn't match the certificate is rejected.
+		config.tls.key_pem = Some(rcgen::KeyPair::generate().unwrap().serialize_pem());
+		assert!(config.clone().init().is_err());
+
+		// Both halves are required.
+		config.tls.key_pem = None;
+		assert!(config.init().is_err());
+	}
+
+	#[cfg(any(feature = "noq", feature = "quinn"))]
+	#[tokio::test]
+	async fn test_tls_generate_alg() {
+		let config: ServerTlsConfig = toml::from_str("generate = \"localhost\"\ngenerate_alg = \"ed25519\"").unwrap();
+		assert_eq!(config.generate_alg, Some(CertAlgorithm::Ed25519));
+
+		// The OID of each algorithm's public key, as it appears in the DER encoding.
+		let cases: [(Option<CertAlgorithm>, &[u8]); 4] = [
+			(None, &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07]),
+			(
+				Some(CertAlgorithm::P256),
+				&[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+			),
+			(Some(CertAlgorithm::P384), &[0x2b, 0x81, 0x04, 0x00, 0x22]),
+			(Some(CertAlgorithm::Ed25519), &[0x06, 0x03, 0x2b, 0x65, 0x70]),
+		];
+
+		for (alg, oid) in cases {
+			let mut config = ServerConfig {
+				bind: Some("[::]:0".to_string()),
+				..Default::default()
+			};
+			config.tls.generate = vec!["localhost".to_string()];
+			config.tls.generate_alg = alg;
+
+			let server = config.init().unwrap();
+			let info = server.tls_info();
+			let info = info.read().unwrap();
+
+			let certified = &info.certs[0];
+			certified.keys_match().unwrap();
+
+			let der = certified.cert[0].as_ref();
+			assert!(der.windows(oid.len()).any(|window| window == oid), "{alg:?}");
+
+			// The fingerprint is still advertised for the generated certificate.
+			let fingerprint = crate::crypto::sha256(&crate::crypto::provider(), der);
+			assert_eq!(info.fingerprints, vec![hex::encode(fingerprint)]);
+		}
+	}
+
+	#[cfg(any(feature = "noq", feature = "quinn", feature = "quiche"))]
+	#[tokio::test]
+	async fn test_close() {
+		let mut config = ServerConfig {
+			bind: Some("[::]:0".to_string()),
+			..Default::default()
+		};
+		config.tls.generate = vec!["localhost".to_string()];
+
+		// Closing with a backend running must not panic, and ends the accept loop.
+		let mut server = config.init().unwrap();
+		server.close().await;
+		assert!(server.accept().await.is_none());
+
+		// Closing twice is harmless.
+		server.close().await;
+	}
+
+	#[derive(clap::Parser)]
+	struct Cli {
+		#[command(flatten)]
+		server: ServerConfig,
+	}
+
+	#[test]
+	fn test_mtu_discovery() {
+		let config: ServerConfig = toml::from_str("mtu_discovery = true").unwrap();
+		assert_eq!(config.mtu_discovery, Some(true));
+
+		let cli = Cli::parse_from(["test", "--server-mtu-discovery"]);
+		assert_eq!(cli.server.mtu_discovery, Some(true));
+
+		let cli = Cli::parse_from(["test", "--server-mtu-discovery=false"]);
+		assert_eq!(cli.server.mtu_discovery, Some(false));
+
+		let cli = Cli::parse_from(["test"]);
+		assert_eq!(cli.server.mtu_discovery, None);
+	}
+
+	#[test]
+	fn test_idle_timeout() {
+		let config: ServerConfig = toml::from_str("idle_timeout = \"1m\"\nkeep_alive = \"10s\"").unwrap();
+		assert_eq!(config.idle_timeout, Some(Duration::from_secs(60)));
+		assert_eq!(config.keep_alive, Some(Duration::from_secs(10)));
+
+		let cli = Cli::parse_from(["test", "--server-idle-timeout", "1m", "--server-keep-alive", "10s"]);
+		assert_eq!(cli.server.idle_timeout, Some(Duration::from_secs(60)));
+		assert_eq!(cli.server.keep_alive, Some(Duration::from_secs(10)));
+
+		let cli = Cli::parse_from(["test"]);
+		assert_eq!(cli.server.idle_timeout, None);
+		assert_eq!(cli.server.keep_alive, None);
+	}
 }
@@ -1,23 +1,42 @@
 use crate::crypto;
-use crate::server::{ServerTlsConfig, ServerTlsInfo};
+use crate::server::{CertAlgorithm, ServerTlsConfig, ServerTlsInfo};
 use anyhow::Context;
 use rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use std::collections::HashSet;
 use std::fs;
-use std::io::{self, Cursor, Read};
+use std::io::Cursor;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
 // ── FingerprintVerifier ─────────────────────────────────────────────
 
+/// Accepts a server certificate if its SHA-256 matches any of the given fingerprints.
 #[derive(Debug)]
 pub(crate) struct FingerprintVerifier {
 	provider: crypto::Provider,
-	fingerprint: Vec<u8>,
+	fingerprints: HashSet<Vec<u8>>,
 }
 
 impl FingerprintVerifier {
-	pub fn new(provider: crypto::Provider, fingerprint: Vec<u8>) -> Self {
-		Self { provider, fingerprint }
+	pub fn new(provider: crypto::Provider, fingerprints: impl IntoIterator<Item = Vec<u8>>) -> Self {
+		Self {
+			provider,
+			fingerprints: fingerprints.into_iter().collect(),
+		}
+	}
+
+	/// Parse the newline-separated hex fingerprints served at `/certificate.sha256`.
+	pub fn parse(text: &str) -> anyhow::Result<Vec<Vec<u8>>> {
+		let fingerprints = text
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty())
+			.map(hex::decode)
+			.collect::<Result<Vec<_>, _>>()
+			.context("invalid fingerprint")?;
+
+		anyhow::ensure!(!fingerprints.is_empty(), "missing fingerprint");
+		Ok(fingerprints)
 	}
 }
 
@@ -31,7 +50,7 @@ impl rustls::client::danger::ServerCertVerifier for FingerprintVerifier {
 		_now: UnixTime,
 	) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
 		let fingerprint = crypto::sha256(&self.provider, end_entity);
-		if fingerprint.as_ref() == self.fingerprint.as_slice() {
+		if self.fingerprints.contains(fingerprint.as_ref()) {
 			Ok(rustls::client::danger::ServerCertVerified::assertion())
 		} else {
 			Err(rustls::Error::General("fingerprint mismatch".into()))
@@ -82,8 +101,9 @@ impl ServeCerts {
 
 	pub fn load_certs(&self, config: &ServerTlsConfig) -> anyhow::Result<()> {
 		anyhow::ensure!(config.cert.len() == config.key.len(), "must provide both cert and key");
+		let pem = config.pem()?;
 		anyhow::ensure!(
-			!config.cert.is_empty() || !config.generate.is_empty(),
+			!config.cert.is_empty() || pem.is_some() || !config.generate.is_empty(),
 			"must provide at least one cert/key pair or generate entry"
 		);
 
@@ -94,9 +114,14 @@ impl ServeCerts {
 			certs.push(Arc::new(self.load(cert, key)?));
 		}
 
+		if let Some((cert, key)) = pem {
+			certs.push(Arc::new(self.load_pem(cert, key)?));
+		}
+
 		// Generate a new certificate if requested.
 		if !config.generate.is_empty() {
-			certs.push(Arc::new(self.generate(&config.generate)?));
+			let alg = config.generate_alg.unwrap_or_default();
+			certs.push(Arc::new(self.generate(&config.generate, alg)?));
 		}
 
 		self.set_certs(certs);
@@ -105,39 +130,38 @@ impl ServeCerts {
 
 	// Load a certificate and corresponding key from a file, but don't add it to the certs
 	fn load(&self, chain_path: &PathBuf, key_path: &PathBuf) -> anyhow::Result<rustls::sign::CertifiedKey> {
-		let chain = fs::File::open(chain_path).context("failed to open cert file")?;
-		let mut chain = io::BufReader::new(chain);
+		let chain = fs::read(chain_path).context("failed to open cert file")?;
+		let key = fs::read(key_path).context("failed to open key file")?;
+
+		self.load_pem(&chain, &key).context(format!(
+			"failed to load key {} with certificate {}",
+			key_path.display(),
+			chain_path.display()
+		))
+	}
 
-		let chain: Vec<CertificateDer> = rustls_pemfile::certs(&mut chain)
+	/// Load a PEM certificate chain and corresponding key, but don't add it to the certs.
+	pub fn load_pem(&self, chain: &[u8], key: &[u8]) -> anyhow::Result<rustls::sign::CertifiedKey> {
+		let chain: Vec<CertificateDer> = rustls_pemfile::certs(&mut Cursor::new(chain))
 			.collect::<Result<_, _>>()
 			.context("failed to read certs")?;
 
 		anyhow::ensure!(!chain.is_empty(), "could not find certificate");
 
-		// Read the PEM private key
-		let mut keys = fs::File::open(key_path).context("failed to open key file")?;
-
-		// Read the keys into a Vec so we can parse it twice.
-		let mut buf = Vec::new();
-		keys.read_to_end(&mut buf)?;
-
-		let key = rustls_pemfile::private_key(&mut Cursor::new(&buf))?.context("missing private key")?;
+		let key = rustls_pemfile::private_key(&mut Cursor::new(key))?.context("missing private key")?;
 		let key = self.provider.key_provider.load_private_key(key)?;
 
 		let certified_key = rustls::sign::CertifiedKey::new(chain, key);
-
-		certified_key.keys_match().context(format!(
-			"private key {} doesn't match certificate {}",
-			key_path.display(),
-			chain_path.display()
-		))?;
+		certified_key
+			.keys_match()
+			.context("private key doesn't match certificate")?;
 
 		Ok(certified_key)
 	}
 
 	#[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
-	fn generate(&self, hostnames: &[String]) -> anyhow::Result<rustls::sign::CertifiedKey> {
-		let key_pair = rcgen::KeyPair::generate()?;
+	fn generate(&self, hostnames: &[String], alg: CertAlgorithm) -> anyhow::Result<rustls::sign::CertifiedKey> {
+		let key_pair = alg.generate()?;
 
 		let mut params = rcgen::CertificateParams::new(hostnames)?;
 
@@ -159,7 +183,7 @@ impl ServeCerts {
 	}
 
 	#[cfg(not(any(feature = "aws-lc-rs", feature = "ring")))]
-	fn generate(&self, _hostnames: &[String]) -> anyhow::Result<rustls::sign::CertifiedKey> {
+	fn generate(&self, _hostnames: &[String], _alg: CertAlgorithm) -> anyhow::Result<rustls::sign::CertifiedKey> {
 		anyhow::bail!("no crypto provider available; enable aws-lc-rs or ring feature");
 	}
 
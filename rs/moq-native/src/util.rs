@@ -60,6 +60,23 @@ fn normalize_family(addr: SocketAddr, local: SocketAddr) -> SocketAddr {
 	}
 }
 
+/// Returns a new URL with a changed scheme.
+///
+/// [`url::Url::set_scheme`] returns an error if the scheme change is not valid according to
+/// [the URL specification's section on legal scheme state overrides](https://url.spec.whatwg.org/#scheme-state).
+///
+/// This function allows all scheme changes, as long as the resulting URL is valid.
+#[cfg(any(feature = "noq", feature = "quinn", feature = "quiche", feature = "iroh"))]
+pub(crate) fn url_set_scheme(url: url::Url, scheme: &str) -> anyhow::Result<url::Url> {
+	let url = format!(
+		"{}:{}",
+		scheme,
+		url.to_string().split_once(":").context("invalid URL")?.1
+	)
+	.parse()?;
+	Ok(url)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -128,6 +145,13 @@ mod tests {
 		assert_eq!(pick_addr([v6], local_v4), Some(v6));
 	}
 
+	#[test]
+	fn url_set_scheme_to_https() {
+		let url = url::Url::parse("moq://relay.example.com:4443/room?jwt=abc").unwrap();
+		let url = url_set_scheme(url, "https").unwrap();
+		assert_eq!(url.as_str(), "https://relay.example.com:4443/room?jwt=abc");
+	}
+
 	#[test]
 	fn pick_addr_empty() {
 		let local: SocketAddr = "0.0.0.0:0".parse().unwrap();
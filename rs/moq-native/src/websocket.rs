@@ -1,8 +1,13 @@
 use anyhow::Context;
+use futures::{Sink, Stream};
 use qmux::tokio_tungstenite;
+use qmux::tungstenite;
 use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::{Arc, LazyLock, Mutex};
-use std::{net, time};
+use std::task::{Context as TaskContext, Poll, ready};
+use std::{io, net, time};
 use url::Url;
 
 // Track servers (hostname:port) where WebSocket won the race, so we won't give QUIC a headstart next time
@@ -34,6 +39,20 @@ pub struct ClientWebSocket {
 	#[serde(with = "humantime_serde")]
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub delay: Option<time::Duration>,
+
+	/// Send a ping after this long without receiving anything, closing the connection
+	/// if nothing arrives within another interval. Disabled by default.
+	///
+	/// Useful behind proxies that silently drop idle connections; combine with
+	/// [`Client::reconnect`](crate::Client::reconnect) to re-establish the session.
+	#[arg(
+		id = "websocket-keep-alive",
+		long = "websocket-keep-alive",
+		env = "MOQ_CLIENT_WEBSOCKET_KEEP_ALIVE",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+	pub keep_alive: Option<time::Duration>,
 }
 
 impl Default for ClientWebSocket {
@@ -41,6 +60,7 @@ impl Default for ClientWebSocket {
 		Self {
 			enabled: true,
 			delay: Some(time::Duration::from_millis(200)),
+			keep_alive: None,
 		}
 	}
 }
@@ -56,7 +76,7 @@ pub(crate) async fn race_handle(
 	}
 
 	// Only attempt WebSocket for HTTP-based schemes.
-	// Custom protocols (moqt://, moql://) use raw QUIC and don't support WebSocket.
+	// Custom protocols (moqt://, moql://, moq://) use QUIC and don't support WebSocket.
 	match url.scheme() {
 		"http" | "https" | "ws" | "wss" => {}
 		_ => return None,
@@ -79,7 +99,7 @@ pub(crate) async fn connect(
 
 	let host = url.host_str().context("missing hostname")?.to_string();
 	let port = url.port().unwrap_or_else(|| match url.scheme() {
-		"https" | "wss" | "moql" | "moqt" => 443,
+		"https" | "wss" | "moql" | "moqt" | "moq" => 443,
 		"http" | "ws" => 80,
 		_ => 443,
 	});
@@ -121,19 +141,164 @@ pub(crate) async fn connect(
 		tokio_tungstenite::Connector::Plain
 	};
 
-	let session = qmux::Client::new()
-		.with_protocols(alpns)
-		.with_connector(connector)
-		.connect(url.as_str())
+	let mut request = tungstenite::client::IntoClientRequest::into_client_request(url.as_str())?;
+	request.headers_mut().insert(
+		tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL,
+		subprotocols(alpns).join(", ").parse()?,
+	);
+
+	let (ws, response) = tokio_tungstenite::connect_async_tls_with_config(request, None, false, Some(connector))
 		.await
 		.context("failed to connect WebSocket")?;
 
+	let protocol = response
+		.headers()
+		.get(tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL)
+		.and_then(|value| value.to_str().ok());
+
+	let session = match config.keep_alive {
+		Some(interval) => qmux::ws::connect(KeepAlive::new(ws, interval), protocol),
+		None => qmux::ws::connect(ws, protocol),
+	};
+
 	tracing::warn!(%url, "using WebSocket fallback");
 	WEBSOCKET_WON.lock().unwrap().insert(key);
 
 	Ok(session)
 }
 
+/// Offer each ALPN with both the qmux and webtransport prefixes, in qmux's preference order.
+///
+/// TODO: qmux::Client performs the same handshake but has no hook to wrap the socket,
+/// which the keep-alive needs. Use it instead once it gains one.
+fn subprotocols(alpns: &[&str]) -> Vec<String> {
+	qmux::ALPNS
+		.iter()
+		.zip(qmux::PREFIXES)
+		.flat_map(|(bare, prefix)| {
+			std::iter::once(bare.to_string()).chain(alpns.iter().map(move |alpn| format!("{prefix}{alpn}")))
+		})
+		.collect()
+}
+
+/// Pings the peer after `interval` without receiving anything, and fails the stream if
+/// nothing arrives within another `interval`.
+///
+/// Any message counts as activity, including the pong sent in reply to our ping.
+struct KeepAlive<S> {
+	inner: S,
+	interval: time::Duration,
+	deadline: Pin<Box<tokio::time::Sleep>>,
+
+	// We've pinged and are waiting for any message.
+	pinged: bool,
+	// The ping still needs to be queued or flushed.
+	send: bool,
+	flush: bool,
+}
+
+impl<S> KeepAlive<S>
+where
+	S: Stream<Item = Result<tungstenite::Message, tungstenite::Error>>
+		+ Sink<tungstenite::Message, Error = tungstenite::Error>
+		+ Unpin,
+{
+	fn new(inner: S, interval: time::Duration) -> Self {
+		Self {
+			inner,
+			interval,
+			deadline: Box::pin(tokio::time::sleep(interval)),
+			pinged: false,
+			send: false,
+			flush: false,
+		}
+	}
+
+	// Queue and flush the ping, as far as the sink allows without blocking reads.
+	fn poll_ping(&mut self, cx: &mut TaskContext<'_>) -> Result<(), tungstenite::Error> {
+		let mut inner = Pin::new(&mut self.inner);
+
+		if self.send {
+			if inner.as_mut().poll_ready(cx)?.is_pending() {
+				return Ok(());
+			}
+
+			inner
+				.as_mut()
+				.start_send(tungstenite::Message::Ping(Default::default()))?;
+			self.send = false;
+			self.flush = true;
+		}
+
+		if self.flush && inner.poll_flush(cx)?.is_ready() {
+			self.flush = false;
+		}
+
+		Ok(())
+	}
+}
+
+impl<S> Stream for KeepAlive<S>
+where
+	S: Stream<Item = Result<tungstenite::Message, tungstenite::Error>>
+		+ Sink<tungstenite::Message, Error = tungstenite::Error>
+		+ Unpin,
+{
+	type Item = Result<tungstenite::Message, tungstenite::Error>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		loop {
+			if let Err(err) = this.poll_ping(cx) {
+				return Poll::Ready(Some(Err(err)));
+			}
+
+			if let Poll::Ready(res) = Pin::new(&mut this.inner).poll_next(cx) {
+				this.pinged = false;
+				let interval = this.interval;
+				this.deadline.as_mut().reset(tokio::time::Instant::now() + interval);
+				return Poll::Ready(res);
+			}
+
+			ready!(this.deadline.as_mut().poll(cx));
+
+			if this.pinged {
+				let err = io::Error::new(io::ErrorKind::TimedOut, "WebSocket keep-alive timed out");
+				return Poll::Ready(Some(Err(err.into())));
+			}
+
+			this.pinged = true;
+			this.send = true;
+			let interval = this.interval;
+			this.deadline.as_mut().reset(tokio::time::Instant::now() + interval);
+		}
+	}
+}
+
+impl<S> Sink<tungstenite::Message> for KeepAlive<S>
+where
+	S: Sink<tungstenite::Message, Error = tungstenite::Error> + Unpin,
+{
+	type Error = tungstenite::Error;
+
+	fn poll_ready(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+		Pin::new(&mut self.get_mut().inner).poll_ready(cx)
+	}
+
+	fn start_send(self: Pin<&mut Self>, item: tungstenite::Message) -> Result<(), Self::Error> {
+		Pin::new(&mut self.get_mut().inner).start_send(item)
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+		Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+	}
+
+	fn poll_close(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+		Pin::new(&mut self.get_mut().inner).poll_close(cx)
+	}
+}
+
 /// Listens for incoming WebSocket connections on a TCP port.
 ///
 /// Use with [`crate::Server::with_websocket`] to accept WebSocket connections
@@ -174,3 +339,58 @@ impl WebSocketListener {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::StreamExt;
+	use tokio_tungstenite::WebSocketStream;
+	use tungstenite::protocol::Role;
+
+	async fn pair(
+		interval: time::Duration,
+	) -> (
+		KeepAlive<WebSocketStream<tokio::io::DuplexStream>>,
+		WebSocketStream<tokio::io::DuplexStream>,
+	) {
+		let (client, server) = tokio::io::duplex(1024);
+		let client = WebSocketStream::from_raw_socket(client, Role::Client, None).await;
+		let server = WebSocketStream::from_raw_socket(server, Role::Server, None).await;
+		(KeepAlive::new(client, interval), server)
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_keep_alive_ping() {
+		let (mut client, mut server) = pair(time::Duration::from_secs(1)).await;
+
+		// Keep reading so the server answers pings.
+		let server = tokio::spawn(async move {
+			let mut pings = 0;
+			while let Some(Ok(message)) = server.next().await {
+				if message.is_ping() {
+					pings += 1;
+				}
+			}
+			pings
+		});
+
+		let start = tokio::time::Instant::now();
+		let message = client.next().await.unwrap().unwrap();
+		assert!(message.is_pong());
+		assert_eq!(start.elapsed(), time::Duration::from_secs(1));
+
+		drop(client);
+		assert_eq!(server.await.unwrap(), 1);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_keep_alive_timeout() {
+		// The server never reads, so the ping goes unanswered.
+		let (mut client, _server) = pair(time::Duration::from_secs(1)).await;
+
+		let start = tokio::time::Instant::now();
+		let err = client.next().await.unwrap().unwrap_err();
+		assert!(matches!(err, tungstenite::Error::Io(err) if err.kind() == io::ErrorKind::TimedOut));
+		assert_eq!(start.elapsed(), time::Duration::from_secs(2));
+	}
+}
@@ -110,6 +110,13 @@ async fn quinn_webtransport() {
 	backend_test("https", moq_native::QuicBackend::Quinn).await;
 }
 
+#[cfg(feature = "quinn")]
+#[tracing_test::traced_test]
+#[tokio::test]
+async fn quinn_dual_offer() {
+	backend_test("moq", moq_native::QuicBackend::Quinn).await;
+}
+
 // ── Quiche backend ──────────────────────────────────────────────────
 
 #[cfg(feature = "quiche")]
@@ -261,3 +268,10 @@ async fn noq_raw_quic() {
 async fn noq_webtransport() {
 	backend_test("https", moq_native::QuicBackend::Noq).await;
 }
+
+#[cfg(feature = "noq")]
+#[tracing_test::traced_test]
+#[tokio::test]
+async fn noq_dual_offer() {
+	backend_test("moq", moq_native::QuicBackend::Noq).await;
+}
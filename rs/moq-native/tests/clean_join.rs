@@ -0,0 +1,123 @@
+//! Integration test: a subscriber that requests `clean_join` never receives a group
+//! that can't be read from its first frame.
+//!
+//! The server publishes a track whose in-progress group has already evicted its
+//! leading frames (as happens with a long GOP that overflows the group cache).
+//! The client joins with `clean_join` set and must receive the next group instead,
+//! starting with its keyframe.
+
+use moq_native::moq_lite::{Origin, Track};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Enough frames to overflow the per-group frame cache and evict the keyframe.
+const PARTIAL_FRAMES: usize = 2048;
+
+#[tracing_test::traced_test]
+#[tokio::test]
+async fn clean_join_skips_truncated_group() {
+	// ── publisher (server) ──────────────────────────────────────────
+	let pub_origin = Origin::random().produce();
+	let mut broadcast = pub_origin.create_broadcast("test").expect("failed to create broadcast");
+	let mut track = broadcast
+		.create_track(Track::new("video"))
+		.expect("failed to create track");
+
+	// A partial group whose keyframe has already been evicted at join time.
+	let mut partial = track.append_group().expect("failed to append group");
+	partial
+		.write_frame(b"keyframe".as_ref())
+		.expect("failed to write frame");
+	for _ in 0..PARTIAL_FRAMES {
+		partial.write_frame(b"delta".as_ref()).expect("failed to write frame");
+	}
+
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+	server_config.version = vec!["moq-lite-05".parse().unwrap()];
+
+	let mut server = server_config.init().expect("failed to init server");
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	// ── subscriber (client) ─────────────────────────────────────────
+	let sub_origin = Origin::random().produce();
+	let mut announcements = sub_origin.consume();
+
+	let mut client_config = moq_native::ClientConfig::default();
+	client_config.tls.disable_verify = Some(true);
+	client_config.version = vec!["moq-lite-05".parse().unwrap()];
+
+	let client = client_config.init().expect("failed to init client");
+	let url: url::Url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+
+	let (joined_tx, joined_rx) = tokio::sync::oneshot::channel::<()>();
+
+	// ── run server and client concurrently ──────────────────────────
+	let server_handle = tokio::spawn(async move {
+		let request = server.accept().await.expect("no incoming connection");
+		let session = request.with_publish(pub_origin.consume()).ok().await?;
+
+		// Start a clean group once the subscriber has joined.
+		joined_rx.await.ok();
+		partial.finish()?;
+
+		let mut group = track.append_group()?;
+		group.write_frame(b"keyframe".as_ref())?;
+		group.finish()?;
+
+		// Keep producers alive so the subscriber can read data.
+		let _broadcast = broadcast;
+		let _track = track;
+
+		// Block until the client disconnects.
+		let _ = session.closed().await;
+		Ok::<_, anyhow::Error>(())
+	});
+
+	let client = client.with_consume(sub_origin);
+	let session = tokio::time::timeout(TIMEOUT, client.connect(url))
+		.await
+		.expect("client connect timed out")
+		.expect("client connect failed");
+
+	let (_, bc) = tokio::time::timeout(TIMEOUT, announcements.announced())
+		.await
+		.expect("announce timed out")
+		.expect("origin closed");
+	let bc = bc.expect("expected announce, got unannounce");
+
+	let mut track_sub = bc
+		.subscribe_track(&Track {
+			clean_join: true,
+			..Track::new("video")
+		})
+		.expect("subscribe_track failed");
+
+	// Give the publisher time to process the SUBSCRIBE before the clean group appears.
+	tokio::time::sleep(Duration::from_millis(100)).await;
+	joined_tx.send(()).ok();
+
+	let mut group_sub = tokio::time::timeout(TIMEOUT, track_sub.recv_group())
+		.await
+		.expect("recv_group timed out")
+		.expect("recv_group failed")
+		.expect("track closed prematurely");
+
+	assert_eq!(group_sub.sequence, 1, "the truncated group should have been skipped");
+
+	let frame = tokio::time::timeout(TIMEOUT, group_sub.read_frame())
+		.await
+		.expect("read_frame timed out")
+		.expect("read_frame failed")
+		.expect("group closed prematurely");
+
+	assert_eq!(&*frame, b"keyframe");
+
+	drop(session);
+	server_handle
+		.await
+		.expect("server task panicked")
+		.expect("server task failed");
+}
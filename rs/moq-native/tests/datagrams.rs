@@ -0,0 +1,127 @@
+//! Integration test: a subscriber that requests `datagrams` receives single-frame
+//! groups as QUIC datagrams, while groups too large for a datagram fall back to a stream.
+
+use moq_native::moq_lite::{Origin, Track};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tracing_test::traced_test]
+#[tokio::test]
+async fn datagrams_deliver_small_groups() {
+	// ── publisher (server) ──────────────────────────────────────────
+	let pub_origin = Origin::random().produce();
+	let mut broadcast = pub_origin.create_broadcast("test").expect("failed to create broadcast");
+	let mut track = broadcast
+		.create_track(Track::new("audio"))
+		.expect("failed to create track");
+
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+	server_config.version = vec!["moq-lite-05".parse().unwrap()];
+
+	let mut server = server_config.init().expect("failed to init server");
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	// ── subscriber (client) ─────────────────────────────────────────
+	let sub_origin = Origin::random().produce();
+	let mut announcements = sub_origin.consume();
+
+	let mut client_config = moq_native::ClientConfig::default();
+	client_config.tls.disable_verify = Some(true);
+	client_config.version = vec!["moq-lite-05".parse().unwrap()];
+
+	let client = client_config.init().expect("failed to init client");
+	let url: url::Url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+
+	let (joined_tx, joined_rx) = tokio::sync::oneshot::channel::<()>();
+
+	// ── run server and client concurrently ──────────────────────────
+	let server_handle = tokio::spawn(async move {
+		let request = server.accept().await.expect("no incoming connection");
+		let session = request.with_publish(pub_origin.consume()).ok().await?;
+
+		// Write the groups once the subscriber has joined.
+		joined_rx.await.ok();
+		track.write_frame(b"opus".as_ref())?;
+		track.write_frame(vec![7u8; 64 * 1024])?;
+
+		// Keep producers alive so the subscriber can read data.
+		let _broadcast = broadcast;
+		let _track = track;
+
+		// Block until the client disconnects.
+		let _ = session.closed().await;
+		Ok::<_, anyhow::Error>(())
+	});
+
+	let client = client.with_consume(sub_origin);
+	let session = tokio::time::timeout(TIMEOUT, client.connect(url))
+		.await
+		.expect("client connect timed out")
+		.expect("client connect failed");
+
+	// QUIC negotiates datagram support, leaving room for at least a small group.
+	let max_datagram_size = session.max_datagram_size().expect("datagrams not negotiated");
+	assert!(
+		max_datagram_size >= 1000,
+		"unexpected max datagram size: {max_datagram_size}"
+	);
+
+	let (_, bc) = tokio::time::timeout(TIMEOUT, announcements.announced())
+		.await
+		.expect("announce timed out")
+		.expect("origin closed");
+	let bc = bc.expect("expected announce, got unannounce");
+
+	let mut track_sub = bc
+		.subscribe_track(&Track {
+			datagrams: true,
+			..Track::new("audio")
+		})
+		.expect("subscribe_track failed");
+
+	// Give the publisher time to process the SUBSCRIBE before the groups appear.
+	tokio::time::sleep(Duration::from_millis(100)).await;
+	joined_tx.send(()).ok();
+
+	let mut frames = Vec::new();
+	while frames.len() < 2 {
+		let mut group = tokio::time::timeout(TIMEOUT, track_sub.recv_group())
+			.await
+			.expect("recv_group timed out")
+			.expect("recv_group failed")
+			.expect("track closed prematurely");
+
+		let frame = tokio::time::timeout(TIMEOUT, group.read_frame())
+			.await
+			.expect("read_frame timed out")
+			.expect("read_frame failed")
+			.expect("group closed prematurely");
+
+		frames.push((group.sequence, frame));
+	}
+	frames.sort_by_key(|(sequence, _)| *sequence);
+
+	assert_eq!(&*frames[0].1, b"opus");
+	assert_eq!(frames[1].1.len(), 64 * 1024);
+
+	// Only the small group went out as a datagram.
+	logs_assert(|lines: &[&str]| {
+		match lines
+			.iter()
+			.filter(|line| line.contains("sent group as datagram"))
+			.count()
+		{
+			1 => Ok(()),
+			n => Err(format!("expected 1 datagram, got {n}")),
+		}
+	});
+
+	drop(session);
+	server_handle
+		.await
+		.expect("server task panicked")
+		.expect("server task failed");
+}
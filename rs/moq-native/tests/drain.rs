@@ -0,0 +1,122 @@
+//! Integration test: `Server::drain` lets open sessions finish before closing.
+//!
+//! A drain stops accepting new sessions, waits for the existing ones to close,
+//! and only force-closes them once the deadline passes.
+
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A client for the server, along with the URL to connect to.
+fn client(server: &moq_native::Server) -> (moq_native::Client, url::Url) {
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	let mut client_config = moq_native::ClientConfig::default();
+	client_config.tls.disable_verify = Some(true);
+
+	let client = client_config.init().expect("failed to init client");
+	let url: url::Url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+
+	let origin = moq_native::moq_lite::Origin::random().produce();
+	(client.with_consume(origin), url)
+}
+
+/// Start a server and connect a single client session to it.
+async fn connect() -> (
+	moq_native::Server,
+	moq_native::moq_lite::Session,
+	moq_native::moq_lite::Session,
+) {
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+
+	let mut server = server_config.init().expect("failed to init server");
+	let (client, url) = client(&server);
+
+	let (server_session, client_session) = tokio::time::timeout(TIMEOUT, async {
+		let server_session = async {
+			let request = server.accept().await.expect("no incoming connection");
+			request.ok().await.expect("server handshake failed")
+		};
+		let client_session = async { client.connect(url).await.expect("client connect failed") };
+		tokio::join!(server_session, client_session)
+	})
+	.await
+	.expect("connect timed out");
+
+	(server, server_session, client_session)
+}
+
+#[tokio::test]
+async fn drain_waits_for_sessions() {
+	let (mut server, server_session, client_session) = connect().await;
+
+	// The client hangs up on its own, well before the deadline.
+	tokio::spawn(async move {
+		tokio::time::sleep(Duration::from_millis(100)).await;
+		drop(client_session);
+	});
+
+	let start = Instant::now();
+	tokio::time::timeout(TIMEOUT, server.drain(Duration::from_secs(30)))
+		.await
+		.expect("drain did not finish once the session closed");
+	assert!(
+		start.elapsed() >= Duration::from_millis(100),
+		"drain returned too early"
+	);
+
+	assert!(
+		server.accept().await.is_none(),
+		"accept should return None once drained"
+	);
+	drop(server_session);
+}
+
+#[tokio::test]
+async fn drain_closes_after_deadline() {
+	let (mut server, server_session, client_session) = connect().await;
+
+	tokio::time::timeout(TIMEOUT, server.drain(Duration::from_millis(100)))
+		.await
+		.expect("drain ignored the deadline");
+
+	// The session that outlived the deadline was closed by the server.
+	tokio::time::timeout(TIMEOUT, client_session.closed())
+		.await
+		.expect("client session was not closed")
+		.expect_err("closed always returns an error");
+
+	assert!(
+		server.accept().await.is_none(),
+		"accept should return None once drained"
+	);
+	drop(server_session);
+}
+
+#[tokio::test]
+async fn drain_refuses_new_connections() {
+	let (mut server, server_session, client_session) = connect().await;
+	let (client, url) = client(&server);
+
+	let drain = tokio::spawn(async move {
+		server.drain(Duration::from_secs(30)).await;
+	});
+
+	// Give the drain a moment to start refusing.
+	tokio::time::sleep(Duration::from_millis(100)).await;
+
+	// Refused outright, rather than left to hit the client's connect timeout.
+	let res = tokio::time::timeout(Duration::from_secs(2), client.connect(url))
+		.await
+		.expect("connect was not refused");
+	assert!(res.is_err(), "connect should fail while draining");
+
+	drop(client_session);
+	drop(server_session);
+	tokio::time::timeout(TIMEOUT, drain)
+		.await
+		.expect("drain did not finish once the session closed")
+		.unwrap();
+}
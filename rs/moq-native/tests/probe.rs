@@ -0,0 +1,46 @@
+//! Integration test: probe a server without publishing or subscribing anything.
+
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tracing_test::traced_test]
+#[tokio::test]
+async fn probe() {
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+
+	let mut server = server_config.init().expect("failed to init server");
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	let server_handle = tokio::spawn(async move {
+		let request = server.accept().await.expect("no incoming connection");
+		let session = request.ok().await.expect("server handshake failed");
+
+		// The probe closes the session as soon as the handshake completes.
+		tokio::time::timeout(TIMEOUT, session.closed())
+			.await
+			.expect("probe didn't close the session")
+	});
+
+	let version: moq_native::moq_lite::Version = "moq-lite-03".parse().unwrap();
+
+	let mut client_config = moq_native::ClientConfig::default();
+	client_config.tls.disable_verify = Some(true);
+	client_config.version = vec![version];
+	let client = client_config.init().expect("failed to init client");
+
+	// Origins configured on the client are ignored by the probe.
+	let origin = moq_native::moq_lite::Origin::random().produce();
+	let client = client.with_publish(origin.consume()).with_consume(origin);
+
+	let url: url::Url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+	let info = client.probe(url).await.expect("probe failed");
+
+	assert_eq!(info.version, version);
+	assert!(info.rtt.is_some());
+	assert!(info.elapsed < TIMEOUT);
+
+	server_handle.await.expect("server task panicked").unwrap_err();
+}
@@ -0,0 +1,61 @@
+//! Integration test: verify that a server rejecting a raw QUIC session with
+//! [`moq_native::Request::close_with`] delivers its code and reason to the client.
+//!
+//! The client is a bare Quinn endpoint so the QUIC close frame can be inspected directly.
+
+#![cfg(feature = "quinn")]
+
+use std::{sync::Arc, time::Duration};
+
+use moq_native::web_transport_quinn::quinn;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test]
+async fn quinn_close_with_reason() {
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+	server_config.backend = Some(moq_native::QuicBackend::Quinn);
+
+	let mut server = server_config.init().expect("failed to init server");
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	let server_handle = tokio::spawn(async move {
+		let request = server.accept().await.expect("no incoming connection");
+		request.close_with(401, "token_expired").await
+	});
+
+	let mut client_tls = moq_native::ClientTls::default();
+	client_tls.disable_verify = Some(true);
+	let mut tls = client_tls.build().expect("failed to build client TLS");
+	tls.alpn_protocols = moq_native::moq_lite::ALPNS
+		.iter()
+		.map(|alpn| alpn.as_bytes().to_vec())
+		.collect();
+
+	let crypto = quinn::crypto::rustls::QuicClientConfig::try_from(tls).expect("invalid client TLS");
+	let mut endpoint = quinn::Endpoint::client("[::]:0".parse().unwrap()).expect("failed to bind client");
+	endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(crypto)));
+
+	let server_addr = (std::net::Ipv6Addr::LOCALHOST, addr.port()).into();
+	let conn = tokio::time::timeout(TIMEOUT, endpoint.connect(server_addr, "localhost").unwrap())
+		.await
+		.expect("client connect timed out")
+		.expect("failed to connect");
+
+	let err = tokio::time::timeout(TIMEOUT, conn.closed())
+		.await
+		.expect("connection close timed out");
+
+	server_handle
+		.await
+		.expect("server task panicked")
+		.expect("server failed to reject");
+
+	let quinn::ConnectionError::ApplicationClosed(close) = err else {
+		panic!("unexpected close: {err}");
+	};
+	assert_eq!(close.error_code, quinn::VarInt::from_u32(401));
+	assert_eq!(&close.reason[..], b"token_expired");
+}
@@ -0,0 +1,48 @@
+//! Integration test: read the transport's connection stats over loopback.
+#![cfg(feature = "quinn")]
+
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tracing_test::traced_test]
+#[tokio::test]
+async fn quinn_stats() {
+	let mut server_config = moq_native::ServerConfig::default();
+	server_config.bind = Some("[::]:0".to_string());
+	server_config.tls.generate = vec!["localhost".into()];
+	server_config.backend = Some(moq_native::QuicBackend::Quinn);
+
+	let mut server = server_config.init().expect("failed to init server");
+	let addr = server.local_addr().expect("failed to get local addr");
+
+	let server_handle = tokio::spawn(async move {
+		let request = server.accept().await.expect("no incoming connection");
+		let session = request.ok().await.expect("server handshake failed");
+		let _ = tokio::time::timeout(TIMEOUT, session.closed()).await;
+	});
+
+	let mut client_config = moq_native::ClientConfig::default();
+	client_config.tls.disable_verify = Some(true);
+	client_config.backend = Some(moq_native::QuicBackend::Quinn);
+	let client = client_config.init().expect("failed to init client");
+
+	let url: url::Url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+	let session = tokio::time::timeout(TIMEOUT, client.connect(url))
+		.await
+		.expect("connect timed out")
+		.expect("connect failed");
+
+	// The handshake alone is enough to measure the RTT and count some traffic.
+	let stats = session.stats();
+	let rtt = stats.rtt.expect("missing rtt");
+	assert!(rtt > Duration::ZERO && rtt < TIMEOUT, "{rtt:?}");
+	assert!(stats.bytes_sent.unwrap() > 0);
+	assert!(stats.bytes_received.unwrap() > 0);
+	assert!(stats.packets_sent.unwrap() > 0);
+	assert!(stats.packets_lost.is_some());
+	assert!(stats.estimated_send_rate.unwrap() > 0);
+
+	drop(session);
+	server_handle.await.expect("server task panicked");
+}
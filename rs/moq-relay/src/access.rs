@@ -0,0 +1,195 @@
+use std::{
+	io::Write,
+	net::SocketAddr,
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	time::{Instant, SystemTime},
+};
+
+use anyhow::Context;
+use moq_lite::PublishStats;
+use serde::Serialize;
+
+/// Configuration for the access log, written as one JSON line per closed session.
+///
+/// Unlike the tracing output, each record summarizes a whole session (who connected, what they
+/// announced and subscribed to, and how much was transferred), for billing or abuse analysis.
+#[derive(clap::Args, Clone, Debug, serde::Serialize, serde::Deserialize, Default)]
+#[serde_with::skip_serializing_none]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+#[group(id = "access-config")]
+pub struct AccessConfig {
+	/// Append the access log to this file, or `-` for stdout. Disabled by default.
+	#[arg(id = "access-log", long = "access-log", env = "MOQ_ACCESS_LOG")]
+	pub log: Option<PathBuf>,
+}
+
+impl AccessConfig {
+	/// Open the configured sink, or return a disabled log if none is configured.
+	pub fn init(&self) -> anyhow::Result<AccessLog> {
+		let Some(path) = &self.log else {
+			return Ok(AccessLog::default());
+		};
+
+		if path.as_os_str() == "-" {
+			return Ok(AccessLog::new(std::io::stdout()));
+		}
+
+		let file = std::fs::OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(path)
+			.with_context(|| format!("failed to open access log: {}", path.display()))?;
+		Ok(AccessLog::new(file))
+	}
+}
+
+/// A sink for [`AccessRecord`]s, disabled by default.
+///
+/// Cloning shares the sink.
+#[derive(Clone, Default)]
+pub struct AccessLog {
+	sink: Option<Arc<Mutex<Box<dyn Write + Send>>>>,
+}
+
+impl AccessLog {
+	/// Write each record as a line of JSON to the given writer.
+	pub fn new(sink: impl Write + Send + 'static) -> Self {
+		Self {
+			sink: Some(Arc::new(Mutex::new(Box::new(sink)))),
+		}
+	}
+
+	/// Returns true if records are written anywhere.
+	pub fn is_enabled(&self) -> bool {
+		self.sink.is_some()
+	}
+
+	/// Start accounting for a session, counting into `stats` as well as the session itself.
+	pub fn start(
+		&self,
+		id: u64,
+		transport: &str,
+		remote: Option<SocketAddr>,
+		root: &str,
+		stats: PublishStats,
+	) -> AccessSession {
+		// Only split out the session's counters when they'll be logged.
+		let stats = match self.is_enabled() {
+			true => stats.child(),
+			false => stats,
+		};
+
+		AccessSession {
+			log: self.clone(),
+			id,
+			transport: transport.to_string(),
+			remote,
+			root: root.to_string(),
+			stats,
+			started: Instant::now(),
+		}
+	}
+
+	/// Write a record to the sink, if enabled.
+	pub fn record(&self, record: &AccessRecord) {
+		let Some(sink) = &self.sink else {
+			return;
+		};
+
+		let mut line = serde_json::to_vec(record).expect("failed to encode access record");
+		line.push(b'\n');
+
+		let mut sink = sink.lock().unwrap();
+		if let Err(err) = sink.write_all(&line).and_then(|_| sink.flush()) {
+			tracing::warn!(%err, "failed to write access log");
+		}
+	}
+}
+
+/// Accumulates the stats of a single session until it closes. See [`AccessLog::start`].
+pub struct AccessSession {
+	log: AccessLog,
+	id: u64,
+	transport: String,
+	remote: Option<SocketAddr>,
+	root: String,
+	stats: PublishStats,
+	started: Instant,
+}
+
+impl AccessSession {
+	/// The counters to pass to the session via `with_stats`.
+	pub fn stats(&self) -> PublishStats {
+		self.stats.clone()
+	}
+
+	/// Record the closed session, along with the error that closed it.
+	pub fn finish(self, session: &moq_lite::Session, err: Option<&moq_lite::Error>) {
+		if !self.log.is_enabled() {
+			return;
+		}
+
+		let timestamp = SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis() as u64;
+
+		let stats = session.stats();
+
+		self.log.record(&AccessRecord {
+			timestamp,
+			id: self.id,
+			transport: self.transport,
+			remote: self.remote,
+			root: self.root,
+			version: session.version().to_string(),
+			duration_ms: self.started.elapsed().as_millis() as u64,
+			subscribes: self.stats.subscribes(),
+			announces: self.stats.announces(),
+			groups: self.stats.groups(),
+			bytes_sent: stats.bytes_sent,
+			bytes_received: stats.bytes_received,
+			rtt_us: stats.rtt.map(|rtt| rtt.as_micros() as u64),
+			packets_lost: stats.packets_lost,
+			error: err.map(|err| err.to_string()),
+		});
+	}
+}
+
+/// A summary of a closed session, written to the [`AccessLog`].
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct AccessRecord {
+	/// When the session closed, in milliseconds since the Unix epoch.
+	pub timestamp: u64,
+	/// The connection identifier, matching the `id` in the tracing output.
+	pub id: u64,
+	/// The transport used, such as `quic`, `iroh` or `websocket`.
+	pub transport: String,
+	/// The peer's address, if known.
+	pub remote: Option<SocketAddr>,
+	/// The authenticated root path.
+	pub root: String,
+	/// The negotiated protocol version.
+	pub version: String,
+	/// How long the session was open.
+	pub duration_ms: u64,
+	/// Subscriptions the peer made to the relay.
+	pub subscribes: u64,
+	/// Broadcasts the peer announced to the relay.
+	pub announces: u64,
+	/// Groups the relay served to the peer.
+	pub groups: u64,
+	/// Bytes sent by the transport, or `None` if it doesn't count them (WebSocket).
+	pub bytes_sent: Option<u64>,
+	/// Bytes received by the transport, or `None` if it doesn't count them (WebSocket).
+	pub bytes_received: Option<u64>,
+	/// The final round-trip time estimate in microseconds, or `None` if not measured (WebSocket).
+	pub rtt_us: Option<u64>,
+	/// Packets the transport detected as lost, or `None` if not counted (WebSocket).
+	pub packets_lost: Option<u64>,
+	/// The error that closed the session, if any.
+	pub error: Option<String>,
+}
@@ -4,7 +4,7 @@ use http_cache_reqwest::{Cache, CacheMode, HttpCache, HttpCacheOptions, MokaMana
 #[cfg(test)]
 use moq_lite::AsPath;
 use moq_lite::{Path, PathOwned, PathPrefixes};
-use moq_token::{Key, KeyId};
+use moq_token::{JtiStore, Key, KeyId, MemoryJtiStore};
 use reqwest_middleware::ClientWithMiddleware;
 use serde::{Deserialize, Serialize};
 use serde_with::{OneOrMany, formats::PreferMany, serde_as};
@@ -115,6 +115,12 @@ pub enum AuthError {
 	#[error("the path does not match the root")]
 	IncorrectRoot,
 
+	#[error("the token audience does not match this relay")]
+	IncorrectAudience,
+
+	#[error("the token was already used")]
+	TokenReplayed,
+
 	#[error("key not found")]
 	KeyNotFound,
 
@@ -243,6 +249,22 @@ pub struct AuthConfig {
 	#[arg(long = "auth-key-dir", env = "MOQ_AUTH_KEY_DIR")]
 	pub key_dir: Option<String>,
 
+	/// The audience this relay expects in signed tokens.
+	///
+	/// When set, a JWT is only accepted if its `aud` claim matches exactly, so a
+	/// token minted for another cluster can't be replayed here. Tokens without an
+	/// `aud` claim are rejected too. Has no effect on public access.
+	#[arg(long = "auth-audience", env = "MOQ_AUTH_AUDIENCE")]
+	pub audience: Option<String>,
+
+	/// Reject a JWT if its `jti` claim was already used.
+	///
+	/// Seen IDs are kept in memory until the token expires, for at most a day.
+	/// Tokens without a `jti` claim are unaffected. Clients that reconnect or
+	/// make several HTTP requests need a fresh token each time.
+	#[arg(long = "auth-single-use", env = "MOQ_AUTH_SINGLE_USE")]
+	pub single_use: bool,
+
 	/// TLS configuration for outbound HTTP auth requests (JWK + public-API).
 	#[command(flatten)]
 	#[serde(default)]
@@ -554,6 +576,9 @@ impl KeyResolver {
 	}
 }
 
+/// The longest a used `jti` is remembered, for tokens that expire later or never.
+const JTI_TTL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
 /// Verifies JWT tokens and resolves connection permissions.
 ///
 /// Clone this freely — the underlying state is shared via [`Arc`].
@@ -563,10 +588,14 @@ impl KeyResolver {
 #[derive(Clone, Default)]
 pub struct Auth {
 	resolver: Option<Arc<KeyResolver>>,
+	/// The required `aud` claim for signed tokens. See [`AuthConfig::audience`].
+	audience: Option<String>,
 	/// Public (unauthenticated) access with static prefixes and/or an API.
 	public: PublicAccess,
 	/// Domain suffixes for subdomain-based slug routing. See [`AuthConfig::domains`].
 	domains: Arc<[String]>,
+	/// Rejects JWTs whose `jti` was already used. See [`AuthConfig::single_use`].
+	jti: Option<Arc<dyn JtiStore>>,
 }
 
 impl Auth {
@@ -676,13 +705,28 @@ impl Auth {
 		}
 		domains.sort_by_key(|d| std::cmp::Reverse(d.len()));
 
+		let jti = match config.single_use {
+			true => Some(Arc::new(MemoryJtiStore::new()) as Arc<dyn JtiStore>),
+			false => None,
+		};
+
 		Ok(Self {
 			resolver,
+			audience: config.audience,
 			public,
 			domains: Arc::from(domains.into_boxed_slice()),
+			jti,
 		})
 	}
 
+	/// Reject JWTs whose `jti` was already seen by `store`, replacing any existing store.
+	///
+	/// Use this to share seen IDs between relays instead of the in-memory default.
+	pub fn with_jti_store(mut self, store: Arc<dyn JtiStore>) -> Self {
+		self.jti = Some(store);
+		self
+	}
+
 	/// Build [`AuthParams`] from an incoming connection URL, applying any
 	/// configured subdomain-based slug routing.
 	pub(crate) fn params_from_url(&self, url: &url::Url) -> AuthParams {
@@ -709,8 +753,16 @@ impl Auth {
 			// Resolve the key (kid requirement depends on the source type)
 			let key = resolver.resolve(header.kid.as_deref()).await?;
 
-			// Verify the token with the resolved key
-			key.decode(token).map_err(|_| AuthError::DecodeFailed)?
+			// Verify the token with the resolved key, checking the audience if configured
+			let claims = match &self.audience {
+				Some(audience) => key.decode_for(token, audience),
+				None => key.decode(token),
+			};
+
+			claims.map_err(|err| match err {
+				moq_token::Error::InvalidAudience => AuthError::IncorrectAudience,
+				_ => AuthError::DecodeFailed,
+			})?
 		} else if !self.public.is_empty() {
 			// No JWT — use public access (static prefixes + optional API).
 			let root = Path::new(&params.path);
@@ -758,6 +810,11 @@ impl Auth {
 			return Err(AuthError::IncorrectRoot);
 		};
 
+		// Only consume the token ID once we know the token applies to this path.
+		if let Some(store) = &self.jti {
+			store.check(&claims, JTI_TTL).map_err(|_| AuthError::TokenReplayed)?;
+		}
+
 		let scope = |paths: Vec<String>| -> PathPrefixes {
 			paths
 				.into_iter()
@@ -1445,6 +1502,158 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn test_jwt_audience() -> anyhow::Result<()> {
+		let key = create_test_key_with_kid("test-key");
+		let dir = setup_key_dir(&[("test-key", &key)]);
+
+		let auth = Auth::new(AuthConfig {
+			key_dir: Some(dir.path().to_string_lossy().to_string()),
+			audience: Some("cluster-a".to_string()),
+			..Default::default()
+		})
+		.await?;
+
+		let verify = |audience: Option<&str>| {
+			let claims = moq_token::Claims {
+				root: "room".to_string(),
+				subscribe: vec!["".to_string()],
+				audience: audience.into_iter().map(str::to_string).collect(),
+				..Default::default()
+			};
+			let token = key.encode(&claims).unwrap();
+			let auth = auth.clone();
+			async move {
+				auth.verify(&AuthParams {
+					path: "/room".into(),
+					jwt: Some(token),
+				})
+				.await
+			}
+		};
+
+		let token = verify(Some("cluster-a")).await?;
+		assert_eq!(token.root, "room".as_path());
+
+		let result = verify(Some("cluster-b")).await;
+		assert!(matches!(result, Err(AuthError::IncorrectAudience)));
+
+		// A token without an audience is rejected once the relay expects one.
+		let result = verify(None).await;
+		assert!(matches!(result, Err(AuthError::IncorrectAudience)));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_jwt_single_use() -> anyhow::Result<()> {
+		let key = create_test_key_with_kid("test-key");
+		let dir = setup_key_dir(&[("test-key", &key)]);
+
+		let auth = Auth::new(AuthConfig {
+			key_dir: Some(dir.path().to_string_lossy().to_string()),
+			single_use: true,
+			..Default::default()
+		})
+		.await?;
+
+		let sign = |jti: Option<&str>| {
+			let claims = moq_token::Claims {
+				root: "room".to_string(),
+				subscribe: vec!["".to_string()],
+				jti: jti.map(str::to_string),
+				..Default::default()
+			};
+			AuthParams {
+				path: "/room".into(),
+				jwt: Some(key.encode(&claims).unwrap()),
+			}
+		};
+
+		let params = sign(Some("abc"));
+		auth.verify(&params).await?;
+
+		// The same jti is rejected on the second use.
+		let result = auth.verify(&params).await;
+		assert!(matches!(result, Err(AuthError::TokenReplayed)));
+
+		// A different jti is still accepted.
+		auth.verify(&sign(Some("def"))).await?;
+
+		// Tokens without a jti can be reused.
+		let params = sign(None);
+		auth.verify(&params).await?;
+		auth.verify(&params).await?;
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_jwt_custom_jti_store() -> anyhow::Result<()> {
+		/// Rejects every token ID, so any token with a jti fails.
+		struct RejectAll;
+
+		impl JtiStore for RejectAll {
+			fn insert(&self, _jti: &str, _expires: std::time::SystemTime) -> bool {
+				false
+			}
+		}
+
+		let key = create_test_key_with_kid("test-key");
+		let dir = setup_key_dir(&[("test-key", &key)]);
+
+		let auth = Auth::new(AuthConfig {
+			key_dir: Some(dir.path().to_string_lossy().to_string()),
+			..Default::default()
+		})
+		.await?
+		.with_jti_store(Arc::new(RejectAll));
+
+		let claims = moq_token::Claims {
+			root: "room".to_string(),
+			subscribe: vec!["".to_string()],
+			jti: Some("abc".to_string()),
+			..Default::default()
+		};
+		let result = auth
+			.verify(&AuthParams {
+				path: "/room".into(),
+				jwt: Some(key.encode(&claims)?),
+			})
+			.await;
+		assert!(matches!(result, Err(AuthError::TokenReplayed)));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_jwt_audience_ignored_when_unconfigured() -> anyhow::Result<()> {
+		let key = create_test_key_with_kid("test-key");
+		let dir = setup_key_dir(&[("test-key", &key)]);
+
+		let auth = Auth::new(AuthConfig {
+			key_dir: Some(dir.path().to_string_lossy().to_string()),
+			..Default::default()
+		})
+		.await?;
+
+		let claims = moq_token::Claims {
+			root: "room".to_string(),
+			subscribe: vec!["".to_string()],
+			audience: vec!["cluster-b".to_string()],
+			..Default::default()
+		};
+		let token = key.encode(&claims)?;
+
+		auth.verify(&AuthParams {
+			path: "/room".into(),
+			jwt: Some(token),
+		})
+		.await?;
+
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn test_kid_validation() {
 		assert!(KeyId::decode("abc-123_DEF").is_ok());
@@ -0,0 +1,232 @@
+use std::{
+	collections::HashMap,
+	hash::Hash,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use tokio::time::Instant;
+
+/// Configuration for per-connection work accounting.
+///
+/// Each connection may spend up to `work` doing expensive work (such as assembling
+/// fetch responses) within every `window`. A connection that exceeds its budget is
+/// throttled until the window ends, so one abusive client can't starve the others.
+/// Accounting is coarse wall-clock time, not true CPU time.
+#[derive(clap::Args, Clone, Debug, serde::Serialize, serde::Deserialize, Default)]
+#[serde_with::skip_serializing_none]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+#[group(id = "budget-config")]
+pub struct BudgetConfig {
+	/// The work each connection may do per window. Unlimited by default.
+	#[arg(
+		id = "budget-work",
+		long = "budget-work",
+		env = "MOQ_BUDGET_WORK",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(with = "humantime_serde")]
+	pub work: Option<Duration>,
+
+	/// The window over which work is accounted. Defaults to 1s.
+	#[arg(
+		id = "budget-window",
+		long = "budget-window",
+		env = "MOQ_BUDGET_WINDOW",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(with = "humantime_serde")]
+	pub window: Option<Duration>,
+}
+
+impl BudgetConfig {
+	const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+
+	/// Create a registry of per-connection budgets, or `None` if no limit is configured.
+	pub fn init<K: Eq + Hash>(&self) -> Option<Budgets<K>> {
+		let work = self.work?;
+		let window = self.window.unwrap_or(Self::DEFAULT_WINDOW);
+		Some(Budgets::new(work, window))
+	}
+}
+
+/// A registry of [`WorkBudget`]s keyed by connection.
+///
+/// Cloning shares the registry.
+pub struct Budgets<K> {
+	work: Duration,
+	window: Duration,
+	connections: Arc<Mutex<HashMap<K, WorkBudget>>>,
+}
+
+impl<K> Clone for Budgets<K> {
+	fn clone(&self) -> Self {
+		Self {
+			work: self.work,
+			window: self.window,
+			connections: self.connections.clone(),
+		}
+	}
+}
+
+impl<K: Eq + Hash> Budgets<K> {
+	pub fn new(work: Duration, window: Duration) -> Self {
+		Self {
+			work,
+			window,
+			connections: Default::default(),
+		}
+	}
+
+	/// Return the budget for the given connection, creating it if needed.
+	///
+	/// Budgets that carry no debt and aren't in use are pruned here.
+	pub fn get(&self, key: K) -> WorkBudget {
+		let mut connections = self.connections.lock().unwrap();
+		let now = Instant::now();
+		connections.retain(|_, budget| !budget.is_idle(now));
+
+		connections
+			.entry(key)
+			.or_insert_with(|| WorkBudget::new(self.work, self.window))
+			.clone()
+	}
+}
+
+/// Tracks how much work a single connection has done in the current window.
+///
+/// Cloning shares the accounting.
+#[derive(Clone)]
+pub struct WorkBudget {
+	work: Duration,
+	window: Duration,
+	state: Arc<Mutex<WorkWindow>>,
+}
+
+struct WorkWindow {
+	start: Instant,
+	spent: Duration,
+}
+
+impl WorkBudget {
+	pub fn new(work: Duration, window: Duration) -> Self {
+		Self {
+			work,
+			window,
+			state: Arc::new(Mutex::new(WorkWindow {
+				start: Instant::now(),
+				spent: Duration::ZERO,
+			})),
+		}
+	}
+
+	/// Run `f` and charge the time it takes against the budget.
+	pub fn measure<T>(&self, f: impl FnOnce() -> T) -> T {
+		let start = Instant::now();
+		let result = f();
+		self.charge(start.elapsed());
+		result
+	}
+
+	/// Charge `work` against the budget.
+	pub fn charge(&self, work: Duration) {
+		let mut state = self.state.lock().unwrap();
+		state.roll(Instant::now(), self.work, self.window);
+		state.spent += work;
+	}
+
+	/// Returns true if the budget is exhausted for the current window.
+	pub fn is_throttled(&self) -> bool {
+		let mut state = self.state.lock().unwrap();
+		state.roll(Instant::now(), self.work, self.window);
+		state.spent >= self.work
+	}
+
+	/// Wait until the connection is back within its budget.
+	///
+	/// Resolves immediately unless the budget is exhausted, in which case it sleeps until
+	/// enough windows have passed to pay off the excess.
+	pub async fn throttle(&self) {
+		let until = {
+			let mut state = self.state.lock().unwrap();
+			state.roll(Instant::now(), self.work, self.window);
+			if state.spent < self.work {
+				return;
+			}
+
+			// Each window forgives up to `work`, so heavy debt spans multiple windows.
+			let windows = state.spent.as_nanos() / self.work.as_nanos().max(1);
+			state.start + self.window * windows as u32
+		};
+
+		tracing::debug!(?until, "connection exceeded its work budget; throttling");
+		tokio::time::sleep_until(until).await;
+	}
+
+	fn is_idle(&self, now: Instant) -> bool {
+		if Arc::strong_count(&self.state) > 1 {
+			return false;
+		}
+
+		let mut state = self.state.lock().unwrap();
+		state.roll(now, self.work, self.window);
+		state.spent.is_zero()
+	}
+}
+
+impl WorkWindow {
+	/// Start a new window once the current one has ended, forgiving `work` per elapsed window.
+	fn roll(&mut self, now: Instant, work: Duration, window: Duration) {
+		let elapsed = now.duration_since(self.start);
+		if elapsed < window {
+			return;
+		}
+
+		let windows = (elapsed.as_nanos() / window.as_nanos().max(1)) as u32;
+		self.start += window * windows;
+		self.spent = self.spent.saturating_sub(work.saturating_mul(windows));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn heavy_connection_is_throttled() {
+		tokio::time::pause();
+
+		let budgets = Budgets::new(Duration::from_millis(100), Duration::from_secs(1));
+		let heavy = budgets.get("heavy");
+		let light = budgets.get("light");
+
+		// Synthetic heavy decode: spend well over the budget.
+		heavy.charge(Duration::from_millis(250));
+		light.charge(Duration::from_millis(10));
+
+		assert!(heavy.is_throttled());
+		assert!(!light.is_throttled());
+
+		// The light connection stays responsive.
+		let start = Instant::now();
+		light.throttle().await;
+		assert_eq!(start.elapsed(), Duration::ZERO);
+
+		// The heavy one waits until its debt is paid off.
+		heavy.throttle().await;
+		assert!(start.elapsed() >= Duration::from_secs(1));
+		assert!(!heavy.is_throttled());
+	}
+
+	#[tokio::test]
+	async fn budgets_are_per_connection() {
+		tokio::time::pause();
+
+		let budgets = Budgets::new(Duration::from_millis(100), Duration::from_secs(1));
+		budgets.get("a").charge(Duration::from_millis(200));
+
+		assert!(budgets.get("a").is_throttled());
+		assert!(!budgets.get("b").is_throttled());
+	}
+}
@@ -1,10 +1,17 @@
-use std::path::PathBuf;
+use std::{
+	path::PathBuf,
+	sync::{
+		Arc,
+		atomic::{AtomicUsize, Ordering},
+	},
+	time::Duration,
+};
 
 use anyhow::Context;
 use moq_lite::{Origin, OriginConsumer, OriginProducer};
 use url::Url;
 
-use crate::AuthToken;
+use crate::{AuthToken, Metrics};
 
 /// Configuration for relay clustering.
 ///
@@ -33,6 +40,38 @@ pub struct ClusterConfig {
 	/// Use the token in this file when connecting to other nodes.
 	#[arg(id = "cluster-token", long = "cluster-token", env = "MOQ_CLUSTER_TOKEN")]
 	pub token: Option<PathBuf>,
+
+	/// The delay before reconnecting to a peer, doubled after each failure. Defaults to 1s.
+	#[arg(
+		id = "cluster-backoff",
+		long = "cluster-backoff",
+		env = "MOQ_CLUSTER_BACKOFF",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(with = "humantime_serde")]
+	pub backoff: Option<Duration>,
+
+	/// The longest delay between reconnect attempts. Defaults to 5m.
+	#[arg(
+		id = "cluster-backoff-max",
+		long = "cluster-backoff-max",
+		env = "MOQ_CLUSTER_BACKOFF_MAX",
+		value_parser = humantime::parse_duration,
+	)]
+	#[serde(with = "humantime_serde")]
+	pub backoff_max: Option<Duration>,
+
+	/// Refuse broadcasts that crossed more than this many hops, limiting how far announcements spread.
+	///
+	/// The session from the publishing client counts as the first hop, so 1 keeps every broadcast
+	/// on the relay it was published to. Defaults to the protocol limit of 32.
+	#[arg(id = "cluster-max-hops", long = "cluster-max-hops", env = "MOQ_CLUSTER_MAX_HOPS")]
+	pub max_hops: Option<usize>,
+}
+
+impl ClusterConfig {
+	const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+	const DEFAULT_BACKOFF_MAX: Duration = Duration::from_secs(300);
 }
 
 /// A relay cluster built around a single [`OriginProducer`].
@@ -44,6 +83,10 @@ pub struct ClusterConfig {
 pub struct Cluster {
 	config: ClusterConfig,
 	client: moq_native::Client,
+	metrics: Metrics,
+
+	/// The number of peer links currently up.
+	peers: Arc<AtomicUsize>,
 
 	/// All broadcasts, local and remote. Downstream sessions read from here
 	/// (filtered by their auth token) and remote dials both read and write here.
@@ -53,9 +96,24 @@ pub struct Cluster {
 impl Cluster {
 	/// Creates a new cluster with the given configuration and QUIC client.
 	pub fn new(config: ClusterConfig, client: moq_native::Client) -> Self {
-		let origin = Origin::random().produce();
+		let mut origin = Origin::random().produce();
+		if let Some(max_hops) = config.max_hops {
+			origin = origin.with_max_hops(max_hops);
+		}
 		tracing::info!(origin_id = %origin.id, "cluster initialized");
-		Cluster { config, client, origin }
+		Cluster {
+			config,
+			client,
+			metrics: Metrics::default(),
+			peers: Default::default(),
+			origin,
+		}
+	}
+
+	/// Count reconnects to cluster peers in the given metrics.
+	pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+		self.metrics = metrics;
+		self
 	}
 
 	/// Returns an [`OriginConsumer`] scoped to this session's subscribe permissions.
@@ -68,6 +126,11 @@ impl Cluster {
 		self.origin.with_root(&token.root)?.scope(&token.publish)
 	}
 
+	/// Returns true if the relay is standalone or at least one cluster peer link is up.
+	pub fn is_ready(&self) -> bool {
+		self.config.connect.is_empty() || self.peers.load(Ordering::Relaxed) > 0
+	}
+
 	/// Runs the cluster event loop, dialing the configured peers and keeping
 	/// each connection alive indefinitely with exponential backoff on failure.
 	///
@@ -110,16 +173,27 @@ impl Cluster {
 			url.query_pairs_mut().append_pair("jwt", &token);
 		}
 
-		let base_backoff = tokio::time::Duration::from_secs(1);
-		let max_backoff = tokio::time::Duration::from_secs(300);
+		let base_backoff = self.config.backoff.unwrap_or(ClusterConfig::DEFAULT_BACKOFF);
+		let max_backoff = self
+			.config
+			.backoff_max
+			.unwrap_or(ClusterConfig::DEFAULT_BACKOFF_MAX)
+			.max(base_backoff);
 		// Sessions shorter than this are treated as churn: we keep backing off
 		// instead of resetting, otherwise a peer that rejects us instantly would
 		// turn into a tight reconnect loop.
 		let stable_threshold = tokio::time::Duration::from_secs(10);
 
 		let mut backoff = base_backoff;
+		let mut attempt = 0u64;
 
 		loop {
+			if attempt > 0 {
+				tracing::info!(attempt, "reconnecting to cluster peer");
+				self.metrics.cluster_reconnect();
+			}
+			attempt += 1;
+
 			let started = tokio::time::Instant::now();
 			let result = self.run_remote_once(&url).await;
 			let elapsed = started.elapsed();
@@ -154,6 +228,163 @@ impl Cluster {
 			.await
 			.context("failed to connect to cluster peer")?;
 
+		let _link = PeerLink::new(&self.peers);
 		session.closed().await.map_err(Into::into)
 	}
 }
+
+/// Counts a peer link as up until dropped.
+struct PeerLink {
+	peers: Arc<AtomicUsize>,
+}
+
+impl PeerLink {
+	fn new(peers: &Arc<AtomicUsize>) -> Self {
+		peers.fetch_add(1, Ordering::Relaxed);
+		Self { peers: peers.clone() }
+	}
+}
+
+impl Drop for PeerLink {
+	fn drop(&mut self) {
+		self.peers.fetch_sub(1, Ordering::Relaxed);
+	}
+}
+
+#[cfg(all(test, feature = "quinn"))]
+mod tests {
+	use futures::FutureExt;
+
+	use super::*;
+
+	const TIMEOUT: Duration = Duration::from_secs(10);
+
+	#[tokio::test]
+	async fn reannounce_after_reconnect() {
+		// Stands in for a peer relay, so we can drop the link from the other side.
+		let mut server_config = moq_native::ServerConfig::default();
+		server_config.bind = Some("[::]:0".to_string());
+		server_config.tls.generate = vec!["localhost".to_string()];
+		let mut peer = server_config.init().unwrap();
+		let addr = peer.local_addr().unwrap();
+
+		let mut client_config = moq_native::ClientConfig::default();
+		client_config.tls.disable_verify = Some(true);
+		let client = client_config.init().unwrap();
+
+		let config = ClusterConfig {
+			connect: vec![format!("localhost:{}", addr.port())],
+			backoff: Some(Duration::from_millis(10)),
+			..Default::default()
+		};
+		let metrics = Metrics::new();
+		let cluster = Cluster::new(config, client).with_metrics(metrics.clone());
+
+		let broadcast = moq_lite::Broadcast::new().produce();
+		cluster.origin.publish_broadcast("demo", broadcast.consume());
+		tokio::spawn(cluster.clone().run());
+
+		for _ in 0..2 {
+			let request = tokio::time::timeout(TIMEOUT, peer.accept())
+				.await
+				.expect("cluster didn't connect")
+				.unwrap();
+
+			let origin = Origin::random().produce();
+			let mut announced = origin.consume();
+			let session = request.with_consume(origin).ok().await.unwrap();
+
+			let (path, active) = tokio::time::timeout(TIMEOUT, announced.announced())
+				.await
+				.expect("broadcast wasn't announced")
+				.unwrap();
+			assert_eq!(path.as_str(), "demo");
+			assert!(active.is_some());
+
+			// Simulate the link dropping.
+			drop(session);
+		}
+
+		assert!(
+			metrics.encode().contains("moq_relay_cluster_reconnects_total 1\n"),
+			"{}",
+			metrics.encode()
+		);
+	}
+
+	#[tokio::test]
+	async fn max_hops_stops_propagation() {
+		let client = moq_native::ClientConfig::default().init().unwrap();
+		let config = ClusterConfig {
+			max_hops: Some(2),
+			..Default::default()
+		};
+		let cluster = Cluster::new(config, client);
+		let publisher = cluster.publisher(&AuthToken::unrestricted()).unwrap();
+		let mut peers = cluster.subscriber(&AuthToken::unrestricted()).unwrap();
+
+		let chain = |len: usize| {
+			let hops = (0..len).map(|_| Origin::random()).collect::<Vec<_>>();
+			moq_lite::Broadcast {
+				hops: hops.try_into().unwrap(),
+			}
+			.produce()
+		};
+
+		// Announced by a peer two hops from the publisher: still served here.
+		let near = chain(2);
+		assert!(publisher.publish_broadcast("near", near.consume()));
+		let (path, _) = peers.announced().now_or_never().unwrap().unwrap();
+		assert_eq!(path.as_str(), "near");
+
+		// One hop further is refused, so it's never re-announced to other peers.
+		let far = chain(3);
+		assert!(!publisher.publish_broadcast("far", far.consume()));
+		assert!(peers.announced().now_or_never().is_none());
+	}
+
+	#[tokio::test]
+	async fn ready_while_peer_connected() {
+		let mut server_config = moq_native::ServerConfig::default();
+		server_config.bind = Some("[::]:0".to_string());
+		server_config.tls.generate = vec!["localhost".to_string()];
+		let mut peer = server_config.init().unwrap();
+		let addr = peer.local_addr().unwrap();
+
+		let mut client_config = moq_native::ClientConfig::default();
+		client_config.tls.disable_verify = Some(true);
+		let client = client_config.init().unwrap();
+
+		// A standalone relay has no links to wait for.
+		assert!(Cluster::new(ClusterConfig::default(), client.clone()).is_ready());
+
+		let config = ClusterConfig {
+			connect: vec![format!("localhost:{}", addr.port())],
+			backoff: Some(Duration::from_millis(10)),
+			..Default::default()
+		};
+		let cluster = Cluster::new(config, client);
+		assert!(!cluster.is_ready());
+		tokio::spawn(cluster.clone().run());
+
+		let wait_for = async |ready: bool| {
+			tokio::time::timeout(TIMEOUT, async {
+				while cluster.is_ready() != ready {
+					tokio::time::sleep(Duration::from_millis(10)).await;
+				}
+			})
+			.await
+			.expect("readiness didn't change");
+		};
+
+		let request = tokio::time::timeout(TIMEOUT, peer.accept())
+			.await
+			.expect("cluster didn't connect")
+			.unwrap();
+		let session = request.ok().await.unwrap();
+		wait_for(true).await;
+
+		drop(session);
+		wait_for(false).await;
+	}
+}
@@ -1,7 +1,7 @@
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 
-use crate::{AuthConfig, ClusterConfig, WebConfig};
+use crate::{AccessConfig, AuthConfig, BudgetConfig, ClusterConfig, LimitConfig, WebConfig};
 
 /// Top-level relay configuration, loadable from CLI arguments, environment
 /// variables, or a TOML file.
@@ -35,6 +35,21 @@ pub struct Config {
 	#[serde(default)]
 	pub auth: AuthConfig,
 
+	/// Per-connection work budget.
+	#[command(flatten)]
+	#[serde(default)]
+	pub budget: BudgetConfig,
+
+	/// Limits on the number of concurrent connections.
+	#[command(flatten)]
+	#[serde(default)]
+	pub limit: LimitConfig,
+
+	/// Per-session access logging, disabled by default.
+	#[command(flatten)]
+	#[serde(default)]
+	pub access: AccessConfig,
+
 	/// Optionally run a TCP HTTP/WebSocket server.
 	#[command(flatten)]
 	#[serde(default)]
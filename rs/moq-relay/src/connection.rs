@@ -1,4 +1,4 @@
-use crate::{Auth, AuthError, AuthParams, AuthToken, Cluster};
+use crate::{AccessLog, Auth, AuthError, AuthParams, AuthToken, Cluster, Metrics};
 
 use axum::http;
 use moq_native::Request;
@@ -34,6 +34,10 @@ pub struct Connection {
 	pub cluster: Cluster,
 	/// The authenticator used to verify credentials.
 	pub auth: Auth,
+	/// Relay-wide counters updated by this connection.
+	pub metrics: Metrics,
+	/// Where to record the session once it closes.
+	pub access: AccessLog,
 }
 
 impl Connection {
@@ -72,19 +76,41 @@ impl Connection {
 		// NOTE: subscribe and publish seem backwards because of how relays work.
 		// We publish the tracks the client is allowed to subscribe to.
 		// We subscribe to the tracks the client is allowed to publish.
+		let access = self.access.start(
+			self.id,
+			transport,
+			self.request.remote_addr(),
+			token.root.as_str(),
+			self.metrics.published(),
+		);
+
 		let session = self
 			.request
 			.with_publish(subscribe)
 			.with_consume(publish)
-			// TODO: Uncomment when observability feature is merged
-			// .with_stats(stats)
+			.with_stats(access.stats())
 			.ok()
 			.await?;
 
+		let _active = self.metrics.session();
+
 		tracing::info!(version = %session.version(), transport, "negotiated");
 
 		// Wait until the session is closed.
-		session.closed().await?;
+		let res = session.closed().await;
+
+		let stats = session.stats();
+		tracing::info!(
+			rtt = ?stats.rtt,
+			bytes_sent = ?stats.bytes_sent,
+			bytes_received = ?stats.bytes_received,
+			packets_lost = ?stats.packets_lost,
+			send_rate = ?stats.estimated_send_rate,
+			"session closed"
+		);
+
+		access.finish(&session, res.as_ref().err());
+		res?;
 		Ok(())
 	}
 
@@ -113,3 +139,125 @@ impl Connection {
 		Ok(self.auth.verify(&params).await?)
 	}
 }
+
+#[cfg(all(test, feature = "quinn"))]
+mod tests {
+	use std::{
+		io::Write,
+		sync::{Arc, Mutex},
+		time::Duration,
+	};
+
+	use super::*;
+	use crate::{AccessLog, AuthConfig, ClusterConfig, PublicConfig, PublicDetailed};
+
+	const TIMEOUT: Duration = Duration::from_secs(10);
+
+	/// A shared buffer standing in for the access log file.
+	#[derive(Clone, Default)]
+	struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+	impl Write for Buffer {
+		fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+			self.0.lock().unwrap().write(buf)
+		}
+
+		fn flush(&mut self) -> std::io::Result<()> {
+			Ok(())
+		}
+	}
+
+	#[tokio::test]
+	async fn access_log_records_session() {
+		let mut server_config = moq_native::ServerConfig::default();
+		server_config.bind = Some("[::]:0".to_string());
+		server_config.tls.generate = vec!["localhost".to_string()];
+		let mut server = server_config.init().unwrap();
+		let addr = server.local_addr().unwrap();
+
+		let mut client_config = moq_native::ClientConfig::default();
+		client_config.tls.disable_verify = Some(true);
+		let client = client_config.init().unwrap();
+
+		let auth = Auth::new(AuthConfig {
+			public: Some(PublicConfig::Detailed(PublicDetailed {
+				subscribe: vec!["".to_string()],
+				publish: vec!["".to_string()],
+				api: None,
+			})),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		let buffer = Buffer::default();
+		let cluster = Cluster::new(ClusterConfig::default(), client.clone());
+		let access = AccessLog::new(buffer.clone());
+		let relay = tokio::spawn(async move {
+			let conn = Connection {
+				id: 7,
+				request: server.accept().await.unwrap(),
+				cluster,
+				auth,
+				metrics: Metrics::new(),
+				access,
+			};
+			conn.run().await
+		});
+
+		// Publish a broadcast, then read it back through the relay.
+		let mut broadcast = moq_lite::Broadcast::new().produce();
+		let mut track = broadcast.create_track(moq_lite::Track::new("video")).unwrap();
+		track.write_frame("hello").unwrap();
+
+		let publish = moq_lite::Origin::random().produce();
+		publish.publish_broadcast("bbb", broadcast.consume());
+		let consume = moq_lite::Origin::random().produce();
+		let mut announced = consume.consume();
+
+		let url = format!("moqt://localhost:{}", addr.port()).parse().unwrap();
+		let session = client
+			.with_publish(publish.consume())
+			.with_consume(consume)
+			.connect(url)
+			.await
+			.unwrap();
+
+		let (path, active) = tokio::time::timeout(TIMEOUT, announced.announced())
+			.await
+			.expect("broadcast wasn't announced")
+			.unwrap();
+		assert_eq!(path.as_str(), "bbb");
+
+		let mut video = active.unwrap().subscribe_track(&moq_lite::Track::new("video")).unwrap();
+		let frame = tokio::time::timeout(TIMEOUT, video.read_frame())
+			.await
+			.expect("frame wasn't relayed")
+			.unwrap();
+		assert_eq!(frame.as_deref(), Some(&b"hello"[..]));
+
+		drop(session);
+		tokio::time::timeout(TIMEOUT, relay)
+			.await
+			.expect("session didn't close")
+			.unwrap()
+			.unwrap_err();
+
+		let log = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+		let records: Vec<serde_json::Value> = log.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+		assert_eq!(records.len(), 1, "{log}");
+
+		let record = &records[0];
+		assert_eq!(record["id"], 7);
+		assert_eq!(record["transport"], "quic");
+		// Raw QUIC has no URL path, so the session is scoped to the public root.
+		assert_eq!(record["root"], "");
+		assert_eq!(record["subscribes"], 1);
+		assert_eq!(record["announces"], 1);
+		assert!(record["bytes_sent"].as_u64().unwrap() > 0, "{log}");
+		assert!(record["bytes_received"].as_u64().unwrap() > 0, "{log}");
+		assert!(record["rtt_us"].as_u64().unwrap() > 0, "{log}");
+		assert!(record["packets_lost"].is_u64(), "{log}");
+		assert!(record["remote"].is_string(), "{log}");
+	}
+}
@@ -7,10 +7,14 @@
 //!
 //! See `main.rs` for a complete example of how these pieces fit together.
 
+mod access;
 mod auth;
+mod budget;
 mod cluster;
 mod config;
 mod connection;
+mod limit;
+mod metrics;
 mod web;
 #[cfg(feature = "websocket")]
 mod websocket;
@@ -19,8 +23,12 @@ mod websocket;
 /// to handle many concurrent subscriptions across connections.
 pub const DEFAULT_MAX_STREAMS: u64 = 10_000;
 
+pub use access::*;
 pub use auth::*;
+pub use budget::*;
 pub use cluster::*;
 pub use config::*;
 pub use connection::*;
+pub use limit::*;
+pub use metrics::*;
 pub use web::*;
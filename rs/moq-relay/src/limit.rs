@@ -0,0 +1,152 @@
+use std::{
+	collections::HashMap,
+	net::IpAddr,
+	sync::{Arc, Mutex},
+};
+
+/// Configuration for limiting the number of concurrent connections.
+///
+/// Without a limit, a single client can exhaust the relay's memory by opening
+/// thousands of sessions. Connections over the limit are rejected with a 503.
+#[derive(clap::Args, Clone, Debug, serde::Serialize, serde::Deserialize, Default)]
+#[serde_with::skip_serializing_none]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+#[group(id = "limit-config")]
+pub struct LimitConfig {
+	/// The maximum number of concurrent connections. Unlimited by default.
+	#[arg(id = "max-connections", long = "max-connections", env = "MOQ_MAX_CONNECTIONS")]
+	pub connections: Option<usize>,
+
+	/// The maximum number of concurrent connections from a single IP address. Unlimited by default.
+	#[arg(
+		id = "max-connections-per-ip",
+		long = "max-connections-per-ip",
+		env = "MOQ_MAX_CONNECTIONS_PER_IP"
+	)]
+	pub connections_per_ip: Option<usize>,
+}
+
+impl LimitConfig {
+	/// Create the shared connection limits.
+	pub fn init(&self) -> ConnectionLimits {
+		ConnectionLimits::new(self.connections, self.connections_per_ip)
+	}
+}
+
+/// Counts the open connections against the configured limits.
+///
+/// Cloning shares the counts.
+#[derive(Clone, Default)]
+pub struct ConnectionLimits {
+	total: Option<usize>,
+	per_ip: Option<usize>,
+	state: Arc<Mutex<LimitState>>,
+}
+
+#[derive(Default)]
+struct LimitState {
+	total: usize,
+	per_ip: HashMap<IpAddr, usize>,
+}
+
+impl ConnectionLimits {
+	pub fn new(total: Option<usize>, per_ip: Option<usize>) -> Self {
+		Self {
+			total,
+			per_ip,
+			state: Default::default(),
+		}
+	}
+
+	/// Reserve a slot for a new connection from the given address, if there's room.
+	///
+	/// The slot is released when the returned [`ConnectionPermit`] is dropped.
+	/// Connections without an IP address (ex. iroh) only count towards the global limit.
+	pub fn acquire(&self, ip: Option<IpAddr>) -> Option<ConnectionPermit> {
+		// Dual-stack sockets report IPv4 clients as IPv4-mapped IPv6 addresses.
+		let ip = ip.map(|ip| ip.to_canonical());
+
+		let mut state = self.state.lock().unwrap();
+		if self.total.is_some_and(|max| state.total >= max) {
+			return None;
+		}
+
+		if let Some(ip) = ip {
+			let count = state.per_ip.get(&ip).copied().unwrap_or_default();
+			if self.per_ip.is_some_and(|max| count >= max) {
+				return None;
+			}
+			state.per_ip.insert(ip, count + 1);
+		}
+
+		state.total += 1;
+
+		Some(ConnectionPermit {
+			state: self.state.clone(),
+			ip,
+		})
+	}
+}
+
+/// A slot reserved by [`ConnectionLimits::acquire`], released on drop.
+pub struct ConnectionPermit {
+	state: Arc<Mutex<LimitState>>,
+	ip: Option<IpAddr>,
+}
+
+impl Drop for ConnectionPermit {
+	fn drop(&mut self) {
+		let mut state = self.state.lock().unwrap();
+		state.total -= 1;
+
+		if let Some(ip) = self.ip
+			&& let Some(count) = state.per_ip.get_mut(&ip)
+		{
+			*count -= 1;
+			if *count == 0 {
+				state.per_ip.remove(&ip);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn global_limit() {
+		let limits = ConnectionLimits::new(Some(2), None);
+
+		let a = limits.acquire(Some([10, 0, 0, 1].into())).unwrap();
+		let _b = limits.acquire(Some([10, 0, 0, 2].into())).unwrap();
+		assert!(limits.acquire(Some([10, 0, 0, 3].into())).is_none());
+		assert!(limits.acquire(None).is_none());
+
+		// Closing a connection frees its slot.
+		drop(a);
+		assert!(limits.acquire(Some([10, 0, 0, 3].into())).is_some());
+	}
+
+	#[test]
+	fn per_ip_limit() {
+		let limits = ConnectionLimits::new(None, Some(1));
+		let ip: IpAddr = [10, 0, 0, 1].into();
+
+		let first = limits.acquire(Some(ip)).unwrap();
+		assert!(limits.acquire(Some(ip)).is_none());
+
+		// The IPv4-mapped form is the same client.
+		let mapped = IpAddr::V6(std::net::Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped());
+		assert!(limits.acquire(Some(mapped)).is_none());
+
+		// Other clients aren't affected.
+		let _other = limits.acquire(Some([10, 0, 0, 2].into())).unwrap();
+		let _iroh = limits.acquire(None).unwrap();
+
+		drop(first);
+		assert!(limits.acquire(Some(ip)).is_some());
+		assert_eq!(limits.state.lock().unwrap().per_ip.len(), 1);
+	}
+}
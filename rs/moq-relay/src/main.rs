@@ -1,5 +1,10 @@
 use moq_relay::*;
 
+use std::sync::{
+	Arc,
+	atomic::{AtomicU64, Ordering},
+};
+
 use anyhow::Context;
 
 #[cfg(feature = "jemalloc")]
@@ -49,7 +54,14 @@ async fn main() -> anyhow::Result<()> {
 		config.auth.init().await?
 	};
 
-	let cluster = Cluster::new(config.cluster, client);
+	let limits = config.limit.init();
+	let access = config.access.init()?;
+	let conn_id = Arc::new(AtomicU64::default());
+	let metrics = Metrics::new();
+	let cluster = Cluster::new(config.cluster, client).with_metrics(metrics.clone());
+
+	#[cfg(feature = "metrics")]
+	tokio::spawn(metrics.clone().run(cluster.origin.consume()));
 
 	// Create a web server too. mTLS for HTTPS is opt-in via `--web-https-root`.
 	let web = Web::new(
@@ -57,7 +69,10 @@ async fn main() -> anyhow::Result<()> {
 			auth: auth.clone(),
 			cluster: cluster.clone(),
 			tls_info: server.tls_info(),
-			conn_id: Default::default(),
+			conn_id: conn_id.clone(),
+			metrics: metrics.clone(),
+			budgets: config.budget.init(),
+			access: access.clone(),
 		},
 		config.web,
 	);
@@ -76,30 +91,77 @@ async fn main() -> anyhow::Result<()> {
 	tokio::select! {
 		Err(err) = cluster.clone().run() => return Err(err).context("cluster failed"),
 		Err(err) = web.run() => return Err(err).context("web server failed"),
-		Err(err) = serve(server, cluster, auth) => return Err(err).context("server failed"),
+		res = serve(server, cluster, auth, metrics, limits, access, conn_id) => res.context("server failed"),
 		Err(err) = jemalloc => return Err(err).context("jemalloc profiler failed"),
 		else => Ok(()),
 	}
 }
 
-async fn serve(mut server: moq_native::Server, cluster: Cluster, auth: Auth) -> anyhow::Result<()> {
-	let mut conn_id = 0;
+async fn serve(
+	mut server: moq_native::Server,
+	cluster: Cluster,
+	auth: Auth,
+	metrics: Metrics,
+	limits: ConnectionLimits,
+	access: AccessLog,
+	conn_id: Arc<AtomicU64>,
+) -> anyhow::Result<()> {
+	loop {
+		let request = tokio::select! {
+			request = server.accept() => request,
+			_ = shutdown() => {
+				let timeout = server.drain_timeout();
+				tracing::info!(?timeout, "shutting down, draining sessions");
+				server.drain(timeout).await;
+				return Ok(());
+			}
+		};
+
+		// The server drains on its own when interrupted.
+		let Some(request) = request else {
+			return Ok(());
+		};
+
+		let remote = request.remote_addr();
+		let Some(permit) = limits.acquire(remote.map(|addr| addr.ip())) else {
+			tracing::warn!(?remote, "too many connections, rejecting");
+			tokio::spawn(request.close(axum::http::StatusCode::SERVICE_UNAVAILABLE.as_u16()));
+			continue;
+		};
 
-	while let Some(request) = server.accept().await {
 		let conn = Connection {
-			id: conn_id,
+			id: conn_id.fetch_add(1, Ordering::Relaxed),
 			request,
 			cluster: cluster.clone(),
 			auth: auth.clone(),
+			metrics: metrics.clone(),
+			access: access.clone(),
 		};
 
-		conn_id += 1;
 		tokio::spawn(async move {
 			if let Err(err) = conn.run().await {
 				tracing::warn!(%err, "connection closed");
 			}
+
+			// Release the slot only once the session is closed.
+			drop(permit);
 		});
 	}
+}
+
+/// Resolves on Ctrl-C, or on SIGTERM from a service manager.
+async fn shutdown() {
+	#[cfg(unix)]
+	{
+		use tokio::signal::unix::{SignalKind, signal};
+
+		let mut terminate = signal(SignalKind::terminate()).expect("failed to listen for signals");
+		tokio::select! {
+			_ = tokio::signal::ctrl_c() => {}
+			_ = terminate.recv() => {}
+		}
+	}
 
-	anyhow::bail!("stopped accepting connections")
+	#[cfg(not(unix))]
+	let _ = tokio::signal::ctrl_c().await;
 }
@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::sync::{
+	Arc, Mutex,
+	atomic::{AtomicU64, Ordering},
+};
+
+use moq_lite::{OriginConsumer, PathOwned, PublishStats, TrackSnapshot};
+
+/// The most tracks labeled in the output, keeping the busiest by bytes served.
+///
+/// Bounds the cardinality of the per-track metrics no matter how many broadcasts are active.
+const MAX_LABELED_TRACKS: usize = 256;
+
+/// Reads one counter from a [`TrackSnapshot`].
+type TrackValue = fn(&TrackSnapshot) -> u64;
+
+/// Relay-wide counters, served in the Prometheus text format at `/metrics`.
+///
+/// Cloning shares the counters. The route is only registered with the `metrics` feature.
+#[derive(Clone, Default)]
+pub struct Metrics {
+	inner: Arc<MetricsInner>,
+}
+
+#[derive(Default)]
+struct MetricsInner {
+	connections: AtomicU64,
+	sessions: AtomicU64,
+	announces: AtomicU64,
+	cluster_reconnects: AtomicU64,
+	broadcasts: Mutex<HashSet<PathOwned>>,
+	published: PublishStats,
+}
+
+impl Metrics {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The counters to pass to each session via `with_stats`.
+	pub fn published(&self) -> PublishStats {
+		self.inner.published.clone()
+	}
+
+	/// Record an accepted session, which stays active until the returned guard is dropped.
+	pub fn session(&self) -> SessionGuard {
+		self.inner.connections.fetch_add(1, Ordering::Relaxed);
+		self.inner.sessions.fetch_add(1, Ordering::Relaxed);
+		SessionGuard { metrics: self.clone() }
+	}
+
+	/// Record a reconnect attempt to a cluster peer.
+	pub fn cluster_reconnect(&self) {
+		self.inner.cluster_reconnects.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Track the broadcasts announced to the given origin until it closes.
+	pub async fn run(self, mut origin: OriginConsumer) {
+		while let Some((path, active)) = origin.announced().await {
+			let mut broadcasts = self.inner.broadcasts.lock().unwrap();
+			if active.is_some() {
+				self.inner.announces.fetch_add(1, Ordering::Relaxed);
+				broadcasts.insert(path);
+			} else {
+				broadcasts.remove(&path);
+			}
+		}
+	}
+
+	/// Encode every metric in the Prometheus text exposition format.
+	pub fn encode(&self) -> String {
+		let mut out = String::new();
+		let broadcasts = self.inner.broadcasts.lock().unwrap();
+
+		let metrics = [
+			(
+				"moq_relay_connections_total",
+				"counter",
+				"Sessions accepted since startup.",
+				self.inner.connections.load(Ordering::Relaxed),
+			),
+			(
+				"moq_relay_sessions",
+				"gauge",
+				"Sessions currently active.",
+				self.inner.sessions.load(Ordering::Relaxed),
+			),
+			(
+				"moq_relay_subscribes_total",
+				"counter",
+				"Subscriptions accepted from downstream sessions.",
+				self.inner.published.subscribes(),
+			),
+			(
+				"moq_relay_groups_total",
+				"counter",
+				"Groups served to downstream sessions.",
+				self.inner.published.groups(),
+			),
+			(
+				"moq_relay_cache_hits_total",
+				"counter",
+				"Groups requested by downstream sessions that were already cached.",
+				self.inner.published.cache().hits(),
+			),
+			(
+				"moq_relay_cache_misses_total",
+				"counter",
+				"Groups requested by downstream sessions that had to be waited for.",
+				self.inner.published.cache().misses(),
+			),
+			(
+				"moq_relay_announces_total",
+				"counter",
+				"Broadcast announcements since startup.",
+				self.inner.announces.load(Ordering::Relaxed),
+			),
+			(
+				"moq_relay_broadcasts",
+				"gauge",
+				"Broadcasts currently announced.",
+				broadcasts.len() as u64,
+			),
+			(
+				"moq_relay_cluster_reconnects_total",
+				"counter",
+				"Reconnect attempts to cluster peers.",
+				self.inner.cluster_reconnects.load(Ordering::Relaxed),
+			),
+		];
+
+		for (name, kind, help, value) in metrics {
+			writeln!(out, "# HELP {name} {help}").unwrap();
+			writeln!(out, "# TYPE {name} {kind}").unwrap();
+			writeln!(out, "{name} {value}").unwrap();
+		}
+
+		writeln!(
+			out,
+			"# HELP moq_relay_broadcast Broadcasts currently announced, by path."
+		)
+		.unwrap();
+		writeln!(out, "# TYPE moq_relay_broadcast gauge").unwrap();
+		let mut paths: Vec<_> = broadcasts.iter().map(|path| path.as_str()).collect();
+		paths.sort();
+		for path in paths {
+			writeln!(out, "moq_relay_broadcast{{broadcast=\"{}\"}} 1", escape_label(path)).unwrap();
+		}
+
+		self.encode_tracks(&mut out);
+
+		out
+	}
+
+	/// Encode per-track counters for the tracks currently served, labeled by broadcast and track.
+	fn encode_tracks(&self, out: &mut String) {
+		let mut tracks = self.inner.published.tracks();
+		tracks.sort_by(|a, b| {
+			b.bytes
+				.cmp(&a.bytes)
+				.then_with(|| (a.broadcast.as_str(), &a.track).cmp(&(b.broadcast.as_str(), &b.track)))
+		});
+		tracks.truncate(MAX_LABELED_TRACKS);
+
+		let metrics: [(&str, &str, &str, TrackValue); 3] = [
+			(
+				"moq_relay_track_subscribers",
+				"gauge",
+				"Active subscriptions, by broadcast and track.",
+				|track| track.subscribers,
+			),
+			(
+				"moq_relay_track_bytes_total",
+				"counter",
+				"Bytes served to active subscriptions, by broadcast and track.",
+				|track| track.bytes,
+			),
+			(
+				"moq_relay_track_dropped_groups_total",
+				"counter",
+				"Groups skipped or not delivered in full, by broadcast and track.",
+				|track| track.dropped,
+			),
+		];
+
+		for (name, kind, help, value) in metrics {
+			writeln!(out, "# HELP {name} {help}").unwrap();
+			writeln!(out, "# TYPE {name} {kind}").unwrap();
+			for track in &tracks {
+				writeln!(
+					out,
+					"{name}{{broadcast=\"{}\",track=\"{}\"}} {}",
+					escape_label(track.broadcast.as_str()),
+					escape_label(&track.track),
+					value(track)
+				)
+				.unwrap();
+			}
+		}
+	}
+}
+
+/// Marks a session as active until dropped. See [`Metrics::session`].
+pub struct SessionGuard {
+	metrics: Metrics,
+}
+
+impl Drop for SessionGuard {
+	fn drop(&mut self) {
+		self.metrics.inner.sessions.fetch_sub(1, Ordering::Relaxed);
+	}
+}
+
+fn escape_label(value: &str) -> String {
+	value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn encode_tracks_sessions_and_broadcasts() {
+		let metrics = Metrics::new();
+		let origin = moq_lite::Origin::random().produce();
+		tokio::spawn(metrics.clone().run(origin.consume()));
+
+		let guard = metrics.session();
+		let _other = metrics.session();
+		drop(guard);
+
+		let broadcast = moq_lite::Broadcast::new().produce();
+		origin.publish_broadcast("demo/\"bbb\"", broadcast.consume());
+		tokio::task::yield_now().await;
+
+		let text = metrics.encode();
+		assert!(text.contains("moq_relay_connections_total 2\n"), "{text}");
+		assert!(text.contains("moq_relay_sessions 1\n"), "{text}");
+		assert!(text.contains("moq_relay_announces_total 1\n"), "{text}");
+		assert!(text.contains("moq_relay_broadcasts 1\n"), "{text}");
+		assert!(
+			text.contains("moq_relay_broadcast{broadcast=\"demo/\\\"bbb\\\"\"} 1\n"),
+			"{text}"
+		);
+
+		drop(broadcast);
+		tokio::task::yield_now().await;
+		assert!(metrics.encode().contains("moq_relay_broadcasts 0\n"));
+	}
+
+	#[test]
+	fn encode_labels_active_tracks() {
+		let metrics = Metrics::new();
+		let published = metrics.published();
+
+		let first = published.track("demo/bbb", "video");
+		let second = published.track("demo/bbb", "video");
+		first.add_bytes(1000);
+		second.add_bytes(500);
+		second.add_dropped();
+
+		let text = metrics.encode();
+		let labels = "{broadcast=\"demo/bbb\",track=\"video\"}";
+		assert!(
+			text.contains(&format!("moq_relay_track_subscribers{labels} 2\n")),
+			"{text}"
+		);
+		assert!(
+			text.contains(&format!("moq_relay_track_bytes_total{labels} 1500\n")),
+			"{text}"
+		);
+		assert!(
+			text.contains(&format!("moq_relay_track_dropped_groups_total{labels} 1\n")),
+			"{text}"
+		);
+
+		// The series disappear once the last subscription ends.
+		drop(first);
+		assert!(
+			metrics
+				.encode()
+				.contains(&format!("moq_relay_track_subscribers{labels} 1\n"))
+		);
+		drop(second);
+		assert!(!metrics.encode().contains("track=\"video\""));
+	}
+
+	#[tokio::test]
+	async fn encode_cache_hits_and_misses() {
+		let metrics = Metrics::new();
+
+		let mut track = moq_lite::Track::new("video").produce();
+		track.append_group().unwrap();
+		let consumer = track.consume().with_cache_stats(metrics.published().cache());
+
+		// Group 0 is cached; group 1 has to be waited for.
+		consumer.get_group(0).await.unwrap().unwrap();
+		let pending = tokio::spawn({
+			let consumer = consumer.clone();
+			async move { consumer.get_group(1).await }
+		});
+		tokio::task::yield_now().await;
+		track.append_group().unwrap();
+		pending.await.unwrap().unwrap().unwrap();
+
+		let text = metrics.encode();
+		assert!(text.contains("moq_relay_cache_hits_total 1\n"), "{text}");
+		assert!(text.contains("moq_relay_cache_misses_total 1\n"), "{text}");
+	}
+
+	#[test]
+	fn encode_limits_labeled_tracks() {
+		let metrics = Metrics::new();
+		let published = metrics.published();
+
+		let tracks: Vec<_> = (0..MAX_LABELED_TRACKS + 10)
+			.map(|i| {
+				let track = published.track("demo", &format!("track{i}"));
+				track.add_bytes(i as u64);
+				track
+			})
+			.collect();
+
+		let text = metrics.encode();
+		let labeled = text
+			.lines()
+			.filter(|line| line.starts_with("moq_relay_track_subscribers{"))
+			.count();
+		assert_eq!(labeled, MAX_LABELED_TRACKS);
+
+		// The busiest tracks are kept.
+		assert!(text.contains(&format!("track=\"track{}\"", tracks.len() - 1)));
+		assert!(!text.contains("track=\"track0\""));
+	}
+}
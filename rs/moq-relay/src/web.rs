@@ -10,9 +10,12 @@ use std::{
 use axum::{
 	Router,
 	body::Body,
-	extract::{Extension, Path, Query, State},
+	extract::{ConnectInfo, Extension, Path, Query, State},
 	http::{self, Method, StatusCode},
-	response::{Html, IntoResponse, Response},
+	response::{
+		Html, IntoResponse, Response,
+		sse::{Event, KeepAlive, Sse},
+	},
 	routing::get,
 };
 use axum_server::{
@@ -22,12 +25,13 @@ use axum_server::{
 use bytes::Bytes;
 use clap::Parser;
 use futures::{FutureExt, future::BoxFuture};
+use rand::Rng;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_rustls::server::TlsStream;
 use tower_http::cors::{Any, CorsLayer};
 use tower_service::Service;
 
-use crate::{Auth, AuthParams, AuthToken, Cluster};
+use crate::{Auth, AuthParams, AuthToken, Budgets, Cluster, WorkBudget};
 
 /// Configuration for the HTTP/HTTPS web server.
 #[derive(Parser, Clone, Debug, serde::Deserialize, serde::Serialize, Default)]
@@ -44,6 +48,11 @@ pub struct WebConfig {
 	#[serde(default)]
 	pub https: HttpsConfig,
 
+	/// Metrics listener settings.
+	#[command(flatten)]
+	#[serde(default)]
+	pub metrics: MetricsConfig,
+
 	/// If true (default), expose a WebTransport compatible WebSocket polyfill.
 	#[arg(long = "web-ws", env = "MOQ_WEB_WS", default_value = "true")]
 	#[serde(default = "default_true")]
@@ -60,6 +69,23 @@ pub struct HttpConfig {
 	pub listen: Option<net::SocketAddr>,
 }
 
+/// Metrics listener configuration.
+#[derive(clap::Args, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[non_exhaustive]
+pub struct MetricsConfig {
+	/// Socket address to serve `/metrics` on, over plain HTTP.
+	///
+	/// Metrics aren't authenticated, so they're never served on the public listeners;
+	/// bind this to a private interface. Requires the `metrics` feature.
+	#[arg(
+		long = "web-metrics-listen",
+		id = "web-metrics-listen",
+		env = "MOQ_WEB_METRICS_LISTEN"
+	)]
+	pub listen: Option<net::SocketAddr>,
+}
+
 /// HTTPS listener configuration with TLS certificate and key.
 #[serde_with::serde_as]
 #[derive(clap::Args, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
@@ -105,8 +131,14 @@ pub struct WebState {
 	pub cluster: Cluster,
 	/// TLS certificate information served at `/certificate.sha256`.
 	pub tls_info: Arc<std::sync::RwLock<moq_native::ServerTlsInfo>>,
-	/// Monotonically increasing connection counter for WebSocket sessions.
-	pub conn_id: AtomicU64,
+	/// Monotonically increasing connection counter, shared with the QUIC listener.
+	pub conn_id: Arc<AtomicU64>,
+	/// Relay-wide counters, served at `/metrics` on the metrics listener with the `metrics` feature.
+	pub metrics: crate::Metrics,
+	/// Per-connection work budgets, keyed by peer address, if a limit is configured.
+	pub budgets: Option<Budgets<net::SocketAddr>>,
+	/// Where to record WebSocket sessions once they close.
+	pub access: crate::AccessLog,
 }
 
 /// Run a HTTP server using Axum
@@ -123,6 +155,8 @@ impl Web {
 	/// Runs the HTTP and/or HTTPS listeners until they shut down.
 	pub async fn run(self) -> anyhow::Result<()> {
 		let app = Router::new()
+			.route("/healthz", get(serve_healthz))
+			.route("/readyz", get(serve_readyz))
 			.route("/certificate.sha256", get(serve_fingerprint))
 			.route("/announced", get(serve_announced))
 			.route("/announced/{*prefix}", get(serve_announced))
@@ -135,11 +169,14 @@ impl Web {
 			false => app,
 		};
 
+		let state = Arc::new(self.state);
+		let metrics = serve_metrics_on(self.config.metrics.listen, state.clone())?;
+
 		let app = app
 			.fallback(serve_landing)
 			.layer(CorsLayer::new().allow_origin(Any).allow_methods([Method::GET]))
-			.with_state(Arc::new(self.state))
-			.into_make_service();
+			.with_state(state)
+			.into_make_service_with_connect_info::<net::SocketAddr>();
 
 		let http = if let Some(listen) = self.config.http.listen {
 			let server = axum_server::bind(listen);
@@ -175,6 +212,7 @@ impl Web {
 		tokio::select! {
 			Some(res) = async move { Some(http?.await) } => res?,
 			Some(res) = async move { Some(https?.await) } => res?,
+			Some(res) = async move { Some(metrics?.await) } => res?,
 			else => {},
 		};
 
@@ -364,17 +402,54 @@ async fn serve_landing() -> Response {
 	landing_response()
 }
 
+/// Liveness: the relay is running if it can answer at all.
+async fn serve_healthz() -> StatusCode {
+	StatusCode::OK
+}
+
+/// Readiness: the QUIC listener is bound before the web server starts, so only the cluster can hold it back.
+async fn serve_readyz(State(state): State<Arc<WebState>>) -> StatusCode {
+	match state.cluster.is_ready() {
+		true => StatusCode::OK,
+		false => StatusCode::SERVICE_UNAVAILABLE,
+	}
+}
+
 async fn serve_fingerprint(State(state): State<Arc<WebState>>) -> String {
-	// Get the first certificate's fingerprint.
-	// TODO serve all of them so we can support multiple signature algorithms.
-	state
-		.tls_info
-		.read()
-		.expect("tls_info lock poisoned")
-		.fingerprints
-		.first()
-		.expect("missing certificate")
-		.clone()
+	fingerprints(&state.tls_info)
+}
+
+/// Serve `/metrics` on its own listener, if configured, so it's never exposed publicly.
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+fn serve_metrics_on(
+	listen: Option<net::SocketAddr>,
+	state: Arc<WebState>,
+) -> anyhow::Result<Option<BoxFuture<'static, std::io::Result<()>>>> {
+	let Some(listen) = listen else {
+		return Ok(None);
+	};
+
+	#[cfg(not(feature = "metrics"))]
+	anyhow::bail!("--web-metrics-listen {listen} requires the metrics feature");
+
+	#[cfg(feature = "metrics")]
+	{
+		let app = Router::new().route("/metrics", get(serve_metrics)).with_state(state);
+		Ok(Some(axum_server::bind(listen).serve(app.into_make_service()).boxed()))
+	}
+}
+
+#[cfg(feature = "metrics")]
+async fn serve_metrics(State(state): State<Arc<WebState>>) -> impl IntoResponse {
+	(
+		[(http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+		state.metrics.encode(),
+	)
+}
+
+/// Return every certificate fingerprint, one per line, so clients can accept any signature algorithm.
+fn fingerprints(tls_info: &std::sync::RwLock<moq_native::ServerTlsInfo>) -> String {
+	tls_info.read().expect("tls_info lock poisoned").fingerprints.join("\n")
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -392,6 +467,90 @@ struct FetchParams {
 
 	#[serde(default)]
 	frame: FetchFrame,
+
+	// Return the last frame at or before this timestamp, in microseconds, instead of `frame`.
+	// Only meaningful for tracks whose frames start with a timestamp, like hang's Legacy container.
+	timestamp: Option<u64>,
+
+	// Stream every frame timestamped within `from..=to`, in milliseconds, across groups.
+	// Takes precedence over `group`, `frame` and `timestamp`; both ends are required.
+	from: Option<u64>,
+	to: Option<u64>,
+
+	#[serde(default)]
+	format: FetchFormat,
+}
+
+impl FetchParams {
+	fn range(&self) -> Result<Option<FetchRange>, StatusCode> {
+		let (from, to) = match (self.from, self.to) {
+			(None, None) => return Ok(None),
+			(Some(from), Some(to)) if from <= to => (from, to),
+			_ => return Err(StatusCode::BAD_REQUEST),
+		};
+
+		Ok(Some(FetchRange {
+			from: moq_lite::Timescale::from_millis(from).map_err(|_| StatusCode::BAD_REQUEST)?,
+			to: moq_lite::Timescale::from_millis(to).map_err(|_| StatusCode::BAD_REQUEST)?,
+		}))
+	}
+}
+
+// An inclusive window of frame timestamps, decoded from the prefix of hang's Legacy frames.
+#[derive(Debug, Clone, Copy)]
+struct FetchRange {
+	from: moq_lite::Timescale<1_000_000>,
+	to: moq_lite::Timescale<1_000_000>,
+}
+
+impl FetchRange {
+	fn contains(&self, timestamp: moq_lite::Timescale<1_000_000>) -> bool {
+		self.from <= timestamp && timestamp <= self.to
+	}
+
+	// Find the cached group to start streaming from: the last one that starts at or before `from`,
+	// or the oldest one if they all start later.
+	//
+	// Only cached groups and frames are considered, without waiting for new ones.
+	// Returns None if the range is entirely outside the cached window.
+	fn seek(&self, track: &mut moq_lite::TrackConsumer) -> moq_lite::Result<Option<moq_lite::GroupConsumer>> {
+		let mut start: Option<(moq_lite::Timescale<1_000_000>, moq_lite::GroupConsumer)> = None;
+		let mut end = None;
+
+		while let Some(group) = track.next_group().now_or_never() {
+			let Some(group) = group? else {
+				break;
+			};
+
+			// The first frame of a truncated group was evicted, so we can't tell where it starts.
+			if group.is_truncated() {
+				continue;
+			}
+
+			let mut index = 0;
+			while let Some(frame) = group.get_frame(index).now_or_never() {
+				let Some(mut frame) = frame? else {
+					break;
+				};
+				let Some(data) = frame.read_all().now_or_never() else {
+					break;
+				};
+				let timestamp = moq_lite::Timescale::<1_000_000>::decode(&mut data?)?;
+
+				if index == 0 && (start.is_none() || timestamp <= self.from) {
+					let begin = start.as_ref().map_or(timestamp, |(begin, _)| *begin);
+					start = Some((begin, group.clone()));
+				}
+				end = end.max(Some(timestamp));
+				index += 1;
+			}
+		}
+
+		match (start, end) {
+			(Some((begin, group)), Some(end)) if self.to >= begin && self.from <= end => Ok(Some(group)),
+			_ => Ok(None),
+		}
+	}
 }
 
 #[derive(Debug, Default)]
@@ -419,7 +578,10 @@ impl<'de> serde::Deserialize<'de> for FetchGroup {
 
 #[derive(Debug, Default)]
 enum FetchFrame {
+	// Return the frame at the given index within the group.
 	Num(usize),
+
+	// Return every frame in the group.
 	#[default]
 	Chunked,
 }
@@ -429,7 +591,7 @@ impl<'de> serde::Deserialize<'de> for FetchFrame {
 		let s = String::deserialize(deserializer)?;
 		if let Ok(num) = s.parse::<usize>() {
 			Ok(FetchFrame::Num(num))
-		} else if s == "chunked" {
+		} else if s == "chunked" || s == "all" {
 			Ok(FetchFrame::Chunked)
 		} else {
 			Err(serde::de::Error::custom(format!("invalid frame value: {s}")))
@@ -437,13 +599,38 @@ impl<'de> serde::Deserialize<'de> for FetchFrame {
 	}
 }
 
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FetchFormat {
+	// Concatenate the frame payloads.
+	#[default]
+	Raw,
+
+	// Return `multipart/mixed`, one part per frame.
+	Multipart,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct AnnouncedParams {
+	jwt: Option<String>,
+
+	// Keep the response open and stream changes as server-sent events.
+	#[serde(default)]
+	stream: bool,
+}
+
 /// Serve the announced broadcasts for a given prefix.
+///
+/// Returns a newline-separated snapshot by default. With `?stream=true` or `Accept: text/event-stream`,
+/// the response stays open as server-sent events: an `announce` or `unannounce` event per broadcast,
+/// starting with those already active, until the client disconnects.
 async fn serve_announced(
 	path: Option<Path<String>>,
-	Query(query): Query<AuthQuery>,
+	Query(query): Query<AnnouncedParams>,
+	headers: http::HeaderMap,
 	mtls: Option<Extension<MtlsPeer>>,
 	State(state): State<Arc<WebState>>,
-) -> axum::response::Result<String> {
+) -> axum::response::Result<Response> {
 	let prefix = match path {
 		Some(Path(prefix)) => prefix,
 		None => String::new(),
@@ -462,6 +649,25 @@ async fn serve_announced(
 		return Err(StatusCode::UNAUTHORIZED.into());
 	};
 
+	let event_stream = headers
+		.get_all(http::header::ACCEPT)
+		.iter()
+		.filter_map(|value| value.to_str().ok())
+		.any(|value| value.contains("text/event-stream"));
+
+	if query.stream || event_stream {
+		// The origin is dropped along with the stream when the client disconnects.
+		let events = futures::stream::unfold(origin, |mut origin| async move {
+			let (path, active) = origin.announced().await?;
+			let event = Event::default()
+				.event(if active.is_some() { "announce" } else { "unannounce" })
+				.data(path.as_str());
+			Some((Ok::<_, std::convert::Infallible>(event), origin))
+		});
+
+		return Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response());
+	}
+
 	let mut broadcasts = Vec::new();
 
 	while let Some((suffix, active)) = origin.try_announced() {
@@ -470,7 +676,12 @@ async fn serve_announced(
 		}
 	}
 
-	Ok(broadcasts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join("\n"))
+	Ok(broadcasts
+		.iter()
+		.map(|p| p.to_string())
+		.collect::<Vec<_>>()
+		.join("\n")
+		.into_response())
 }
 
 /// Serve the given group for a given track
@@ -478,6 +689,7 @@ async fn serve_fetch(
 	Path(path): Path<String>,
 	Query(params): Query<FetchParams>,
 	mtls: Option<Extension<MtlsPeer>>,
+	ConnectInfo(peer): ConnectInfo<net::SocketAddr>,
 	State(state): State<Arc<WebState>>,
 ) -> axum::response::Result<ServeGroup> {
 	// The path containts a broadcast/track
@@ -489,6 +701,8 @@ async fn serve_fetch(
 		return Err(StatusCode::BAD_REQUEST.into());
 	}
 
+	let range = params.range()?;
+
 	let broadcast = path.join("/");
 	let auth = AuthParams {
 		path: broadcast.clone(),
@@ -506,10 +720,8 @@ async fn serve_fetch(
 
 	tracing::info!(%broadcast, %track, "fetching track");
 
-	let track = moq_lite::Track {
-		name: track,
-		priority: 0,
-	};
+	let track = moq_lite::Track::new(track);
+	let budget = state.budgets.as_ref().map(|budgets| budgets.get(peer));
 
 	let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(30);
 
@@ -522,6 +734,36 @@ async fn serve_fetch(
 			moq_lite::Error::NotFound => StatusCode::NOT_FOUND,
 			_ => StatusCode::INTERNAL_SERVER_ERROR,
 		})?;
+
+		if let Some(range) = range {
+			// Seek with a clone so the scan below starts again after the first group.
+			let group = match range.seek(&mut track.clone()) {
+				Ok(Some(group)) => group,
+				Ok(None) => return Err(StatusCode::RANGE_NOT_SATISFIABLE),
+				Err(moq_lite::Error::Decode(_)) => return Err(StatusCode::UNPROCESSABLE_ENTITY),
+				Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+			};
+			track.start_at(group.sequence + 1);
+
+			tracing::info!(track = %track.name, group = %group.sequence, ?range, "serving range");
+
+			let multipart = match params.format {
+				FetchFormat::Raw => None,
+				FetchFormat::Multipart => Some(Multipart::new(group.sequence)),
+			};
+
+			return Ok(ServeGroup {
+				group: Some(group),
+				frame: None,
+				index: 0,
+				start: true,
+				multipart,
+				deadline,
+				budget,
+				range: Some((track, range)),
+			});
+		}
+
 		let group = match params.group {
 			FetchGroup::Latest => match track.latest() {
 				Some(sequence) => track.get_group(sequence).await,
@@ -538,12 +780,42 @@ async fn serve_fetch(
 
 		tracing::info!(track = %track.name, group = %group.sequence, "serving group");
 
+		let multipart = match params.format {
+			FetchFormat::Raw => None,
+			FetchFormat::Multipart => Some(Multipart::new(group.sequence)),
+		};
+
+		if let Some(timestamp) = params.timestamp {
+			let timestamp =
+				moq_lite::Timescale::<1_000_000>::from_micros(timestamp).map_err(|_| StatusCode::BAD_REQUEST)?;
+			return match group.frame_at(timestamp).await {
+				Ok(Some((index, frame))) => Ok(ServeGroup {
+					group: None,
+					frame: Some(frame),
+					index,
+					start: index == 0,
+					multipart,
+					deadline,
+					budget,
+					range: None,
+				}),
+				Ok(None) => Err(StatusCode::NOT_FOUND),
+				Err(moq_lite::Error::Decode(_)) => Err(StatusCode::UNPROCESSABLE_ENTITY),
+				Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+			};
+		}
+
 		match params.frame {
 			FetchFrame::Num(index) => match group.get_frame(index).await {
 				Ok(Some(frame)) => Ok(ServeGroup {
 					group: None,
 					frame: Some(frame),
+					index,
+					start: index == 0,
+					multipart,
 					deadline,
+					budget,
+					range: None,
 				}),
 				Ok(None) => Err(StatusCode::NOT_FOUND),
 				Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
@@ -551,7 +823,12 @@ async fn serve_fetch(
 			FetchFrame::Chunked => Ok(ServeGroup {
 				group: Some(group),
 				frame: None,
+				index: 0,
+				start: true,
+				multipart,
 				deadline,
+				budget,
+				range: None,
 			}),
 		}
 	})
@@ -567,18 +844,72 @@ async fn serve_fetch(
 struct ServeGroup {
 	group: Option<moq_lite::GroupConsumer>,
 	frame: Option<moq_lite::FrameConsumer>,
+
+	// The index of the current frame within the group.
+	index: usize,
+
+	// Whether the next frame starts its group, making it a keyframe.
+	start: bool,
+
+	// Wrap each frame in a multipart section when set.
+	multipart: Option<Multipart>,
+
 	deadline: tokio::time::Instant,
+
+	// Throttle the connection when it exceeds its work budget.
+	budget: Option<WorkBudget>,
+
+	// Advance through the track's groups, streaming only frames within the range.
+	range: Option<(moq_lite::TrackConsumer, FetchRange)>,
 }
 
 impl ServeGroup {
 	async fn next(&mut self) -> moq_lite::Result<Option<Bytes>> {
-		while self.group.is_some() || self.frame.is_some() {
+		while self.group.is_some() || self.frame.is_some() || self.range.is_some() {
+			if let Some(budget) = &self.budget {
+				budget.throttle().await;
+			}
+
 			if let Some(frame) = self.frame.as_mut() {
 				let data = tokio::time::timeout_at(self.deadline, frame.read_all())
 					.await
-					.map_err(|_| moq_lite::Error::Timeout)?;
+					.map_err(|_| moq_lite::Error::Timeout)??;
 				self.frame.take();
-				return Ok(Some(data?));
+
+				let index = self.index;
+				self.index += 1;
+				let keyframe = std::mem::take(&mut self.start);
+
+				let mut timestamp = None;
+				if let Some((_, range)) = &self.range {
+					let decoded = moq_lite::Timescale::<1_000_000>::decode(&mut data.clone())?;
+					timestamp = Some(decoded);
+					if keyframe && decoded > range.to {
+						// Groups are scanned in ascending order, so every later frame is out of range too.
+						self.group.take();
+						self.range.take();
+						continue;
+					}
+					if !range.contains(decoded) {
+						continue;
+					}
+				}
+
+				let data = match &self.multipart {
+					Some(multipart) => {
+						// Label the part with the frame's timestamp prefix, when it has one.
+						let timestamp =
+							timestamp.or_else(|| moq_lite::Timescale::<1_000_000>::decode(&mut data.clone()).ok());
+						let part = || multipart.part(index, keyframe, timestamp, data);
+						match &self.budget {
+							Some(budget) => budget.measure(part),
+							None => part(),
+						}
+					}
+					None => data,
+				};
+
+				return Ok(Some(data));
 			}
 
 			if let Some(group) = self.group.as_mut() {
@@ -588,16 +919,107 @@ impl ServeGroup {
 				if self.frame.is_none() {
 					self.group.take();
 				}
+				continue;
+			}
+
+			if let Some((track, _)) = self.range.as_mut() {
+				match tokio::time::timeout_at(self.deadline, track.next_group())
+					.await
+					.map_err(|_| moq_lite::Error::Timeout)??
+				{
+					Some(group) => {
+						self.index = 0;
+						self.start = true;
+						if let Some(multipart) = self.multipart.as_mut() {
+							multipart.group = group.sequence;
+						}
+						self.group = Some(group);
+					}
+					None => {
+						self.range.take();
+					}
+				}
 			}
 		}
 
+		// Close the multipart body exactly once.
+		if let Some(multipart) = self.multipart.take() {
+			return Ok(Some(multipart.close()));
+		}
+
 		Ok(None)
 	}
 }
 
 impl IntoResponse for ServeGroup {
 	fn into_response(self) -> Response {
-		Response::new(Body::new(self))
+		let content_type = self.multipart.as_ref().map(Multipart::content_type);
+
+		let mut response = Response::new(Body::new(self));
+		if let Some(content_type) = content_type
+			&& let Ok(value) = http::HeaderValue::from_str(&content_type)
+		{
+			response.headers_mut().insert(http::header::CONTENT_TYPE, value);
+		}
+
+		response
+	}
+}
+
+/// Encodes frames as `multipart/mixed` sections, preserving frame boundaries.
+///
+/// Each part is labeled with its group sequence, its frame index, and whether it starts
+/// the group (the keyframe). moq-lite frames don't carry a timestamp, so frames prefixed
+/// with one, like hang's Legacy container, are also labeled with it in microseconds.
+struct Multipart {
+	boundary: String,
+	group: u64,
+}
+
+impl Multipart {
+	fn new(group: u64) -> Self {
+		let boundary = rand::rng()
+			.sample_iter(rand::distr::Alphanumeric)
+			.take(32)
+			.map(char::from)
+			.collect();
+
+		Self { boundary, group }
+	}
+
+	fn content_type(&self) -> String {
+		format!("multipart/mixed; boundary={}", self.boundary)
+	}
+
+	fn part(
+		&self,
+		index: usize,
+		keyframe: bool,
+		timestamp: Option<moq_lite::Timescale<1_000_000>>,
+		data: Bytes,
+	) -> Bytes {
+		let mut header = format!(
+			"--{}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nMoq-Group: {}\r\nMoq-Frame: {}\r\nMoq-Keyframe: {}\r\n",
+			self.boundary,
+			data.len(),
+			self.group,
+			index,
+			keyframe,
+		);
+		if let Some(timestamp) = timestamp {
+			header.push_str(&format!("Moq-Timestamp: {}\r\n", timestamp.as_micros()));
+		}
+		header.push_str("\r\n");
+
+		let mut part = bytes::BytesMut::with_capacity(header.len() + data.len() + 2);
+		part.extend_from_slice(header.as_bytes());
+		part.extend_from_slice(&data);
+		part.extend_from_slice(b"\r\n");
+		part.freeze()
+	}
+
+	fn close(&self) -> Bytes {
+		Bytes::from(format!("--{}--\r\n", self.boundary))
 	}
 }
 
@@ -675,6 +1097,41 @@ mod tests {
 		(ca_path, cert_path, key_path)
 	}
 
+	#[tokio::test]
+	async fn fingerprints_serves_every_cert() {
+		let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+		let dir = TempDir::new().unwrap();
+
+		let mut config = moq_native::ServerConfig::default();
+		config.bind = Some("127.0.0.1:0".to_string());
+
+		for (name, alg) in [
+			("ecdsa", &rcgen::PKCS_ECDSA_P256_SHA256),
+			("ed25519", &rcgen::PKCS_ED25519),
+		] {
+			let kp = KeyPair::generate_for(alg).unwrap();
+			let cert = CertificateParams::new(vec!["localhost".to_string()])
+				.unwrap()
+				.self_signed(&kp)
+				.unwrap();
+
+			let cert_path = dir.path().join(format!("{name}.cert.pem"));
+			let key_path = dir.path().join(format!("{name}.key.pem"));
+			std::fs::write(&cert_path, cert.pem()).unwrap();
+			std::fs::write(&key_path, kp.serialize_pem()).unwrap();
+			config.tls.cert.push(cert_path);
+			config.tls.key.push(key_path);
+		}
+
+		let server = config.init().unwrap();
+		let tls_info = server.tls_info();
+		let expected = tls_info.read().unwrap().fingerprints.clone();
+		assert_eq!(expected.len(), 2);
+
+		let body = fingerprints(&tls_info);
+		assert_eq!(body.lines().collect::<Vec<_>>(), expected);
+	}
+
 	#[tokio::test]
 	async fn build_https_config_round_trips() {
 		let dir = TempDir::new().unwrap();
@@ -777,4 +1234,289 @@ mod tests {
 			"SetMtlsExtension(None) must NOT surface MtlsPeer"
 		);
 	}
+
+	#[tokio::test]
+	async fn multipart_fetch_preserves_frames() {
+		let mut group = moq_lite::Group { sequence: 7 }.produce();
+		let frames: [&[u8]; 3] = [b"key", b"delta-1", b"delta\r\n2"];
+		for frame in frames {
+			group.write_frame(frame).unwrap();
+		}
+		group.finish().unwrap();
+
+		let mut serve = ServeGroup {
+			group: Some(group.consume()),
+			frame: None,
+			index: 0,
+			start: true,
+			multipart: Some(Multipart::new(7)),
+			deadline: tokio::time::Instant::now() + tokio::time::Duration::from_secs(5),
+			budget: None,
+			range: None,
+		};
+
+		let parts = multipart_parts(&mut serve).await;
+		assert_eq!(parts.len(), frames.len());
+
+		for (index, ((headers, payload), frame)) in parts.iter().zip(frames).enumerate() {
+			assert_eq!(payload.as_bytes(), frame);
+			assert!(headers.contains(&format!("Content-Length: {}", frame.len())));
+			assert!(headers.contains(&"Moq-Group: 7".to_string()));
+			assert!(headers.contains(&format!("Moq-Frame: {index}")));
+			assert!(headers.contains(&format!("Moq-Keyframe: {}", index == 0)));
+		}
+	}
+
+	#[tokio::test]
+	async fn multipart_range_labels_parts() {
+		let (_producer, mut track) = range_track();
+		let range = range(150, 450);
+
+		let group = range.seek(&mut track.clone()).unwrap().expect("range is cached");
+		track.start_at(group.sequence + 1);
+
+		let mut serve = ServeGroup {
+			group: Some(group),
+			frame: None,
+			index: 0,
+			start: true,
+			multipart: Some(Multipart::new(0)),
+			deadline: tokio::time::Instant::now() + tokio::time::Duration::from_secs(5),
+			budget: None,
+			range: Some((track, range)),
+		};
+
+		let parts = multipart_parts(&mut serve).await;
+		let labels: Vec<_> = parts
+			.iter()
+			.map(|(headers, _)| {
+				let header = |name: &str| {
+					headers
+						.iter()
+						.find_map(|header| header.strip_prefix(name))
+						.unwrap()
+						.to_string()
+				};
+				(
+					header("Moq-Group: "),
+					header("Moq-Frame: "),
+					header("Moq-Keyframe: "),
+					header("Moq-Timestamp: "),
+				)
+			})
+			.collect();
+
+		// Only the frame starting the second group is a keyframe; the range starts mid-group.
+		let expected = [
+			("0", "2", "false", "200000"),
+			("1", "0", "true", "300000"),
+			("1", "1", "false", "400000"),
+		];
+		assert_eq!(labels.len(), expected.len());
+		for (label, (group, frame, keyframe, timestamp)) in labels.iter().zip(expected) {
+			assert_eq!(label, &(group.into(), frame.into(), keyframe.into(), timestamp.into()));
+		}
+	}
+
+	// Read a multipart response, returning the headers and payload of each part.
+	async fn multipart_parts(serve: &mut ServeGroup) -> Vec<(Vec<String>, String)> {
+		let content_type = serve.multipart.as_ref().unwrap().content_type();
+		let boundary = content_type
+			.strip_prefix("multipart/mixed; boundary=")
+			.unwrap()
+			.to_string();
+
+		let mut body = Vec::new();
+		while let Some(chunk) = serve.next().await.unwrap() {
+			body.extend_from_slice(&chunk);
+		}
+		// Timestamp prefixes aren't UTF-8, but the headers and boundaries are.
+		let body = String::from_utf8_lossy(&body);
+
+		let body = body
+			.strip_suffix(&format!("--{boundary}--\r\n"))
+			.expect("missing closing boundary");
+		body.split(&format!("--{boundary}\r\n"))
+			.filter(|part| !part.is_empty())
+			.map(|part| {
+				let (headers, payload) = part.split_once("\r\n\r\n").unwrap();
+				let headers = headers.split("\r\n").map(str::to_string).collect();
+				(headers, payload.strip_suffix("\r\n").unwrap().to_string())
+			})
+			.collect()
+	}
+
+	// A live track of three groups, each with three Legacy frames 100ms apart.
+	fn range_track() -> (moq_lite::TrackProducer, moq_lite::TrackConsumer) {
+		let mut track = moq_lite::Track::new("video").produce();
+		let consumer = track.consume();
+
+		for sequence in 0..3u64 {
+			let mut group = track.append_group().unwrap();
+			for index in 0..3u64 {
+				let mut frame = bytes::BytesMut::new();
+				moq_lite::Timescale::<1_000_000>::from_millis((sequence * 3 + index) * 100)
+					.unwrap()
+					.encode(&mut frame)
+					.unwrap();
+				frame.extend_from_slice(b"payload");
+				group.write_frame(frame.freeze()).unwrap();
+			}
+			group.finish().unwrap();
+		}
+
+		(track, consumer)
+	}
+
+	fn range(from: u64, to: u64) -> FetchRange {
+		FetchRange {
+			from: moq_lite::Timescale::from_millis(from).unwrap(),
+			to: moq_lite::Timescale::from_millis(to).unwrap(),
+		}
+	}
+
+	#[tokio::test]
+	async fn range_fetch_spans_groups() {
+		let (_producer, mut track) = range_track();
+		let range = range(250, 550);
+
+		let group = range.seek(&mut track.clone()).unwrap().expect("range is cached");
+		assert_eq!(group.sequence, 0);
+		track.start_at(group.sequence + 1);
+
+		let mut serve = ServeGroup {
+			group: Some(group),
+			frame: None,
+			index: 0,
+			start: true,
+			multipart: None,
+			deadline: tokio::time::Instant::now() + tokio::time::Duration::from_secs(5),
+			budget: None,
+			range: Some((track, range)),
+		};
+
+		// The scan ends at the first group past the range, without waiting for the live edge.
+		let mut timestamps = Vec::new();
+		while let Some(mut frame) = serve.next().await.unwrap() {
+			let timestamp = moq_lite::Timescale::<1_000_000>::decode(&mut frame).unwrap();
+			timestamps.push(timestamp.as_millis());
+		}
+		assert_eq!(timestamps, [300, 400, 500]);
+	}
+
+	#[test]
+	fn range_fetch_outside_cache() {
+		let (_producer, track) = range_track();
+
+		assert!(range(0, 0).seek(&mut track.clone()).unwrap().is_some());
+		assert!(range(800, 900).seek(&mut track.clone()).unwrap().is_some());
+		assert!(range(900, 1_000).seek(&mut track.clone()).unwrap().is_none());
+
+		// Nothing cached at all.
+		let empty = moq_lite::Track::new("empty").produce();
+		assert!(range(0, 1_000).seek(&mut empty.consume()).unwrap().is_none());
+	}
+
+	#[tokio::test]
+	async fn announced_streams_events() {
+		use futures::StreamExt;
+
+		let mut server_config = moq_native::ServerConfig::default();
+		server_config.bind = Some("127.0.0.1:0".to_string());
+		server_config.tls.generate = vec!["localhost".to_string()];
+		let server = server_config.init().unwrap();
+
+		let auth = Auth::new(crate::AuthConfig {
+			public: Some(crate::PublicConfig::Detailed(crate::PublicDetailed {
+				subscribe: vec!["".to_string()],
+				publish: vec![],
+				api: None,
+			})),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		let client = moq_native::ClientConfig::default().init().unwrap();
+		let cluster = Cluster::new(crate::ClusterConfig::default(), client);
+		let origin = cluster.publisher(&AuthToken::unrestricted()).unwrap();
+
+		let before = moq_lite::Broadcast::new().produce();
+		origin.publish_broadcast("before", before.consume());
+
+		let state = Arc::new(WebState {
+			auth,
+			cluster,
+			tls_info: server.tls_info(),
+			conn_id: Default::default(),
+			metrics: crate::Metrics::new(),
+			budgets: None,
+			access: Default::default(),
+		});
+
+		let mut headers = http::HeaderMap::new();
+		headers.insert(http::header::ACCEPT, "text/event-stream".parse().unwrap());
+		let query = AnnouncedParams {
+			jwt: None,
+			stream: false,
+		};
+		let response = serve_announced(None, Query(query), headers, None, State(state))
+			.await
+			.unwrap();
+		assert_eq!(response.headers()[http::header::CONTENT_TYPE], "text/event-stream");
+
+		let mut body = response.into_body().into_data_stream();
+		let mut next = async || {
+			let chunk = tokio::time::timeout(tokio::time::Duration::from_secs(1), body.next())
+				.await
+				.expect("no event")
+				.unwrap()
+				.unwrap();
+			String::from_utf8(chunk.to_vec()).unwrap()
+		};
+
+		// Already active broadcasts come first, then changes as they happen.
+		assert_eq!(next().await, "event: announce\ndata: before\n\n");
+
+		let after = moq_lite::Broadcast::new().produce();
+		origin.publish_broadcast("after", after.consume());
+		assert_eq!(next().await, "event: announce\ndata: after\n\n");
+
+		drop(after);
+		assert_eq!(next().await, "event: unannounce\ndata: after\n\n");
+	}
+
+	#[tokio::test]
+	async fn health_endpoints() {
+		let mut server_config = moq_native::ServerConfig::default();
+		server_config.bind = Some("127.0.0.1:0".to_string());
+		server_config.tls.generate = vec!["localhost".to_string()];
+		let server = server_config.init().unwrap();
+		let client = moq_native::ClientConfig::default().init().unwrap();
+
+		let state = |cluster: crate::ClusterConfig| {
+			Arc::new(WebState {
+				auth: Auth::default(),
+				cluster: Cluster::new(cluster, client.clone()),
+				tls_info: server.tls_info(),
+				conn_id: Default::default(),
+				metrics: crate::Metrics::new(),
+				budgets: None,
+				access: Default::default(),
+			})
+		};
+
+		assert_eq!(serve_healthz().await, StatusCode::OK);
+
+		// A standalone relay is ready as soon as it's serving.
+		let standalone = state(crate::ClusterConfig::default());
+		assert_eq!(serve_readyz(State(standalone)).await, StatusCode::OK);
+
+		// A clustered relay isn't ready until a peer link is up.
+		let clustered = state(crate::ClusterConfig {
+			connect: vec!["localhost:1".to_string()],
+			..Default::default()
+		});
+		assert_eq!(serve_readyz(State(clustered)).await, StatusCode::SERVICE_UNAVAILABLE);
+	}
 }
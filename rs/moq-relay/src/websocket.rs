@@ -17,7 +17,9 @@ use axum::{
 };
 use moq_lite::{OriginConsumer, OriginProducer};
 
-use crate::{AuthParams, AuthToken, WebState, web::AuthQuery, web::MtlsPeer, web::landing_response};
+use crate::{
+	AccessSession, AuthParams, AuthToken, Metrics, WebState, web::AuthQuery, web::MtlsPeer, web::landing_response,
+};
 
 pub(crate) async fn serve_ws(
 	ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
@@ -61,7 +63,10 @@ pub(crate) async fn serve_ws(
 				tungstenite::Error::ConnectionClosed
 			})
 			.with(tungstenite_to_axum);
-		let _ = handle_socket(id, socket, publish, subscribe).await;
+		let access = state
+			.access
+			.start(id, "websocket", None, token.root.as_str(), state.metrics.published());
+		let _ = handle_socket(id, socket, publish, subscribe, state.metrics.clone(), access).await;
 	}))
 }
 
@@ -71,6 +76,8 @@ async fn handle_socket<T>(
 	socket: T,
 	publish: Option<OriginProducer>,
 	subscribe: Option<OriginConsumer>,
+	metrics: Metrics,
+	access: AccessSession,
 ) -> anyhow::Result<()>
 where
 	T: futures::Stream<Item = Result<tungstenite::Message, tungstenite::Error>>
@@ -84,11 +91,14 @@ where
 	let session = moq_lite::Server::new()
 		.with_publish(subscribe)
 		.with_consume(publish)
-		// TODO: Uncomment when observability feature is merged
-		// .with_stats(stats)
+		.with_stats(access.stats())
 		.accept(ws)
 		.await?;
-	session.closed().await.map_err(Into::into)
+
+	let _active = metrics.session();
+	let res = session.closed().await;
+	access.finish(&session, res.as_ref().err());
+	res.map_err(Into::into)
 }
 
 // https://github.com/tokio-rs/axum/discussions/848#discussioncomment-11443587
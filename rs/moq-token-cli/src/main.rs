@@ -70,6 +70,18 @@ enum Commands {
 		/// Issued-at time as a unix timestamp.
 		#[arg(long, value_parser = parse_unix_timestamp)]
 		issued: Option<std::time::SystemTime>,
+
+		/// The relay identities the token is intended for (repeatable).
+		#[arg(long)]
+		audience: Vec<String>,
+
+		/// Reject the token before this unix timestamp.
+		#[arg(long, value_parser = parse_unix_timestamp)]
+		not_before: Option<std::time::SystemTime>,
+
+		/// A unique token ID, so relays can reject replays.
+		#[arg(long)]
+		jti: Option<String>,
 	},
 
 	/// Verify a token from stdin, writing the payload to stdout.
@@ -128,6 +140,9 @@ fn main() -> anyhow::Result<()> {
 			subscribe,
 			expires,
 			issued,
+			audience,
+			not_before,
+			jti,
 		} => {
 			let key = moq_token::Key::from_file(key)?;
 
@@ -137,6 +152,9 @@ fn main() -> anyhow::Result<()> {
 				subscribe,
 				expires,
 				issued,
+				audience,
+				not_before,
+				jti,
 			};
 
 			let token = key.encode(&payload)?;
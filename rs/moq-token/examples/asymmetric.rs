@@ -22,6 +22,9 @@ fn main() -> anyhow::Result<()> {
 		subscribe: vec!["".to_string()],
 		expires: Some(SystemTime::now() + Duration::from_secs(3600)),
 		issued: Some(SystemTime::now()),
+		audience: Vec::new(),
+		not_before: None,
+		jti: None,
 	};
 
 	let token = private_key.encode(&claims)?;
@@ -1,6 +1,6 @@
 // cargo run --example basic
 
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
 
 fn main() -> anyhow::Result<()> {
 	// Generate an HMAC key with a random key ID.
@@ -11,16 +11,13 @@ fn main() -> anyhow::Result<()> {
 	println!("Generated key:\n{key_str}\n");
 
 	// Create claims for the token.
-	let claims = moq_token::Claims {
-		root: "demo".to_string(),
-		publish: vec!["my-stream".to_string()], // Can publish to demo/my-stream
-		subscribe: vec!["".to_string()],        // Can subscribe to anything under demo/
-		expires: Some(SystemTime::now() + Duration::from_secs(3600)),
-		issued: Some(SystemTime::now()),
-	};
-
-	// Validate the claims (ensures at least one publish or subscribe path).
-	claims.validate()?;
+	// The builder checks paths stay under the root and that at least one is allowed.
+	let claims = moq_token::Claims::builder()
+		.root("demo")
+		.allow_publish("my-stream") // Can publish to demo/my-stream
+		.allow_subscribe("") // Can subscribe to anything under demo/
+		.expires_in(Duration::from_secs(3600))
+		.build()?;
 
 	// Sign a JWT token.
 	let token = key.encode(&claims)?;
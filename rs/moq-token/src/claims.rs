@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
-use serde_with::{OneOrMany, TimestampSeconds, formats::PreferMany, serde_as};
+use serde_with::{
+	OneOrMany, TimestampSeconds,
+	formats::{PreferMany, PreferOne},
+	serde_as,
+};
 
 #[serde_with::skip_serializing_none]
 #[serde_as]
@@ -33,6 +37,23 @@ pub struct Claims {
 	#[serde(rename = "iat")]
 	#[serde_as(as = "Option<TimestampSeconds<i64>>")]
 	pub issued: Option<std::time::SystemTime>,
+
+	/// The intended audiences of the token, typically the identity of a relay cluster.
+	/// A JWT may carry a single string or an array; a single audience is encoded as a string.
+	/// If not specified, the token is rejected by any verifier that requires an audience.
+	#[serde(default, rename = "aud", skip_serializing_if = "Vec::is_empty")]
+	#[serde_as(as = "OneOrMany<_, PreferOne>")]
+	pub audience: Vec<String>,
+
+	/// The time before which the token must be rejected, as a unix timestamp.
+	#[serde(rename = "nbf")]
+	#[serde_as(as = "Option<TimestampSeconds<i64>>")]
+	pub not_before: Option<std::time::SystemTime>,
+
+	/// A unique identifier for the token.
+	/// Verifiers with a [crate::JtiStore] accept each identifier only once.
+	#[serde(rename = "jti")]
+	pub jti: Option<String>,
 }
 
 impl Claims {
@@ -43,6 +64,151 @@ impl Claims {
 
 		Ok(())
 	}
+
+	/// Returns an error unless the token was minted for the `expected` audience.
+	///
+	/// Tokens without an audience are rejected, since they could be replayed anywhere.
+	pub fn validate_audience(&self, expected: &str) -> crate::Result<()> {
+		match self.audience.iter().any(|audience| audience == expected) {
+			true => Ok(()),
+			false => Err(crate::Error::InvalidAudience),
+		}
+	}
+}
+
+/// A builder for [Claims] that checks every path stays within the root.
+///
+/// Publish and subscribe paths are relative to [Self::root]. A path with a leading slash is
+/// treated as absolute instead, and must live under the root; it is rewritten relative to it.
+/// Paths containing `..` are rejected. [Self::build] sets the issued time to now.
+///
+/// ```ignore
+/// let claims = moq_token::Claims::builder()
+///     .root("rooms/123")
+///     .allow_publish("alice")
+///     .allow_subscribe("")
+///     .expires_in(Duration::from_secs(3600))
+///     .build()?;
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ClaimsBuilder {
+	root: String,
+	publish: Vec<String>,
+	subscribe: Vec<String>,
+	expires_in: Option<std::time::Duration>,
+	audience: Vec<String>,
+	not_before: Option<std::time::SystemTime>,
+	jti: Option<String>,
+}
+
+impl ClaimsBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the root that all publish and subscribe paths are scoped to.
+	pub fn root(mut self, root: impl Into<String>) -> Self {
+		self.root = root.into();
+		self
+	}
+
+	/// Allow publishing any broadcast under the given path.
+	pub fn allow_publish(mut self, path: impl Into<String>) -> Self {
+		self.publish.push(path.into());
+		self
+	}
+
+	/// Allow subscribing to any broadcast under the given path.
+	pub fn allow_subscribe(mut self, path: impl Into<String>) -> Self {
+		self.subscribe.push(path.into());
+		self
+	}
+
+	/// Expire the token this long after it was issued.
+	pub fn expires_in(mut self, duration: std::time::Duration) -> Self {
+		self.expires_in = Some(duration);
+		self
+	}
+
+	/// Restrict the token to the given audience, typically the identity of a relay cluster.
+	///
+	/// May be called multiple times; the token is accepted by any of the audiences.
+	pub fn audience(mut self, audience: impl Into<String>) -> Self {
+		self.audience.push(audience.into());
+		self
+	}
+
+	/// Reject the token until the given time.
+	pub fn not_before(mut self, time: std::time::SystemTime) -> Self {
+		self.not_before = Some(time);
+		self
+	}
+
+	/// Set a unique identifier for the token, so verifiers can reject replays.
+	pub fn jti(mut self, jti: impl Into<String>) -> Self {
+		self.jti = Some(jti.into());
+		self
+	}
+
+	/// Validate the paths and produce the [Claims], issued now.
+	pub fn build(self) -> crate::Result<Claims> {
+		let root = self.root.trim_matches('/');
+		if root.split('/').any(|segment| segment == "..") {
+			return Err(crate::Error::InvalidPath(self.root));
+		}
+
+		let scope = |paths: Vec<String>| -> crate::Result<Vec<String>> {
+			paths.into_iter().map(|path| Self::scope(root, path)).collect()
+		};
+
+		let issued = std::time::SystemTime::now();
+		let claims = Claims {
+			root: root.to_string(),
+			publish: scope(self.publish)?,
+			subscribe: scope(self.subscribe)?,
+			expires: self.expires_in.map(|duration| issued + duration),
+			issued: Some(issued),
+			audience: self.audience,
+			not_before: self.not_before,
+			jti: self.jti,
+		};
+		claims.validate()?;
+
+		Ok(claims)
+	}
+
+	/// Return the path relative to the root, or an error if it would escape it.
+	fn scope(root: &str, path: String) -> crate::Result<String> {
+		let relative = match path.strip_prefix('/') {
+			Some(absolute) => {
+				let absolute = absolute.trim_end_matches('/');
+				if root.is_empty() {
+					absolute
+				} else if absolute == root {
+					""
+				} else {
+					match absolute.strip_prefix(root).and_then(|rest| rest.strip_prefix('/')) {
+						Some(rest) => rest,
+						None => return Err(crate::Error::InvalidPath(path)),
+					}
+				}
+			}
+			None => path.trim_matches('/'),
+		};
+
+		if relative.split('/').any(|segment| segment == "..") {
+			return Err(crate::Error::InvalidPath(path));
+		}
+
+		Ok(relative.to_string())
+	}
+}
+
+impl Claims {
+	/// Start building claims; see [ClaimsBuilder].
+	pub fn builder() -> ClaimsBuilder {
+		ClaimsBuilder::new()
+	}
 }
 
 #[cfg(test)]
@@ -58,6 +224,9 @@ mod tests {
 			subscribe: vec!["test-sub".into()],
 			expires: Some(SystemTime::now() + Duration::from_secs(3600)),
 			issued: Some(SystemTime::now()),
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		}
 	}
 
@@ -75,6 +244,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		let result = claims.validate();
@@ -95,6 +267,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -108,6 +283,9 @@ mod tests {
 			subscribe: vec!["test-sub".into()],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -121,6 +299,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		let result = claims.validate();
@@ -135,6 +316,9 @@ mod tests {
 			subscribe: vec!["relative-sub".into()], // relative path without leading slash
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		let result = claims.validate();
@@ -149,6 +333,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -162,6 +349,9 @@ mod tests {
 			subscribe: vec!["/absolute-sub".into()], // absolute path with leading slash
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -175,6 +365,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -188,6 +381,9 @@ mod tests {
 			subscribe: vec!["".into()], // empty string
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -201,6 +397,9 @@ mod tests {
 			subscribe: vec!["relative-sub".into()], // relative path is ok when path is prefix
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -214,6 +413,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		assert!(claims.validate().is_ok());
@@ -278,4 +480,97 @@ mod tests {
 		assert_eq!(claims.publish, vec!["single"]);
 		assert_eq!(claims.subscribe, vec!["multi1", "multi2"]);
 	}
+
+	#[test]
+	fn test_validate_audience() {
+		let mut claims = create_test_claims();
+		assert!(claims.validate_audience("relay").is_err());
+
+		claims.audience = vec!["relay".to_string()];
+		assert!(claims.validate_audience("relay").is_ok());
+		assert!(claims.validate_audience("other").is_err());
+	}
+
+	#[test]
+	fn test_audience_one_or_many() {
+		let json = r#"{"get": "", "aud": ["relay-a", "relay-b"]}"#;
+		let claims: Claims = serde_json::from_str(json).unwrap();
+		assert_eq!(claims.audience, vec!["relay-a", "relay-b"]);
+		assert!(claims.validate_audience("relay-b").is_ok());
+		assert!(claims.validate_audience("relay-c").is_err());
+
+		let json = r#"{"get": "", "aud": "relay-a"}"#;
+		let claims: Claims = serde_json::from_str(json).unwrap();
+		assert_eq!(claims.audience, vec!["relay-a"]);
+
+		// A single audience is still encoded as a plain string.
+		let encoded = serde_json::to_value(&claims).unwrap();
+		assert_eq!(encoded["aud"], "relay-a");
+	}
+
+	#[test]
+	fn test_builder() {
+		let claims = Claims::builder()
+			.root("/rooms/123/")
+			.allow_publish("alice")
+			.allow_subscribe("/rooms/123/bob")
+			.allow_subscribe("/rooms/123")
+			.expires_in(Duration::from_secs(60))
+			.audience("relay")
+			.build()
+			.unwrap();
+
+		assert_eq!(claims.root, "rooms/123");
+		assert_eq!(claims.publish, vec!["alice"]);
+		assert_eq!(claims.subscribe, vec!["bob", ""]);
+		assert_eq!(claims.audience, vec!["relay"]);
+
+		let issued = claims.issued.unwrap();
+		assert_eq!(claims.expires, Some(issued + Duration::from_secs(60)));
+	}
+
+	#[test]
+	fn test_builder_rejects_escaping_paths() {
+		let builder = Claims::builder().root("rooms/123");
+
+		// Absolute paths outside the root, including a sibling sharing its prefix.
+		for path in ["/rooms/456", "/rooms", "/rooms/1234/alice", "/"] {
+			let err = builder.clone().allow_publish(path).build().unwrap_err();
+			assert!(matches!(err, crate::Error::InvalidPath(p) if p == path), "{path}");
+		}
+
+		for path in ["../456", "alice/../../456", "/rooms/123/../456"] {
+			let err = builder.clone().allow_subscribe(path).build().unwrap_err();
+			assert!(matches!(err, crate::Error::InvalidPath(_)), "{path}");
+		}
+
+		assert!(matches!(
+			Claims::builder().root("../rooms").allow_publish("alice").build(),
+			Err(crate::Error::InvalidPath(_))
+		));
+	}
+
+	#[test]
+	fn test_builder_without_root() {
+		let claims = Claims::builder().allow_publish("/anywhere").build().unwrap();
+		assert_eq!(claims.root, "");
+		assert_eq!(claims.publish, vec!["anywhere"]);
+		assert_eq!(claims.expires, None);
+
+		assert!(matches!(Claims::builder().build(), Err(crate::Error::UselessToken)));
+	}
+
+	#[test]
+	fn test_audience_serde() {
+		let mut claims = create_test_claims();
+		let json = serde_json::to_string(&claims).unwrap();
+		assert!(!json.contains("aud"));
+
+		claims.audience = vec!["relay".to_string()];
+		let json = serde_json::to_string(&claims).unwrap();
+		assert!(json.contains("\"aud\":\"relay\""));
+
+		let claims: Claims = serde_json::from_str(&json).unwrap();
+		assert_eq!(claims.audience, vec!["relay"]);
+	}
 }
@@ -56,6 +56,18 @@ pub enum Error {
 	#[error("token has expired")]
 	TokenExpired,
 
+	#[error("token is not valid yet")]
+	TokenNotYetValid,
+
+	#[error("token id has already been used")]
+	TokenReplayed,
+
+	#[error("token audience does not match")]
+	InvalidAudience,
+
+	#[error("path escapes the token root: {0}")]
+	InvalidPath(String),
+
 	#[error(transparent)]
 	Json(#[from] serde_json::Error),
 
@@ -93,6 +105,10 @@ pub enum Error {
 	#[error(transparent)]
 	Reqwest(#[from] reqwest::Error),
 
+	#[cfg(feature = "jwks-loader")]
+	#[error("JWKS endpoint unavailable; circuit is open")]
+	CircuitOpen,
+
 	#[error("{0}")]
 	Other(String),
 }
@@ -0,0 +1,115 @@
+use std::{
+	collections::HashMap,
+	sync::Mutex,
+	time::{Duration, SystemTime},
+};
+
+use crate::Claims;
+
+/// Remembers token IDs (`jti`) so a token can only be used once.
+///
+/// Implement this to share seen IDs between verifiers, for example in a database.
+pub trait JtiStore: Send + Sync {
+	/// Record `jti` as used until `expires`.
+	///
+	/// Returns false if the ID was already recorded and hasn't expired yet.
+	fn insert(&self, jti: &str, expires: SystemTime) -> bool;
+
+	/// Returns an error if the token has an ID that was already used.
+	///
+	/// Tokens without a `jti` are always accepted.
+	fn check(&self, claims: &Claims, ttl: Duration) -> crate::Result<()> {
+		let Some(jti) = claims.jti.as_deref() else {
+			return Ok(());
+		};
+
+		// Remember the ID for as long as the token could be presented, capped at the TTL.
+		let cap = SystemTime::now() + ttl;
+		let expires = claims.expires.map_or(cap, |expires| expires.min(cap));
+
+		match self.insert(jti, expires) {
+			true => Ok(()),
+			false => Err(crate::Error::TokenReplayed),
+		}
+	}
+}
+
+/// An in-memory [JtiStore], local to this process.
+///
+/// Expired IDs are pruned as new ones are inserted.
+#[derive(Debug, Default)]
+pub struct MemoryJtiStore {
+	seen: Mutex<HashMap<String, SystemTime>>,
+}
+
+impl MemoryJtiStore {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl JtiStore for MemoryJtiStore {
+	fn insert(&self, jti: &str, expires: SystemTime) -> bool {
+		let now = SystemTime::now();
+		let mut seen = self.seen.lock().unwrap();
+		seen.retain(|_, expires| *expires > now);
+
+		if seen.contains_key(jti) {
+			return false;
+		}
+
+		seen.insert(jti.to_string(), expires);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const TTL: Duration = Duration::from_secs(3600);
+
+	fn claims(jti: Option<&str>) -> Claims {
+		Claims {
+			subscribe: vec!["".to_string()],
+			jti: jti.map(str::to_string),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_jti_replay_rejected() {
+		let store = MemoryJtiStore::new();
+		let claims = claims(Some("abc"));
+
+		store.check(&claims, TTL).unwrap();
+		assert!(matches!(store.check(&claims, TTL), Err(crate::Error::TokenReplayed)));
+	}
+
+	#[test]
+	fn test_jti_distinct_ids_accepted() {
+		let store = MemoryJtiStore::new();
+		store.check(&claims(Some("a")), TTL).unwrap();
+		store.check(&claims(Some("b")), TTL).unwrap();
+	}
+
+	#[test]
+	fn test_jti_missing_always_accepted() {
+		let store = MemoryJtiStore::new();
+		store.check(&claims(None), TTL).unwrap();
+		store.check(&claims(None), TTL).unwrap();
+	}
+
+	#[test]
+	fn test_jti_expired_entry_forgotten() {
+		let store = MemoryJtiStore::new();
+		let claims = Claims {
+			expires: Some(SystemTime::now() - Duration::from_secs(1)),
+			..claims(Some("abc"))
+		};
+
+		// The entry expires immediately, so it's pruned before the next insert.
+		store.check(&claims, TTL).unwrap();
+		store.check(&claims, TTL).unwrap();
+	}
+}
@@ -0,0 +1,268 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::KeySet;
+
+/// Retry and circuit-breaker parameters for [`JwksLoader`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct JwksRetry {
+	/// Maximum number of requests made by a single [`JwksLoader::load`] call.
+	pub attempts: u32,
+
+	/// Delay before the first retry. Doubles after each failed attempt.
+	pub initial_backoff: Duration,
+
+	/// Upper bound for the delay between retries.
+	pub max_backoff: Duration,
+
+	/// Timeout for each individual request.
+	pub timeout: Duration,
+
+	/// Number of consecutive failed loads before the circuit opens.
+	pub failure_threshold: u32,
+
+	/// How long the circuit stays open before requests are attempted again.
+	pub cooldown: Duration,
+}
+
+impl Default for JwksRetry {
+	fn default() -> Self {
+		Self {
+			attempts: 5,
+			initial_backoff: Duration::from_millis(500),
+			max_backoff: Duration::from_secs(10),
+			timeout: Duration::from_secs(10),
+			failure_threshold: 3,
+			cooldown: Duration::from_secs(60),
+		}
+	}
+}
+
+/// Fetches a [`KeySet`] from a JWKS endpoint, tolerating transient outages.
+///
+/// Each [`load`](Self::load) retries with exponential backoff. Once a key set has been
+/// loaded it is cached, and later failures return the cached keys instead of an error.
+/// After [`JwksRetry::failure_threshold`] consecutive failed loads the circuit opens and
+/// the endpoint is not contacted again until [`JwksRetry::cooldown`] has elapsed.
+pub struct JwksLoader {
+	uri: String,
+	retry: JwksRetry,
+	client: reqwest::Client,
+
+	cached: Option<KeySet>,
+	failures: u32,
+	open_until: Option<Instant>,
+}
+
+impl JwksLoader {
+	/// Create a loader with the default retry parameters.
+	pub fn new(uri: impl Into<String>) -> crate::Result<Self> {
+		Self::with_retry(uri, JwksRetry::default())
+	}
+
+	/// Create a loader with custom retry parameters.
+	pub fn with_retry(uri: impl Into<String>, retry: JwksRetry) -> crate::Result<Self> {
+		let client = reqwest::Client::builder().timeout(retry.timeout).build()?;
+
+		Ok(Self {
+			uri: uri.into(),
+			retry,
+			client,
+			cached: None,
+			failures: 0,
+			open_until: None,
+		})
+	}
+
+	/// Returns the most recently loaded key set, if any.
+	pub fn keys(&self) -> Option<&KeySet> {
+		self.cached.as_ref()
+	}
+
+	/// Fetch the key set, retrying on failure.
+	///
+	/// Returns the cached key set if the endpoint can't be reached after a previous success.
+	/// Otherwise returns the last error, or [`Error::CircuitOpen`](crate::Error::CircuitOpen)
+	/// while the circuit is open.
+	pub async fn load(&mut self) -> crate::Result<KeySet> {
+		if let Some(open_until) = self.open_until {
+			if Instant::now() < open_until {
+				return self.cached.clone().ok_or(crate::Error::CircuitOpen);
+			}
+
+			// Half-open: allow a single load through.
+			self.open_until = None;
+		}
+
+		match self.fetch_with_retry().await {
+			Ok(keys) => {
+				self.failures = 0;
+				self.cached = Some(keys.clone());
+				Ok(keys)
+			}
+			Err(err) => {
+				self.failures += 1;
+				if self.failures >= self.retry.failure_threshold {
+					self.open_until = Some(Instant::now() + self.retry.cooldown);
+				}
+
+				self.cached.clone().ok_or(err)
+			}
+		}
+	}
+
+	async fn fetch_with_retry(&self) -> crate::Result<KeySet> {
+		let mut backoff = self.retry.initial_backoff;
+		let mut attempt = 1;
+
+		loop {
+			match self.fetch().await {
+				Ok(keys) => return Ok(keys),
+				Err(err) if attempt >= self.retry.attempts => return Err(err),
+				Err(_) => {}
+			}
+
+			tokio::time::sleep(backoff).await;
+			backoff = (backoff * 2).min(self.retry.max_backoff);
+			attempt += 1;
+		}
+	}
+
+	async fn fetch(&self) -> crate::Result<KeySet> {
+		let response = self.client.get(&self.uri).send().await?.error_for_status()?;
+		let jwks_json = response.text().await?;
+		KeySet::from_str(&jwks_json)
+	}
+}
+
+/// Fetch a key set from a JWKS endpoint using the default retry parameters.
+pub async fn load_keys(jwks_uri: &str) -> crate::Result<KeySet> {
+	JwksLoader::new(jwks_uri)?.load().await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::sync::{Arc, Mutex};
+
+	use tokio::io::{AsyncReadExt, AsyncWriteExt};
+	use tokio::net::TcpListener;
+
+	const JWKS: &str = r#"{"keys":[{"kty":"oct","k":"2AJvfDJMVfWe9WMRPJP-4zCGN8F62LOy3dUr--rogR8","alg":"HS256","key_ops":["verify","sign"],"kid":"1"}]}"#;
+
+	/// Serves the scripted status codes in order, repeating the last one forever.
+	async fn serve(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		let hits = Arc::new(AtomicUsize::new(0));
+		let statuses = Arc::new(Mutex::new(statuses));
+
+		let counter = hits.clone();
+		tokio::spawn(async move {
+			loop {
+				let (mut socket, _) = listener.accept().await.unwrap();
+
+				let mut request = Vec::new();
+				let mut buf = [0u8; 1024];
+				while !request.ends_with(b"\r\n\r\n") {
+					let n = socket.read(&mut buf).await.unwrap();
+					if n == 0 {
+						break;
+					}
+					request.extend_from_slice(&buf[..n]);
+				}
+
+				counter.fetch_add(1, Ordering::SeqCst);
+				let status = {
+					let mut statuses = statuses.lock().unwrap();
+					if statuses.len() > 1 {
+						statuses.remove(0)
+					} else {
+						statuses[0]
+					}
+				};
+
+				let body = if status == 200 { JWKS } else { "" };
+				let response = format!(
+					"HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+					body.len()
+				);
+				let _ = socket.write_all(response.as_bytes()).await;
+			}
+		});
+
+		(format!("http://{addr}/jwks.json"), hits)
+	}
+
+	fn fast_retry() -> JwksRetry {
+		JwksRetry {
+			attempts: 5,
+			initial_backoff: Duration::from_millis(1),
+			max_backoff: Duration::from_millis(4),
+			timeout: Duration::from_secs(5),
+			failure_threshold: 3,
+			cooldown: Duration::from_secs(60),
+		}
+	}
+
+	#[tokio::test]
+	async fn retries_until_success() {
+		let (uri, hits) = serve(vec![503, 500, 200]).await;
+		let mut loader = JwksLoader::with_retry(uri, fast_retry()).unwrap();
+
+		let keys = loader.load().await.unwrap();
+		assert!(keys.find_key("1").is_some());
+		assert_eq!(hits.load(Ordering::SeqCst), 3);
+		assert!(loader.keys().is_some());
+	}
+
+	#[tokio::test]
+	async fn gives_up_after_attempts() {
+		let (uri, hits) = serve(vec![500]).await;
+		let retry = JwksRetry {
+			attempts: 2,
+			..fast_retry()
+		};
+		let mut loader = JwksLoader::with_retry(uri, retry).unwrap();
+
+		assert!(loader.load().await.is_err());
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+		assert!(loader.keys().is_none());
+	}
+
+	#[tokio::test]
+	async fn keeps_cached_keys_after_failure() {
+		let (uri, hits) = serve(vec![200, 500]).await;
+		let mut loader = JwksLoader::with_retry(uri, fast_retry()).unwrap();
+
+		loader.load().await.unwrap();
+
+		// The endpoint is now down; the refresh falls back to the cached keys.
+		let keys = loader.load().await.unwrap();
+		assert!(keys.find_key("1").is_some());
+		assert_eq!(hits.load(Ordering::SeqCst), 6);
+	}
+
+	#[tokio::test]
+	async fn circuit_opens_after_threshold() {
+		let (uri, hits) = serve(vec![500]).await;
+		let retry = JwksRetry {
+			attempts: 1,
+			failure_threshold: 2,
+			..fast_retry()
+		};
+		let mut loader = JwksLoader::with_retry(uri, retry).unwrap();
+
+		assert!(loader.load().await.is_err());
+		assert!(loader.load().await.is_err());
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+		// The circuit is open, so the endpoint isn't contacted.
+		assert!(matches!(loader.load().await, Err(crate::Error::CircuitOpen)));
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+}
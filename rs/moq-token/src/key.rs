@@ -184,6 +184,19 @@ impl<'de> Deserialize<'de> for Key {
 			obj.insert("kty".to_string(), serde_json::Value::String("oct".to_string()));
 		}
 
+		// Identity providers publish signing keys with "use": "sig" instead of "key_ops".
+		// Such keys can always verify, and can sign only if the private component is included.
+		if let Some(obj) = value.as_object_mut()
+			&& !obj.contains_key("key_ops")
+			&& obj.get("use").and_then(|u| u.as_str()) == Some("sig")
+		{
+			let operations = match obj.contains_key("d") {
+				true => serde_json::json!(["sign", "verify"]),
+				false => serde_json::json!(["verify"]),
+			};
+			obj.insert("key_ops".to_string(), operations);
+		}
+
 		Self::deserialize(value).map_err(serde::de::Error::custom)
 	}
 }
@@ -423,6 +436,7 @@ impl Key {
 		let mut validation = jsonwebtoken::Validation::new(self.algorithm.into());
 		validation.required_spec_claims = Default::default(); // Don't require exp, but still validate it if present
 		validation.validate_exp = false; // We validate exp ourselves to handle null values
+		validation.validate_aud = false; // We validate aud ourselves, only when an audience is expected
 
 		let token = jsonwebtoken::decode::<Claims>(token, decode, &validation)?;
 
@@ -432,11 +446,24 @@ impl Key {
 			return Err(crate::Error::TokenExpired);
 		}
 
+		if let Some(nbf) = token.claims.not_before
+			&& nbf > std::time::SystemTime::now()
+		{
+			return Err(crate::Error::TokenNotYetValid);
+		}
+
 		token.claims.validate()?;
 
 		Ok(token.claims)
 	}
 
+	/// Like [Self::decode], but also requires the token to be minted for `audience`.
+	pub fn decode_for(&self, token: &str, audience: &str) -> crate::Result<Claims> {
+		let claims = self.decode(token)?;
+		claims.validate_audience(audience)?;
+		Ok(claims)
+	}
+
 	pub fn encode(&self, payload: &Claims) -> crate::Result<String> {
 		if !self.operations.contains(&KeyOperation::Sign) {
 			return Err(KeyError::SignUnsupported.into());
@@ -536,6 +563,9 @@ mod tests {
 			subscribe: vec!["test-sub".into()],
 			expires: Some(SystemTime::now() + Duration::from_secs(3600)),
 			issued: Some(SystemTime::now()),
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		}
 	}
 
@@ -634,6 +664,9 @@ mod tests {
 			subscribe: vec![],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		let result = key.encode(&invalid_claims);
@@ -702,6 +735,31 @@ mod tests {
 		assert!(result.is_err());
 	}
 
+	#[test]
+	fn test_key_verify_not_yet_valid_token() {
+		let key = create_test_key();
+		let mut claims = create_test_claims();
+		claims.not_before = Some(SystemTime::now() + Duration::from_secs(3600)); // 1 hour from now
+		let token = key.encode(&claims).unwrap();
+
+		assert!(matches!(key.decode(&token), Err(crate::Error::TokenNotYetValid)));
+	}
+
+	#[test]
+	fn test_key_verify_audience() {
+		let key = create_test_key();
+		let mut claims = create_test_claims();
+		claims.audience = vec!["relay".to_string()];
+		let token = key.encode(&claims).unwrap();
+
+		assert!(key.decode(&token).is_ok());
+		assert!(key.decode_for(&token, "relay").is_ok());
+		assert!(matches!(
+			key.decode_for(&token, "other"),
+			Err(crate::Error::InvalidAudience)
+		));
+	}
+
 	#[test]
 	fn test_key_verify_token_without_exp() {
 		let key = create_test_key();
@@ -711,6 +769,9 @@ mod tests {
 			subscribe: vec!["".to_string()],
 			expires: None,
 			issued: None,
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 		let token = key.encode(&claims).unwrap();
 
@@ -730,6 +791,9 @@ mod tests {
 			subscribe: vec!["test-sub".into()],
 			expires: Some(SystemTime::now() + Duration::from_secs(3600)),
 			issued: Some(SystemTime::now()),
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		};
 
 		let token = key.encode(&original_claims).unwrap();
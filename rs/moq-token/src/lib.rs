@@ -9,6 +9,9 @@ mod algorithm;
 mod claims;
 mod error;
 mod generate;
+mod jti;
+#[cfg(feature = "jwks-loader")]
+mod jwks;
 mod key;
 mod key_id;
 mod set;
@@ -16,6 +19,9 @@ mod set;
 pub use algorithm::*;
 pub use claims::*;
 pub use error::*;
+pub use jti::*;
+#[cfg(feature = "jwks-loader")]
+pub use jwks::*;
 pub use key::*;
 pub use key_id::*;
 pub use set::*;
@@ -4,9 +4,6 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::path::Path;
 use std::sync::Arc;
 
-#[cfg(feature = "jwks-loader")]
-use std::time::Duration;
-
 /// JWK Set to spec <https://datatracker.ietf.org/doc/html/rfc7517#section-5>
 #[derive(Default, Clone)]
 pub struct KeySet {
@@ -93,6 +90,15 @@ impl KeySet {
 	}
 
 	pub fn decode(&self, token: &str) -> crate::Result<Claims> {
+		self.find_token_key(token)?.decode(token)
+	}
+
+	/// Like [Self::decode], but also requires the token to be minted for `audience`.
+	pub fn decode_for(&self, token: &str, audience: &str) -> crate::Result<Claims> {
+		self.find_token_key(token)?.decode_for(token, audience)
+	}
+
+	fn find_token_key(&self, token: &str) -> crate::Result<Arc<Key>> {
 		let header = jsonwebtoken::decode_header(token)?;
 
 		let key = match header.kid {
@@ -109,19 +115,10 @@ impl KeySet {
 			}
 		}?;
 
-		key.decode(token)
+		Ok(key)
 	}
 }
 
-#[cfg(feature = "jwks-loader")]
-pub async fn load_keys(jwks_uri: &str) -> crate::Result<KeySet> {
-	let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
-
-	let jwks_json = client.get(jwks_uri).send().await?.error_for_status()?.text().await?;
-
-	KeySet::from_str(&jwks_json)
-}
-
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -135,6 +132,9 @@ mod tests {
 			subscribe: vec!["test-sub".into()],
 			expires: Some(SystemTime::now() + Duration::from_secs(3600)),
 			issued: Some(SystemTime::now()),
+			audience: Vec::new(),
+			not_before: None,
+			jti: None,
 		}
 	}
 
@@ -337,6 +337,23 @@ mod tests {
 		assert_eq!(decoded.root, claims.root);
 	}
 
+	#[test]
+	fn test_decode_fail_not_yet_valid() {
+		let key = create_test_key(Some("1"));
+		let set = KeySet {
+			keys: vec![Arc::new(key)],
+		};
+
+		let mut claims = create_test_claims();
+		claims.not_before = Some(SystemTime::now() + Duration::from_secs(3600));
+		let token = set.encode(&claims).unwrap();
+		assert!(matches!(set.decode(&token), Err(crate::Error::TokenNotYetValid)));
+
+		claims.not_before = Some(SystemTime::now() - Duration::from_secs(60));
+		let token = set.encode(&claims).unwrap();
+		assert!(set.decode(&token).is_ok());
+	}
+
 	#[test]
 	fn test_decode_fail_multiple_keys_no_kid() {
 		let key1 = create_test_key(None);
@@ -402,4 +419,68 @@ mod tests {
 		// Clean up
 		let _ = std::fs::remove_file(path);
 	}
+
+	#[test]
+	fn test_asymmetric_round_trip() {
+		for algorithm in [Algorithm::RS256, Algorithm::RS384, Algorithm::RS512, Algorithm::EdDSA] {
+			let key = Key::generate(algorithm, Some(crate::KeyId::decode("1").unwrap())).unwrap();
+			let set = KeySet {
+				keys: vec![Arc::new(key)],
+			};
+
+			// The private set survives serialization and can still sign.
+			let set = KeySet::from_str(&set.to_str().unwrap()).unwrap();
+			let claims = create_test_claims();
+			let token = set.encode(&claims).unwrap();
+
+			// The public set verifies the token without any private components.
+			let public_json = set.to_public_set().unwrap().to_str().unwrap();
+			for private in ["\"d\"", "\"p\"", "\"q\"", "\"dp\"", "\"dq\"", "\"qi\""] {
+				assert!(
+					!public_json.contains(private),
+					"{algorithm}: public set leaks {private}"
+				);
+			}
+
+			let public_set = KeySet::from_str(&public_json).unwrap();
+			let decoded = public_set.decode(&token).unwrap();
+			assert_eq!(decoded.root, claims.root, "{algorithm}: wrong claims");
+		}
+	}
+
+	#[test]
+	fn test_public_key_cannot_sign() {
+		let key = Key::generate(Algorithm::RS256, Some(crate::KeyId::decode("1").unwrap())).unwrap();
+		let set = KeySet {
+			keys: vec![Arc::new(key)],
+		};
+		let public_set = set.to_public_set().unwrap();
+		let claims = create_test_claims();
+
+		let result = public_set.encode(&claims);
+		assert!(result.unwrap_err().to_string().contains("cannot find signing key"));
+
+		// Even when the operation is allowed, there's no private key to sign with.
+		let mut public_key = public_set.keys[0].as_ref().clone();
+		public_key.operations.insert(KeyOperation::Sign);
+		let result = public_key.encode(&claims);
+		assert!(matches!(result, Err(crate::Error::Key(KeyError::MissingPrivateKey))));
+	}
+
+	#[test]
+	fn test_keyset_from_identity_provider() {
+		// Identity providers omit "key_ops" and advertise "use": "sig" instead.
+		let key = Key::generate(Algorithm::EdDSA, Some(crate::KeyId::decode("idp").unwrap())).unwrap();
+		let token = key.encode(&create_test_claims()).unwrap();
+
+		let mut jwk = serde_json::to_value(key.to_public().unwrap()).unwrap();
+		let obj = jwk.as_object_mut().unwrap();
+		obj.remove("key_ops");
+		obj.insert("use".to_string(), "sig".into());
+
+		let json = serde_json::json!({ "keys": [jwk] }).to_string();
+		let set = KeySet::from_str(&json).unwrap();
+		assert_eq!(set.keys[0].operations, [KeyOperation::Verify].into());
+		assert!(set.decode(&token).is_ok());
+	}
 }